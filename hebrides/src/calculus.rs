@@ -0,0 +1,352 @@
+//! Calculus on piecewise and approximated functions.
+//!
+//! A [`Piecewise`] function is defined by separate pieces on consecutive
+//! intervals. Polynomial pieces are differentiated and integrated exactly,
+//! and arbitrary closures numerically, in both cases without ever
+//! straddling a breakpoint, where the function may jump or kink. A
+//! [`FunctionApprox`] instead captures a smooth function on a whole
+//! interval as one Chebyshev series, on which rootfinding, optimization,
+//! and integration are all exact operations. Data known only at sample
+//! points, as from an experiment, is integrated directly by [`trapz`],
+//! [`cumtrapz`], and [`simpson`].
+
+use std::rc::Rc;
+
+use crate::interp::SmoothingSpline;
+
+mod chebyshev;
+mod sampled;
+
+pub use chebyshev::*;
+pub use sampled::*;
+
+/// The definition of a [`Piecewise`] function on one of its intervals.
+#[derive(Clone)]
+pub struct Piece {
+	kind: PieceKind
+}
+
+#[derive(Clone)]
+enum PieceKind {
+	/// Coefficients in ascending powers of the offset from the left
+	/// breakpoint of the piece.
+	Polynomial(Vec<f64>),
+	Function(Rc<dyn Fn(f64) -> f64>)
+}
+
+impl Piece {
+
+	/// Constructs a polynomial piece c₀ + c₁(x - a) + c₂(x - a)² + ... from
+	/// its `coefficients`, where a is the left breakpoint of the interval
+	/// the piece is placed on.
+	pub fn polynomial(coefficients: Vec<f64>) -> Piece {
+		Piece { kind: PieceKind::Polynomial(coefficients) }
+	}
+
+	/// Constructs a constant piece.
+	pub fn constant(value: f64) -> Piece {
+		Piece::polynomial(vec![value])
+	}
+
+	/// Constructs a piece from an arbitrary function of x.
+	pub fn function(f: impl Fn(f64) -> f64 + 'static) -> Piece {
+		Piece { kind: PieceKind::Function(Rc::new(f)) }
+	}
+
+	/// Returns whether or not the piece is a polynomial, and so is
+	/// differentiated and integrated exactly.
+	pub fn is_polynomial(&self) -> bool {
+		matches!(self.kind, PieceKind::Polynomial(_))
+	}
+
+	fn eval(&self, left: f64, x: f64) -> f64 {
+		match &self.kind {
+			PieceKind::Polynomial(c) => c.iter().rev().fold(0.0, |sum, c| sum * (x - left) + c),
+			PieceKind::Function(f) => f(x)
+		}
+	}
+
+	/// Returns the derivative of the piece on [`left`, `right`].
+	fn derivative(&self, left: f64, right: f64) -> Piece {
+		match &self.kind {
+			PieceKind::Polynomial(c) => {
+				Piece::polynomial(c.iter().enumerate().skip(1).map(|(k, c)| k as f64 * c).collect())
+			},
+			PieceKind::Function(f) => {
+				let f = Rc::clone(f);
+				// Second order differences, made one-sided near the ends so
+				// that no sample leaves the interval.
+				Piece::function(move |x| {
+					let h = f64::EPSILON.cbrt() * x.abs().max(1.0);
+					if x - h < left {
+						(-3.0 * f(x) + 4.0 * f(x + h) - f(x + 2.0 * h)) / (2.0 * h)
+					} else if x + h > right {
+						(3.0 * f(x) - 4.0 * f(x - h) + f(x - 2.0 * h)) / (2.0 * h)
+					} else {
+						(f(x + h) - f(x - h)) / (2.0 * h)
+					}
+				})
+			}
+		}
+	}
+
+	/// Returns the integral of the piece over [`a`, `b`], a subinterval of
+	/// the piece's interval starting at `left`.
+	fn integral(&self, left: f64, a: f64, b: f64) -> f64 {
+		match &self.kind {
+			PieceKind::Polynomial(c) => {
+				let antiderivative = |s: f64| c.iter().enumerate().rev().fold(0.0, |sum, (k, c)| (sum + c / (k + 1) as f64) * s);
+				antiderivative(b - left) - antiderivative(a - left)
+			},
+			PieceKind::Function(f) => adaptive_gauss(f.as_ref(), a, b, 1e-12 * (b - a).abs().max(1.0), 40)
+		}
+	}
+
+}
+
+/// Five point Gauss-Legendre quadrature of `f` over [`a`, `b`].
+fn gauss(f: &dyn Fn(f64) -> f64, a: f64, b: f64) -> f64 {
+	const NODES: [(f64, f64); 5] = [
+		(0.0, 0.568_888_888_888_888_9),
+		(0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+		(-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+		(0.906_179_845_938_664, 0.236_926_885_056_189_08),
+		(-0.906_179_845_938_664, 0.236_926_885_056_189_08)
+	];
+	let (center, half) = ((a + b) / 2.0, (b - a) / 2.0);
+	half * NODES.iter().map(|(x, w)| w * f(center + half * x)).sum::<f64>()
+}
+
+/// Integrates `f` over [`a`, `b`], bisecting until the two halves agree
+/// with the whole to within `tolerance` or `depth` runs out.
+fn adaptive_gauss(f: &dyn Fn(f64) -> f64, a: f64, b: f64, tolerance: f64, depth: usize) -> f64 {
+	let middle = (a + b) / 2.0;
+	let (whole, left, right) = (gauss(f, a, b), gauss(f, a, middle), gauss(f, middle, b));
+	if depth == 0 || (left + right - whole).abs() <= tolerance {
+		return left + right;
+	}
+	adaptive_gauss(f, a, middle, tolerance / 2.0, depth - 1) + adaptive_gauss(f, middle, b, tolerance / 2.0, depth - 1)
+}
+
+/// A real function defined by pieces on consecutive intervals.
+///
+/// Each piece covers the interval from its breakpoint up to, but not
+/// including, the next, except the last, which includes the right end of
+/// the domain. Outside the domain the function is NaN.
+///
+/// ```
+/// # use hebrides::calculus::{Piecewise, Piece};
+/// // |x| on [-1, 2], with a kink at 0.
+/// let f = Piecewise::new(vec![-1.0, 0.0, 2.0], vec![Piece::function(|x| -x), Piece::polynomial(vec![0.0, 1.0])]);
+/// assert_eq!(f.eval(-0.5), 0.5);
+/// assert_eq!(f.integral(-1.0, 2.0), 2.5);
+/// let df = f.derivative();
+/// assert!((df.limits(1).0 + 1.0).abs() < 1e-8 && df.limits(1).1 == 1.0);
+/// assert!(f.eval(3.0).is_nan());
+/// ```
+#[derive(Clone)]
+pub struct Piecewise {
+	breakpoints: Vec<f64>,
+	pieces: Vec<Piece>
+}
+
+impl Piecewise {
+
+	/// Constructs a piecewise function with `pieces[i]` defined between
+	/// `breakpoints[i]` and `breakpoints[i + 1]`.
+	///
+	/// # Panics
+	/// Panics if there is not exactly one more breakpoint than pieces, if
+	/// there are no pieces, or if `breakpoints` is not strictly increasing.
+	pub fn new(breakpoints: Vec<f64>, pieces: Vec<Piece>) -> Piecewise {
+		if pieces.is_empty() || breakpoints.len() != pieces.len() + 1 {
+			panic!("Piecewise function needs one more breakpoint than pieces")
+		}
+		if breakpoints.windows(2).any(|w| w[0] >= w[1]) {
+			panic!("Breakpoints must be strictly increasing")
+		}
+		Piecewise { breakpoints, pieces }
+	}
+
+	/// Returns the breakpoints of `self`, including the ends of its domain.
+	pub fn breakpoints(&self) -> &[f64] {
+		&self.breakpoints
+	}
+
+	/// Returns the pieces of `self`, in order.
+	pub fn pieces(&self) -> &[Piece] {
+		&self.pieces
+	}
+
+	/// Returns the interval on which `self` is defined.
+	pub fn domain(&self) -> (f64, f64) {
+		(self.breakpoints[0], self.breakpoints[self.breakpoints.len() - 1])
+	}
+
+	/// Returns the index of the piece defining `self` at `x`, or `None` if
+	/// `x` is outside the domain.
+	pub fn piece_at(&self, x: f64) -> Option<usize> {
+		let (a, b) = self.domain();
+		if !(a..=b).contains(&x) {
+			return None;
+		}
+		Some((self.breakpoints.partition_point(|p| *p <= x) - 1).min(self.pieces.len() - 1))
+	}
+
+	/// Returns the value of `self` at `x`, or NaN outside the domain.
+	pub fn eval(&self, x: f64) -> f64 {
+		match self.piece_at(x) {
+			Some(i) => self.pieces[i].eval(self.breakpoints[i], x),
+			None => f64::NAN
+		}
+	}
+
+	/// Returns the limits of `self` from the left and from the right at
+	/// breakpoint `i`. At the ends of the domain, the missing side is NaN.
+	///
+	/// # Panics
+	/// Panics if `i` is not the index of a breakpoint.
+	pub fn limits(&self, i: usize) -> (f64, f64) {
+		if i >= self.breakpoints.len() {
+			panic!("Breakpoint index out of bounds for Piecewise")
+		}
+		let x = self.breakpoints[i];
+		let left = if i == 0 { f64::NAN } else { self.pieces[i - 1].eval(self.breakpoints[i - 1], x) };
+		let right = if i == self.pieces.len() { f64::NAN } else { self.pieces[i].eval(x, x) };
+		(left, right)
+	}
+
+	/// Returns the jump in `self` across interior breakpoint `i`, the right
+	/// limit less the left.
+	///
+	/// # Panics
+	/// Panics if `i` is not the index of an interior breakpoint.
+	pub fn jump(&self, i: usize) -> f64 {
+		if i == 0 || i >= self.pieces.len() {
+			panic!("Jumps are only defined at interior breakpoints")
+		}
+		let (left, right) = self.limits(i);
+		right - left
+	}
+
+	/// Returns whether or not every jump of `self` is at most `tolerance`
+	/// in magnitude.
+	pub fn is_continuous(&self, tolerance: f64) -> bool {
+		(1..self.pieces.len()).all(|i| self.jump(i).abs() <= tolerance)
+	}
+
+	/// Returns the derivative of `self`, differentiating each piece on its
+	/// own interval.
+	///
+	/// Polynomial pieces are differentiated exactly and function pieces by
+	/// finite differences that stay within their interval, so one-sided
+	/// derivatives at the breakpoints are available through
+	/// [`limits`](Self::limits). The derivative of a jump is not
+	/// represented.
+	pub fn derivative(&self) -> Piecewise {
+		let pieces = self.pieces.iter().enumerate().map(|(i, piece)| {
+			piece.derivative(self.breakpoints[i], self.breakpoints[i + 1])
+		}).collect();
+		Piecewise { breakpoints: self.breakpoints.clone(), pieces }
+	}
+
+	/// Returns the integral of `self` from `a` to `b`, split at the
+	/// breakpoints so that each piece is integrated only where it applies.
+	///
+	/// # Panics
+	/// Panics if `a` or `b` is outside the domain.
+	pub fn integral(&self, a: f64, b: f64) -> f64 {
+		let (start, end) = self.domain();
+		if !(start..=end).contains(&a) || !(start..=end).contains(&b) {
+			panic!("Integration bounds must lie within the domain of the Piecewise")
+		}
+		if a > b {
+			return -self.integral(b, a);
+		}
+		self.pieces.iter().enumerate().map(|(i, piece)| {
+			let (left, right) = (self.breakpoints[i].max(a), self.breakpoints[i + 1].min(b));
+			if left < right { piece.integral(self.breakpoints[i], left, right) } else { 0.0 }
+		}).sum()
+	}
+
+}
+
+/// Converts the spline to its cubic pieces between consecutive knots. The
+/// linear extension beyond the knots is not included.
+impl From<&SmoothingSpline> for Piecewise {
+	fn from(spline: &SmoothingSpline) -> Piecewise {
+		let (t, g, gamma) = (spline.knots(), spline.fitted(), spline.second_derivatives());
+		let pieces = (0..t.len() - 1).map(|i| {
+			let h = t[i + 1] - t[i];
+			Piece::polynomial(vec![
+				g[i],
+				(g[i + 1] - g[i]) / h - h * (2.0 * gamma[i] + gamma[i + 1]) / 6.0,
+				gamma[i] / 2.0,
+				(gamma[i + 1] - gamma[i]) / (6.0 * h)
+			])
+		}).collect();
+		Piecewise::new(t.to_vec(), pieces)
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+	use crate::linal::Vector;
+	use crate::interp::smoothing_spline;
+
+	#[test]
+	fn step_function() {
+		let f = Piecewise::new(vec![0.0, 1.0, 3.0], vec![Piece::constant(2.0), Piece::constant(-1.0)]);
+		assert_eq!(f.eval(1.0), -1.0);
+		assert_eq!(f.eval(3.0), -1.0);
+		assert_eq!(f.jump(1), -3.0);
+		assert!(!f.is_continuous(1e-12));
+		assert_eq!(f.integral(0.0, 1.5), 1.5);
+		assert_eq!(f.integral(3.0, 0.5), 1.0);
+		assert!(f.limits(0).0.is_nan() && f.limits(2).1.is_nan())
+	}
+
+	#[test]
+	fn function_pieces_integrate_and_differentiate() {
+		let f = Piecewise::new(vec![0.0, 1.0, 2.0], vec![Piece::function(f64::exp), Piece::function(|x| x.sin())]);
+		let expected = (1.0f64.exp() - 1.0) + (1.0f64.cos() - 2.0f64.cos());
+		assert!((f.integral(0.0, 2.0) - expected).abs() < 1e-12);
+		let df = f.derivative();
+		let (left, right) = df.limits(1);
+		assert!((left - 1.0f64.exp()).abs() < 1e-7 && (right - 1.0f64.cos()).abs() < 1e-7);
+		assert!((df.eval(2.0) - 2.0f64.cos()).abs() < 1e-7)
+	}
+
+	#[test]
+	fn polynomial_pieces_are_local() {
+		// (x - 1)² on [1, 2], written about the left breakpoint.
+		let f = Piecewise::new(vec![1.0, 2.0], vec![Piece::polynomial(vec![0.0, 0.0, 1.0])]);
+		assert_eq!(f.eval(1.5), 0.25);
+		assert_eq!(f.integral(1.0, 2.0), 1.0 / 3.0);
+		assert_eq!(f.derivative().derivative().eval(1.7), 2.0);
+		assert!(f.pieces()[0].is_polynomial())
+	}
+
+	#[test]
+	fn spline_conversion_preserves_values_and_smoothness() {
+		let x = Vector::new(vec![0.0, 0.4, 1.0, 1.5, 2.5]);
+		let y = Vector::new(vec![0.0, 1.0, -0.5, 0.3, 2.0]);
+		let spline = smoothing_spline(&x, &y, Some(0.01));
+		let f = Piecewise::from(&spline);
+		for t in [0.0, 0.2, 0.4, 0.9, 1.25, 2.5] {
+			assert!((f.eval(t) - spline.eval(t)).abs() < 1e-12);
+		}
+		assert!(f.is_continuous(1e-12));
+		assert!(f.derivative().is_continuous(1e-12));
+		assert!(f.derivative().derivative().is_continuous(1e-12))
+	}
+
+	#[test]
+	#[should_panic]
+	fn mismatched_pieces() {
+		Piecewise::new(vec![0.0, 1.0], vec![Piece::constant(1.0), Piece::constant(2.0)]);
+	}
+
+}
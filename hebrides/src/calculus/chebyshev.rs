@@ -0,0 +1,351 @@
+//! Chebyshev approximation of functions on an interval.
+
+use std::f64::consts::PI;
+
+use crate::linal::{Matrix, Vector};
+use crate::signal::fft;
+use crate::Complex;
+
+/// The largest number of Chebyshev points sampled before giving up on
+/// convergence.
+const MAX_POINTS: usize = 1 << 16;
+
+/// The degree above which rootfinding splits the interval rather than
+/// taking the eigenvalues of the colleague matrix directly.
+const MAX_COLLEAGUE_DEGREE: usize = 50;
+
+/// Returns the Chebyshev coefficients of the polynomial interpolating
+/// `values` at the n + 1 points cos(jπ/n), by a discrete cosine transform.
+fn chebyshev_coefficients(values: &[f64]) -> Vec<f64> {
+	let n = values.len() - 1;
+	if n == 0 {
+		return values.to_vec();
+	}
+	// The even extension of the samples turns the cosine transform into a
+	// Fourier transform of length 2n.
+	let extended: Vec<Complex> = values.iter().chain(values[1..n].iter().rev()).map(|v| Complex::new(*v, 0.0)).collect();
+	let transformed = fft(&Vector::new(extended));
+	(0..=n).map(|k| {
+		let scale = if k == 0 || k == n { 2.0 * n as f64 } else { n as f64 };
+		transformed[k].re() / scale
+	}).collect()
+}
+
+/// Returns Σcₖ Tₖ(`t`) by Clenshaw's recurrence.
+fn clenshaw(coefficients: &[f64], t: f64) -> f64 {
+	let (mut b1, mut b2) = (0.0, 0.0);
+	for c in coefficients[1..].iter().rev() {
+		(b1, b2) = (c + 2.0 * t * b1 - b2, b1);
+	}
+	coefficients[0] + t * b1 - b2
+}
+
+/// Returns the n + 1 Chebyshev points cos(jπ/n) mapped to [`a`, `b`].
+fn chebyshev_points(n: usize, a: f64, b: f64) -> Vec<f64> {
+	(0..=n).map(|j| (a + b) / 2.0 + (b - a) / 2.0 * (j as f64 * PI / n as f64).cos()).collect()
+}
+
+/// Drops trailing coefficients no larger than `threshold`, keeping at least
+/// one.
+fn chop(mut coefficients: Vec<f64>, threshold: f64) -> Vec<f64> {
+	while coefficients.len() > 1 && coefficients.last().unwrap().abs() <= threshold {
+		coefficients.pop();
+	}
+	coefficients
+}
+
+/// A function on a closed interval represented to a tolerance by a single
+/// Chebyshev series, in the manner of Chebfun.
+///
+/// Once built, the approximation is cheap to evaluate, and calculus on it
+/// is carried out exactly on the series: differentiation and integration
+/// act on the coefficients, and roots are the eigenvalues of a colleague
+/// matrix. Smooth functions need few terms; functions with kinks or
+/// singularities need very many and may not converge at all.
+///
+/// ```
+/// # use hebrides::calculus::FunctionApprox;
+/// let f = FunctionApprox::new(|x| (3.0 * x).sin() + x / 2.0, -2.0, 2.0, 1e-14);
+/// assert!(f.converged() && f.degree() < 40);
+/// assert!((f.eval(1.3) - (3.9f64.sin() + 0.65)).abs() < 1e-13);
+/// assert_eq!(f.roots().len(), 5);
+/// let (x, _) = f.max();
+/// assert!((f.derivative().eval(x)).abs() < 1e-10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FunctionApprox {
+	a: f64,
+	b: f64,
+	coefficients: Vec<f64>,
+	tolerance: f64,
+	converged: bool
+}
+
+impl FunctionApprox {
+
+	/// Approximates `f` on [`a`, `b`] by a Chebyshev series accurate to
+	/// about `tolerance` relative to the size of `f`.
+	///
+	/// Samples `f` at 17, 33, 65, ... Chebyshev points until the trailing
+	/// coefficients fall below the tolerance, then discards those that do.
+	/// If this has not happened by 65537 points, the last series is kept
+	/// and [`converged`](Self::converged) reports false.
+	///
+	/// # Panics
+	/// Panics if `a` is not less than `b`, if `tolerance` is not positive,
+	/// or if `f` takes a value that is not finite.
+	pub fn new(f: impl Fn(f64) -> f64, a: f64, b: f64, tolerance: f64) -> FunctionApprox {
+		if a.partial_cmp(&b) != Some(std::cmp::Ordering::Less) {
+			panic!("Interval of approximation must have a < b")
+		}
+		if tolerance <= 0.0 {
+			panic!("Approximation tolerance must be positive")
+		}
+		let mut n = 16;
+		loop {
+			let values: Vec<f64> = chebyshev_points(n, a, b).into_iter().map(&f).collect();
+			if values.iter().any(|v| !v.is_finite()) {
+				panic!("Function to approximate must be finite on the interval")
+			}
+			let coefficients = chebyshev_coefficients(&values);
+			let scale = coefficients.iter().chain(values.iter()).fold(0.0_f64, |max, c| max.max(c.abs()));
+			let threshold = tolerance * scale;
+			let converged = coefficients[n - n / 8..].iter().all(|c| c.abs() <= threshold);
+			if converged || n >= MAX_POINTS {
+				let coefficients = if converged { chop(coefficients, threshold) } else { coefficients };
+				return FunctionApprox { a, b, coefficients, tolerance, converged };
+			}
+			n *= 2;
+		}
+	}
+
+	/// Returns the interval on which `self` approximates its function.
+	pub fn domain(&self) -> (f64, f64) {
+		(self.a, self.b)
+	}
+
+	/// Returns the Chebyshev coefficients of `self`, in terms of
+	/// polynomials shifted to its domain.
+	pub fn coefficients(&self) -> &[f64] {
+		&self.coefficients
+	}
+
+	/// Returns the degree of the series.
+	pub fn degree(&self) -> usize {
+		self.coefficients.len() - 1
+	}
+
+	/// Returns whether or not the series resolved its function to the
+	/// requested tolerance.
+	pub fn converged(&self) -> bool {
+		self.converged
+	}
+
+	/// Returns the value of `self` at `x`, or NaN outside its domain.
+	pub fn eval(&self, x: f64) -> f64 {
+		if !(self.a..=self.b).contains(&x) {
+			return f64::NAN;
+		}
+		clenshaw(&self.coefficients, (2.0 * x - self.a - self.b) / (self.b - self.a))
+	}
+
+	/// Returns the derivative of `self`, differentiating the series term by
+	/// term. Each derivative loses some relative accuracy, roughly a factor
+	/// of the degree squared.
+	pub fn derivative(&self) -> FunctionApprox {
+		let c = &self.coefficients;
+		let n = c.len() - 1;
+		let mut d = vec![0.0; n + 2];
+		for k in (1..=n).rev() {
+			d[k - 1] = d[k + 1] + 2.0 * k as f64 * c[k];
+		}
+		d[0] /= 2.0;
+		d.truncate(n.max(1));
+		let scale = 2.0 / (self.b - self.a);
+		FunctionApprox { coefficients: d.iter().map(|e| e * scale).collect(), ..self.clone() }
+	}
+
+	/// Returns the integral of `self` over its domain, which is
+	/// Clenshaw-Curtis quadrature of the original function.
+	pub fn integral(&self) -> f64 {
+		let sum: f64 = self.coefficients.iter().enumerate().step_by(2).map(|(k, c)| 2.0 * c / (1.0 - (k * k) as f64)).sum();
+		sum * (self.b - self.a) / 2.0
+	}
+
+	/// Returns the real roots of `self` in its domain, in increasing order.
+	///
+	/// The roots of a series of low degree are the eigenvalues of its
+	/// colleague matrix; a series of high degree is first split into
+	/// pieces and re-expanded on each, as Chebfun does, which keeps the
+	/// cost near O(n²). Roots of even multiplicity may be missed or
+	/// reported more than once, and a function identically zero has none.
+	pub fn roots(&self) -> Vec<f64> {
+		let scale = self.coefficients.iter().fold(0.0_f64, |max, c| max.max(c.abs()));
+		if scale == 0.0 {
+			return Vec::new();
+		}
+		let mut roots = Vec::new();
+		self.roots_between(-1.0, 1.0, &self.coefficients, scale, &mut roots);
+		// Polish each root with Newton's method on the full series.
+		let derivative = self.derivative();
+		let mut roots: Vec<f64> = roots.into_iter().map(|t| {
+			let mut x = ((self.a + self.b) + t * (self.b - self.a)) / 2.0;
+			for _ in 0..3 {
+				let step = self.eval(x) / derivative.eval(x);
+				let next = x - step;
+				if !step.is_finite() || !(self.a..=self.b).contains(&next) || self.eval(next).abs() >= self.eval(x).abs() {
+					break;
+				}
+				x = next;
+			}
+			x
+		}).collect();
+		roots.sort_by(f64::total_cmp);
+		let gap = 1e-12 * (self.b - self.a);
+		roots.dedup_by(|later, earlier| *later - *earlier <= gap);
+		roots
+	}
+
+	/// Collects into `roots` the roots in [`lo`, `hi`] ⊆ [-1, 1] of the
+	/// series, where `local` are its coefficients re-expanded on [`lo`, `hi`].
+	fn roots_between(&self, lo: f64, hi: f64, local: &[f64], scale: f64, roots: &mut Vec<f64>) {
+		// Beyond the tolerance the re-expanded coefficients are noise, and
+		// keeping them would stop the degree from falling as pieces shrink.
+		let local = chop(local.to_vec(), self.tolerance.max(f64::EPSILON) * scale);
+		let n = local.len() - 1;
+		if n > MAX_COLLEAGUE_DEGREE && hi - lo > 1e-8 {
+			// Splitting slightly off center keeps roots at simple
+			// locations such as 0 away from the split.
+			let split = lo + (hi - lo) * 0.495_150_165_082_474_8;
+			for (start, end) in [(lo, split), (split, hi)] {
+				let values: Vec<f64> = chebyshev_points(n, start, end).into_iter().map(|t| clenshaw(&self.coefficients, t)).collect();
+				self.roots_between(start, end, &chebyshev_coefficients(&values), scale, roots);
+			}
+			return;
+		}
+		let candidates: Vec<f64> = match n {
+			0 => Vec::new(),
+			1 => vec![-local[0] / local[1]],
+			_ => {
+				// Colleague matrix: the Chebyshev three-term recurrence, with
+				// its last row closed off by the series itself.
+				let mut rows = vec![vec![0.0; n]; n];
+				rows[0][1] = 1.0;
+				for i in 1..n {
+					rows[i][i - 1] = 0.5;
+					if i + 1 < n {
+						rows[i][i + 1] = 0.5;
+					}
+				}
+				for j in 0..n {
+					rows[n - 1][j] -= local[j] / (2.0 * local[n]);
+				}
+				Matrix::new(rows).eigenvalues().unwrap_or_default().into_iter()
+					.filter(|z| z.im().abs() <= 1e-8)
+					.map(|z| z.re())
+					.collect()
+			}
+		};
+		let slack = 1e-8;
+		for u in candidates.into_iter().filter(|u| u.abs() <= 1.0 + slack) {
+			roots.push(lo + (u.clamp(-1.0, 1.0) + 1.0) / 2.0 * (hi - lo));
+		}
+	}
+
+	/// Returns the location and value of the global minimum of `self`,
+	/// found among the roots of its derivative and the ends of its domain.
+	pub fn min(&self) -> (f64, f64) {
+		self.extremum(|candidate, best| candidate < best)
+	}
+
+	/// Returns the location and value of the global maximum of `self`,
+	/// found among the roots of its derivative and the ends of its domain.
+	pub fn max(&self) -> (f64, f64) {
+		self.extremum(|candidate, best| candidate > best)
+	}
+
+	fn extremum(&self, better: impl Fn(f64, f64) -> bool) -> (f64, f64) {
+		let mut candidates = self.derivative().roots();
+		candidates.extend([self.a, self.b]);
+		candidates.into_iter().map(|x| (x, self.eval(x))).fold((f64::NAN, f64::NAN), |best, current| {
+			if best.1.is_nan() || better(current.1, best.1) { current } else { best }
+		})
+	}
+
+	/// Returns an approximation of `g` ∘ `self`, g(f(x)), on the same domain
+	/// and to the same tolerance.
+	///
+	/// ```
+	/// # use hebrides::calculus::FunctionApprox;
+	/// let f = FunctionApprox::new(f64::cos, 0.0, 3.0, 1e-14);
+	/// let g = f.compose(f64::exp);
+	/// assert!((g.eval(2.0) - 2.0f64.cos().exp()).abs() < 1e-13);
+	/// ```
+	pub fn compose(&self, g: impl Fn(f64) -> f64) -> FunctionApprox {
+		FunctionApprox::new(|x| g(self.eval(x)), self.a, self.b, self.tolerance)
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn exponential_is_resolved_quickly() {
+		let f = FunctionApprox::new(f64::exp, -1.0, 1.0, 1e-15);
+		assert!(f.converged() && f.degree() < 20);
+		for x in [-1.0, -0.3, 0.0, 0.77, 1.0] {
+			assert!((f.eval(x) - x.exp()).abs() < 1e-14);
+		}
+		assert!((f.integral() - (1.0f64.exp() - (-1.0f64).exp())).abs() < 1e-14);
+		assert!(f.eval(1.5).is_nan())
+	}
+
+	#[test]
+	fn derivative_of_sine() {
+		let f = FunctionApprox::new(f64::sin, 0.0, 4.0, 1e-15);
+		let df = f.derivative();
+		for x in [0.0, 1.0, 2.5, 4.0] {
+			assert!((df.eval(x) - x.cos()).abs() < 1e-12);
+		}
+		assert_eq!(FunctionApprox::new(|_| 2.0, 0.0, 1.0, 1e-14).derivative().coefficients(), &[0.0])
+	}
+
+	#[test]
+	fn roots_including_endpoints() {
+		let f = FunctionApprox::new(f64::sin, 0.0, 10.0, 1e-14);
+		let roots = f.roots();
+		assert_eq!(roots.len(), 4);
+		for (k, r) in roots.iter().enumerate() {
+			assert!((r - k as f64 * PI).abs() < 1e-12, "{r}");
+		}
+	}
+
+	#[test]
+	fn roots_of_high_degree_series_by_splitting() {
+		let f = FunctionApprox::new(|x| (100.0 * x).sin(), -1.0, 1.0, 1e-14);
+		assert!(f.degree() > MAX_COLLEAGUE_DEGREE);
+		let roots = f.roots();
+		let expected: Vec<f64> = (-31..=31).map(|k| k as f64 * PI / 100.0).collect();
+		assert_eq!(roots.len(), expected.len());
+		assert!(roots.iter().zip(expected.iter()).all(|(r, e)| (r - e).abs() < 1e-12))
+	}
+
+	#[test]
+	fn extrema() {
+		let f = FunctionApprox::new(|x| (x - 0.3).powi(2), -1.0, 1.0, 1e-14);
+		let (x, value) = f.min();
+		assert!((x - 0.3).abs() < 1e-10 && value.abs() < 1e-14);
+		assert_eq!(f.max(), (-1.0, f.eval(-1.0)))
+	}
+
+	#[test]
+	fn kink_does_not_converge() {
+		let f = FunctionApprox::new(f64::abs, -1.0, 1.0, 1e-15);
+		assert!(!f.converged());
+		assert!((f.eval(0.25) - 0.25).abs() < 1e-6)
+	}
+
+}
@@ -0,0 +1,169 @@
+//! Integration of sampled data.
+
+use crate::linal::Vector;
+
+/// Panics unless the sample locations and values have the same length.
+fn check_samples(x: &Vector<f64>, y: &Vector<f64>) {
+	if x.dim() != y.dim() {
+		panic!("Sample locations and values must have the same length")
+	}
+}
+
+/// Returns the integral of `y` over `x` by the trapezoidal rule, treating
+/// the samples as joined by straight lines.
+///
+/// The sample locations need not be evenly spaced. They are taken in the
+/// order given, so decreasing locations give the negated integral, and a
+/// single sample integrates to zero.
+///
+/// # Panics
+///
+/// Panics if `x` and `y` differ in length.
+///
+/// ```
+/// # use hebrides::calculus::trapz;
+/// # use hebrides::linal::Vector;
+/// let x = Vector::new(vec![0.0, 1.0, 3.0]);
+/// let y = Vector::new(vec![0.0, 2.0, 2.0]);
+/// assert_eq!(trapz(&x, &y), 5.0);
+/// ```
+pub fn trapz(x: &Vector<f64>, y: &Vector<f64>) -> f64 {
+	cumtrapz(x, y)[x.dim() - 1]
+}
+
+/// Returns the running integrals of `y` over `x` by the trapezoidal rule,
+/// from the first sample location to each of them in turn.
+///
+/// The result has one entry per sample, starting from zero, so that it
+/// lines up with `x` and its last entry is [`trapz`].
+///
+/// # Panics
+///
+/// Panics if `x` and `y` differ in length.
+///
+/// ```
+/// # use hebrides::calculus::cumtrapz;
+/// # use hebrides::linal::Vector;
+/// let x = Vector::new(vec![0.0, 0.5, 2.0]);
+/// let y = Vector::new(vec![1.0, 1.0, 3.0]);
+/// assert_eq!(cumtrapz(&x, &y), Vector::new(vec![0.0, 0.5, 3.5]));
+/// ```
+pub fn cumtrapz(x: &Vector<f64>, y: &Vector<f64>) -> Vector<f64> {
+	check_samples(x, y);
+	let (x, y): (Vec<f64>, Vec<f64>) = (x.iter().copied().collect(), y.iter().copied().collect());
+	let mut total = 0.0;
+	let mut running = vec![0.0];
+	for i in 1..x.len() {
+		total += (x[i] - x[i - 1]) * (y[i - 1] + y[i]) / 2.0;
+		running.push(total);
+	}
+	Vector::new(running)
+}
+
+/// Returns the integral of `y` over `x` by Simpson's rule, treating each
+/// consecutive pair of intervals as spanned by the parabola through its
+/// three samples.
+///
+/// The sample locations need not be evenly spaced; the weights account for
+/// each pair's spacing, so the rule is exact for quadratics regardless, and
+/// for cubics on even spacing. With an odd number of intervals, the last
+/// is integrated under the parabola through the last three samples, and
+/// with only two samples this falls back to [`trapz`].
+///
+/// # Panics
+///
+/// Panics if `x` and `y` differ in length, or if two consecutive sample
+/// locations coincide.
+///
+/// ```
+/// # use hebrides::calculus::simpson;
+/// # use hebrides::linal::Vector;
+/// let x = Vector::new(vec![0.0, 0.5, 2.0, 3.0]);
+/// let y = x.map(|t| t * t);
+/// assert!((simpson(&x, &y) - 9.0).abs() < 1e-14);
+/// ```
+pub fn simpson(x: &Vector<f64>, y: &Vector<f64>) -> f64 {
+	check_samples(x, y);
+	if x.dim() < 3 {
+		return trapz(x, y);
+	}
+	let (x, y): (Vec<f64>, Vec<f64>) = (x.iter().copied().collect(), y.iter().copied().collect());
+	let h: Vec<f64> = x.windows(2).map(|w| w[1] - w[0]).collect();
+	if h.contains(&0.0) {
+		panic!("Consecutive sample locations must be distinct")
+	}
+	let pairs = h.len() / 2;
+	let mut total = 0.0;
+	for k in 0..pairs {
+		let i = 2 * k;
+		let (h0, h1) = (h[i], h[i + 1]);
+		total += (h0 + h1) / 6.0 * (
+			(2.0 - h1 / h0) * y[i]
+			+ (h0 + h1) * (h0 + h1) / (h0 * h1) * y[i + 1]
+			+ (2.0 - h0 / h1) * y[i + 2]
+		);
+	}
+	if h.len() % 2 == 1 {
+		// The parabola through the last three samples, integrated over the
+		// last interval alone.
+		let n = x.len();
+		let (h0, h1) = (h[n - 3], h[n - 2]);
+		total += (2.0 * h1 * h1 + 3.0 * h0 * h1) / (6.0 * (h0 + h1)) * y[n - 1]
+			+ (h1 * h1 + 3.0 * h0 * h1) / (6.0 * h0) * y[n - 2]
+			- h1 * h1 * h1 / (6.0 * h0 * (h0 + h1)) * y[n - 3];
+	}
+	total
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn trapezoids_on_uneven_samples() {
+		let x = Vector::new(vec![0.0, 0.1, 0.4, 1.0]);
+		let y = x.map(|t| 2.0 * t + 1.0);
+		assert!((trapz(&x, &y) - 2.0).abs() < 1e-15);
+		let running = cumtrapz(&x, &y);
+		for (t, integral) in x.iter().zip(running.iter()) {
+			assert!((integral - (t * t + t)).abs() < 1e-15)
+		}
+		assert_eq!(trapz(&Vector::new(vec![2.0]), &Vector::new(vec![5.0])), 0.0)
+	}
+
+	#[test]
+	fn reversed_samples_negate_the_integral() {
+		let x = Vector::new(vec![0.0, 0.3, 0.5, 1.2, 2.0]);
+		let y = x.map(f64::exp);
+		let (rx, ry) = (Vector::new(x.iter().rev().copied().collect()), Vector::new(y.iter().rev().copied().collect()));
+		assert!((trapz(&x, &y) + trapz(&rx, &ry)).abs() < 1e-14);
+		assert!((simpson(&x, &y) + simpson(&rx, &ry)).abs() < 1e-14)
+	}
+
+	#[test]
+	fn simpson_is_exact_for_quadratics() {
+		let f = |t: f64| 3.0 * t * t - t + 2.0;
+		let antiderivative = |t: f64| t * t * t - t * t / 2.0 + 2.0 * t;
+		for n in 3..8 {
+			let x = Vector::from_fn(n, |i| (i as f64 * 0.7).powf(1.3));
+			let exact = antiderivative(x[n - 1]) - antiderivative(0.0);
+			assert!((simpson(&x, &x.map(f)) - exact).abs() < 1e-12, "{n}")
+		}
+	}
+
+	#[test]
+	fn simpson_converges_faster_than_trapezoids() {
+		let x = Vector::from_fn(41, |i| std::f64::consts::PI * i as f64 / 40.0);
+		let y = x.map(f64::sin);
+		assert!((trapz(&x, &y) - 2.0).abs() > 1e-4);
+		assert!((simpson(&x, &y) - 2.0).abs() < 1e-6)
+	}
+
+	#[test]
+	#[should_panic]
+	fn mismatched_lengths_panic() {
+		trapz(&Vector::new(vec![0.0, 1.0]), &Vector::new(vec![1.0]));
+	}
+
+}
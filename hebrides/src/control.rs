@@ -0,0 +1,201 @@
+//! State-space control.
+//!
+//! Controllability and observability of linear time-invariant systems
+//! ẋ = Ax + Bu, y = Cx, and pole placement for state feedback and state
+//! observers.
+
+use crate::linal::{Vector, Matrix};
+use crate::{Complex, Real};
+
+fn identity(n: usize, scale: f64) -> Matrix<f64> {
+	Matrix::from_diagonal(&Vector::new(vec![scale; n]))
+}
+
+fn real_part(z: Complex) -> f64 {
+	Real::try_from((z + z.conjugate()) * Complex::new(0.5, 0.0)).unwrap().value()
+}
+
+/// Returns the coefficients c₀, ..., cₙ₋₁ of the monic polynomial with the
+/// given roots, sᵑ + cₙ₋₁sⁿ⁻¹ + ... + c₀.
+fn monic_from_roots(roots: &[Complex]) -> Vec<f64> {
+	for root in roots {
+		let scale = 1.0 + root.norm().value();
+		if !roots.iter().any(|other| (*other - root.conjugate()).norm().value() <= 1e-12 * scale) {
+			panic!("Desired poles must be closed under complex conjugation")
+		}
+	}
+	let mut coefficients = vec![Complex::ONE];
+	for root in roots {
+		let mut next = vec![Complex::ZERO; coefficients.len() + 1];
+		for (k, c) in coefficients.iter().enumerate() {
+			next[k + 1] = next[k + 1] + *c;
+			next[k] = next[k] - *root * *c;
+		}
+		coefficients = next;
+	}
+	coefficients.pop();
+	coefficients.into_iter().map(real_part).collect()
+}
+
+/// Returns the controllability matrix [B AB ... Aⁿ⁻¹B] of the pair (`a`,
+/// `b`), which has full row rank exactly when every state can be steered by
+/// the inputs.
+///
+/// ```
+/// # use hebrides::linal::Matrix;
+/// # use hebrides::control::controllability;
+/// let a = Matrix::new(vec![vec![0.0, 1.0], vec![0.0, 0.0]]);
+/// let b = Matrix::new(vec![vec![0.0], vec![1.0]]);
+/// assert_eq!(controllability(&a, &b), Matrix::new(vec![vec![0.0, 1.0], vec![1.0, 0.0]]));
+/// ```
+///
+/// # Panics
+/// Panics if `a` is not square or if `b` does not have as many rows as `a`.
+pub fn controllability(a: &Matrix<f64>, b: &Matrix<f64>) -> Matrix<f64> {
+	let n = a.dims.num_rows;
+	if !a.is_square() || b.dims.num_rows != n {
+		panic!("Input Matrix must have as many rows as the square state Matrix")
+	}
+	let mut blocks = vec![b.clone()];
+	for k in 1..n {
+		blocks.push(a.clone() * blocks[k - 1].clone());
+	}
+	Matrix::new((0..n).map(|i| blocks.iter().flat_map(|block| block.rows[i].clone()).collect()).collect())
+}
+
+/// Returns the observability matrix [C; CA; ...; CAⁿ⁻¹] of the pair (`a`,
+/// `c`), which has full column rank exactly when the state can be
+/// reconstructed from the outputs.
+///
+/// # Panics
+/// Panics if `a` is not square or if `c` does not have as many columns as
+/// `a`.
+pub fn observability(a: &Matrix<f64>, c: &Matrix<f64>) -> Matrix<f64> {
+	if !a.is_square() || c.dims.num_cols != a.dims.num_cols {
+		panic!("Output Matrix must have as many columns as the square state Matrix")
+	}
+	controllability(&a.transpose(), &c.transpose()).transpose()
+}
+
+/// Returns the state feedback gain K placing the eigenvalues of A - BK at
+/// `poles`, for a single-input system with an n x 1 input matrix `b`.
+///
+/// K is computed by Ackermann's formula K = eₙᵀ 𝒞⁻¹ φ(A), where 𝒞 is the
+/// [controllability] matrix and φ the monic polynomial with roots `poles`.
+/// Returns `None` if the pair (`a`, `b`) is not controllable.
+///
+/// ```
+/// # use hebrides::linal::Matrix;
+/// # use hebrides::Complex;
+/// # use hebrides::control::place;
+/// let a = Matrix::new(vec![vec![0.0, 1.0], vec![-2.0, -3.0]]);
+/// let b = Matrix::new(vec![vec![0.0], vec![1.0]]);
+/// let k = place(&a, &b, &[Complex::new(-5.0, 0.0), Complex::new(-6.0, 0.0)]).unwrap();
+/// assert_eq!(k, Matrix::new(vec![vec![28.0, 8.0]]));
+/// ```
+///
+/// # Panics
+/// Panics if `a` is not square, if `b` is not a single column with as many
+/// rows as `a`, if there are not as many poles as states, or if the poles
+/// are not closed under complex conjugation.
+pub fn place(a: &Matrix<f64>, b: &Matrix<f64>, poles: &[Complex]) -> Option<Matrix<f64>> {
+	let n = a.dims.num_rows;
+	if b.dims.num_cols != 1 {
+		panic!("Pole placement supports single-input systems only")
+	}
+	if poles.len() != n {
+		panic!("There must be as many desired poles as states")
+	}
+	let coefficients = monic_from_roots(poles);
+	let reachable = controllability(a, b);
+	let mut last = vec![0.0; n];
+	if let Some(e) = last.last_mut() {
+		*e = 1.0;
+	}
+	let row = reachable.transpose().solve(&Vector::new(last))?;
+	let mut phi = identity(n, 1.0);
+	for c in coefficients.iter().rev() {
+		phi = phi * a.clone() + identity(n, *c);
+	}
+	let gain = (0..n).map(|j| (0..n).map(|i| row[i] * phi.rows[i][j]).sum()).collect();
+	Some(Matrix::new(vec![gain]))
+}
+
+/// Returns the observer gain L placing the eigenvalues of A - LC at `poles`,
+/// for a single-output system with a 1 x n output matrix `c`.
+///
+/// This is [place] applied to the dual pair (Aᵀ, Cᵀ). Returns `None` if the
+/// pair (`a`, `c`) is not observable.
+///
+/// # Panics
+/// Panics if `a` is not square, if `c` is not a single row with as many
+/// columns as `a`, if there are not as many poles as states, or if the poles
+/// are not closed under complex conjugation.
+pub fn place_observer(a: &Matrix<f64>, c: &Matrix<f64>, poles: &[Complex]) -> Option<Matrix<f64>> {
+	if c.dims.num_cols != a.dims.num_cols {
+		panic!("Output Matrix must have as many columns as the square state Matrix")
+	}
+	place(&a.transpose(), &c.transpose(), poles).map(|gain| gain.transpose())
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn assert_poles(closed_loop: &Matrix<f64>, poles: &[Complex]) {
+		let values = closed_loop.eigen().unwrap();
+		for pole in poles {
+			assert!(values.values().iter().any(|lambda| (*lambda - *pole).norm().value() < 1e-8));
+		}
+	}
+
+	fn system() -> (Matrix<f64>, Matrix<f64>, Matrix<f64>) {
+		let a = Matrix::new(vec![
+			vec![1.0, 2.0, 0.0],
+			vec![0.0, -1.0, 1.0],
+			vec![3.0, 0.0, 2.0]
+		]);
+		let b = Matrix::new(vec![vec![0.0], vec![0.0], vec![1.0]]);
+		let c = Matrix::new(vec![vec![1.0, 0.0, 0.0]]);
+		(a, b, c)
+	}
+
+	#[test]
+	fn feedback_places_complex_poles() {
+		let (a, b, _) = system();
+		let poles = [Complex::new(-1.0, 2.0), Complex::new(-1.0, -2.0), Complex::new(-4.0, 0.0)];
+		let k = place(&a, &b, &poles).unwrap();
+		assert_poles(&(a - b * k), &poles)
+	}
+
+	#[test]
+	fn observer_places_poles() {
+		let (a, _, c) = system();
+		let poles = [Complex::new(-2.0, 0.0), Complex::new(-3.0, 0.5), Complex::new(-3.0, -0.5)];
+		let l = place_observer(&a, &c, &poles).unwrap();
+		assert_eq!(l.dims.num_cols, 1);
+		assert_poles(&(a - l * c), &poles)
+	}
+
+	#[test]
+	fn observability_is_dual_to_controllability() {
+		let (a, _, c) = system();
+		assert_eq!(observability(&a, &c), controllability(&a.transpose(), &c.transpose()).transpose())
+	}
+
+	#[test]
+	fn uncontrollable_system() {
+		let a = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 2.0]]);
+		let b = Matrix::new(vec![vec![1.0], vec![0.0]]);
+		assert!(place(&a, &b, &[Complex::new(-1.0, 0.0), Complex::new(-2.0, 0.0)]).is_none())
+	}
+
+	#[test]
+	#[should_panic]
+	fn unpaired_complex_pole() {
+		let (a, b, _) = system();
+		let _ = place(&a, &b, &[Complex::new(-1.0, 2.0), Complex::new(-1.0, 1.0), Complex::new(-4.0, 0.0)]);
+	}
+
+}
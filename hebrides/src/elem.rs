@@ -2,15 +2,84 @@
 //!
 //! `elem` provides two main structs: Real and Complex. Trigonometry is 
 //! conducted through Angle objects, which are intermediaries between degree
-//! and radian units.
+//! and radian units. Affine forms propagate correlated uncertainty through
+//! arithmetic more tightly than intervals, while Uncertain values carry a
+//! standard deviation through arithmetic to first order. BigReal offers
+//! more precision than f64 when a computation needs it, while Rational and
+//! BigInt provide exact arithmetic with no rounding at all. Quaternions
+//! represent rotations of three-dimensional space, and Zmod integers modulo
+//! a fixed modulus, which for a prime modulus form a finite field. The
+//! Semiring, Ring, and Field traits record which of these obey which
+//! arithmetic laws, and bound the generic parts of linal. The special module
+//! provides the gamma, beta, and error functions, Bessel functions, and
+//! complete elliptic integrals.
 
 use std::ops::{Add, Sub, Mul, Div, Neg};
 
+use crate::interval::Interval;
+
+mod algebra;
+mod big;
+mod quaternion;
+mod rational;
+pub mod special;
+mod zmod;
+
+pub use algebra::*;
+pub use big::*;
+pub use quaternion::*;
+pub use rational::*;
+pub use zmod::*;
+
 /// Evaluates approximate equality betwen two values.
-pub(self) fn approx_eq(left: f64, right: f64) -> bool {
+fn approx_eq(left: f64, right: f64) -> bool {
     (left - right).abs() <= f64::EPSILON
 }
 
+/// Transcendental functions backing [`Real`], taken from [`crate::strict`]
+/// when the `strict` feature is enabled and from the platform otherwise.
+mod libm {
+
+    #[cfg(feature = "strict")]
+    pub(super) use crate::strict::{sin, cos, tan, asin, acos, atan, atan2, sinh, cosh, tanh, asinh, acosh, atanh, exp, ln, log, powf, cbrt, hypot};
+
+    macro_rules! platform {
+        ($($name:ident => $method:ident),*) => {
+            $(
+                #[cfg(not(feature = "strict"))]
+                pub(super) fn $name(x: f64) -> f64 {
+                    x.$method()
+                }
+            )*
+        }
+    }
+
+    platform![sin => sin, cos => cos, tan => tan, asin => asin, acos => acos, atan => atan,
+              sinh => sinh, cosh => cosh, tanh => tanh, asinh => asinh, acosh => acosh, atanh => atanh,
+              exp => exp, ln => ln, cbrt => cbrt];
+
+    #[cfg(not(feature = "strict"))]
+    pub(super) fn log(x: f64, base: f64) -> f64 {
+        x.log(base)
+    }
+
+    #[cfg(not(feature = "strict"))]
+    pub(super) fn powf(x: f64, power: f64) -> f64 {
+        x.powf(power)
+    }
+
+    #[cfg(not(feature = "strict"))]
+    pub(super) fn atan2(y: f64, x: f64) -> f64 {
+        y.atan2(x)
+    }
+
+    #[cfg(not(feature = "strict"))]
+    pub(super) fn hypot(x: f64, y: f64) -> f64 {
+        x.hypot(y)
+    }
+
+}
+
 /// Error type for errors involving domain restrictions.
 #[derive(Debug, Clone)]
 pub struct DomainError;
@@ -31,6 +100,16 @@ impl std::fmt::Display for ConversionError {
     }
 }
 
+/// Error type for errors involving parsing values from strings.
+#[derive(Debug, Clone)]
+pub struct ParseError;
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "parse error due to malformed number")
+    }
+}
+
 /// Representation of angular values.
 ///
 /// Angles are often described in either radians or degrees. This datatype is
@@ -217,12 +296,12 @@ impl Real {
     /// Sine.
     /// 
     /// ```
-    /// # use hebrides::Real;
+    /// # use hebrides::{Real, ApproxEq};
     /// let theta = Real::new(std::f64::consts::PI);
-    /// assert_eq!(theta.sin(), Real::ZERO);
+    /// assert!(theta.sin().abs_diff_eq(&Real::ZERO, 1e-15));
     /// ```
     pub fn sin(&self) -> Self {
-        Real::new(self.inner.sin())
+        Real::new(libm::sin(self.inner))
     }
 
     /// Cosine.
@@ -233,18 +312,18 @@ impl Real {
     /// assert_eq!(theta.cos(), -Real::ONE);
     /// ```
     pub fn cos(&self) -> Self {
-        Real::new(self.inner.cos())
+        Real::new(libm::cos(self.inner))
     }
 
     /// Tangent.
     ///
     /// ```
-    /// # use hebrides::Real;
+    /// # use hebrides::{Real, ApproxEq};
     /// let theta = Real::new(std::f64::consts::FRAC_PI_4);
-    /// assert_eq!(theta.tan(), Real::ONE);
+    /// assert!(theta.tan().ulps_eq(&Real::ONE, 1));
     /// ```
     pub fn tan(&self) -> Self {
-        Real::new(self.inner.tan())
+        Real::new(libm::tan(self.inner))
     }
 
     /// Arcsine.
@@ -261,7 +340,7 @@ impl Real {
         if self.inner < -1.0 || self.inner > 1.0 {
             return Err(DomainError);
         }
-        Ok(Angle::from_radians(self.inner.asin()))
+        Ok(Angle::from_radians(libm::asin(self.inner)))
     }
 
     /// Arccosine.
@@ -278,7 +357,7 @@ impl Real {
         if self.inner < -1.0 || self.inner > 1.0 {
             return Err(DomainError);
         }
-        Ok(Angle::from_radians(self.inner.acos()))
+        Ok(Angle::from_radians(libm::acos(self.inner)))
     }
 
     /// Arctangent.
@@ -291,7 +370,7 @@ impl Real {
     /// assert_eq!(h.arctan().to_radians(), 1.0_f64.atan());
     /// ```
     pub fn arctan(&self) -> Angle {
-        Angle::from_radians(self.inner.atan())
+        Angle::from_radians(libm::atan(self.inner))
     }
 
     /// Hyperbolic sine.
@@ -302,7 +381,7 @@ impl Real {
     /// assert_eq!(x.sinh(), Real::new(2.0_f64.sinh()));
     /// ```
     pub fn sinh(&self) -> Self {
-        Real::new(self.inner.sinh())
+        Real::new(libm::sinh(self.inner))
     }
 
     /// Hyperbolic cosine.
@@ -313,7 +392,7 @@ impl Real {
     /// assert_eq!(x.cosh(), Real::new(3.0_f64.cosh()));
     /// ```
     pub fn cosh(&self) -> Self {
-        Real::new(self.inner.cosh())
+        Real::new(libm::cosh(self.inner))
     }
 
     /// Hyperbolic tangent.
@@ -324,18 +403,18 @@ impl Real {
     /// assert_eq!(x.tanh(), Real::new(4.0_f64.tanh()));
     /// ```
     pub fn tanh(&self) -> Self {
-        Real::new(self.inner.tanh())
+        Real::new(libm::tanh(self.inner))
     }
 
     /// Hyperbolic arcsine.
     ///
     /// ```
-    /// # use hebrides::Real;
+    /// # use hebrides::{Real, ApproxEq};
     /// let x = Real::new(3.0);
-    /// assert_eq!(x.arcsinh().to_radians(), 3.0_f64.asinh());
+    /// assert!(x.arcsinh().to_radians().ulps_eq(&3.0_f64.asinh(), 1));
     /// ```
     pub fn arcsinh(&self) -> Angle {
-        Angle::from_radians(self.inner.asinh())
+        Angle::from_radians(libm::asinh(self.inner))
     }
 
     /// Hyperbolic arccosine.
@@ -351,7 +430,7 @@ impl Real {
         if self.inner < 1.0 {
             return Err(DomainError);
         }
-        Ok(Angle::from_radians(self.inner.acosh()))
+        Ok(Angle::from_radians(libm::acosh(self.inner)))
     }
 
     /// Hyperbolic arctangent.
@@ -367,20 +446,20 @@ impl Real {
         if self.inner <= -1.0 || self.inner >= 1.0 {
             return Err(DomainError);
         }
-        Ok(Angle::from_radians(self.inner.atanh()))
+        Ok(Angle::from_radians(libm::atanh(self.inner)))
     }
 
     /// e to the power of `self`.
     ///
     /// ```
-    /// # use hebrides::Real;
+    /// # use hebrides::{Real, ApproxEq};
     /// let x = Real::ZERO;
     /// assert_eq!(x.exp(), Real::ONE);
     /// let y = Real::ONE;
-    /// assert_eq!(Real::ONE.exp(), Real::new(std::f64::consts::E));
+    /// assert!(y.exp().ulps_eq(&Real::new(std::f64::consts::E), 1));
     /// ```
     pub fn exp(&self) -> Self {
-        Real::new(self.inner.exp())
+        Real::new(libm::exp(self.inner))
     }
 
     /// `self` to the power of `power`.
@@ -392,7 +471,7 @@ impl Real {
     /// assert_eq!(x*x, x.pow(Real::new(2.0)));
     /// ```
     pub fn pow(&self, power: Self) -> Self {
-        Real::new(self.inner.powf(power.inner))
+        Real::new(libm::powf(self.inner, power.inner))
     }
 
     /// `self` to the power of `power`.
@@ -410,7 +489,7 @@ impl Real {
         if self.negative() {
             return Err(DomainError);
         }
-        Ok(Real::new(self.inner.powf(power)))
+        Ok(Real::new(libm::powf(self.inner, power)))
     }
 
     /// `self` to the power of `power`.
@@ -422,7 +501,7 @@ impl Real {
     /// assert_eq!(x*x, x.powi(2));
     /// ```
     pub fn powi(&self, power: i64) -> Self {
-        Real::new(self.inner.powf(power as f64))
+        Real::new(libm::powf(self.inner, power as f64))
     }
 
     /// Returns the square of `self`.
@@ -446,7 +525,21 @@ impl Real {
     /// assert_eq!(x.sqrt().unwrap(), Real::new(2.0));
     /// ```
     pub fn sqrt(&self) -> Result<Self, DomainError> {
-        self.powf(0.5)
+        if self.negative() {
+            return Err(DomainError);
+        }
+        Ok(Real::new(self.inner.sqrt()))
+    }
+
+    /// Returns the cube root of `self`, which unlike the square root is
+    /// defined for negative values.
+    ///
+    /// ```
+    /// # use hebrides::Real;
+    /// assert_eq!(Real::new(-8.0).cbrt(), Real::new(-2.0));
+    /// ```
+    pub fn cbrt(&self) -> Self {
+        Real::new(libm::cbrt(self.inner))
     }
 
     /// Natural logarithm.
@@ -464,7 +557,7 @@ impl Real {
         if self.inner <= 0.0 {
             return Err(DomainError);
         }
-        Ok(Real::new(self.inner.ln()))
+        Ok(Real::new(libm::ln(self.inner)))
     }
 
     /// Logarithm with base 'base'.
@@ -481,7 +574,7 @@ impl Real {
         if self.inner <= 0.0 || base.inner <= 0.0 {
             return Err(DomainError);
         }
-        Ok(Real::new(self.inner.log(base.inner)))
+        Ok(Real::new(libm::log(self.inner, base.inner)))
     }
 
     /// Logarithm with base 'base'.
@@ -498,7 +591,7 @@ impl Real {
         if self.inner <= 0.0 || base <= 0.0 {
             return Err(DomainError);
         }
-        Ok(Real::new(self.inner.log(base)))
+        Ok(Real::new(libm::log(self.inner, base)))
     }
 
     /// Logarithm with base 'base'.
@@ -515,7 +608,7 @@ impl Real {
         if self.inner <= 0.0 || base <= 0 {
             return Err(DomainError);
         }
-        Ok(Real::new(self.inner.log(base as f64)))
+        Ok(Real::new(libm::log(self.inner, base as f64)))
     }
 
     /// Constructs a [`Complex`] from `self`.
@@ -537,9 +630,20 @@ impl std::fmt::Display for Real {
     }
 }
 
+/// Parses a Real from anything [`f64`] parses, such as `"3.5"`, `"-2e-3"`,
+/// or `"inf"`, ignoring surrounding whitespace.
+impl std::str::FromStr for Real {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        s.trim().parse().map(Real::new).map_err(|_| ParseError)
+    }
+}
+
+/// Equality agrees exactly with the total order on [`Real`] below; use
+/// [`ApproxEq`] to compare up to a tolerance.
 impl PartialEq for Real {
     fn eq(&self, other: &Self) -> bool {
-        approx_eq(self.inner, other.inner)
+        self.cmp(other) == std::cmp::Ordering::Equal
     }
 }
 
@@ -551,15 +655,32 @@ impl PartialOrd for Real {
     }
 }
 
+/// Reals are totally ordered, so that they can be sorted and used as keys
+/// of ordered collections. NaN is greater than every other value, including
+/// infinity, and all NaNs are equal to one another regardless of sign or
+/// payload; negative and positive zero are equal.
+///
+/// ```
+/// # use hebrides::Real;
+/// let mut xs: Vec<Real> = [2.0, f64::NAN, -1.0, f64::INFINITY].map(Real::new).to_vec();
+/// xs.sort();
+/// assert_eq!(xs[..3], [Real::new(-1.0), Real::new(2.0), Real::new(f64::INFINITY)]);
+/// assert!(xs[3].value().is_nan());
+/// ```
 impl Ord for Real {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        if self.inner < other.inner {
-            return std::cmp::Ordering::Less;
-        }
-        if self.inner > other.inner {
-            return std::cmp::Ordering::Greater;
+        match (self.inner.is_nan(), other.inner.is_nan()) {
+            (true, true) => std::cmp::Ordering::Equal,
+            (true, false) => std::cmp::Ordering::Greater,
+            (false, true) => std::cmp::Ordering::Less,
+            (false, false) => self.inner.partial_cmp(&other.inner).unwrap()
         }
-        std::cmp::Ordering::Equal
+    }
+}
+
+impl Default for Real {
+    fn default() -> Self {
+        Real::new(0.0)
     }
 }
 
@@ -621,6 +742,185 @@ impl_real_from_primitive![i64];
 impl_real_from_primitive![f32];
 impl_real_from_primitive![f64];
 
+/// Scalars that behave like floating point numbers.
+///
+/// This lets algorithms needing little more than a square root, such as
+/// [`Vector::norm`](crate::linal::Vector::norm), be written once for
+/// [`f32`], [`f64`], and [`Real`].
+pub trait Float: Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Neg<Output = Self> {
+
+    /// The additive identity.
+    const ZERO: Self;
+
+    /// The multiplicative identity.
+    const ONE: Self;
+
+    /// Returns the square root of `self`, which is NaN if `self` is negative.
+    fn sqrt(&self) -> Self;
+
+    /// Returns the absolute value of `self`.
+    fn abs(&self) -> Self;
+
+}
+
+macro_rules! impl_float_primitive {
+    ($t:ty) => {
+        impl Float for $t {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+            fn sqrt(&self) -> Self {
+                <$t>::sqrt(*self)
+            }
+            fn abs(&self) -> Self {
+                <$t>::abs(*self)
+            }
+        }
+    }
+}
+
+impl_float_primitive![f32];
+impl_float_primitive![f64];
+
+impl Float for Real {
+    const ZERO: Self = Real::ZERO;
+    const ONE: Self = Real::ONE;
+    fn sqrt(&self) -> Self {
+        Real::sqrt(self).unwrap_or(Real::new(f64::NAN))
+    }
+    fn abs(&self) -> Self {
+        Real::abs(self)
+    }
+}
+
+/// Scalars with a complex conjugate.
+///
+/// Conjugation is the identity on real scalars. This is what lets
+/// [`Vector::dot`](crate::linal::Vector::dot) compute the Hermitian inner
+/// product of complex vectors while leaving real dot products unchanged.
+///
+/// ```
+/// # use hebrides::{Complex, Conjugate};
+/// assert_eq!(Conjugate::conjugate(&3.5), 3.5);
+/// assert_eq!(Conjugate::conjugate(&Complex::new(1.0, 2.0)), Complex::new(1.0, -2.0));
+/// ```
+pub trait Conjugate {
+
+    /// Returns the complex conjugate of `self`.
+    fn conjugate(&self) -> Self;
+
+}
+
+macro_rules! impl_real_conjugate {
+    ($($t:ty),*) => {
+        $(
+            impl Conjugate for $t {
+                fn conjugate(&self) -> Self {
+                    *self
+                }
+            }
+        )*
+    }
+}
+
+impl_real_conjugate![i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, Real];
+
+impl Conjugate for Complex {
+    fn conjugate(&self) -> Self {
+        Complex::conjugate(self)
+    }
+}
+
+/// Approximate equality for float-backed values.
+///
+/// Exact equality is rarely the right question to ask of the result of a
+/// floating-point computation. Values can instead be compared within an
+/// absolute tolerance, within a tolerance relative to their magnitude, or
+/// within a number of units in the last place (ULPs). Compound values such
+/// as [`Complex`] numbers compare component by component and are equal only
+/// if every component is.
+///
+/// ```
+/// # use hebrides::{ApproxEq, Real};
+/// let x: f64 = 0.1 + 0.2;
+/// assert_ne!(x, 0.3);
+/// assert!(x.abs_diff_eq(&0.3, 1e-15));
+/// assert!(x.relative_eq(&0.3, 0.0, 1e-15));
+/// assert!(Real::new(x).ulps_eq(&Real::new(0.3), 1));
+/// ```
+pub trait ApproxEq {
+
+    /// Returns whether `self` and `other` differ by at most `epsilon`.
+    fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool;
+
+    /// Returns whether `self` and `other` differ by at most `epsilon` or by
+    /// at most `max_relative` times the larger of their magnitudes.
+    ///
+    /// The absolute `epsilon` is what makes comparisons against zero
+    /// meaningful, where any relative tolerance vanishes.
+    fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool;
+
+    /// Returns whether `self` and `other` are at most `max_ulps`
+    /// representable floats apart. Values of opposite sign are never
+    /// equal under this measure, except for positive and negative zero.
+    fn ulps_eq(&self, other: &Self, max_ulps: u64) -> bool;
+
+}
+
+macro_rules! impl_approx_eq_primitive {
+    ($t:ty) => {
+        impl ApproxEq for $t {
+            fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+                self == other || ((*self - *other).abs() as f64) <= epsilon
+            }
+            fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool {
+                if self == other {
+                    return true;
+                }
+                let difference = (*self - *other).abs() as f64;
+                let largest = self.abs().max(other.abs()) as f64;
+                difference <= epsilon || difference <= largest * max_relative
+            }
+            fn ulps_eq(&self, other: &Self, max_ulps: u64) -> bool {
+                if self == other {
+                    return true;
+                }
+                if self.is_nan() || other.is_nan() || self.is_sign_negative() != other.is_sign_negative() {
+                    return false;
+                }
+                (self.to_bits().abs_diff(other.to_bits()) as u64) <= max_ulps
+            }
+        }
+    }
+}
+
+impl_approx_eq_primitive![f32];
+impl_approx_eq_primitive![f64];
+
+impl ApproxEq for Real {
+    fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self.inner.abs_diff_eq(&other.inner, epsilon)
+    }
+    fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool {
+        self.inner.relative_eq(&other.inner, epsilon, max_relative)
+    }
+    fn ulps_eq(&self, other: &Self, max_ulps: u64) -> bool {
+        self.inner.ulps_eq(&other.inner, max_ulps)
+    }
+}
+
+impl ApproxEq for Complex {
+    fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self.real.abs_diff_eq(&other.real, epsilon) && self.imag.abs_diff_eq(&other.imag, epsilon)
+    }
+    fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool {
+        self.real.relative_eq(&other.real, epsilon, max_relative)
+            && self.imag.relative_eq(&other.imag, epsilon, max_relative)
+    }
+    fn ulps_eq(&self, other: &Self, max_ulps: u64) -> bool {
+        self.real.ulps_eq(&other.real, max_ulps) && self.imag.ulps_eq(&other.imag, max_ulps)
+    }
+}
+
 /// Representation of complex numbers
 #[derive(Copy, Clone, Debug)]
 pub struct Complex {
@@ -651,11 +951,27 @@ impl Complex {
     pub fn from_polar(r: f64, theta: Angle) -> Self {
         let as_radians = theta.to_radians();
         Self {
-            real: Real::new(r*as_radians.cos()),
-            imag: Real::new(r*as_radians.sin())
+            real: Real::new(r*libm::cos(as_radians)),
+            imag: Real::new(r*libm::sin(as_radians))
         }
     }
 
+    /// Returns the real part of `self`.
+    ///
+    /// ```
+    /// # use hebrides::Complex;
+    /// let z = Complex::new(3.0, 2.0);
+    /// assert_eq!((z.re(), z.im()), (3.0, 2.0));
+    /// ```
+    pub fn re(&self) -> f64 {
+        self.real.inner
+    }
+
+    /// Returns the imaginary part of `self`.
+    pub fn im(&self) -> f64 {
+        self.imag.inner
+    }
+
     /// Returns whether or not `self` is real.
     ///
     /// ```
@@ -720,14 +1036,14 @@ impl Complex {
     /// Evaluates e to the power of `self`.
     ///
     /// ```
-    /// # use hebrides::Complex;
+    /// # use hebrides::{Complex, ApproxEq};
     /// let e = Complex::new(std::f64::consts::E, 0.0);
     /// let z = Complex::new(1.0, std::f64::consts::FRAC_PI_2);
-    /// assert_eq!(z.exp(), e*Complex::I);
+    /// assert!(z.exp().relative_eq(&(e*Complex::I), 1e-15, 1e-15));
     /// let q = Complex::new(1.0, 1.0);
-    /// assert_eq!(q.exp(), Complex::new(1.4686939399158851, 2.2873552871788423));
+    /// assert!(q.exp().relative_eq(&Complex::new(1.4686939399158851, 2.2873552871788423), 1e-15, 1e-15));
     /// let w = Complex::new(3.0, 7.0);
-    /// assert_eq!(w.exp(), Complex::new(15.142531566086864, 13.195928586605717))
+    /// assert!(w.exp().relative_eq(&Complex::new(15.142531566086864, 13.195928586605717), 1e-15, 1e-15))
     /// ```
     pub fn exp(&self) -> Complex {
         let real_exp = Complex::new(self.real.exp().inner, 0.0);
@@ -740,13 +1056,11 @@ impl Complex {
     /// ```
     /// # use hebrides::Complex;
     /// let z = Complex::new(1.0, std::f64::consts::FRAC_PI_2);
-    /// assert_eq!(z.sin(), Complex::new(2.1114008854951747, 1.2433971034084503));
+    /// assert!((z.sin() - Complex::new(2.1114008854951747, 1.2433971034084503)).modulus() < 1e-14);
     /// ```
     pub fn sin(&self) -> Complex {
-        let power = Complex::I * *self;
-        let num = power.exp() - (-power).exp();
-        let den = Complex::new(0.0, 2.0);
-        num / den
+        let (x, y) = (self.real.inner, self.imag.inner);
+        Complex::new(libm::sin(x) * libm::cosh(y), libm::cos(x) * libm::sinh(y))
     }
 
     /// Complex cosine.
@@ -756,13 +1070,11 @@ impl Complex {
     /// let z = Complex::new(0.0, 0.0);
     /// assert_eq!(z.cos(), Complex::new(1.0, 0.0));
     /// let w = Complex::new(1.0, 2.0);
-    /// assert_eq!(w.cos(), Complex::new(2.0327230070196656, -3.0518977991517997));
+    /// assert!((w.cos() - Complex::new(2.0327230070196656, -3.0518977991517997)).modulus() < 1e-14);
     /// ```
     pub fn cos(&self) -> Complex {
-        let power = Complex::I * *self;
-        let num = power.exp() + (-power).exp();
-        let den = Complex::new(2.0, 0.0);
-        num / den
+        let (x, y) = (self.real.inner, self.imag.inner);
+        Complex::new(libm::cos(x) * libm::cosh(y), -libm::sin(x) * libm::sinh(y))
     }
 
     /// Complex tangent.
@@ -773,7 +1085,15 @@ impl Complex {
     /// assert_eq!(z.tan(), Complex::new(-0.0037640256415040815, 1.0032386273536098));
     /// ```
     pub fn tan(&self) -> Complex {
-        self.sin() / self.cos()
+        if self.imag.inner == 0.0 {
+            return Complex::new(libm::tan(self.real.inner), self.imag.inner);
+        }
+        let (x, y) = (2.0 * self.real.inner, 2.0 * self.imag.inner);
+        if y.abs() > 40.0 {
+            return Complex::new(0.0, y.signum());
+        }
+        let den = libm::cos(x) + libm::cosh(y);
+        Complex::new(libm::sin(x) / den, libm::sinh(y) / den)
     }
 
     /// Complex arcsin.
@@ -790,14 +1110,17 @@ impl Complex {
     /// let x = Complex::new(0.8, 0.0);
     /// assert_eq!(x.arcsin(), Real::new(Real::new(0.8).arcsin().unwrap().to_radians()).to_complex());
     /// let z = Complex::new(2.0, 3.0);
-    /// assert_eq!(z.arcsin(), Complex::new(1.5413069599437077, -1.3679718444908728));
+    /// assert_eq!(z.arcsin(), Complex::new(0.570652784321099, 1.9833870299165357));
     /// ```
     pub fn arcsin(&self) -> Complex {
         -Complex::I * (*self*Complex::I + (Complex::ONE - self.squared()).sqrt()).ln()
     }
 
     /// Complex arccos.
-    /// 
+    ///
+    /// Computed as pi/2 - arcsin(z), so that its branch cuts, along the real
+    /// axis outside [-1, 1], match those of [`Complex::arcsin`].
+    ///
     /// Even if `self` is real, this will return only a [`Complex`] and not an [`Angle`]
     /// as might be expected based off of the behavior of the arccosine implementation on
     /// [`Real`].
@@ -813,7 +1136,7 @@ impl Complex {
     /// assert_eq!(z.arccos(), Complex::new(1.0001435424737972, -1.9833870299165355));
     /// ```
     pub fn arccos(&self) -> Complex {
-        -Complex::I * (*self + (self.squared() - Complex::ONE).sqrt()).ln()
+        Complex::new(std::f64::consts::FRAC_PI_2, 0.0) - self.arcsin()
     }
 
     /// Complex arctan.
@@ -844,10 +1167,11 @@ impl Complex {
     /// ```
     /// # use hebrides::Complex;
     /// let z = Complex::new(3.0, 2.0);
-    /// assert_eq!(z.sinh(), Complex::new(-4.168906959966565, 9.154499146911428));
+    /// assert!((z.sinh() - Complex::new(-4.168906959966565, 9.154499146911428)).modulus() < 1e-14);
     /// ```
     pub fn sinh(&self) -> Complex {
-        (self.exp() - ((-*self).exp())) / Real::new(2.0).to_complex()
+        let (x, y) = (self.real.inner, self.imag.inner);
+        Complex::new(libm::sinh(x) * libm::cos(y), libm::cosh(x) * libm::sin(y))
     }
 
     /// Complex hyperbolic cosine.
@@ -858,7 +1182,8 @@ impl Complex {
     /// assert_eq!(z.cosh(), Complex::new(-4.189625690968807, 9.109227893755337));
     /// ```
     pub fn cosh(&self) -> Complex {
-        (self.exp() + ((-*self).exp())) / Real::new(2.0).to_complex()
+        let (x, y) = (self.real.inner, self.imag.inner);
+        Complex::new(libm::cosh(x) * libm::cos(y), libm::sinh(x) * libm::sin(y))
     }
 
     /// Complex hyperbolic tangent.
@@ -869,7 +1194,8 @@ impl Complex {
     /// assert_eq!(z.tanh(), Complex::new(1.0032386273536098, -0.0037640256415040815));
     /// ```
     pub fn tanh(&self) -> Complex {
-        (self.exp() - (-*self).exp()) / (self.exp() + (-*self).exp())
+        let tan = Complex::new(-self.imag.inner, self.real.inner).tan();
+        Complex::new(tan.imag.inner, -tan.real.inner)
     }
 
     /// Complex inverse hyperbolic sine.
@@ -887,8 +1213,11 @@ impl Complex {
     }
 
     /// Complex inverse hyperbolic cosine.
-    /// 
-    /// Mathematical justification for the implementation can be found at 
+    ///
+    /// Computed as ln(z + sqrt(z + 1) sqrt(z - 1)), the principal branch,
+    /// whose real part is never negative.
+    ///
+    /// Mathematical justification for the implementation can be found at
     /// [Wolfram Research](https://mathworld.wolfram.com/InverseHyperbolicCosine.html).
     ///
     /// ```
@@ -897,13 +1226,13 @@ impl Complex {
     /// assert_eq!(z.arccosh(), Complex::new(1.528570919480998, 1.1437177404024204));
     /// ```
     pub fn arccosh(&self) -> Complex {
-        (*self + (self.squared() - Complex::ONE).sqrt()).ln()
+        (*self + (*self + Complex::ONE).sqrt() * (*self - Complex::ONE).sqrt()).ln()
     }
 
     /// Complex inverse hyperbolic tangent.
     ///
-    /// The result here is only valid for `self`.norm() < 1.0. Any other value will yeild
-    /// an Err(DomainError).
+    /// Computed as ln((1 + z) / (1 - z)) / 2. The function has poles at 1
+    /// and -1, where this yields an Err(DomainError).
     ///
     /// Mathematical justification for the implementation can be found at
     /// [Wolfram Research](https://mathworld.wolfram.com/InverseHyperbolicTangent.html).
@@ -911,30 +1240,33 @@ impl Complex {
     /// ```
     /// # use hebrides::{Real, Complex};
     /// let z = Complex::new(0.5, 0.25);
-    /// assert_eq!(z.arctanh().unwrap(), Complex::new(-0.5003700000525311, -0.31439814320771653));
+    /// assert!((z.arctanh().unwrap() - Complex::new(0.5003700000525311, 0.31439814320771653)).modulus() < 1e-15);
+    /// assert!(Complex::ONE.arctanh().is_err());
+    /// ```
     pub fn arctanh(&self) -> Result<Complex, DomainError> {
-        if self.norm() >= Real::ONE {
+        if self.imag.inner == 0.0 && self.real.inner.abs() == 1.0 {
             return Err(DomainError);
         }
-        let num = Complex::ONE - *self;
-        let den = Complex::ONE + *self;
+        let num = Complex::ONE + *self;
+        let den = Complex::ONE - *self;
         Ok(Real::new(0.5).to_complex() * (num / den).ln())
     }
 
     /// Complex natural logarithm.
     ///
-    /// Computes the natural logarithm of `self` on the principal branch of Ln(x).
+    /// Computes the natural logarithm of `self` on the principal branch of
+    /// Ln(x), whose imaginary part is the [argument](Complex::arg) of `self`
+    /// in (-pi, pi].
     ///
     /// ```
     /// # use hebrides::Complex;
     /// let z = Complex::new(3.0, 4.0);
     /// assert_eq!(z.ln(), Complex::new(5.0_f64.ln(), 0.8_f64.asin()));
+    /// let x = Complex::new(-1.0, 0.0);
+    /// assert_eq!(x.ln(), Complex::new(0.0, std::f64::consts::PI));
     /// ```
     pub fn ln(&self) -> Complex {
-        if self.is_real() {
-            return Complex::new(self.real.ln().unwrap().inner, 0.0);
-        }
-        Complex::new(self.norm().ln().unwrap().inner, self.azimuthal().to_radians())
+        Complex::new(libm::ln(self.modulus()), self.arg())
     }
 
     /// Complex norm.
@@ -1001,7 +1333,49 @@ impl Complex {
         if self.real > Real::ZERO && self.imag < Real::ZERO {
             return (self.imag / self.norm()).arcsin().unwrap();
         }
-        return Angle::from_radians(0.0);
+        Angle::from_radians(0.0)
+    }
+
+    /// Modulus of `self`, the same value as [`norm`](Complex::norm) but as an
+    /// `f64` and computed without undue overflow or underflow.
+    ///
+    /// ```
+    /// # use hebrides::Complex;
+    /// assert!((Complex::new(3e200, 4e200).modulus() / 5e200 - 1.0).abs() < 1e-15);
+    /// ```
+    pub fn modulus(&self) -> f64 {
+        libm::hypot(self.real.inner, self.imag.inner)
+    }
+
+    /// Argument of `self` in radians, in the range (-pi, pi].
+    ///
+    /// Unlike [`azimuthal`](Complex::azimuthal), which ranges over [0, 2pi),
+    /// this follows the convention of `atan2`, so it is continuous across
+    /// the positive real axis. A negative zero imaginary part on the
+    /// negative real axis gives -pi.
+    ///
+    /// ```
+    /// # use hebrides::Complex;
+    /// use std::f64::consts::FRAC_PI_2;
+    /// assert_eq!(Complex::new(0.0, -2.0).arg(), -FRAC_PI_2);
+    /// assert_eq!(Complex::new(-1.0, 0.0).arg(), std::f64::consts::PI);
+    /// ```
+    pub fn arg(&self) -> f64 {
+        libm::atan2(self.imag.inner, self.real.inner)
+    }
+
+    /// Polar coordinates of `self`: its modulus and argument, the inverse of
+    /// [`from_polar`](Complex::from_polar).
+    ///
+    /// ```
+    /// # use hebrides::{Angle, Complex};
+    /// let z = Complex::from_polar(2.0, Angle::from_degrees(150.0));
+    /// let (r, theta) = z.to_polar();
+    /// assert!((r - 2.0).abs() < 1e-15);
+    /// assert!((theta.to_degrees() - 150.0).abs() < 1e-12);
+    /// ```
+    pub fn to_polar(&self) -> (f64, Angle) {
+        (self.modulus(), Angle::from_radians(self.arg()))
     }
 
     /// Returns `self` squared.
@@ -1015,18 +1389,61 @@ impl Complex {
         *self * *self
     }
 
-    /// Returns the square root of `self`.
+    /// Returns the principal square root of `self`, which has a nonnegative
+    /// real part and an imaginary part of the same sign as that of `self`.
     ///
     /// ```
     /// # use hebrides::Complex;
     /// let z = Complex::new(2.0, 3.0);
     /// assert_eq!(z.sqrt(), Complex::new(1.67414922803554, 0.8959774761298381));
+    /// assert_eq!(z.conjugate().sqrt(), z.sqrt().conjugate());
     /// ```
     pub fn sqrt(&self) -> Complex {
-        let root_r = self.norm().sqrt().unwrap().to_complex();
-        let theta = self.azimuthal().to_radians();
-        root_r * Complex::new(((1.0 + theta.cos()) / 2.0).sqrt(), ((1.0 - theta.cos()) / 2.0).sqrt())
-        
+        let (re, im) = (self.real.inner, self.imag.inner);
+        if re == 0.0 && im == 0.0 {
+            return Complex::ZERO;
+        }
+        let t = ((self.modulus() + re.abs()) / 2.0).sqrt();
+        if re >= 0.0 {
+            Complex::new(t, im / (2.0 * t))
+        } else {
+            Complex::new(im.abs() / (2.0 * t), t.copysign(im))
+        }
+    }
+
+    /// Returns `self` raised to the real power `n`, on the principal branch.
+    ///
+    /// ```
+    /// # use hebrides::Complex;
+    /// let z = Complex::new(0.0, 2.0);
+    /// assert!((z.powf(2.0) - Complex::new(-4.0, 0.0)).modulus() < 1e-15);
+    /// let root = Complex::new(-8.0, 0.0).powf(1.0 / 3.0);
+    /// assert!((root - Complex::new(1.0, 3.0_f64.sqrt())).modulus() < 1e-15);
+    /// ```
+    pub fn powf(&self, n: f64) -> Complex {
+        if self.real.inner == 0.0 && self.imag.inner == 0.0 {
+            return if n == 0.0 { Complex::ONE } else { Complex::ZERO };
+        }
+        Complex::from_polar(libm::powf(self.modulus(), n), Angle::from_radians(n * self.arg()))
+    }
+
+    /// Returns `self` raised to the complex power `w`, exp(w Ln(`self`)), on
+    /// the principal branch.
+    ///
+    /// Zero raised to zero is one, and zero raised to any other power is
+    /// zero.
+    ///
+    /// ```
+    /// # use hebrides::Complex;
+    /// // i^i = e^(-pi/2)
+    /// let z = Complex::I.powc(Complex::I);
+    /// assert_eq!(z, Complex::new((-std::f64::consts::FRAC_PI_2).exp(), 0.0));
+    /// ```
+    pub fn powc(&self, w: Complex) -> Complex {
+        if self.real.inner == 0.0 && self.imag.inner == 0.0 {
+            return if w == Complex::ZERO { Complex::ONE } else { Complex::ZERO };
+        }
+        (w * self.ln()).exp()
     }
 
 }
@@ -1037,6 +1454,45 @@ impl std::fmt::Display for Complex {
     }
 }
 
+/// Parses a Complex in rectangular form a + bi, where either part may be
+/// omitted, a unit imaginary coefficient may be left implicit, and each part
+/// may use scientific notation. Whitespace is ignored, so the output of
+/// [`Display`](std::fmt::Display) parses back.
+///
+/// ```
+/// # use hebrides::Complex;
+/// assert_eq!("2+3i".parse::<Complex>().unwrap(), Complex::new(2.0, 3.0));
+/// assert_eq!("-i".parse::<Complex>().unwrap(), -Complex::I);
+/// assert_eq!("1.5e2 - 2e-1i".parse::<Complex>().unwrap(), Complex::new(150.0, -0.2));
+/// assert_eq!("4".parse::<Complex>().unwrap(), Complex::new(4.0, 0.0));
+/// assert!("2+3".parse::<Complex>().is_err());
+/// ```
+impl std::str::FromStr for Complex {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        let Some(body) = s.strip_suffix('i') else {
+            return Ok(Complex { real: s.parse()?, imag: Real::ZERO });
+        };
+        // The imaginary part starts at the first sign that neither leads the
+        // string nor belongs to an exponent.
+        let bytes = body.as_bytes();
+        let split = (1..bytes.len()).find(|k| {
+            matches!(bytes[*k], b'+' | b'-') && !matches!(bytes[*k - 1], b'e' | b'E')
+        });
+        let (real, imag) = match split {
+            Some(k) => (body[..k].parse()?, &body[k..]),
+            None => (Real::ZERO, body)
+        };
+        let imag = match imag.strip_prefix('+').unwrap_or(imag) {
+            "" => Real::ONE,
+            "-" => -Real::ONE,
+            coefficient => coefficient.parse()?
+        };
+        Ok(Complex { real, imag })
+    }
+}
+
 impl PartialEq for Complex {
     fn eq(&self, other: &Self) -> bool {
         let reals_eq = approx_eq(self.real.inner, other.real.inner);
@@ -1047,6 +1503,12 @@ impl PartialEq for Complex {
 
 impl Eq for Complex {}
 
+impl Default for Complex {
+    fn default() -> Self {
+        Complex::new(0.0, 0.0)
+    }
+}
+
 impl Add<Self> for Complex {
     type Output = Self;
     fn add(self, other: Self) -> Self {
@@ -1101,6 +1563,380 @@ impl TryFrom<Complex> for Real {
     }
 }
 
+/// An affine form x₀ + x₁ε₁ + ... + x_Nε_N ± e over `N` shared noise
+/// symbols εᵢ ∈ [-1, 1].
+///
+/// Unlike an [`Interval`], an affine form records which sources of
+/// uncertainty a quantity depends on, so correlated errors cancel: for
+/// x = 1 + ε₁, x - x is 0 where interval arithmetic gives [-2, 2]. Linear
+/// operations are exact up to rounding, while nonlinear operations fold
+/// their approximation error, along with all rounding error, into the
+/// uncorrelated radius e.
+///
+/// ```
+/// # use hebrides::Affine;
+/// let x = Affine::<2>::variable(1.0, 0.5, 0);
+/// let y = Affine::<2>::variable(2.0, 0.1, 1);
+/// let z = (x + y) - x;
+/// assert!((z.radius() - 0.1).abs() < 1e-12);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Affine<const N: usize> {
+    center: f64,
+    coefficients: [f64; N],
+    error: f64
+}
+
+impl<const N: usize> Affine<N> {
+
+    /// Constructs the exact affine form for `x`.
+    pub fn constant(x: f64) -> Self {
+        Self { center: x, coefficients: [0.0; N], error: 0.0 }
+    }
+
+    /// Constructs the affine form `center` + `radius`·ε, attributing the
+    /// uncertainty to noise symbol number `symbol`.
+    ///
+    /// # Panics
+    /// Panics if `symbol` is not less than `N`.
+    pub fn variable(center: f64, radius: f64, symbol: usize) -> Self {
+        if symbol >= N {
+            panic!("Noise symbol index must be less than the number of noise symbols")
+        }
+        let mut coefficients = [0.0; N];
+        coefficients[symbol] = radius.abs();
+        Self { center, coefficients, error: 0.0 }
+    }
+
+    /// Constructs the affine form spanning `interval`, attributing its width
+    /// to noise symbol number `symbol`.
+    ///
+    /// # Panics
+    /// Panics if `symbol` is not less than `N`.
+    pub fn from_interval(interval: &Interval, symbol: usize) -> Self {
+        let center = interval.mid();
+        let radius = (interval.hi() - center).max(center - interval.lo());
+        Self::variable(center, radius, symbol)
+    }
+
+    /// Returns the central value x₀.
+    pub fn center(&self) -> f64 {
+        self.center
+    }
+
+    /// Returns the coefficients of the noise symbols.
+    pub fn coefficients(&self) -> &[f64; N] {
+        &self.coefficients
+    }
+
+    /// Returns the radius of the uncorrelated error term.
+    pub fn error(&self) -> f64 {
+        self.error
+    }
+
+    /// Returns the total radius, the largest distance from the center to
+    /// any value the form can take.
+    pub fn radius(&self) -> f64 {
+        self.coefficients.iter().map(|c| c.abs()).sum::<f64>() + self.error
+    }
+
+    /// Returns the [`Interval`] of values the form can take.
+    pub fn to_interval(&self) -> Interval {
+        let radius = self.radius();
+        Interval::new((self.center - radius).next_down(), (self.center + radius).next_up())
+    }
+
+    fn rounded(mut self, error: f64) -> Self {
+        self.error = error + f64::EPSILON * (self.center.abs() + self.radius());
+        self
+    }
+
+    /// Returns the affine form 1/`self`, using the min-range linear
+    /// approximation of the reciprocal over the range of `self`.
+    ///
+    /// # Panics
+    /// Panics if the range of `self` contains zero.
+    pub fn recip(self) -> Self {
+        let (lo, hi) = (self.center - self.radius(), self.center + self.radius());
+        if lo <= 0.0 && 0.0 <= hi {
+            panic!("Cannot take the reciprocal of an Affine form whose range contains zero")
+        }
+        if hi < 0.0 {
+            return -(-self).recip();
+        }
+        let slope = -1.0 / (hi * hi);
+        let (largest, smallest) = (1.0 / lo - slope * lo, 1.0 / hi - slope * hi);
+        let result = Self {
+            center: slope * self.center + (largest + smallest) / 2.0,
+            coefficients: self.coefficients.map(|c| slope * c),
+            error: 0.0
+        };
+        result.rounded(slope.abs() * self.error + (largest - smallest) / 2.0)
+    }
+
+}
+
+impl<const N: usize> Default for Affine<N> {
+    fn default() -> Self {
+        Self::constant(0.0)
+    }
+}
+
+impl<const N: usize> From<Affine<N>> for Interval {
+    fn from(value: Affine<N>) -> Interval {
+        value.to_interval()
+    }
+}
+
+impl<const N: usize> Add<Self> for Affine<N> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let mut coefficients = self.coefficients;
+        for (c, d) in coefficients.iter_mut().zip(other.coefficients.iter()) {
+            *c += d;
+        }
+        let sum = Affine { center: self.center + other.center, coefficients, error: 0.0 };
+        sum.rounded(self.error + other.error)
+    }
+}
+
+impl<const N: usize> Sub<Self> for Affine<N> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<const N: usize> Mul<Self> for Affine<N> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let mut coefficients = [0.0; N];
+        for (c, (x, y)) in coefficients.iter_mut().zip(self.coefficients.iter().zip(other.coefficients.iter())) {
+            *c = self.center * y + other.center * x;
+        }
+        let product = Affine { center: self.center * other.center, coefficients, error: 0.0 };
+        let error = self.center.abs() * other.error + other.center.abs() * self.error + self.radius() * other.radius();
+        product.rounded(error)
+    }
+}
+
+impl<const N: usize> Div<Self> for Affine<N> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        Mul::mul(self, other.recip())
+    }
+}
+
+impl<const N: usize> Neg for Affine<N> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Affine { center: -self.center, coefficients: self.coefficients.map(|c| -c), error: self.error }
+    }
+}
+
+/// A measured value with a standard deviation, propagated through
+/// arithmetic to first order.
+///
+/// Each operation treats its operands as independent, so the variance of
+/// f(x, y) is (∂f/∂x)²σₓ² + (∂f/∂y)²σᵧ². Reusing a value within one
+/// expression therefore misstates its contribution (x - x keeps a nonzero
+/// deviation), and quantities that share error sources should be gathered
+/// into an
+/// [`UncertainVector`](crate::linal::UncertainVector), which tracks their
+/// full covariance.
+///
+/// ```
+/// # use hebrides::Uncertain;
+/// let length = Uncertain::new(3.0, 0.03);
+/// let width = Uncertain::new(4.0, 0.04);
+/// let area = length * width;
+/// assert_eq!(area.value(), 12.0);
+/// assert!((area.std_dev() - 0.12 * 2.0_f64.sqrt()).abs() < 1e-12);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Uncertain {
+    value: f64,
+    std_dev: f64
+}
+
+impl Uncertain {
+
+    /// Constructs `value` ± `std_dev`.
+    pub fn new(value: f64, std_dev: f64) -> Self {
+        Uncertain { value, std_dev: std_dev.abs() }
+    }
+
+    /// Constructs a value known exactly.
+    pub fn exact(value: f64) -> Self {
+        Uncertain { value, std_dev: 0.0 }
+    }
+
+    /// Returns the central value.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns the standard deviation.
+    pub fn std_dev(&self) -> f64 {
+        self.std_dev
+    }
+
+    /// Returns the variance, the square of the standard deviation.
+    pub fn variance(&self) -> f64 {
+        self.std_dev * self.std_dev
+    }
+
+    /// Returns the standard deviation relative to the magnitude of the
+    /// value.
+    pub fn relative(&self) -> f64 {
+        self.std_dev / self.value.abs()
+    }
+
+    /// Returns f(`self`), given the derivative f' of f at the value.
+    fn chain(&self, value: f64, derivative: f64) -> Self {
+        Uncertain::new(value, derivative * self.std_dev)
+    }
+
+    /// Returns e^`self`.
+    pub fn exp(&self) -> Self {
+        let value = libm::exp(self.value);
+        self.chain(value, value)
+    }
+
+    /// Returns the natural logarithm of `self`.
+    pub fn ln(&self) -> Self {
+        self.chain(libm::ln(self.value), 1.0 / self.value)
+    }
+
+    /// Returns the square root of `self`.
+    pub fn sqrt(&self) -> Self {
+        let value = self.value.sqrt();
+        self.chain(value, 0.5 / value)
+    }
+
+    /// Returns `self` raised to the exact power `n`.
+    pub fn powf(&self, n: f64) -> Self {
+        self.chain(libm::powf(self.value, n), n * libm::powf(self.value, n - 1.0))
+    }
+
+    /// Returns the sine of `self`, taken in radians.
+    pub fn sin(&self) -> Self {
+        self.chain(libm::sin(self.value), libm::cos(self.value))
+    }
+
+    /// Returns the cosine of `self`, taken in radians.
+    pub fn cos(&self) -> Self {
+        self.chain(libm::cos(self.value), -libm::sin(self.value))
+    }
+
+}
+
+impl std::fmt::Display for Uncertain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ± {}", self.value, self.std_dev)
+    }
+}
+
+impl From<f64> for Uncertain {
+    fn from(value: f64) -> Self {
+        Uncertain::exact(value)
+    }
+}
+
+impl Add<Self> for Uncertain {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Uncertain::new(self.value + other.value, libm::hypot(self.std_dev, other.std_dev))
+    }
+}
+
+impl Sub<Self> for Uncertain {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Uncertain::new(self.value - other.value, libm::hypot(self.std_dev, other.std_dev))
+    }
+}
+
+impl Mul<Self> for Uncertain {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let std_dev = libm::hypot(other.value * self.std_dev, self.value * other.std_dev);
+        Uncertain::new(self.value * other.value, std_dev)
+    }
+}
+
+impl Div<Self> for Uncertain {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        let value = self.value / other.value;
+        let std_dev = libm::hypot(self.std_dev / other.value, value * other.std_dev / other.value);
+        Uncertain::new(value, std_dev)
+    }
+}
+
+impl Neg for Uncertain {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Uncertain::new(-self.value, self.std_dev)
+    }
+}
+
+/// Mathematical constants.
+///
+/// Each constant is a [`Real`], with the same value available as a plain
+/// f64 under [`consts::float`](consts::float) for code that works with primitives.
+///
+/// ```
+/// # use hebrides::{Real, consts};
+/// assert_eq!(consts::PI, Real::new(consts::float::PI));
+/// assert_eq!(consts::TAU, consts::PI * Real::new(2.0));
+/// ```
+pub mod consts {
+
+    use super::{Complex, Real};
+
+    macro_rules! constants {
+        ($($(#[$doc:meta])* $name:ident = $value:expr;)*) => {
+            /// The constants of [`consts`](super) as f64 values.
+            pub mod float {
+                $($(#[$doc])* pub const $name: f64 = $value;)*
+            }
+            $($(#[$doc])* pub const $name: Real = Real { inner: float::$name };)*
+        };
+    }
+
+    constants! {
+        /// Archimedes' constant, π.
+        PI = std::f64::consts::PI;
+        /// The full circle constant, τ = 2π.
+        TAU = std::f64::consts::TAU;
+        /// Euler's number, e.
+        E = std::f64::consts::E;
+        /// The square root of 2.
+        SQRT_2 = std::f64::consts::SQRT_2;
+        /// The square root of 1/2.
+        FRAC_1_SQRT_2 = std::f64::consts::FRAC_1_SQRT_2;
+        /// π/2.
+        FRAC_PI_2 = std::f64::consts::FRAC_PI_2;
+        /// π/4.
+        FRAC_PI_4 = std::f64::consts::FRAC_PI_4;
+        /// 1/π.
+        FRAC_1_PI = std::f64::consts::FRAC_1_PI;
+        /// The natural logarithm of 2.
+        LN_2 = std::f64::consts::LN_2;
+        /// The natural logarithm of 10.
+        LN_10 = std::f64::consts::LN_10;
+        /// The golden ratio, φ = (1 + √5)/2.
+        GOLDEN_RATIO = 1.618033988749894848204586834365638118_f64;
+        /// The Euler-Mascheroni constant, γ.
+        EULER_GAMMA = 0.577215664901532860606512090082402431_f64;
+    }
+
+    /// The imaginary unit, i.
+    pub const I: Complex = Complex::I;
+
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -1134,12 +1970,103 @@ mod tests {
             assert_eq!(Real::new(12.0) / Real::ONE, Real::new(12.0));
         }
 
+        #[test]
+        fn total_order_handles_nan_and_zero() {
+            use std::collections::BTreeMap;
+            let nan = Real::new(f64::NAN);
+            assert_eq!(nan, Real::new(-f64::NAN));
+            assert!(nan > Real::new(f64::INFINITY));
+            assert_eq!(Real::new(f64::INFINITY), Real::new(f64::INFINITY));
+            assert_eq!(Real::new(-0.0).cmp(&Real::ZERO), std::cmp::Ordering::Equal);
+            let mut counts = BTreeMap::new();
+            for x in [1.5, f64::NAN, -3.0, 1.5, f64::NAN, -0.0, 0.0] {
+                *counts.entry(Real::new(x)).or_insert(0) += 1;
+            }
+            let keys: Vec<f64> = counts.keys().map(|k| k.value()).collect();
+            assert_eq!(keys[..3], [-3.0, -0.0, 1.5]);
+            assert!(keys[3].is_nan());
+            assert_eq!(counts.values().copied().collect::<Vec<i32>>(), vec![1, 2, 2, 2])
+        }
+
+        #[test]
+        fn equality_agrees_with_order() {
+            let one = Real::ONE;
+            let next = Real::new(f64::from_bits(1.0_f64.to_bits() + 1));
+            for (a, b) in [(one, next), (next, one), (one, one), (Real::new(-0.0), Real::ZERO), (Real::new(f64::NAN), Real::new(f64::NAN))] {
+                assert_eq!(a == b, a.cmp(&b) == std::cmp::Ordering::Equal);
+            }
+            assert_ne!(one, next);
+            assert!(one.ulps_eq(&next, 1))
+        }
+
+        #[test]
+        fn approximate_equality() {
+            let (a, b) = (Real::new(1.0), Real::new(1.0 + 2.0 * f64::EPSILON));
+            assert!(a.ulps_eq(&b, 2) && !a.ulps_eq(&b, 1));
+            assert!(Real::new(1e8).relative_eq(&Real::new(1e8 + 1.0), 0.0, 1e-8));
+            assert!(!Real::new(1e8).abs_diff_eq(&Real::new(1e8 + 1.0), 1e-8));
+            assert!(Real::ZERO.relative_eq(&Real::new(1e-20), 1e-16, 1e-8));
+            assert!(Real::new(0.0).ulps_eq(&Real::new(-0.0), 0));
+            assert!(!Real::new(f64::MIN_POSITIVE).ulps_eq(&Real::new(-f64::MIN_POSITIVE), 10));
+            assert!(!Real::new(f64::NAN).abs_diff_eq(&Real::new(f64::NAN), f64::INFINITY))
+        }
+
+        #[test]
+        fn constants_agree_with_identities() {
+            let close = |a: Real, b: Real| (a - b).abs().value() < 1e-15;
+            assert!(close(consts::GOLDEN_RATIO.squared(), consts::GOLDEN_RATIO + Real::ONE));
+            assert!(close(consts::SQRT_2 * consts::FRAC_1_SQRT_2, Real::ONE));
+            assert!(close(consts::E.ln().unwrap(), Real::ONE));
+            assert!(close(consts::TAU, consts::FRAC_PI_2 * Real::new(4.0)));
+            assert_eq!(consts::I.squared(), -Complex::ONE)
+        }
+
+        #[test]
+        fn transcendental_inverses() {
+            let close = |a: Real, b: Real| (a - b).abs().value() < 1e-12;
+            for x in [0.1, 0.5, 0.9, 1.7, 3.2] {
+                let x = Real::new(x);
+                assert!(close(x.ln().unwrap().exp(), x));
+                assert!(close(x.powf(2.5).unwrap().powf(0.4).unwrap(), x));
+                assert!(close(x.logf(3.0).unwrap(), x.ln().unwrap() / Real::new(3.0_f64.ln())));
+                assert!(close(x.sqrt().unwrap().squared(), x));
+                assert!(close(x.cbrt() * x.cbrt() * x.cbrt(), x));
+                assert!(close(Real::new(x.arctan().to_radians()).tan(), x));
+                assert!(close(Real::new(x.arcsinh().to_radians()).sinh(), x));
+            }
+            assert!(Real::new(-4.0).sqrt().is_err());
+            assert_eq!(Real::new(-27.0).cbrt(), Real::new(-3.0))
+        }
+
     }
 
     mod complex {
 
         use super::*;
 
+        #[test]
+        fn parses_rectangular_forms() {
+            let parse = |s: &str| s.parse::<Complex>().ok();
+            assert_eq!(parse("i"), Some(Complex::I));
+            assert_eq!(parse("+3.5i"), Some(Complex::new(0.0, 3.5)));
+            assert_eq!(parse(" 1 - i "), Some(Complex::new(1.0, -1.0)));
+            assert_eq!(parse("-1E+2+1e-2i"), Some(Complex::new(-100.0, 0.01)));
+            assert_eq!(parse(&Complex::new(0.5, -2.0).to_string()), Some(Complex::new(0.5, -2.0)));
+            for bad in ["", "i2", "1+2j", "1+2i+3i", "1+2i3", "--i"] {
+                assert_eq!(parse(bad), None, "{bad}");
+            }
+            assert_eq!(" -2.5e1 ".parse::<Real>().unwrap(), Real::new(-25.0));
+            assert!("2i".parse::<Real>().is_err())
+        }
+
+        #[test]
+        fn approximate_equality_is_componentwise() {
+            let z = Complex::new(1.0, 2.0).sqrt().squared();
+            assert!(z.relative_eq(&Complex::new(1.0, 2.0), 0.0, 1e-15));
+            assert!(!Complex::new(1.0, 2.0).abs_diff_eq(&Complex::new(1.0, 2.1), 0.05));
+            assert!(Complex::I.ulps_eq(&Complex::new(0.0, 1.0 + f64::EPSILON), 1))
+        }
+
         #[test]
         fn addition() {
             assert_eq!(Complex::ONE + Complex::new(12.0, 23.0), Complex::new(13.0, 23.0));
@@ -1164,6 +2091,142 @@ mod tests {
             assert_eq!(Complex::new(4.0, 2.0) / Complex::new(0.0, 2.0), Complex::new(1.0, -2.0));
         }
 
+        #[test]
+        fn roots_and_powers_are_principal() {
+            for (re, im) in [(3.0, -4.0), (-3.0, -4.0), (-3.0, 4.0), (-9.0, 0.0), (0.25, 0.0)] {
+                let z = Complex::new(re, im);
+                let root = z.sqrt();
+                assert!((root.squared() - z).modulus() < 1e-14);
+                assert!(root.arg().abs() <= std::f64::consts::FRAC_PI_2);
+                assert!((z.powf(0.5) - root).modulus() < 1e-14);
+                assert!((z.powc(Complex::new(0.5, 0.0)) - root).modulus() < 1e-14);
+                assert!((z.ln().exp() - z).modulus() < 1e-14);
+            }
+            assert_eq!(Complex::new(-1.0, 0.0).ln(), Complex::new(0.0, std::f64::consts::PI));
+            assert_eq!(Complex::ZERO.powf(0.0), Complex::ONE)
+        }
+
+        #[test]
+        fn trigonometric_inverses_are_principal() {
+            let close = |a: Complex, b: Complex| (a - b).modulus() < 1e-12;
+            for (re, im) in [(0.3, 0.4), (-2.0, 3.0), (1.5, -0.7), (-0.2, -1.1)] {
+                let z = Complex::new(re, im);
+                assert!(close(z.arcsin().sin(), z) && close(z.arccos().cos(), z) && close(z.arctan().tan(), z));
+                assert!(close(z.arcsinh().sinh(), z) && close(z.arccosh().cosh(), z));
+                assert!(close(z.arctanh().unwrap().tanh(), z));
+                assert!(close(z.sin().squared() + z.cos().squared(), Complex::ONE));
+                assert!(close(z.cosh().squared() - z.sinh().squared(), Complex::ONE));
+                assert!(z.arccosh().real.inner >= 0.0);
+            }
+            assert!(close(Complex::new(-2.0, 0.0).arccosh(), Complex::new(2.0_f64.acosh(), std::f64::consts::PI)));
+            assert!(close(Complex::new(0.0, 2.0).arctanh().unwrap(), Complex::new(0.0, 2.0_f64.atan())));
+            assert_eq!(Complex::new(0.0, 400.0).tan(), Complex::I);
+            assert!(Complex::new(1.0, 0.0).sin().is_real())
+        }
+
+        #[test]
+        fn polar_round_trip() {
+            for (re, im) in [(1.0, 1.0), (-2.0, 0.5), (-0.5, -3.0), (4.0, -1e-3)] {
+                let z = Complex::new(re, im);
+                let (r, theta) = z.to_polar();
+                assert!(theta.to_radians().abs() <= std::f64::consts::PI);
+                assert!((Complex::from_polar(r, theta) - z).modulus() < 1e-14);
+            }
+            assert_eq!(Complex::ZERO.to_polar().0, 0.0)
+        }
+
+    }
+
+    mod uncertain {
+
+        use super::*;
+
+        #[test]
+        fn propagation_matches_derivatives() {
+            let x = Uncertain::new(2.0, 0.1);
+            let y = Uncertain::new(-4.0, 0.2);
+            assert!(((x + y).std_dev() - 0.05_f64.sqrt()).abs() < 1e-15);
+            assert!(((x / y).relative() - (x * y).relative()).abs() < 1e-15);
+            assert!((x.powf(3.0).std_dev() - 3.0 * 4.0 * 0.1).abs() < 1e-12);
+            assert!((x.ln().std_dev() - 0.05).abs() < 1e-15);
+            assert!((x.sin().std_dev() - 2.0_f64.cos().abs() * 0.1).abs() < 1e-15);
+            assert_eq!((-x).std_dev(), 0.1);
+            // Reusing x treats each occurrence as independent, understating 3x.
+            assert!(((x * Uncertain::from(3.0)).std_dev() - (x + x + x).std_dev() * 3.0_f64.sqrt()).abs() < 1e-15)
+        }
+
+        #[test]
+        fn matrix_products_propagate_componentwise() {
+            use crate::linal::{Vector, Matrix};
+            let m = Matrix::new(vec![vec![Uncertain::exact(1.0), Uncertain::exact(2.0)]]);
+            let v = Vector::new(vec![Uncertain::new(1.0, 0.3), Uncertain::new(1.0, 0.2)]);
+            let product = (m * v)[0];
+            assert_eq!(product.value(), 3.0);
+            assert!((product.std_dev() - 0.5).abs() < 1e-15)
+        }
+
+        #[test]
+        fn displays_with_plus_minus() {
+            assert_eq!(Uncertain::new(1.5, -0.25).to_string(), "1.5 ± 0.25")
+        }
+
     }
 
-}
\ No newline at end of file
+    mod affine {
+
+        use super::*;
+        use crate::linal::{Vector, Matrix};
+
+        fn encloses<const N: usize>(form: &Affine<N>, noise: [f64; N], value: f64) -> bool {
+            let at: f64 = form.center() + form.coefficients().iter().zip(noise.iter()).map(|(c, e)| c * e).sum::<f64>();
+            (at - value).abs() <= form.error() + 1e-15 * value.abs().max(1.0)
+        }
+
+        #[test]
+        fn correlated_terms_cancel() {
+            let x = Affine::<1>::variable(1.0, 1.0, 0);
+            assert!((x - x).radius() < 1e-15);
+            assert!((x - x).to_interval().width() < 1e-14);
+            let interval = x.to_interval();
+            assert!((interval - interval).width() >= 4.0)
+        }
+
+        #[test]
+        fn nonlinear_operations_enclose_exact_values() {
+            let x = Affine::<2>::variable(3.0, 0.5, 0);
+            let y = Affine::<2>::variable(-2.0, 0.25, 1);
+            let (product, quotient) = (x * y, x / y);
+            for noise in [[-1.0, -1.0], [-1.0, 1.0], [1.0, -1.0], [1.0, 1.0], [0.3, -0.7], [0.0, 0.0]] {
+                let (a, b) = (3.0 + 0.5 * noise[0], -2.0 + 0.25 * noise[1]);
+                assert!(encloses(&product, noise, a * b));
+                assert!(encloses(&quotient, noise, a / b));
+            }
+        }
+
+        #[test]
+        fn interval_round_trip() {
+            let interval = Interval::new(1.0, 4.0);
+            let form = Affine::<1>::from_interval(&interval, 0);
+            assert_eq!(form.center(), 2.5);
+            let back = Interval::from(form);
+            assert!(back.contains(1.0) && back.contains(4.0) && back.width() < 3.0 + 1e-12)
+        }
+
+        #[test]
+        fn matrix_vector_product_keeps_correlation() {
+            let x = Affine::<1>::variable(1.0, 0.1, 0);
+            let a = Matrix::new(vec![vec![1.0, -1.0], vec![2.0, 1.0]]).map(Affine::<1>::constant);
+            let y = a * Vector::new(vec![x, x]);
+            assert!(y[0].radius() < 1e-14);
+            assert!((y[1].radius() - 0.3).abs() < 1e-14)
+        }
+
+        #[test]
+        #[should_panic]
+        fn reciprocal_of_form_containing_zero() {
+            let _ = Affine::<1>::variable(0.5, 1.0, 0).recip();
+        }
+
+    }
+
+}
@@ -0,0 +1,141 @@
+//! Algebraic structures.
+//!
+//! These are marker traits: implementing one asserts that a type's arithmetic
+//! obeys the corresponding laws, on top of the operator traits it requires.
+//! The generic parts of [`linal`](crate::linal) are bounded on them, so a
+//! user-defined scalar opts into the whole API at once by implementing the
+//! operators and then the structure they form. `T::default()` is taken as the
+//! additive identity throughout.
+//!
+//! Floating-point types only satisfy the laws up to rounding, as usual.
+
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+use crate::interval::Interval;
+
+use super::{Real, Complex, Affine, Uncertain, Rational, BigReal, Zmod, Quaternion, Float};
+
+/// A type with associative addition and multiplication, the former
+/// commutative with identity `T::default()`, and multiplication
+/// distributing over addition.
+///
+/// This is all that matrix products need, so unsigned integers, which have
+/// no additive inverses, qualify.
+pub trait Semiring: Copy + Default + Add<Output = Self> + Mul<Output = Self> {
+
+    /// Returns the sum of `values`, or `T::default()` if there are none.
+    ///
+    /// Sums in [`linal`](crate::linal) reductions and dot products go
+    /// through this, left to right by default. Under the `strict` feature,
+    /// `f64` and [`Real`] override it with the compensated
+    /// [`strict::sum`](crate::strict::sum).
+    fn sum_of(values: impl Iterator<Item = Self>) -> Self {
+        values.reduce(|a, b| a + b).unwrap_or_default()
+    }
+
+}
+
+/// A type whose addition forms an abelian group with identity
+/// `T::default()`, negation giving inverses and subtraction adding them.
+pub trait AdditiveGroup: Copy + Default + Add<Output = Self> + Sub<Output = Self> + Neg<Output = Self> {}
+
+/// A [`Semiring`] whose addition is an [`AdditiveGroup`].
+///
+/// Multiplication need not commute, so square matrices and quaternions
+/// are rings too.
+pub trait Ring: Semiring + AdditiveGroup {}
+
+/// A commutative [`Ring`] in which every nonzero element has a
+/// multiplicative inverse, reached through division.
+pub trait Field: Ring + Div<Output = Self> {}
+
+macro_rules! impl_semiring {
+    ($($t:ty),*) => {
+        $(
+            impl Semiring for $t {}
+        )*
+    }
+}
+
+macro_rules! impl_ring {
+    ($($t:ty),*) => {
+        $(
+            impl Semiring for $t {}
+            impl AdditiveGroup for $t {}
+            impl Ring for $t {}
+        )*
+    }
+}
+
+macro_rules! impl_field {
+    ($($t:ty),*) => {
+        $(
+            impl_ring![$t];
+            impl Field for $t {}
+        )*
+    }
+}
+
+impl_semiring![u8, u16, u32, u64, u128, usize];
+impl_ring![i8, i16, i32, i64, i128, isize];
+impl_field![f32, Complex, Rational];
+
+macro_rules! impl_strict_field {
+    ($t:ty, $from:expr, $to:expr) => {
+        impl Semiring for $t {
+            #[cfg(feature = "strict")]
+            fn sum_of(values: impl Iterator<Item = Self>) -> Self {
+                let values: Vec<f64> = values.map($to).collect();
+                $from(crate::strict::sum(&values))
+            }
+        }
+        impl AdditiveGroup for $t {}
+        impl Ring for $t {}
+        impl Field for $t {}
+    }
+}
+
+impl_strict_field![f64, |x| x, |x| x];
+impl_strict_field![Real, Real::new, |x: Real| x.value()];
+
+impl<const LIMBS: usize> Semiring for BigReal<LIMBS> {}
+impl<const LIMBS: usize> AdditiveGroup for BigReal<LIMBS> {}
+impl<const LIMBS: usize> Ring for BigReal<LIMBS> {}
+impl<const LIMBS: usize> Field for BigReal<LIMBS> {}
+
+// Only a ring in general; for prime N it is the field GF(N), but that is not
+// visible to the type system.
+impl<const N: u64> Semiring for Zmod<N> {}
+impl<const N: u64> AdditiveGroup for Zmod<N> {}
+impl<const N: u64> Ring for Zmod<N> {}
+
+// Interval arithmetic only satisfies the distributive law and x - x = 0 as
+// enclosures, and uncertainty propagation only to first order, which is all
+// that computations through linal with them rely on.
+impl_ring![Interval, Uncertain];
+
+impl<const N: usize> Semiring for Affine<N> {}
+impl<const N: usize> AdditiveGroup for Affine<N> {}
+impl<const N: usize> Ring for Affine<N> {}
+
+// A noncommutative ring, and so not a field despite its inverses.
+impl<T> Semiring for Quaternion<T> where T: Float {}
+impl<T> AdditiveGroup for Quaternion<T> where T: Float {}
+impl<T> Ring for Quaternion<T> where T: Float {}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn sum_of_products<T>(a: &[T], b: &[T]) -> T where T: Semiring {
+        a.iter().zip(b).fold(T::default(), |sum, (x, y)| sum + *x * *y)
+    }
+
+    #[test]
+    fn semiring_bound_admits_unsigned_and_fields() {
+        assert_eq!(sum_of_products(&[1_u8, 2, 3], &[4, 5, 6]), 32);
+        assert_eq!(sum_of_products(&[0.5, 2.0], &[4.0, 0.25]), 2.5)
+    }
+
+}
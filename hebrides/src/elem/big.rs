@@ -0,0 +1,1320 @@
+//! Arbitrary-precision arithmetic.
+//!
+//! [`BigReal`] is a binary floating-point type whose mantissa is a fixed but
+//! configurable number of 64-bit limbs. Because its size is fixed it is
+//! `Copy`, so it can be used anywhere [`Real`](super::Real) can, including
+//! as the entries of a [`Vector`] or [`Matrix`]. [`BigInt`] is an integer
+//! that grows to hold its value exactly, for number theory and for exact
+//! determinants of integer matrices.
+
+use std::cmp::Ordering;
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
+
+use super::{ApproxEq, ConversionError, Conjugate, Float, ParseError, Rational, Real};
+use crate::linal::{Matrix, Scalar, Vector};
+
+/// Operations on unsigned magnitudes stored as little-endian 64-bit limbs.
+mod magnitude {
+
+    use std::cmp::Ordering;
+
+    /// Removes the most significant limbs that are zero.
+    pub fn trim(mut a: Vec<u64>) -> Vec<u64> {
+        while a.last() == Some(&0) {
+            a.pop();
+        }
+        a
+    }
+
+    pub fn bit_length(a: &[u64]) -> usize {
+        match a.iter().rposition(|limb| *limb != 0) {
+            Some(i) => 64 * i + 64 - a[i].leading_zeros() as usize,
+            None => 0
+        }
+    }
+
+    pub fn bit(a: &[u64], i: usize) -> bool {
+        a.get(i / 64).is_some_and(|limb| limb >> (i % 64) & 1 == 1)
+    }
+
+    /// Returns whether any of the lowest `n` bits of `a` are set.
+    pub fn any_below(a: &[u64], n: usize) -> bool {
+        let whole = (n / 64).min(a.len());
+        a[..whole].iter().any(|limb| *limb != 0)
+            || (!n.is_multiple_of(64) && whole < a.len() && a[whole] & ((1 << (n % 64)) - 1) != 0)
+    }
+
+    pub fn shl(a: &[u64], n: usize) -> Vec<u64> {
+        let (limbs, bits) = (n / 64, n % 64);
+        let mut result = vec![0; limbs];
+        let mut carry = 0;
+        for limb in a {
+            result.push(limb << bits | carry);
+            carry = if bits == 0 { 0 } else { limb >> (64 - bits) };
+        }
+        result.push(carry);
+        trim(result)
+    }
+
+    pub fn shr(a: &[u64], n: usize) -> Vec<u64> {
+        let (limbs, bits) = (n / 64, n % 64);
+        if limbs >= a.len() {
+            return Vec::new();
+        }
+        let result = (limbs..a.len()).map(|i| {
+            let high = if bits == 0 { 0 } else { a.get(i + 1).map_or(0, |limb| limb << (64 - bits)) };
+            a[i] >> bits | high
+        }).collect();
+        trim(result)
+    }
+
+    pub fn cmp(a: &[u64], b: &[u64]) -> Ordering {
+        let (a, b) = (trim(a.to_vec()), trim(b.to_vec()));
+        a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+
+    pub fn add(a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut result = Vec::with_capacity(a.len().max(b.len()) + 1);
+        let mut carry = false;
+        for i in 0..a.len().max(b.len()) {
+            let (sum, c1) = a.get(i).copied().unwrap_or(0).overflowing_add(b.get(i).copied().unwrap_or(0));
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            result.push(sum);
+            carry = c1 || c2;
+        }
+        result.push(carry as u64);
+        trim(result)
+    }
+
+    /// Returns `a` - `b`, where `a` is at least `b`.
+    pub fn sub(a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut result = Vec::with_capacity(a.len());
+        let mut borrow = false;
+        for (i, limb) in a.iter().enumerate() {
+            let (difference, b1) = limb.overflowing_sub(b.get(i).copied().unwrap_or(0));
+            let (difference, b2) = difference.overflowing_sub(borrow as u64);
+            result.push(difference);
+            borrow = b1 || b2;
+        }
+        trim(result)
+    }
+
+    pub fn mul(a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut result = vec![0; a.len() + b.len()];
+        for (i, x) in a.iter().enumerate() {
+            let mut carry = 0_u128;
+            for (j, y) in b.iter().enumerate() {
+                let product = *x as u128 * *y as u128 + result[i + j] as u128 + carry;
+                result[i + j] = product as u64;
+                carry = product >> 64;
+            }
+            result[i + b.len()] = carry as u64;
+        }
+        trim(result)
+    }
+
+    pub fn mul_small(a: &[u64], m: u64) -> Vec<u64> {
+        mul(a, &[m])
+    }
+
+    /// Returns the quotient and remainder of `a` by `b`, by binary long
+    /// division.
+    pub fn div_rem(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+        let mut quotient = vec![0; a.len()];
+        let mut remainder = Vec::new();
+        for i in (0..bit_length(a)).rev() {
+            remainder = shl(&remainder, 1);
+            if bit(a, i) {
+                remainder = add(&remainder, &[1]);
+            }
+            if cmp(&remainder, b) != Ordering::Less {
+                remainder = sub(&remainder, b);
+                quotient[i / 64] |= 1 << (i % 64);
+            }
+        }
+        (trim(quotient), remainder)
+    }
+
+    /// Returns the quotient of `a` by `b` and whether the remainder is
+    /// nonzero.
+    pub fn div(a: &[u64], b: &[u64]) -> (Vec<u64>, bool) {
+        let (quotient, remainder) = div_rem(a, b);
+        (quotient, !remainder.is_empty())
+    }
+
+    /// Returns the quotient and remainder of `a` by the nonzero `d`.
+    pub fn div_small(a: &[u64], d: u64) -> (Vec<u64>, u64) {
+        let mut quotient = vec![0; a.len()];
+        let mut remainder = 0_u128;
+        for i in (0..a.len()).rev() {
+            let current = remainder << 64 | a[i] as u128;
+            quotient[i] = (current / d as u128) as u64;
+            remainder = current % d as u128;
+        }
+        (trim(quotient), remainder as u64)
+    }
+
+    /// Returns the integer square root of `a` and whether `a` is not a
+    /// perfect square, digit by digit.
+    pub fn sqrt(a: &[u64]) -> (Vec<u64>, bool) {
+        let mut remainder = trim(a.to_vec());
+        let mut root = Vec::new();
+        let length = bit_length(&remainder);
+        if length == 0 {
+            return (root, false);
+        }
+        let mut position = (length - 1) & !1;
+        loop {
+            let candidate = add(&root, &shl(&[1], position));
+            if cmp(&remainder, &candidate) != Ordering::Less {
+                remainder = sub(&remainder, &candidate);
+                root = add(&shr(&root, 1), &shl(&[1], position));
+            } else {
+                root = shr(&root, 1);
+            }
+            if position < 2 {
+                break;
+            }
+            position -= 2;
+        }
+        (root, !remainder.is_empty())
+    }
+
+    pub fn pow10(n: usize) -> Vec<u64> {
+        (0..n).fold(vec![1], |p, _| mul_small(&p, 10))
+    }
+
+    /// Returns `m` and `e` such that `m`·2^`e` is 10^`n` with every
+    /// intermediate product of the repeated squaring truncated to `bits`
+    /// bits, along with whether anything was truncated.
+    ///
+    /// Squaring at most doubles the relative error of the base, so for `n`
+    /// below 2⁶⁰ the result is within a relative 2^(62 - `bits`) of 10^`n`.
+    pub fn pow10_truncated(mut n: u64, bits: usize) -> (Vec<u64>, i64, bool) {
+        let truncate = |a: Vec<u64>, e: i64, inexact: bool| {
+            let excess = bit_length(&a).saturating_sub(bits);
+            (shr(&a, excess), e + excess as i64, inexact || any_below(&a, excess))
+        };
+        let mut result = (vec![1], 0, false);
+        let mut base = (vec![10], 0, false);
+        while n > 0 {
+            if n & 1 == 1 {
+                result = truncate(mul(&result.0, &base.0), result.1 + base.1, result.2 || base.2);
+            }
+            n >>= 1;
+            if n > 0 {
+                base = truncate(mul(&base.0, &base.0), 2 * base.1, base.2);
+            }
+        }
+        result
+    }
+
+    /// Returns the decimal digits of `a`, most significant first.
+    pub fn to_decimal(a: &[u64]) -> String {
+        let mut a = trim(a.to_vec());
+        if a.is_empty() {
+            return "0".to_owned();
+        }
+        let mut chunks = Vec::new();
+        while !a.is_empty() {
+            let (quotient, remainder) = div_small(&a, 10_000_000_000_000_000_000);
+            chunks.push(remainder);
+            a = quotient;
+        }
+        let mut digits = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:019}", chunk));
+        }
+        digits
+    }
+
+}
+
+/// Largest decimal exponent [`BigReal`] parses and prints by exact integer
+/// arithmetic. Beyond it, powers of ten are instead built by repeated
+/// squaring with [`GUARD_BITS`] extra bits of precision.
+const EXACT_DECIMAL_EXPONENT: u64 = 1024;
+
+/// Bits carried beyond the mantissa when scaling by large powers of ten.
+const GUARD_BITS: usize = 128;
+
+/// Largest magnitude of decimal exponent [`BigReal`] parses, keeping the
+/// binary exponent within an `i64`.
+const MAX_DECIMAL_EXPONENT: u64 = 1_000_000_000_000_000_000;
+
+/// An arbitrary-precision binary floating-point number with a mantissa of
+/// `LIMBS` 64-bit limbs, giving 64·`LIMBS` bits of precision.
+///
+/// Every operation is correctly rounded to nearest, ties to even, and the
+/// exponent is effectively unbounded, so there is no overflow or underflow.
+/// Undefined results such as division by zero or the square root of a
+/// negative number are NaN; there are no infinities.
+///
+/// ```
+/// # use hebrides::BigReal;
+/// type Quad = BigReal<2>;
+/// let third = Quad::ONE / Quad::from(3);
+/// assert_eq!(third.to_string(), "0.33333333333333333333333333333333333333");
+/// assert_eq!(third * Quad::from(3), Quad::ONE);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct BigReal<const LIMBS: usize> {
+    negative: bool,
+    nan: bool,
+    exponent: i64,
+    mantissa: [u64; LIMBS]
+}
+
+impl<const LIMBS: usize> BigReal<LIMBS> {
+
+    /// Zero.
+    pub const ZERO: Self = BigReal { negative: false, nan: false, exponent: 0, mantissa: [0; LIMBS] };
+
+    /// One.
+    pub const ONE: Self = {
+        let mut mantissa = [0; LIMBS];
+        mantissa[LIMBS - 1] = 1 << 63;
+        BigReal { negative: false, nan: false, exponent: 1 - Self::PRECISION as i64, mantissa }
+    };
+
+    /// Not a number, the result of undefined operations.
+    pub const NAN: Self = BigReal { negative: false, nan: true, exponent: 0, mantissa: [0; LIMBS] };
+
+    /// The number of bits in the mantissa.
+    pub const PRECISION: usize = 64 * LIMBS;
+
+    /// Rounds the magnitude `mantissa` · 2^`exponent` to the precision of
+    /// `Self`, where `sticky` records whether nonzero bits below the
+    /// magnitude were already discarded.
+    fn round(negative: bool, mantissa: &[u64], mut exponent: i64, sticky: bool) -> Self {
+        let length = magnitude::bit_length(mantissa);
+        if length == 0 {
+            return Self::ZERO;
+        }
+        let kept = if length > Self::PRECISION {
+            let shift = length - Self::PRECISION;
+            let mut kept = magnitude::shr(mantissa, shift);
+            exponent += shift as i64;
+            let guard = magnitude::bit(mantissa, shift - 1);
+            let rest = sticky || magnitude::any_below(mantissa, shift - 1);
+            if guard && (rest || magnitude::bit(&kept, 0)) {
+                kept = magnitude::add(&kept, &[1]);
+                if magnitude::bit_length(&kept) > Self::PRECISION {
+                    kept = magnitude::shr(&kept, 1);
+                    exponent += 1;
+                }
+            }
+            kept
+        } else {
+            let shift = Self::PRECISION - length;
+            exponent -= shift as i64;
+            magnitude::shl(mantissa, shift)
+        };
+        let mut limbs = [0; LIMBS];
+        limbs.copy_from_slice(&kept[..LIMBS]);
+        BigReal { negative, nan: false, exponent, mantissa: limbs }
+    }
+
+    /// Returns whether or not `self` is NaN.
+    pub fn is_nan(&self) -> bool {
+        self.nan
+    }
+
+    /// Returns whether or not `self` is zero.
+    pub fn is_zero(&self) -> bool {
+        !self.nan && self.mantissa[LIMBS - 1] == 0
+    }
+
+    /// Returns the absolute value of `self`.
+    pub fn abs(&self) -> Self {
+        BigReal { negative: false, ..*self }
+    }
+
+    /// Returns the correctly rounded square root of `self`, which is NaN if
+    /// `self` is negative.
+    ///
+    /// ```
+    /// # use hebrides::BigReal;
+    /// let root = BigReal::<2>::from(2).sqrt();
+    /// assert_eq!(root.to_string(), "1.4142135623730950488016887242096980786");
+    /// ```
+    pub fn sqrt(&self) -> Self {
+        if self.nan || (self.negative && !self.is_zero()) {
+            return Self::NAN;
+        }
+        if self.is_zero() {
+            return Self::ZERO;
+        }
+        let mut shift = Self::PRECISION as i64 + 2;
+        if (self.exponent - shift) % 2 != 0 {
+            shift += 1;
+        }
+        let (root, inexact) = magnitude::sqrt(&magnitude::shl(&self.mantissa, shift as usize));
+        Self::round(false, &root, (self.exponent - shift) / 2, inexact)
+    }
+
+    /// Returns `self` raised to the integer power `n`, by repeated squaring.
+    pub fn powi(&self, n: i64) -> Self {
+        let (mut base, mut result) = (*self, Self::ONE);
+        let mut remaining = n.unsigned_abs();
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            remaining >>= 1;
+        }
+        if n < 0 { Self::ONE / result } else { result }
+    }
+
+    /// Returns the nearest f64 to `self`.
+    pub fn to_f64(&self) -> f64 {
+        if self.nan {
+            return f64::NAN;
+        }
+        if self.is_zero() {
+            return 0.0;
+        }
+        // Folding the lower limbs into the lowest bit makes the conversion
+        // of the top limb round as the whole mantissa would.
+        let sticky = self.mantissa[..LIMBS - 1].iter().any(|limb| *limb != 0);
+        let mut value = (self.mantissa[LIMBS - 1] | sticky as u64) as f64;
+        let mut exponent = self.exponent + 64 * (LIMBS as i64 - 1);
+        while exponent != 0 && value.is_finite() && value != 0.0 {
+            let step = exponent.clamp(-1000, 1000);
+            value *= 2.0_f64.powi(step as i32);
+            exponent -= step;
+        }
+        if self.negative { -value } else { value }
+    }
+
+    /// Returns the decimal digits of `self` rounded to `digits` significant
+    /// digits, together with the power of ten of the leading digit.
+    fn decimal(&self, digits: usize) -> (String, i64) {
+        // floor(log10(2)·t) for the binary exponent t of the leading bit,
+        // exact up to the last few units for the largest exponents.
+        let top = self.exponent as i128 + Self::PRECISION as i128 - 1;
+        let estimate = (top * 301_029_995_663_981_195).div_euclid(10_i128.pow(18)) as i64;
+        let (integer, point) = if estimate.unsigned_abs() > EXACT_DECIMAL_EXPONENT {
+            // Scaling to a dozen more digits than needed keeps the error of
+            // the truncated power of ten and of the estimate out of the
+            // digits that are kept.
+            let s = estimate - digits as i64 - 12;
+            let (scale, e, _) = magnitude::pow10_truncated(s.unsigned_abs(), Self::PRECISION + GUARD_BITS);
+            let (scaled, exponent) = if s < 0 {
+                (magnitude::mul(&self.mantissa, &scale), self.exponent + e)
+            } else {
+                let shift = magnitude::bit_length(&scale) + Self::PRECISION + 64;
+                (magnitude::div(&magnitude::shl(&self.mantissa, shift), &scale).0, self.exponent - shift as i64 - e)
+            };
+            let integer = if exponent >= 0 {
+                magnitude::shl(&scaled, exponent as usize)
+            } else {
+                magnitude::shr(&scaled, exponent.unsigned_abs() as usize)
+            };
+            (integer, -s)
+        } else if self.exponent >= 0 {
+            (magnitude::shl(&self.mantissa, self.exponent as usize), 0)
+        } else {
+            // M·2^-k = M·5^k / 10^k exactly.
+            let k = self.exponent.unsigned_abs() as usize;
+            let scaled = (0..k / 27).fold(self.mantissa.to_vec(), |a, _| magnitude::mul_small(&a, 7_450_580_596_923_828_125));
+            let scaled = (0..k % 27).fold(scaled, |a, _| magnitude::mul_small(&a, 5));
+            (scaled, k as i64)
+        };
+        let all = magnitude::to_decimal(&integer);
+        let leading = all.len() as i64 - 1 - point;
+        if all.len() <= digits {
+            return (all, leading);
+        }
+        let mut kept: Vec<u8> = all.as_bytes()[..digits].to_vec();
+        if all.as_bytes()[digits] >= b'5' {
+            let mut i = digits;
+            loop {
+                if i == 0 {
+                    kept.insert(0, b'1');
+                    kept.pop();
+                    return (String::from_utf8(kept).unwrap(), leading + 1);
+                }
+                i -= 1;
+                if kept[i] == b'9' {
+                    kept[i] = b'0';
+                } else {
+                    kept[i] += 1;
+                    break;
+                }
+            }
+        }
+        (String::from_utf8(kept).unwrap(), leading)
+    }
+
+}
+
+impl<const LIMBS: usize> Default for BigReal<LIMBS> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+/// Converts exactly, except that infinities become NaN.
+impl<const LIMBS: usize> From<f64> for BigReal<LIMBS> {
+    fn from(x: f64) -> Self {
+        if !x.is_finite() {
+            return Self::NAN;
+        }
+        let bits = x.to_bits();
+        let field = (bits >> 52 & 0x7ff) as i64;
+        let fraction = bits & ((1 << 52) - 1);
+        let (mantissa, exponent) = if field == 0 { (fraction, -1074) } else { (fraction | 1 << 52, field - 1075) };
+        Self::round(x.is_sign_negative(), &[mantissa], exponent, false)
+    }
+}
+
+macro_rules! impl_big_real_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl<const LIMBS: usize> From<$t> for BigReal<LIMBS> {
+                fn from(n: $t) -> Self {
+                    #[allow(unused_comparisons)]
+                    let negative = n < 0;
+                    Self::round(negative, &[(n as i128).unsigned_abs() as u64], 0, false)
+                }
+            }
+        )*
+    }
+}
+
+impl_big_real_from_integer![i8, i16, i32, i64, isize, u8, u16, u32, u64, usize];
+
+/// Prints `self` in decimal to as many significant digits as its precision
+/// supports, or to the formatter's precision if one is given, switching to
+/// scientific notation for very large or small magnitudes.
+///
+/// Magnitudes beyond 10^±1024 are scaled by a power of ten carried with
+/// guard bits rather than expanded exactly, so printing them stays fast.
+impl<const LIMBS: usize> std::fmt::Display for BigReal<LIMBS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.nan {
+            return write!(f, "NaN");
+        }
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let significant = f.precision().unwrap_or((Self::PRECISION as f64 * std::f64::consts::LOG10_2) as usize).max(1);
+        let (digits, leading) = self.decimal(significant);
+        let digits = digits.trim_end_matches('0');
+        let sign = if self.negative { "-" } else { "" };
+        let repr = if leading < -5 || leading >= significant as i64 {
+            let (first, rest) = digits.split_at(1);
+            let point = if rest.is_empty() { "" } else { "." };
+            format!("{sign}{first}{point}{rest}e{leading}")
+        } else if leading < 0 {
+            format!("{sign}0.{}{digits}", "0".repeat((-leading - 1) as usize))
+        } else if digits.len() as i64 <= leading + 1 {
+            format!("{sign}{digits}{}", "0".repeat((leading + 1) as usize - digits.len()))
+        } else {
+            let (whole, fraction) = digits.split_at(leading as usize + 1);
+            format!("{sign}{whole}.{fraction}")
+        };
+        write!(f, "{}", repr)
+    }
+}
+
+/// Parses decimal notation such as `"-12.5"` or `"1e-40"`, correctly
+/// rounded, as well as `"NaN"`.
+///
+/// Decimal exponents larger in magnitude than 10¹⁸ are rejected. A power of
+/// ten beyond the written digits by more than 1024 is built with 128 guard
+/// bits instead of exactly, which still rounds correctly unless the input
+/// lies within 2⁻⁶⁶ ulp of a rounding boundary.
+impl<const LIMBS: usize> std::str::FromStr for BigReal<LIMBS> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("nan") {
+            return Ok(Self::NAN);
+        }
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s))
+        };
+        let (number, power) = match s.find(['e', 'E']) {
+            Some(k) => (&s[..k], s[k + 1..].parse::<i64>().map_err(|_| ParseError)?),
+            None => (s, 0)
+        };
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return Err(ParseError);
+        }
+        let mut digits = vec![];
+        for c in whole.chars().chain(fraction.chars()) {
+            let d = c.to_digit(10).ok_or(ParseError)?;
+            digits = magnitude::add(&magnitude::mul_small(&digits, 10), &[d as u64]);
+        }
+        let power = power.checked_sub(fraction.len() as i64).ok_or(ParseError)?;
+        if power.unsigned_abs() > MAX_DECIMAL_EXPONENT {
+            return Err(ParseError);
+        }
+        if power.unsigned_abs() > EXACT_DECIMAL_EXPONENT + fraction.len() as u64 {
+            let (scale, exponent, truncated) = magnitude::pow10_truncated(power.unsigned_abs(), Self::PRECISION + GUARD_BITS);
+            if power > 0 {
+                return Ok(Self::round(negative, &magnitude::mul(&digits, &scale), exponent, truncated));
+            }
+            let shift = (Self::PRECISION + 2 + magnitude::bit_length(&scale)).saturating_sub(magnitude::bit_length(&digits));
+            let (quotient, inexact) = magnitude::div(&magnitude::shl(&digits, shift), &scale);
+            return Ok(Self::round(negative, &quotient, -(shift as i64) - exponent, truncated || inexact));
+        }
+        if power >= 0 {
+            let integer = magnitude::mul(&digits, &magnitude::pow10(power as usize));
+            return Ok(Self::round(negative, &integer, 0, false));
+        }
+        let divisor = magnitude::pow10(power.unsigned_abs() as usize);
+        let shift = (Self::PRECISION + 2 + magnitude::bit_length(&divisor)).saturating_sub(magnitude::bit_length(&digits));
+        let (quotient, inexact) = magnitude::div(&magnitude::shl(&digits, shift), &divisor);
+        Ok(Self::round(negative, &quotient, -(shift as i64), inexact))
+    }
+}
+
+impl<const LIMBS: usize> PartialEq for BigReal<LIMBS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<const LIMBS: usize> PartialOrd for BigReal<LIMBS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.nan || other.nan {
+            return None;
+        }
+        let sign = |x: &Self| if x.is_zero() { 0 } else if x.negative { -1 } else { 1 };
+        let (a, b) = (sign(self), sign(other));
+        if a != b || a == 0 {
+            return Some(a.cmp(&b));
+        }
+        let magnitudes = self.exponent.cmp(&other.exponent)
+            .then_with(|| self.mantissa.iter().rev().cmp(other.mantissa.iter().rev()));
+        Some(if self.negative { magnitudes.reverse() } else { magnitudes })
+    }
+}
+
+impl<const LIMBS: usize> Add<Self> for BigReal<LIMBS> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        if self.nan || other.nan {
+            return Self::NAN;
+        }
+        if self.is_zero() {
+            return other;
+        }
+        if other.is_zero() {
+            return self;
+        }
+        let (large, small) = if self.exponent >= other.exponent { (self, other) } else { (other, self) };
+        let gap = (large.exponent - small.exponent) as usize;
+        if gap > Self::PRECISION + 2 {
+            // The smaller operand is under a quarter of an ulp of the larger.
+            return large;
+        }
+        let aligned = magnitude::shl(&large.mantissa, gap);
+        if large.negative == small.negative {
+            return Self::round(large.negative, &magnitude::add(&aligned, &small.mantissa), small.exponent, false);
+        }
+        match magnitude::cmp(&aligned, &small.mantissa) {
+            Ordering::Equal => Self::ZERO,
+            Ordering::Greater => Self::round(large.negative, &magnitude::sub(&aligned, &small.mantissa), small.exponent, false),
+            Ordering::Less => Self::round(small.negative, &magnitude::sub(&small.mantissa, &aligned), small.exponent, false)
+        }
+    }
+}
+
+impl<const LIMBS: usize> Sub<Self> for BigReal<LIMBS> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<const LIMBS: usize> Mul<Self> for BigReal<LIMBS> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        if self.nan || other.nan {
+            return Self::NAN;
+        }
+        let product = magnitude::mul(&self.mantissa, &other.mantissa);
+        Self::round(self.negative != other.negative, &product, self.exponent + other.exponent, false)
+    }
+}
+
+impl<const LIMBS: usize> Div<Self> for BigReal<LIMBS> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        if self.nan || other.nan || other.is_zero() {
+            return Self::NAN;
+        }
+        if self.is_zero() {
+            return Self::ZERO;
+        }
+        let shift = Self::PRECISION + 2;
+        let (quotient, inexact) = magnitude::div(&magnitude::shl(&self.mantissa, shift), &other.mantissa);
+        let exponent = self.exponent - shift as i64 - other.exponent;
+        Self::round(self.negative != other.negative, &quotient, exponent, inexact)
+    }
+}
+
+impl<const LIMBS: usize> Neg for BigReal<LIMBS> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        if self.nan || self.is_zero() {
+            return self;
+        }
+        BigReal { negative: !self.negative, ..self }
+    }
+}
+
+impl<const LIMBS: usize> Float for BigReal<LIMBS> {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+    fn sqrt(&self) -> Self {
+        BigReal::sqrt(self)
+    }
+    fn abs(&self) -> Self {
+        BigReal::abs(self)
+    }
+}
+
+impl<const LIMBS: usize> Conjugate for BigReal<LIMBS> {
+    fn conjugate(&self) -> Self {
+        *self
+    }
+}
+
+impl<const LIMBS: usize> Scalar for BigReal<LIMBS> {
+    fn from_real(x: f64) -> Self {
+        BigReal::from(x)
+    }
+    fn conj(self) -> Self {
+        self
+    }
+    fn modulus(self) -> f64 {
+        self.abs().to_f64()
+    }
+}
+
+/// Tolerances are f64s, so relative tolerances finer than 2⁻¹⁰²² cannot be
+/// expressed; [`ulps_eq`](ApproxEq::ulps_eq) counts units in the last place
+/// of the full mantissa.
+impl<const LIMBS: usize> ApproxEq for BigReal<LIMBS> {
+    fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self == other || (*self - *other).abs() <= BigReal::from(epsilon)
+    }
+    fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool {
+        if self == other {
+            return true;
+        }
+        let difference = (*self - *other).abs();
+        let largest = if self.abs() > other.abs() { self.abs() } else { other.abs() };
+        difference <= BigReal::from(epsilon) || difference <= largest * BigReal::from(max_relative)
+    }
+    fn ulps_eq(&self, other: &Self, max_ulps: u64) -> bool {
+        if self == other {
+            return true;
+        }
+        if self.nan || other.nan || self.negative != other.negative {
+            return false;
+        }
+        let ulp = self.exponent.max(other.exponent);
+        (*self - *other).abs() <= Self::round(false, &[max_ulps], ulp, false)
+    }
+}
+
+impl<const LIMBS: usize> Matrix<BigReal<LIMBS>> {
+
+    /// Solves `self` x = `b` for x by Gaussian elimination with partial
+    /// pivoting, carried out entirely at the precision of [`BigReal`].
+    ///
+    /// Returns `None` if `self` is singular.
+    ///
+    /// ```
+    /// # use hebrides::{BigReal, linal::{Matrix, Vector}};
+    /// // The 8×8 Hilbert matrix has condition number above 10¹⁰.
+    /// type Big = BigReal<4>;
+    /// let h = Matrix::new((0..8).map(|i| (0..8).map(|j| Big::ONE / Big::from(i + j + 1)).collect()).collect());
+    /// let ones = Vector::new(vec![Big::ONE; 8]);
+    /// let x = h.solve(&(h.clone() * ones)).unwrap();
+    /// assert!(x.iter().all(|e| (*e - Big::ONE).abs().to_f64() < 1e-50));
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `self` is not square or if `b` does not have as many
+    /// components as `self` has rows.
+    pub fn solve(&self, b: &Vector<BigReal<LIMBS>>) -> Option<Vector<BigReal<LIMBS>>> {
+        let n = self.dims.num_rows;
+        if self.dims.num_cols != n {
+            panic!("Cannot solve a system with a non-square matrix")
+        }
+        if b.dim() != n {
+            panic!("Right-hand side must have as many components as the matrix has rows")
+        }
+        let mut a: Vec<Vec<BigReal<LIMBS>>> = self.rows.iter().zip(b.iter()).map(|(row, e)| {
+            let mut augmented = row.clone();
+            augmented.push(*e);
+            augmented
+        }).collect();
+        for k in 0..n {
+            let pivot = (k..n).max_by(|i, j| a[*i][k].abs().partial_cmp(&a[*j][k].abs()).unwrap_or(Ordering::Equal)).unwrap();
+            if a[pivot][k].is_zero() {
+                return None;
+            }
+            a.swap(k, pivot);
+            let (upper, lower) = a.split_at_mut(k + 1);
+            let pivot_row = &upper[k];
+            for row in lower.iter_mut() {
+                let factor = row[k] / pivot_row[k];
+                for (e, p) in row[k..].iter_mut().zip(pivot_row[k..].iter()) {
+                    *e = *e - factor * *p;
+                }
+            }
+        }
+        let mut x = vec![BigReal::ZERO; n];
+        for i in (0..n).rev() {
+            let sum = (i + 1..n).fold(a[i][n], |sum, j| sum - a[i][j] * x[j]);
+            x[i] = sum / a[i][i];
+        }
+        Some(Vector::new(x))
+    }
+
+}
+
+/// An arbitrary-precision integer.
+///
+/// Unlike [`BigReal`], a `BigInt` grows to hold its value exactly, so it is
+/// not `Copy`; arithmetic is implemented on references as well as values to
+/// avoid needless clones. Division truncates toward zero and the remainder
+/// takes the sign of the dividend, as for the primitive integers.
+///
+/// ```
+/// # use hebrides::BigInt;
+/// let factorial = (1..=30).fold(BigInt::from(1), |p, k| p * BigInt::from(k));
+/// assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+/// assert_eq!(&factorial / &"10000000".parse::<BigInt>().unwrap(), "26525285981219105863630848".parse().unwrap());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u64>
+}
+
+impl BigInt {
+
+    /// Zero.
+    pub const ZERO: BigInt = BigInt { negative: false, magnitude: Vec::new() };
+
+    /// Constructs a BigInt from a sign and a magnitude, keeping zero
+    /// nonnegative so that equal values compare and hash equal.
+    fn from_parts(negative: bool, magnitude: Vec<u64>) -> Self {
+        let magnitude = magnitude::trim(magnitude);
+        BigInt { negative: negative && !magnitude.is_empty(), magnitude }
+    }
+
+    /// Returns whether or not `self` is zero.
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    /// Returns whether or not `self` is less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns -1, 0, or 1 according to the sign of `self`.
+    pub fn signum(&self) -> i32 {
+        if self.is_zero() { 0 } else if self.negative { -1 } else { 1 }
+    }
+
+    /// Returns the absolute value of `self`.
+    pub fn abs(&self) -> Self {
+        BigInt { negative: false, magnitude: self.magnitude.clone() }
+    }
+
+    /// Returns the number of bits needed to write the absolute value of
+    /// `self`, which is zero for zero.
+    pub fn bit_length(&self) -> usize {
+        magnitude::bit_length(&self.magnitude)
+    }
+
+    /// Returns `self` raised to the power `n`, by repeated squaring.
+    pub fn pow(&self, mut n: u32) -> Self {
+        let (mut base, mut result) = (self.clone(), BigInt::from(1));
+        while n > 0 {
+            if n & 1 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            n >>= 1;
+        }
+        result
+    }
+
+    /// Returns the truncated quotient and the remainder of `self` by
+    /// `other` together.
+    ///
+    /// # Panics
+    /// Panics if `other` is zero.
+    pub fn div_rem(&self, other: &BigInt) -> (Self, Self) {
+        if other.is_zero() {
+            panic!("Cannot divide a BigInt by zero")
+        }
+        let (quotient, remainder) = magnitude::div_rem(&self.magnitude, &other.magnitude);
+        (BigInt::from_parts(self.negative != other.negative, quotient), BigInt::from_parts(self.negative, remainder))
+    }
+
+    /// Returns the greatest common divisor of `self` and `other`, which is
+    /// nonnegative.
+    ///
+    /// ```
+    /// # use hebrides::BigInt;
+    /// let a = BigInt::from(2).pow(100) * BigInt::from(3);
+    /// assert_eq!(a.gcd(&BigInt::from(-18)), BigInt::from(6));
+    /// ```
+    pub fn gcd(&self, other: &BigInt) -> Self {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while !b.is_zero() {
+            let remainder = &a % &b;
+            (a, b) = (b, remainder);
+        }
+        a
+    }
+
+    /// Returns the nearest f64 to `self`, or an infinity if `self` is
+    /// beyond the range of f64.
+    pub fn to_f64(&self) -> f64 {
+        let length = self.bit_length();
+        let magnitude = if length <= 64 {
+            self.magnitude.first().copied().unwrap_or(0) as f64
+        } else {
+            // Keep the top 64 bits and fold the rest into a sticky bit, which
+            // is enough for the conversion to round correctly.
+            let shift = length - 64;
+            let top = magnitude::shr(&self.magnitude, shift)[0] | magnitude::any_below(&self.magnitude, shift) as u64;
+            top as f64 * 2.0_f64.powi(shift.min(i32::MAX as usize) as i32)
+        };
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    /// Returns `self` as an i128, or `None` if it does not fit.
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.magnitude.len() > 2 {
+            return None;
+        }
+        let value = self.magnitude.iter().rev().fold(0_u128, |acc, limb| acc << 64 | *limb as u128);
+        if self.negative {
+            (value <= 1 << 127).then(|| (value as i128).wrapping_neg())
+        } else {
+            i128::try_from(value).ok()
+        }
+    }
+
+}
+
+macro_rules! impl_big_int_from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigInt {
+                fn from(n: $t) -> Self {
+                    let value = (n as i128).unsigned_abs();
+                    BigInt::from_parts(n < 0, vec![value as u64, (value >> 64) as u64])
+                }
+            }
+        )*
+    }
+}
+
+impl_big_int_from_signed![i8, i16, i32, i64, i128, isize];
+
+macro_rules! impl_big_int_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigInt {
+                fn from(n: $t) -> Self {
+                    let value = n as u128;
+                    BigInt::from_parts(false, vec![value as u64, (value >> 64) as u64])
+                }
+            }
+        )*
+    }
+}
+
+impl_big_int_from_unsigned![u8, u16, u32, u64, u128, usize];
+
+/// Finite integral Reals convert exactly; any other value is an error.
+impl TryFrom<Real> for BigInt {
+    type Error = ConversionError;
+    fn try_from(value: Real) -> Result<BigInt, ConversionError> {
+        let x = value.value();
+        if !x.is_finite() || x.fract() != 0.0 {
+            return Err(ConversionError);
+        }
+        if x == 0.0 {
+            return Ok(BigInt::ZERO);
+        }
+        let bits = x.abs().to_bits();
+        let exponent = (bits >> 52) as i64 - 1075;
+        let mantissa = [bits & ((1 << 52) - 1) | 1 << 52];
+        let magnitude = if exponent >= 0 {
+            magnitude::shl(&mantissa, exponent as usize)
+        } else {
+            magnitude::shr(&mantissa, -exponent as usize)
+        };
+        Ok(BigInt::from_parts(x < 0.0, magnitude))
+    }
+}
+
+/// Rounds to the nearest Real, overflowing to an infinity.
+impl From<BigInt> for Real {
+    fn from(value: BigInt) -> Real {
+        Real::new(value.to_f64())
+    }
+}
+
+/// Integral Rationals convert exactly; any other value is an error.
+impl TryFrom<Rational> for BigInt {
+    type Error = ConversionError;
+    fn try_from(value: Rational) -> Result<BigInt, ConversionError> {
+        if !value.is_integer() {
+            return Err(ConversionError);
+        }
+        Ok(BigInt::from(value.numer()))
+    }
+}
+
+/// Converts values that fit in the 128-bit numerator of a Rational.
+impl TryFrom<BigInt> for Rational {
+    type Error = ConversionError;
+    fn try_from(value: BigInt) -> Result<Rational, ConversionError> {
+        value.to_i128().map(Rational::from).ok_or(ConversionError)
+    }
+}
+
+/// Rounds to nearest, ties to even.
+impl<const LIMBS: usize> From<BigInt> for BigReal<LIMBS> {
+    fn from(value: BigInt) -> Self {
+        BigReal::round(value.negative, &value.magnitude, 0, false)
+    }
+}
+
+impl std::fmt::Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(!self.negative, "", &magnitude::to_decimal(&self.magnitude))
+    }
+}
+
+/// Parses a decimal integer with an optional sign.
+impl std::str::FromStr for BigInt {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let s = s.trim();
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s))
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseError);
+        }
+        let mut magnitude = Vec::new();
+        for chunk in digits.as_bytes().chunks(19) {
+            // Each chunk of at most 19 digits fits in a single limb.
+            let value: u64 = std::str::from_utf8(chunk).unwrap().parse().map_err(|_| ParseError)?;
+            magnitude = magnitude::add(&magnitude::mul(&magnitude, &magnitude::pow10(chunk.len())), &[value]);
+        }
+        Ok(BigInt::from_parts(negative, magnitude))
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => magnitude::cmp(&self.magnitude, &other.magnitude),
+            (true, true) => magnitude::cmp(&other.magnitude, &self.magnitude)
+        }
+    }
+}
+
+impl Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, magnitude::add(&self.magnitude, &other.magnitude));
+        }
+        match magnitude::cmp(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::from_parts(other.negative, magnitude::sub(&other.magnitude, &self.magnitude)),
+            _ => BigInt::from_parts(self.negative, magnitude::sub(&self.magnitude, &other.magnitude))
+        }
+    }
+}
+
+impl Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(self.negative != other.negative, magnitude::mul(&self.magnitude, &other.magnitude))
+    }
+}
+
+impl Div<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn div(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).1
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude)
+    }
+}
+
+macro_rules! forward_big_int_by_value {
+    ($($trait:ident $method:ident),*) => {
+        $(
+            impl $trait<Self> for BigInt {
+                type Output = BigInt;
+                fn $method(self, other: Self) -> BigInt {
+                    (&self).$method(&other)
+                }
+            }
+        )*
+    }
+}
+
+forward_big_int_by_value![Add add, Sub sub, Mul mul, Div div, Rem rem];
+
+impl Matrix<i64> {
+
+    /// Returns the determinant of `self` exactly, by fraction-free Bareiss
+    /// elimination, in which every intermediate is itself a determinant of
+    /// a submatrix and so stays an integer.
+    ///
+    /// ```
+    /// # use hebrides::{BigInt, linal::Matrix};
+    /// let big = 3_000_000_000;
+    /// let a = Matrix::new(vec![vec![big, 1, 0], vec![1, big, 1], vec![0, 1, big]]);
+    /// assert_eq!(a.exact_determinant().to_string(), "26999999999999999994000000000");
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `self` is not square.
+    pub fn exact_determinant(&self) -> BigInt {
+        let n = self.dims.num_rows;
+        if self.dims.num_cols != n {
+            panic!("Cannot take the determinant of a non-square matrix")
+        }
+        let mut a: Vec<Vec<BigInt>> = self.rows.iter().map(|row| row.iter().map(|e| BigInt::from(*e)).collect()).collect();
+        let (mut previous, mut negate) = (BigInt::from(1), false);
+        for k in 0..n {
+            if a[k][k].is_zero() {
+                match (k + 1..n).find(|i| !a[*i][k].is_zero()) {
+                    Some(i) => {
+                        a.swap(k, i);
+                        negate = !negate;
+                    },
+                    None => return BigInt::ZERO
+                }
+            }
+            let (upper, lower) = a.split_at_mut(k + 1);
+            let pivot_row = &upper[k];
+            for row in lower.iter_mut() {
+                for j in k + 1..n {
+                    row[j] = &(&(&row[j] * &pivot_row[k]) - &(&row[k] * &pivot_row[j])) / &previous;
+                }
+            }
+            previous = pivot_row[k].clone();
+        }
+        if negate { -previous } else { previous }
+    }
+
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    type Double = BigReal<1>;
+    type Quad = BigReal<2>;
+
+    #[test]
+    fn f64_values_round_trip() {
+        for x in [1.0, -0.1, 3.0e300, 5e-324, f64::MIN_POSITIVE, 123456.789, -2.0f64.powi(-60)] {
+            assert_eq!(Double::from(x).to_f64(), x);
+            assert_eq!(Quad::from(x).to_f64(), x);
+        }
+        assert!(Quad::from(f64::INFINITY).is_nan())
+    }
+
+    #[test]
+    fn arithmetic_is_correctly_rounded() {
+        // With one limb, 1 + 2⁻⁶⁴ is a tie between 1 and 1 + 2⁻⁶³ and rounds to even.
+        let tiny = Double::from(2.0f64.powi(-64));
+        assert_eq!(Double::ONE + tiny, Double::ONE);
+        let next = Double::ONE + (tiny + tiny);
+        assert!(next > Double::ONE);
+        assert_eq!(Double::ONE + tiny * Double::from(1.5), next);
+        assert_eq!(next + tiny, Double::ONE + Double::from(2.0f64.powi(-62)));
+        assert_eq!(next - Double::ONE, tiny + tiny);
+        // 0.1 + 0.2 is exact in f64 terms at 128 bits.
+        let (a, b) = (Quad::from(0.1), Quad::from(0.2));
+        assert_eq!((a + b).to_f64(), 0.30000000000000004);
+        assert_eq!((a + b) - b, a);
+        assert_eq!(Quad::from(7) / Quad::from(-2), Quad::from(-3.5));
+        assert_eq!(Quad::from(144).sqrt(), Quad::from(12));
+        assert_eq!(Quad::from(2).powi(-3), Quad::from(0.125));
+        assert!(Quad::ONE.powi(0) == Quad::ONE && (Quad::ONE / Quad::ZERO).is_nan())
+    }
+
+    #[test]
+    fn decimal_conversion() {
+        let parse = |s: &str| s.parse::<Quad>().unwrap();
+        assert_eq!(parse("-12.5"), Quad::from(-12.5));
+        assert_eq!(parse("1e3"), Quad::from(1000));
+        assert_eq!(parse(".5"), Quad::from(0.5));
+        assert_eq!(parse("0.1"), Quad::ONE / Quad::from(10));
+        assert!(parse("nan").is_nan());
+        assert!("1.2.3".parse::<Quad>().is_err() && "".parse::<Quad>().is_err() && "e5".parse::<Quad>().is_err());
+        assert_eq!(Quad::from(1000).to_string(), "1000");
+        assert_eq!(Quad::from(-0.015625).to_string(), "-0.015625");
+        assert_eq!(format!("{:.5}", Quad::ONE / Quad::from(7)), "0.14286");
+        assert_eq!(format!("{:.3}", Quad::from(9999)), "1e4");
+        assert_eq!(format!("{:.4}", parse("1e-40")), "1e-40");
+        assert_eq!(parse("2.718281828459045235360287471352662497757").to_string(), "2.7182818284590452353602874713526624978")
+    }
+
+    #[test]
+    fn huge_decimal_exponents() {
+        let parse = |s: &str| s.parse::<Quad>().unwrap();
+        let huge = parse("1e2000000000");
+        let tiny = parse("1e-2000000000");
+        assert_eq!(huge.to_string(), "1e2000000000");
+        assert_eq!(tiny.to_string(), "1e-2000000000");
+        assert_eq!(format!("{:.3}", parse("-3.25e-1500")), "-3.25e-1500");
+        for digits in ["2.7182818284590452353602874713526624978e5000", "2.7182818284590452353602874713526624978e-5000"] {
+            assert_eq!(parse(digits).to_string(), digits);
+        }
+        assert!(huge * tiny > parse("0.999999999999999") && huge * tiny < parse("1.000000000000001"));
+        // The scaled powers of ten agree with the exact ones written out.
+        assert_eq!(parse("1e1100"), parse(&format!("1{}", "0".repeat(1100))));
+        assert_eq!(parse("7e-1100"), parse(&format!("0.{}7", "0".repeat(1099))));
+        assert!("1e1000000000000000001".parse::<Quad>().is_err())
+    }
+
+    #[test]
+    fn ordering_and_approximate_equality() {
+        let values: Vec<Quad> = [-3.0, -0.5, 0.0, 0.25, 8.0].iter().map(|x| Quad::from(*x)).collect();
+        assert!(values.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(-Quad::ZERO, Quad::ZERO);
+        assert!(Quad::NAN != Quad::NAN);
+        let x = Quad::ONE / Quad::from(3);
+        let y = x + Quad::from(2.0f64.powi(-129));
+        assert!(x != y && x.ulps_eq(&y, 1) && x.abs_diff_eq(&y, 1e-35))
+    }
+
+    #[test]
+    fn more_precision_beats_f64_on_cancellation() {
+        // (1 + 10⁻²⁰) - 1 vanishes in f64 but not with 128 bits.
+        let small = "1e-20".parse::<Quad>().unwrap();
+        assert_eq!((1.0 + 1e-20) - 1.0, 0.0);
+        assert!(((Quad::ONE + small) - Quad::ONE - small).abs() < Quad::from(1e-38))
+    }
+
+    #[test]
+    fn big_int_arithmetic_agrees_with_i128() {
+        let values = [0_i128, 1, -1, 7, -13, 1 << 70, -(1 << 90) + 12345, i64::MAX as i128 * 3];
+        for a in values {
+            for b in values {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                assert_eq!((&x + &y).to_i128(), Some(a + b));
+                assert_eq!((&x - &y).to_i128(), Some(a - b));
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                if let Some(product) = a.checked_mul(b) {
+                    assert_eq!((&x * &y).to_i128(), Some(product));
+                }
+                if b != 0 {
+                    assert_eq!((&x / &y).to_i128(), Some(a / b));
+                    assert_eq!((&x % &y).to_i128(), Some(a % b));
+                }
+            }
+        }
+        assert_eq!(BigInt::from(i128::MIN).to_i128(), Some(i128::MIN));
+        assert_eq!((BigInt::from(i128::MAX) + BigInt::from(1)).to_i128(), None)
+    }
+
+    #[test]
+    fn big_int_text_and_conversions() {
+        let n: BigInt = "-123456789012345678901234567890123456789012".parse().unwrap();
+        assert_eq!(n.to_string(), "-123456789012345678901234567890123456789012");
+        assert_eq!(format!("{:>8}", BigInt::from(-42)), "     -42");
+        assert_eq!("+0".parse::<BigInt>().unwrap(), BigInt::ZERO);
+        assert_eq!("-0".parse::<BigInt>().unwrap().signum(), 0);
+        for bad in ["", "-", "1.5", "12a", "--1"] {
+            assert!(bad.parse::<BigInt>().is_err(), "{bad}");
+        }
+        assert_eq!(n.to_f64(), -1.2345678901234568e41);
+        assert_eq!(BigInt::from(2).pow(1100).to_f64(), f64::INFINITY);
+        // 2⁵³ + 1 is a tie and rounds to even, but a sticky bit below breaks it.
+        let tie = BigInt::from(2).pow(53) + BigInt::from(1);
+        assert_eq!(tie.to_f64(), 2.0f64.powi(53));
+        assert_eq!((&tie * &BigInt::from(2).pow(80) + BigInt::from(1)).to_f64(), (2.0f64.powi(53) + 2.0) * 2.0f64.powi(80));
+        assert_eq!(BigInt::try_from(Real::new(-1e30)).unwrap().to_f64(), -1e30);
+        assert!(BigInt::try_from(Real::new(0.5)).is_err() && BigInt::try_from(Real::new(f64::NAN)).is_err());
+        assert_eq!(Real::from(BigInt::from(12)), Real::new(12.0));
+        assert_eq!(Rational::try_from(BigInt::from(-9)).ok(), Some(Rational::from(-9)));
+        assert!(Rational::try_from(BigInt::from(2).pow(127)).is_err());
+        assert_eq!(BigInt::try_from(Rational::new(8, 2)).ok(), Some(BigInt::from(4)));
+        assert!(BigInt::try_from(Rational::new(1, 2)).is_err());
+        assert_eq!(Quad::from(BigInt::from(3).pow(60)).to_string(), "42391158275216203514294433201")
+    }
+
+    #[test]
+    fn exact_determinants() {
+        // A Vandermonde matrix has determinant ∏(xⱼ - xᵢ).
+        let xs = [1_i64, 2, 3, 4, 5];
+        let vandermonde = Matrix::new(xs.iter().map(|x| (0..5).map(|k| x.pow(k)).collect()).collect());
+        assert_eq!(vandermonde.exact_determinant(), BigInt::from(288));
+        // A row swap away from lower triangular, with a diagonal far beyond i64.
+        let d = 4_000_000_000_000_000_000_i64;
+        let mut rows: Vec<Vec<i64>> = (0..5).map(|i| (0..5).map(|j| if i == j { d } else if j < i { 1 } else { 0 }).collect()).collect();
+        rows.swap(0, 3);
+        assert_eq!(Matrix::new(rows).exact_determinant(), -BigInt::from(d).pow(5));
+        let singular = Matrix::new(vec![vec![0, 1, 2], vec![0, 3, 4], vec![0, 5, 6]]);
+        assert!(singular.exact_determinant().is_zero())
+    }
+
+}
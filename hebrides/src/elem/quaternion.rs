@@ -0,0 +1,396 @@
+//! Quaternions and 3D rotations.
+
+use std::ops::{Add, Sub, Mul, Neg};
+
+use super::{ApproxEq, Conjugate, Float};
+use crate::linal::{Matrix, Vector};
+
+/// A quaternion w + xi + yj + zk.
+///
+/// Unit quaternions represent rotations of three-dimensional space, with
+/// the Hamilton product composing them: `(p * q).rotate(v)` rotates `v`
+/// by `q` and then by `p`. Unlike rotation matrices they cannot drift away
+/// from being a rotation other than in length, which
+/// [`normalize`](Quaternion::normalize) restores, and they interpolate
+/// smoothly with [`slerp`](Quaternion::slerp).
+///
+/// ```
+/// # use hebrides::Quaternion;
+/// # use hebrides::linal::Vector;
+/// use std::f64::consts::FRAC_PI_2;
+/// let quarter_turn = Quaternion::from_axis_angle(&Vector::new(vec![0.0, 0.0, 1.0]), FRAC_PI_2);
+/// let v = quarter_turn.rotate(&Vector::new(vec![1.0, 0.0, 0.0]));
+/// assert!(v[0].abs() < 1e-15 && (v[1] - 1.0).abs() < 1e-15 && v[2] == 0.0);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Quaternion<T> {
+    w: T,
+    x: T,
+    y: T,
+    z: T
+}
+
+impl<T> Quaternion<T> where T: Float {
+
+    /// The multiplicative identity, which represents no rotation.
+    pub const IDENTITY: Self = Quaternion { w: T::ONE, x: T::ZERO, y: T::ZERO, z: T::ZERO };
+
+    /// Constructs the quaternion `w` + `x`i + `y`j + `z`k.
+    pub fn new(w: T, x: T, y: T, z: T) -> Self {
+        Quaternion { w, x, y, z }
+    }
+
+    /// Constructs the pure quaternion whose vector part is the
+    /// three-dimensional `v`.
+    ///
+    /// # Panics
+    /// Panics if `v` is not three-dimensional.
+    pub fn pure(v: &Vector<T>) -> Self {
+        if v.dim() != 3 {
+            panic!("Pure quaternions need a three-dimensional vector part")
+        }
+        Quaternion { w: T::ZERO, x: v[0], y: v[1], z: v[2] }
+    }
+
+    /// Returns the scalar part w.
+    pub fn w(&self) -> T {
+        self.w
+    }
+
+    /// Returns the i component x.
+    pub fn x(&self) -> T {
+        self.x
+    }
+
+    /// Returns the j component y.
+    pub fn y(&self) -> T {
+        self.y
+    }
+
+    /// Returns the k component z.
+    pub fn z(&self) -> T {
+        self.z
+    }
+
+    /// Returns the vector part (x, y, z).
+    pub fn vector(&self) -> Vector<T> {
+        Vector::new(vec![self.x, self.y, self.z])
+    }
+
+    /// Returns the conjugate w - xi - yj - zk, which is the inverse of a
+    /// unit quaternion.
+    pub fn conjugate(&self) -> Self {
+        Quaternion { w: self.w, x: -self.x, y: -self.y, z: -self.z }
+    }
+
+    /// Returns the four-dimensional dot product of `self` and `other`.
+    pub fn dot(&self, other: &Self) -> T {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns the squared norm of `self`.
+    pub fn norm_squared(&self) -> T {
+        self.dot(self)
+    }
+
+    /// Returns the norm of `self`.
+    pub fn norm(&self) -> T {
+        self.norm_squared().sqrt()
+    }
+
+    /// Returns `self` multiplied by the scalar `s`.
+    pub fn scale(&self, s: T) -> Self {
+        Quaternion { w: self.w * s, x: self.x * s, y: self.y * s, z: self.z * s }
+    }
+
+    /// Returns `self` scaled to unit norm. The zero quaternion has no
+    /// direction and normalizes to NaN components.
+    pub fn normalize(&self) -> Self {
+        self.scale(T::ONE / self.norm())
+    }
+
+    /// Returns the multiplicative inverse of `self`, its conjugate divided
+    /// by its squared norm.
+    ///
+    /// # Panics
+    /// Panics if `self` is zero.
+    pub fn inverse(&self) -> Self {
+        let norm_squared = self.norm_squared();
+        if norm_squared == T::ZERO {
+            panic!("Cannot invert the zero quaternion")
+        }
+        self.conjugate().scale(T::ONE / norm_squared)
+    }
+
+    /// Returns `v` rotated by the rotation `self` represents, q v q⁻¹.
+    ///
+    /// `self` need not have unit norm; it is normalized first.
+    ///
+    /// # Panics
+    /// Panics if `v` is not three-dimensional.
+    pub fn rotate(&self, v: &Vector<T>) -> Vector<T> {
+        let rotated = *self * Quaternion::pure(v) * self.inverse();
+        rotated.vector()
+    }
+
+    /// Returns the 3x3 rotation matrix of the rotation `self` represents,
+    /// normalizing it first.
+    pub fn to_rotation_matrix(&self) -> Matrix<T> {
+        let q = self.normalize();
+        let (w, x, y, z) = (q.w, q.x, q.y, q.z);
+        let two = T::ONE + T::ONE;
+        Matrix::new(vec![
+            vec![T::ONE - two * (y * y + z * z), two * (x * y - w * z), two * (x * z + w * y)],
+            vec![two * (x * y + w * z), T::ONE - two * (x * x + z * z), two * (y * z - w * x)],
+            vec![two * (x * z - w * y), two * (y * z + w * x), T::ONE - two * (x * x + y * y)]
+        ])
+    }
+
+}
+
+/// Floats with the trigonometric functions that rotations by
+/// [`Quaternion`]s need.
+pub trait Trigonometric: Float {
+
+    /// The difference between 1 and the next larger representable value.
+    const EPSILON: Self;
+
+    /// Returns the sine of `self` radians.
+    fn sin(&self) -> Self;
+
+    /// Returns the cosine of `self` radians.
+    fn cos(&self) -> Self;
+
+    /// Returns the arccosine of `self` in radians.
+    fn acos(&self) -> Self;
+
+    /// Returns the four-quadrant arctangent of `self`/`other` in radians.
+    fn atan2(&self, other: &Self) -> Self;
+
+}
+
+macro_rules! impl_trigonometric_primitive {
+    ($t:ident) => {
+        impl Trigonometric for $t {
+            const EPSILON: Self = $t::EPSILON;
+            fn sin(&self) -> Self {
+                <$t>::sin(*self)
+            }
+            fn cos(&self) -> Self {
+                <$t>::cos(*self)
+            }
+            fn acos(&self) -> Self {
+                <$t>::acos(*self)
+            }
+            fn atan2(&self, other: &Self) -> Self {
+                <$t>::atan2(*self, *other)
+            }
+        }
+    }
+}
+
+impl_trigonometric_primitive![f32];
+impl_trigonometric_primitive![f64];
+
+impl<T> Quaternion<T> where T: Trigonometric {
+
+    /// Constructs the unit quaternion for a rotation by `angle` radians
+    /// about `axis`, counterclockwise when looking down the axis towards
+    /// the origin.
+    ///
+    /// # Panics
+    /// Panics if `axis` is not three-dimensional or is zero.
+    pub fn from_axis_angle(axis: &Vector<T>, angle: T) -> Self {
+        if axis.dim() != 3 {
+            panic!("Rotation axis must be three-dimensional")
+        }
+        let length = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+        if length == T::ZERO {
+            panic!("Rotation axis must be nonzero")
+        }
+        let half = angle / (T::ONE + T::ONE);
+        let s = half.sin() / length;
+        Quaternion { w: half.cos(), x: axis[0] * s, y: axis[1] * s, z: axis[2] * s }
+    }
+
+    /// Returns the unit axis and the angle in [0, 2π] of the rotation
+    /// `self` represents. The identity rotation reports the x axis.
+    pub fn to_axis_angle(&self) -> (Vector<T>, T) {
+        let q = self.normalize();
+        let length = (q.x * q.x + q.y * q.y + q.z * q.z).sqrt();
+        if length == T::ZERO {
+            return (Vector::new(vec![T::ONE, T::ZERO, T::ZERO]), T::ZERO);
+        }
+        let angle = (T::ONE + T::ONE) * length.atan2(&q.w);
+        (Vector::new(vec![q.x / length, q.y / length, q.z / length]), angle)
+    }
+
+    /// Spherically interpolates from the rotation `self` at `t` = 0 to
+    /// `other` at `t` = 1, at constant angular velocity along the shorter
+    /// arc.
+    ///
+    /// ```
+    /// # use hebrides::Quaternion;
+    /// # use hebrides::linal::Vector;
+    /// let z = Vector::new(vec![0.0_f64, 0.0, 1.0]);
+    /// let halfway = Quaternion::IDENTITY.slerp(&Quaternion::from_axis_angle(&z, 1.0), 0.5);
+    /// assert!((halfway.to_axis_angle().1 - 0.5).abs() < 1e-15);
+    /// ```
+    pub fn slerp(&self, other: &Self, t: T) -> Self {
+        let (from, mut to) = (self.normalize(), other.normalize());
+        let mut cos = from.dot(&to);
+        // q and -q are the same rotation; pick the nearer one.
+        if cos < T::ZERO {
+            (to, cos) = (-to, -cos);
+        }
+        let sixteen = (0..4).fold(T::ONE, |power, _| power + power);
+        if cos > T::ONE - sixteen * T::EPSILON {
+            // The arc is too short to divide by its sine.
+            return (from.scale(T::ONE - t) + to.scale(t)).normalize();
+        }
+        let theta = cos.acos();
+        let sin = theta.sin();
+        from.scale(((T::ONE - t) * theta).sin() / sin) + to.scale((t * theta).sin() / sin)
+    }
+
+}
+
+impl<T> std::fmt::Display for Quaternion<T> where T: std::fmt::Display {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} + {}i + {}j + {}k", self.w, self.x, self.y, self.z)
+    }
+}
+
+/// The zero quaternion.
+impl<T> Default for Quaternion<T> where T: Float {
+    fn default() -> Self {
+        Quaternion { w: T::ZERO, x: T::ZERO, y: T::ZERO, z: T::ZERO }
+    }
+}
+
+impl<T> Add<Self> for Quaternion<T> where T: Float {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Quaternion { w: self.w + other.w, x: self.x + other.x, y: self.y + other.y, z: self.z + other.z }
+    }
+}
+
+impl<T> Sub<Self> for Quaternion<T> where T: Float {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+/// The Hamilton product, which is associative but not commutative.
+impl<T> Mul<Self> for Quaternion<T> where T: Float {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Quaternion {
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w
+        }
+    }
+}
+
+impl<T> Neg for Quaternion<T> where T: Float {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Quaternion { w: -self.w, x: -self.x, y: -self.y, z: -self.z }
+    }
+}
+
+impl<T> Conjugate for Quaternion<T> where T: Float {
+    fn conjugate(&self) -> Self {
+        Quaternion::conjugate(self)
+    }
+}
+
+/// Componentwise, so `q` and `-q` are not approximately equal even though
+/// they represent the same rotation.
+impl<T> ApproxEq for Quaternion<T> where T: ApproxEq {
+    fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self.w.abs_diff_eq(&other.w, epsilon) && self.x.abs_diff_eq(&other.x, epsilon)
+            && self.y.abs_diff_eq(&other.y, epsilon) && self.z.abs_diff_eq(&other.z, epsilon)
+    }
+    fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool {
+        self.w.relative_eq(&other.w, epsilon, max_relative) && self.x.relative_eq(&other.x, epsilon, max_relative)
+            && self.y.relative_eq(&other.y, epsilon, max_relative) && self.z.relative_eq(&other.z, epsilon, max_relative)
+    }
+    fn ulps_eq(&self, other: &Self, max_ulps: u64) -> bool {
+        self.w.ulps_eq(&other.w, max_ulps) && self.x.ulps_eq(&other.x, max_ulps)
+            && self.y.ulps_eq(&other.y, max_ulps) && self.z.ulps_eq(&other.z, max_ulps)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn axis(x: f64, y: f64, z: f64) -> Vector<f64> {
+        Vector::new(vec![x, y, z])
+    }
+
+    #[test]
+    fn hamilton_product_units() {
+        let (i, j, k) = (Quaternion::new(0.0, 1.0, 0.0, 0.0), Quaternion::new(0.0, 0.0, 1.0, 0.0), Quaternion::new(0.0, 0.0, 0.0, 1.0));
+        assert_eq!(i * j, k);
+        assert_eq!(j * i, -k);
+        assert_eq!(i * j * k, -Quaternion::IDENTITY);
+        let q = Quaternion::new(1.0, -2.0, 3.0, 0.5);
+        assert!((q * q.inverse()).abs_diff_eq(&Quaternion::IDENTITY, 1e-15));
+        assert_eq!(q.norm_squared(), 14.25);
+        assert_eq!(q.conjugate().to_string(), "1 + 2i + -3j + -0.5k")
+    }
+
+    #[test]
+    fn rotation_agrees_with_matrix() {
+        let q = Quaternion::from_axis_angle(&axis(1.0, 2.0, -2.0), 0.7);
+        let v = axis(0.3, -1.0, 4.0);
+        let by_matrix = q.to_rotation_matrix() * v.clone();
+        let by_quaternion = q.rotate(&v);
+        assert!(by_matrix.abs_diff_eq(&by_quaternion, 1e-14));
+        assert!((by_quaternion.norm() - v.norm()).abs() < 1e-14);
+        // Scaling the quaternion does not change the rotation.
+        assert!(q.scale(3.0).rotate(&v).abs_diff_eq(&by_quaternion, 1e-14))
+    }
+
+    #[test]
+    fn composition_and_axis_angle_round_trip() {
+        let (x, z) = (Quaternion::from_axis_angle(&axis(1.0, 0.0, 0.0), FRAC_PI_2), Quaternion::from_axis_angle(&axis(0.0, 0.0, 1.0), FRAC_PI_2));
+        // Turning x about z gives y, and then y about x gives z.
+        assert!((x * z).rotate(&axis(1.0, 0.0, 0.0)).abs_diff_eq(&axis(0.0, 0.0, 1.0), 1e-15));
+        let (a, angle) = Quaternion::from_axis_angle(&axis(0.0, 3.0, 4.0), 2.5).to_axis_angle();
+        assert!(a.abs_diff_eq(&axis(0.0, 0.6, 0.8), 1e-15) && (angle - 2.5).abs() < 1e-15);
+        assert_eq!(Quaternion::<f64>::IDENTITY.to_axis_angle().1, 0.0)
+    }
+
+    #[test]
+    fn slerp_takes_the_short_way_at_constant_speed() {
+        let start = Quaternion::from_axis_angle(&axis(0.0, 1.0, 0.0), 0.2);
+        let end = -Quaternion::from_axis_angle(&axis(0.0, 1.0, 0.0), 1.4);
+        for t in [0.0, 0.25, 0.5, 1.0] {
+            let (_, angle) = start.slerp(&end, t).to_axis_angle();
+            assert!((angle - (0.2 + 1.2 * t)).abs() < 1e-14);
+        }
+        assert!(start.slerp(&start, 0.3).abs_diff_eq(&start, 1e-15))
+    }
+
+    #[test]
+    fn single_precision() {
+        let q = Quaternion::from_axis_angle(&Vector::new(vec![0.0f32, 0.0, 1.0]), PI as f32);
+        let v = q.rotate(&Vector::new(vec![1.0f32, 2.0, 3.0]));
+        assert!(v.abs_diff_eq(&Vector::new(vec![-1.0, -2.0, 3.0]), 1e-6))
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_axis() {
+        Quaternion::from_axis_angle(&axis(0.0, 0.0, 0.0), 1.0);
+    }
+
+}
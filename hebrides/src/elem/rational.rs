@@ -0,0 +1,299 @@
+//! Exact rational numbers.
+
+use std::cmp::Ordering;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+use super::{Conjugate, ParseError};
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+fn overflow() -> ! {
+    panic!("Rational arithmetic overflowed 128 bits")
+}
+
+/// An exact rational number p/q in lowest terms with q > 0.
+///
+/// The numerator and denominator are 128-bit, which keeps `Rational` `Copy`
+/// so that exact computations run through [`Matrix`](crate::linal::Matrix)
+/// and [`Vector`](crate::linal::Vector) arithmetic unchanged. Arithmetic
+/// panics rather than rounding if a result does not fit.
+///
+/// ```
+/// # use hebrides::Rational;
+/// let x = Rational::new(1, 3) + Rational::new(1, 6);
+/// assert_eq!(x, Rational::new(1, 2));
+/// assert_eq!(x.to_string(), "1/2");
+/// assert_eq!("-6/4".parse::<Rational>().unwrap(), Rational::new(-3, 2));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i128,
+    denom: i128
+}
+
+impl Rational {
+
+    /// Zero.
+    pub const ZERO: Rational = Rational { numer: 0, denom: 1 };
+
+    /// One.
+    pub const ONE: Rational = Rational { numer: 1, denom: 1 };
+
+    /// Constructs `numer`/`denom` in lowest terms.
+    ///
+    /// # Panics
+    /// Panics if `denom` is zero.
+    pub fn new(numer: i128, denom: i128) -> Self {
+        if denom == 0 {
+            panic!("Rational must have a nonzero denominator")
+        }
+        let divisor = gcd(numer, denom) * denom.signum();
+        Rational { numer: numer / divisor, denom: denom / divisor }
+    }
+
+    /// Returns the numerator, which carries the sign.
+    pub fn numer(&self) -> i128 {
+        self.numer
+    }
+
+    /// Returns the denominator, which is always positive.
+    pub fn denom(&self) -> i128 {
+        self.denom
+    }
+
+    /// Returns whether or not `self` is an integer.
+    pub fn is_integer(&self) -> bool {
+        self.denom == 1
+    }
+
+    /// Returns the absolute value of `self`.
+    pub fn abs(&self) -> Self {
+        Rational { numer: self.numer.abs(), denom: self.denom }
+    }
+
+    /// Returns 1/`self`.
+    ///
+    /// # Panics
+    /// Panics if `self` is zero.
+    pub fn recip(&self) -> Self {
+        Rational::new(self.denom, self.numer)
+    }
+
+    /// Returns the nearest f64 to `self`.
+    pub fn to_f64(&self) -> f64 {
+        self.numer as f64 / self.denom as f64
+    }
+
+    /// Returns the rational with denominator at most `max_denom` closest to
+    /// `x`, found from the continued fraction expansion of `x`.
+    ///
+    /// ```
+    /// # use hebrides::Rational;
+    /// assert_eq!(Rational::approximate(std::f64::consts::PI, 1000), Rational::new(355, 113));
+    /// assert_eq!(Rational::approximate(0.75, 100), Rational::new(3, 4));
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `x` is not finite or `max_denom` is not positive.
+    pub fn approximate(x: f64, max_denom: i128) -> Self {
+        if !x.is_finite() || max_denom < 1 {
+            panic!("Can only approximate finite values with a positive denominator bound")
+        }
+        let (mut h, mut h_prev, mut k, mut k_prev) = (x.floor() as i128, 1, 1, 0);
+        let mut remainder = x - x.floor();
+        while remainder > 1e-12 {
+            let inverse = 1.0 / remainder;
+            let a = inverse.floor() as i128;
+            let next_k = a * k + k_prev;
+            if next_k > max_denom {
+                // The best semiconvergent may still beat the last convergent.
+                let steps = (max_denom - k_prev) / k;
+                let candidate = Rational::new(steps * h + h_prev, steps * k + k_prev);
+                let current = Rational::new(h, k);
+                let distance = |r: Rational| (r.to_f64() - x).abs();
+                return if distance(candidate) < distance(current) { candidate } else { current };
+            }
+            (h, h_prev, k, k_prev) = (a * h + h_prev, h, next_k, k);
+            remainder = inverse - inverse.floor();
+        }
+        Rational::new(h, k)
+    }
+
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Rational::ZERO
+    }
+}
+
+macro_rules! impl_rational_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Rational {
+                fn from(n: $t) -> Self {
+                    Rational { numer: n as i128, denom: 1 }
+                }
+            }
+        )*
+    }
+}
+
+impl_rational_from_integer![i8, i16, i32, i64, i128, u8, u16, u32, u64];
+
+impl std::fmt::Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+/// Parses a fraction such as `"-3/4"`, an integer, or an exact decimal such
+/// as `"0.125"`, ignoring whitespace around each part.
+impl std::str::FromStr for Rational {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        if let Some((numer, denom)) = s.split_once('/') {
+            let numer: Rational = numer.parse()?;
+            let denom: Rational = denom.parse()?;
+            if !numer.is_integer() || !denom.is_integer() || denom.numer == 0 {
+                return Err(ParseError);
+            }
+            return Ok(Rational::new(numer.numer, denom.numer));
+        }
+        let s = s.trim();
+        let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+        if fraction.starts_with(['+', '-']) || (whole.is_empty() && fraction.is_empty()) {
+            return Err(ParseError);
+        }
+        let numer = format!("{whole}{fraction}").parse::<i128>().map_err(|_| ParseError)?;
+        let denom = 10_i128.checked_pow(fraction.len() as u32).ok_or(ParseError)?;
+        Ok(Rational::new(numer, denom))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let left = self.numer.checked_mul(other.denom).unwrap_or_else(|| overflow());
+        let right = other.numer.checked_mul(self.denom).unwrap_or_else(|| overflow());
+        left.cmp(&right)
+    }
+}
+
+impl Add<Self> for Rational {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let divisor = gcd(self.denom, other.denom);
+        let (left, right) = (other.denom / divisor, self.denom / divisor);
+        let numer = self.numer.checked_mul(left)
+            .zip(other.numer.checked_mul(right))
+            .and_then(|(a, b)| a.checked_add(b))
+            .unwrap_or_else(|| overflow());
+        let denom = self.denom.checked_mul(left).unwrap_or_else(|| overflow());
+        Rational::new(numer, denom)
+    }
+}
+
+impl Sub<Self> for Rational {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul<Self> for Rational {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        // Cancelling across before multiplying keeps intermediates small.
+        let (a, b) = (gcd(self.numer, other.denom).max(1), gcd(other.numer, self.denom).max(1));
+        let numer = (self.numer / a).checked_mul(other.numer / b).unwrap_or_else(|| overflow());
+        let denom = (self.denom / b).checked_mul(other.denom / a).unwrap_or_else(|| overflow());
+        Rational::new(numer, denom)
+    }
+}
+
+impl Div<Self> for Rational {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        if other.numer == 0 {
+            panic!("Cannot divide a Rational by zero")
+        }
+        let (a, b) = (gcd(self.numer, other.numer).max(1), gcd(self.denom, other.denom));
+        let numer = (self.numer / a).checked_mul(other.denom / b).unwrap_or_else(|| overflow());
+        let denom = (self.denom / b).checked_mul(other.numer / a).unwrap_or_else(|| overflow());
+        Rational::new(numer, denom)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Rational { numer: -self.numer, denom: self.denom }
+    }
+}
+
+impl Conjugate for Rational {
+    fn conjugate(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn arithmetic_stays_in_lowest_terms() {
+        let (a, b) = (Rational::new(3, -4), Rational::new(5, 6));
+        assert_eq!((a.numer(), a.denom()), (-3, 4));
+        assert_eq!(a + b, Rational::new(1, 12));
+        assert_eq!(a - b, Rational::new(-19, 12));
+        assert_eq!(a * b, Rational::new(-5, 8));
+        assert_eq!(a / b, Rational::new(-9, 10));
+        assert!(a < b && -a > Rational::new(2, 3));
+        assert_eq!(Rational::new(0, -5), Rational::ZERO);
+        let big = Rational::new(i128::MAX / 3, 7);
+        assert_eq!(big * Rational::new(7, i128::MAX / 3), Rational::ONE)
+    }
+
+    #[test]
+    fn parses_fractions_integers_and_decimals() {
+        let parse = |s: &str| s.parse::<Rational>().ok();
+        assert_eq!(parse(" 10 / -4 "), Some(Rational::new(-5, 2)));
+        assert_eq!(parse("-0.125"), Some(Rational::new(-1, 8)));
+        assert_eq!(parse("7"), Some(Rational::from(7)));
+        assert_eq!(parse("-.5"), Some(Rational::new(-1, 2)));
+        for bad in ["", "1/0", "1/2/3", "0.5/2", "a/b", "1.-5", ".", "-."] {
+            assert_eq!(parse(bad), None, "{bad}");
+        }
+    }
+
+    #[test]
+    fn approximation_respects_denominator_bound() {
+        assert_eq!(Rational::approximate(std::f64::consts::PI, 100), Rational::new(311, 99));
+        assert_eq!(Rational::approximate(-1.5, 10), Rational::new(-3, 2));
+        assert_eq!(Rational::approximate(0.333333333, 10), Rational::new(1, 3))
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow_panics() {
+        let _ = Rational::from(i128::MAX) + Rational::ONE;
+    }
+
+}
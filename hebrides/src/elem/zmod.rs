@@ -0,0 +1,383 @@
+//! Modular integers.
+
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+use super::Conjugate;
+use crate::linal::{Vector, Matrix};
+
+/// An integer modulo `N`, stored as its least nonnegative residue.
+///
+/// Arithmetic wraps modulo `N` through 128-bit intermediates, so any `u64`
+/// modulus is supported. For prime `N` this is the finite field GF(`N`), in
+/// which every nonzero element has an [`inverse`](Zmod::inverse); like
+/// [`Rational`](super::Rational), it is `Copy` so that it runs through
+/// [`Matrix`] and [`Vector`] arithmetic unchanged.
+///
+/// ```
+/// # use hebrides::Zmod;
+/// type F7 = Zmod<7>;
+/// let x = F7::new(5);
+/// assert_eq!(x + F7::new(4), F7::new(2));
+/// assert_eq!(x * x.inverse().unwrap(), F7::ONE);
+/// assert_eq!(F7::from(-1), F7::new(6));
+/// assert_eq!(x.pow(6), F7::ONE);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Zmod<const N: u64> {
+    value: u64
+}
+
+impl<const N: u64> Zmod<N> {
+
+    /// Zero.
+    pub const ZERO: Zmod<N> = Zmod { value: 0 };
+
+    /// One, which is zero when `N` is 1.
+    pub const ONE: Zmod<N> = Zmod { value: 1 % N };
+
+    /// Constructs the residue of `value` modulo `N`.
+    ///
+    /// # Panics
+    /// Panics if `N` is zero.
+    pub fn new(value: u64) -> Self {
+        if N == 0 {
+            panic!("Zmod must have a nonzero modulus")
+        }
+        Zmod { value: value % N }
+    }
+
+    /// Returns the modulus `N`.
+    pub fn modulus() -> u64 {
+        N
+    }
+
+    /// Returns the least nonnegative residue of `self`.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Returns whether or not `self` is zero.
+    pub fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    /// Returns whether or not `self` has a multiplicative inverse, which is
+    /// when it is coprime to `N`.
+    pub fn is_unit(&self) -> bool {
+        self.inverse().is_some()
+    }
+
+    /// Returns the multiplicative inverse of `self`, found by the extended
+    /// Euclidean algorithm, or `None` if `self` is not coprime to `N`.
+    pub fn inverse(&self) -> Option<Self> {
+        let (mut r, mut next_r) = (N as i128, self.value as i128);
+        let (mut t, mut next_t) = (0_i128, 1_i128);
+        while next_r != 0 {
+            let q = r / next_r;
+            (r, next_r) = (next_r, r - q * next_r);
+            (t, next_t) = (next_t, t - q * next_t);
+        }
+        if r != 1 {
+            return None;
+        }
+        Some(Zmod { value: t.rem_euclid(N as i128) as u64 })
+    }
+
+    /// Returns `self` raised to the `exponent`th power, by repeated squaring.
+    pub fn pow(&self, mut exponent: u64) -> Self {
+        let (mut base, mut result) = (*self, Self::ONE);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+}
+
+macro_rules! impl_zmod_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl<const N: u64> From<$t> for Zmod<N> {
+                fn from(n: $t) -> Self {
+                    if N == 0 {
+                        panic!("Zmod must have a nonzero modulus")
+                    }
+                    Zmod { value: (n as i128).rem_euclid(N as i128) as u64 }
+                }
+            }
+        )*
+    }
+}
+
+impl_zmod_from_integer![i8, i16, i32, i64, i128, u8, u16, u32, u64];
+
+impl<const N: u64> std::fmt::Display for Zmod<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<const N: u64> Add<Self> for Zmod<N> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Zmod { value: ((self.value as u128 + other.value as u128) % N as u128) as u64 }
+    }
+}
+
+impl<const N: u64> Sub<Self> for Zmod<N> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<const N: u64> Mul<Self> for Zmod<N> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Zmod { value: ((self.value as u128 * other.value as u128) % N as u128) as u64 }
+    }
+}
+
+impl<const N: u64> Div<Self> for Zmod<N> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        match other.inverse() {
+            Some(inverse) => Mul::mul(self, inverse),
+            None => panic!("Cannot divide by a Zmod that is not coprime to its modulus")
+        }
+    }
+}
+
+impl<const N: u64> Neg for Zmod<N> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Zmod { value: if self.value == 0 { 0 } else { N - self.value } }
+    }
+}
+
+impl<const N: u64> Conjugate for Zmod<N> {
+    fn conjugate(&self) -> Self {
+        *self
+    }
+}
+
+/// Row reduces the first `cols` columns of `rows` in place.
+///
+/// Pivots are brought in by the Euclidean algorithm on the residues of each
+/// column, which only ever adds integer multiples of rows to one another and
+/// so works modulo composite `N` too; pivots that are units are scaled to
+/// one. Returns the pivot columns and the determinant of the leading
+/// `cols` x `cols` block, which is only meaningful when that block is square.
+fn reduce<const N: u64>(rows: &mut [Vec<Zmod<N>>], cols: usize) -> (Vec<usize>, Zmod<N>) {
+    let mut pivots = vec![];
+    let mut determinant = Zmod::ONE;
+    let mut r = 0;
+    for c in 0..cols {
+        if r == rows.len() {
+            break;
+        }
+        loop {
+            let smallest = (r..rows.len()).filter(|i| !rows[*i][c].is_zero()).min_by_key(|i| rows[*i][c].value);
+            let Some(smallest) = smallest else { break };
+            if smallest != r {
+                rows.swap(smallest, r);
+                determinant = -determinant;
+            }
+            let pivot = rows[r][c].value;
+            let mut done = true;
+            for i in r + 1..rows.len() {
+                let q = Zmod::new(rows[i][c].value / pivot);
+                for j in c..rows[0].len() {
+                    rows[i][j] = rows[i][j] - q * rows[r][j];
+                }
+                done &= rows[i][c].is_zero();
+            }
+            if done {
+                break;
+            }
+        }
+        if rows[r][c].is_zero() {
+            determinant = Zmod::ZERO;
+            continue;
+        }
+        determinant = determinant * rows[r][c];
+        if let Some(inverse) = rows[r][c].inverse() {
+            for e in rows[r].iter_mut() {
+                *e = *e * inverse;
+            }
+        }
+        let pivot = rows[r][c].value;
+        for i in (0..r).chain(r + 1..rows.len()) {
+            let q = Zmod::new(rows[i][c].value / pivot);
+            for j in 0..rows[0].len() {
+                rows[i][j] = rows[i][j] - q * rows[r][j];
+            }
+        }
+        pivots.push(c);
+        r += 1;
+    }
+    if pivots.len() < cols {
+        determinant = Zmod::ZERO;
+    }
+    (pivots, determinant)
+}
+
+impl<const N: u64> Matrix<Zmod<N>> {
+
+    /// Returns the row echelon form of `self` and its pivot columns.
+    ///
+    /// For prime `N` this is the reduced row echelon form over GF(`N`).
+    /// Modulo a composite `N`, a pivot that is not a unit is left as the
+    /// greatest common divisor of its column, and the entries above it are
+    /// only reduced below it rather than cleared.
+    pub fn rref(&self) -> (Matrix<Zmod<N>>, Vec<usize>) {
+        let mut rows = self.rows.clone();
+        let (pivots, _) = reduce(&mut rows, self.dims.num_cols);
+        (Matrix::new(rows), pivots)
+    }
+
+    /// Returns the rank of `self` over GF(`N`), for prime `N`.
+    ///
+    /// ```
+    /// # use hebrides::{Zmod, linal::Matrix};
+    /// // Full rank over the rationals, but not over GF(2).
+    /// let m = Matrix::new(vec![vec![1, 1, 0], vec![0, 1, 1], vec![1, 0, 1]]);
+    /// assert_eq!(m.map(Zmod::<2>::from).rank(), 2);
+    /// assert_eq!(m.map(Zmod::<3>::from).rank(), 3);
+    /// ```
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    /// Returns the determinant of `self` modulo `N`.
+    ///
+    /// # Panics
+    /// Panics if `self` is not square.
+    pub fn determinant(&self) -> Zmod<N> {
+        if !self.is_square() {
+            panic!("Cannot take the determinant of a non-square matrix")
+        }
+        let mut rows = self.rows.clone();
+        reduce(&mut rows, self.dims.num_cols).1
+    }
+
+    /// Returns the inverse of `self` modulo `N`, or `None` if its
+    /// determinant is not a unit.
+    ///
+    /// ```
+    /// # use hebrides::{Zmod, linal::Matrix};
+    /// // The key of a Hill cipher over the 26 letters.
+    /// let key = Matrix::new(vec![vec![3, 3], vec![2, 5]]).map(Zmod::<26>::from);
+    /// let unkey = key.inverse().unwrap();
+    /// assert_eq!(unkey, Matrix::new(vec![vec![15, 17], vec![20, 9]]).map(Zmod::<26>::from));
+    /// assert!(Matrix::new(vec![vec![2, 0], vec![0, 1]]).map(Zmod::<26>::from).inverse().is_none());
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `self` is not square.
+    pub fn inverse(&self) -> Option<Matrix<Zmod<N>>> {
+        if !self.is_square() {
+            panic!("Cannot invert a non-square matrix")
+        }
+        let n = self.dims.num_rows;
+        let mut rows: Vec<Vec<Zmod<N>>> = self.rows.iter().enumerate().map(|(i, row)| {
+            row.iter().copied().chain((0..n).map(|j| if i == j { Zmod::ONE } else { Zmod::ZERO })).collect()
+        }).collect();
+        let (_, determinant) = reduce(&mut rows, n);
+        if !determinant.is_unit() {
+            return None;
+        }
+        Some(Matrix::new(rows.into_iter().map(|row| row[n..].to_vec()).collect()))
+    }
+
+    /// Solves `self` x = `b` modulo `N`, returning `None` if the determinant
+    /// of `self` is not a unit.
+    ///
+    /// # Panics
+    /// Panics if `self` is not square or if `b` does not have as many
+    /// components as `self` has rows.
+    pub fn solve(&self, b: &Vector<Zmod<N>>) -> Option<Vector<Zmod<N>>> {
+        if !self.is_square() {
+            panic!("Cannot solve a non-square system")
+        }
+        if b.dim() != self.dims.num_rows {
+            panic!("Right-hand side must have as many components as the Matrix has rows")
+        }
+        let n = self.dims.num_rows;
+        let mut rows: Vec<Vec<Zmod<N>>> = self.rows.iter().zip(b.iter()).map(|(row, e)| {
+            row.iter().copied().chain(std::iter::once(*e)).collect()
+        }).collect();
+        let (_, determinant) = reduce(&mut rows, n);
+        if !determinant.is_unit() {
+            return None;
+        }
+        Some(Vector::new(rows.into_iter().map(|row| row[n]).collect()))
+    }
+
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    type F7 = Zmod<7>;
+
+    #[test]
+    fn field_arithmetic() {
+        for a in 1..7 {
+            let x = F7::new(a);
+            assert_eq!(x * x.inverse().unwrap(), F7::ONE);
+            assert_eq!(x / x, F7::ONE);
+            assert_eq!(x - x, F7::ZERO);
+        }
+        assert_eq!(F7::from(-15_i64), F7::new(6));
+        assert_eq!(-F7::ZERO, F7::ZERO);
+        assert!(F7::ZERO.inverse().is_none())
+    }
+
+    #[test]
+    fn composite_modulus_units() {
+        let units: Vec<u64> = (0..12).filter(|a| Zmod::<12>::new(*a).is_unit()).collect();
+        assert_eq!(units, vec![1, 5, 7, 11]);
+        assert_eq!(Zmod::<12>::new(4) * Zmod::new(3), Zmod::ZERO)
+    }
+
+    #[test]
+    fn large_modulus_does_not_overflow() {
+        const P: u64 = 18446744073709551557;
+        let x = Zmod::<P>::new(P - 1);
+        assert_eq!(x * x, Zmod::ONE);
+        assert_eq!(Zmod::<P>::new(3).pow(P - 1), Zmod::ONE)
+    }
+
+    #[test]
+    fn determinant_modulo_composite() {
+        // Neither entry of the first column is a unit modulo 6.
+        let m = Matrix::new(vec![vec![2, 3], vec![3, 2]]).map(Zmod::<6>::from);
+        assert_eq!(m.determinant(), Zmod::from(-5));
+        let inverse = m.inverse().unwrap();
+        assert_eq!(m.clone() * inverse.clone(), Matrix::new(vec![vec![1, 0], vec![0, 1]]).map(Zmod::<6>::from));
+        let b = Vector::new(vec![Zmod::new(1), Zmod::new(4)]);
+        assert_eq!(m.solve(&b).unwrap(), inverse * b)
+    }
+
+    #[test]
+    fn singular_over_field() {
+        let m = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]).map(F7::from);
+        assert_eq!(m.determinant(), F7::ZERO);
+        assert!(m.inverse().is_none());
+        assert_eq!(m.rank(), 2)
+    }
+
+    #[test]
+    #[should_panic]
+    fn divide_by_non_unit() {
+        let _ = Zmod::<12>::new(1) / Zmod::new(4);
+    }
+
+}
@@ -0,0 +1,302 @@
+//! Graphs as matrices.
+//!
+//! Random graph generators producing the adjacency matrices of simple
+//! undirected graphs: symmetric [`SparseMatrix`]s with unit weights and no
+//! self-loops. Graph Laplacians and spectral clustering work on any
+//! symmetric adjacency matrix with nonnegative weights.
+
+use crate::linal::{Vector, Matrix, SparseMatrix};
+use crate::random::Rng;
+use crate::stats;
+
+fn adjacency(n: usize, edges: &[(usize, usize)]) -> SparseMatrix<f64> {
+	let triplets: Vec<(usize, usize, f64)> = edges.iter().flat_map(|(i, j)| [(*i, *j, 1.0), (*j, *i, 1.0)]).collect();
+	SparseMatrix::from_triplets(n, n, &triplets)
+}
+
+/// Returns the adjacency matrix of an Erdős-Rényi random graph on `n`
+/// vertices in which each edge is present independently with probability
+/// `p`.
+///
+/// Edges are sampled by geometric skipping, so the cost is proportional to
+/// the number of vertices plus the number of edges generated.
+///
+/// ```
+/// # use hebrides::{graph, random::Rng};
+/// let g = graph::erdos_renyi(100, 0.05, &mut Rng::new(1));
+/// assert_eq!(g.dims().num_rows, 100);
+/// assert!(g.nnz() > 0);
+/// ```
+///
+/// # Panics
+/// Panics if `p` is not in [0, 1].
+pub fn erdos_renyi(n: usize, p: f64, rng: &mut Rng) -> SparseMatrix<f64> {
+	if !(0.0..=1.0).contains(&p) {
+		panic!("Edge probability must be between 0 and 1")
+	}
+	let mut edges = Vec::new();
+	if p == 1.0 {
+		edges.extend((0..n).flat_map(|i| (0..i).map(move |j| (i, j))));
+	} else if p > 0.0 {
+		// For tiny p, 1 - p rounds to 1 and the skip overflows unless clamped.
+		let log_q = (-p).ln_1p();
+		let pairs = (n * n) as i64;
+		let (mut v, mut w) = (1, -1_i64);
+		while v < n {
+			let skip = ((1.0 - rng.next_f64()).ln() / log_q).floor().min(pairs as f64) as i64;
+			w += 1 + skip;
+			if w >= pairs {
+				break;
+			}
+			while w >= v as i64 && v < n {
+				w -= v as i64;
+				v += 1;
+			}
+			if v < n {
+				edges.push((v, w as usize));
+			}
+		}
+	}
+	adjacency(n, &edges)
+}
+
+/// Returns the adjacency matrix of a Barabási-Albert preferential attachment
+/// graph on `n` vertices.
+///
+/// The graph starts as a complete graph on `m` + 1 vertices, and each
+/// further vertex attaches to `m` distinct existing vertices chosen with
+/// probability proportional to their degree.
+///
+/// # Panics
+/// Panics if `m` is zero or not less than `n`.
+pub fn barabasi_albert(n: usize, m: usize, rng: &mut Rng) -> SparseMatrix<f64> {
+	if m == 0 || m >= n {
+		panic!("Each new vertex must attach to between 1 and n - 1 existing vertices")
+	}
+	let mut edges: Vec<(usize, usize)> = (0..=m).flat_map(|i| (0..i).map(move |j| (i, j))).collect();
+	let mut endpoints: Vec<usize> = edges.iter().flat_map(|(i, j)| [*i, *j]).collect();
+	for v in m + 1..n {
+		let mut targets: Vec<usize> = Vec::with_capacity(m);
+		while targets.len() < m {
+			let candidate = endpoints[rng.below(endpoints.len())];
+			if !targets.contains(&candidate) {
+				targets.push(candidate);
+			}
+		}
+		for t in targets {
+			edges.push((v, t));
+			endpoints.extend([v, t]);
+		}
+	}
+	adjacency(n, &edges)
+}
+
+/// Returns the adjacency matrix of a stochastic block model graph.
+///
+/// The vertices are split into consecutive blocks with the given `sizes`,
+/// and an edge between a vertex in block `a` and one in block `b` is present
+/// independently with probability `probabilities[a][b]`.
+///
+/// ```
+/// # use hebrides::{graph, linal::Matrix, random::Rng};
+/// let p = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+/// let g = graph::stochastic_block_model(&[2, 3], &p, &mut Rng::new(0));
+/// assert_eq!(g.nnz(), 2 * (1 + 3));
+/// ```
+///
+/// # Panics
+/// Panics if `probabilities` is not a symmetric matrix with one row per
+/// block, or if any of its entries is not in [0, 1].
+pub fn stochastic_block_model(sizes: &[usize], probabilities: &Matrix<f64>, rng: &mut Rng) -> SparseMatrix<f64> {
+	if probabilities.dims.num_rows != sizes.len() || !probabilities.is_symmetric() {
+		panic!("Block probabilities must form a symmetric Matrix with one row per block")
+	}
+	if probabilities.rows.iter().flatten().any(|p| !(0.0..=1.0).contains(p)) {
+		panic!("Edge probabilities must be between 0 and 1")
+	}
+	let blocks: Vec<usize> = sizes.iter().enumerate().flat_map(|(b, size)| std::iter::repeat_n(b, *size)).collect();
+	let mut edges = Vec::new();
+	for i in 0..blocks.len() {
+		for j in 0..i {
+			if rng.next_f64() < probabilities.rows[blocks[i]][blocks[j]] {
+				edges.push((i, j));
+			}
+		}
+	}
+	adjacency(blocks.len(), &edges)
+}
+
+/// Returns the weighted degree of each vertex of `adjacency`.
+fn degrees(adjacency: &SparseMatrix<f64>) -> Vec<f64> {
+	(0..adjacency.dims().num_rows).map(|i| adjacency.row(i).map(|(_, w)| *w).sum()).collect()
+}
+
+/// Panics if `adjacency` is not square.
+fn check_square(adjacency: &SparseMatrix<f64>) {
+	let dims = adjacency.dims();
+	if dims.num_rows != dims.num_cols {
+		panic!("Adjacency matrix must be square")
+	}
+}
+
+/// Returns the Laplacian L = D - A of the graph with adjacency matrix
+/// `adjacency`, where D is the diagonal matrix of vertex degrees.
+///
+/// ```
+/// # use hebrides::{graph, linal::{Matrix, SparseMatrix}};
+/// let path = SparseMatrix::from_triplets(3, 3, &[(0, 1, 1.0), (1, 0, 1.0), (1, 2, 1.0), (2, 1, 1.0)]);
+/// assert_eq!(graph::laplacian(&path).to_dense(), Matrix::new(vec![
+///     vec![1.0, -1.0, 0.0],
+///     vec![-1.0, 2.0, -1.0],
+///     vec![0.0, -1.0, 1.0]
+/// ]));
+/// ```
+///
+/// # Panics
+/// Panics if `adjacency` is not square.
+pub fn laplacian(adjacency: &SparseMatrix<f64>) -> SparseMatrix<f64> {
+	check_square(adjacency);
+	let n = adjacency.dims().num_rows;
+	let mut triplets: Vec<(usize, usize, f64)> = degrees(adjacency).into_iter().enumerate().map(|(i, d)| (i, i, d)).collect();
+	triplets.extend((0..n).flat_map(|i| adjacency.row(i).map(move |(j, w)| (i, j, -*w))));
+	SparseMatrix::from_triplets(n, n, &triplets)
+}
+
+/// Returns the symmetric normalized Laplacian I - D^(-1/2) A D^(-1/2) of the
+/// graph with adjacency matrix `adjacency`.
+///
+/// Isolated vertices have a zero row and column.
+///
+/// # Panics
+/// Panics if `adjacency` is not square.
+pub fn normalized_laplacian(adjacency: &SparseMatrix<f64>) -> SparseMatrix<f64> {
+	check_square(adjacency);
+	let n = adjacency.dims().num_rows;
+	let scale: Vec<f64> = degrees(adjacency).iter().map(|d| if *d > 0.0 { 1.0 / d.sqrt() } else { 0.0 }).collect();
+	let mut triplets: Vec<(usize, usize, f64)> = scale.iter().enumerate().filter(|(_, s)| **s > 0.0).map(|(i, _)| (i, i, 1.0)).collect();
+	triplets.extend((0..n).flat_map(|i| {
+		let scale = &scale;
+		adjacency.row(i).map(move |(j, w)| (i, j, -*w * scale[i] * scale[j]))
+	}));
+	SparseMatrix::from_triplets(n, n, &triplets)
+}
+
+/// Partitions the vertices of the graph with adjacency matrix `adjacency`
+/// into `k` clusters, returning each vertex's cluster label.
+///
+/// Follows Ng, Jordan, and Weiss: the eigenvectors of the `k` smallest
+/// eigenvalues of the [`normalized_laplacian`] embed each vertex in k
+/// dimensions, the embeddings are scaled to unit length, and the result is
+/// clustered by [`stats::kmeans`]. The k-means seed is fixed, so the labels
+/// are deterministic.
+///
+/// ```
+/// # use hebrides::{graph, linal::Matrix, random::Rng};
+/// let p = Matrix::new(vec![vec![0.9, 0.01], vec![0.01, 0.9]]);
+/// let g = graph::stochastic_block_model(&[10, 10], &p, &mut Rng::new(5));
+/// let labels = graph::spectral_clustering(&g, 2);
+/// assert!(labels[..10].iter().all(|l| *l == labels[0]) && labels[10..].iter().all(|l| *l == labels[10]));
+/// assert_ne!(labels[0], labels[10]);
+/// ```
+///
+/// # Panics
+/// Panics if `adjacency` is not square and symmetric, or if `k` is zero or
+/// greater than the number of vertices.
+pub fn spectral_clustering(adjacency: &SparseMatrix<f64>, k: usize) -> Vec<usize> {
+	let n = adjacency.dims().num_rows;
+	if k == 0 || k > n {
+		panic!("Number of clusters must be between one and the number of vertices")
+	}
+	let eigen = normalized_laplacian(adjacency).to_dense().symmetric_eigen();
+	let embedding: Vec<Vector<f64>> = (0..n).map(|i| {
+		let row: Vec<f64> = eigen.vectors().rows[i][..k].to_vec();
+		let norm = row.iter().map(|e| e * e).sum::<f64>().sqrt();
+		Vector::new(row.into_iter().map(|e| if norm > 0.0 { e / norm } else { e }).collect())
+	}).collect();
+	stats::kmeans(&embedding, k, &mut Rng::new(0)).0
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn is_simple(g: &SparseMatrix<f64>) -> bool {
+		let dense = g.to_dense();
+		dense.is_symmetric() && (0..dense.dims.num_rows).all(|i| dense.rows[i][i] == 0.0)
+			&& dense.rows.iter().flatten().all(|e| *e == 0.0 || *e == 1.0)
+	}
+
+	#[test]
+	fn erdos_renyi_edge_density() {
+		let n = 400;
+		let g = erdos_renyi(n, 0.1, &mut Rng::new(11));
+		assert!(is_simple(&g));
+		let density = g.nnz() as f64 / (n * (n - 1)) as f64;
+		assert!((density - 0.1).abs() < 0.01)
+	}
+
+	#[test]
+	fn erdos_renyi_extremes() {
+		assert_eq!(erdos_renyi(10, 0.0, &mut Rng::new(0)).nnz(), 0);
+		assert_eq!(erdos_renyi(10, 1.0, &mut Rng::new(0)).nnz(), 90)
+	}
+
+	#[test]
+	fn erdos_renyi_tiny_probability() {
+		let g = erdos_renyi(1000, 1e-18, &mut Rng::new(5));
+		assert_eq!(g.dims().num_rows, 1000);
+		assert_eq!(g.nnz(), 0)
+	}
+
+	#[test]
+	fn barabasi_albert_edge_count() {
+		let (n, m) = (200, 3);
+		let g = barabasi_albert(n, m, &mut Rng::new(4));
+		assert!(is_simple(&g));
+		assert_eq!(g.nnz(), 2 * (m * (m + 1) / 2 + (n - m - 1) * m))
+	}
+
+	#[test]
+	fn stochastic_blocks_are_denser_inside() {
+		let p = Matrix::new(vec![vec![0.5, 0.02], vec![0.02, 0.5]]);
+		let g = stochastic_block_model(&[50, 50], &p, &mut Rng::new(8)).to_dense();
+		let count = |rows: std::ops::Range<usize>, cols: std::ops::Range<usize>| {
+			rows.flat_map(|i| cols.clone().map(move |j| (i, j))).filter(|(i, j)| g.rows[*i][*j] == 1.0).count()
+		};
+		assert!(count(0..50, 0..50) > 10 * count(0..50, 50..100))
+	}
+
+	#[test]
+	fn laplacian_rows_sum_to_zero() {
+		let g = barabasi_albert(30, 2, &mut Rng::new(6));
+		let ones = Vector::new(vec![1.0; 30]);
+		let l = laplacian(&g);
+		assert!((0..30).all(|i| l.matvec(&ones)[i] == 0.0));
+		let eigen = normalized_laplacian(&g).to_dense().symmetric_eigen();
+		assert!(eigen.values()[0].abs() < 1e-12 && eigen.values()[29] <= 2.0 + 1e-12)
+	}
+
+	#[test]
+	fn spectral_clustering_recovers_blocks() {
+		let p = Matrix::new(vec![
+			vec![0.6, 0.02, 0.02],
+			vec![0.02, 0.6, 0.02],
+			vec![0.02, 0.02, 0.6]
+		]);
+		let g = stochastic_block_model(&[20, 25, 15], &p, &mut Rng::new(3));
+		let labels = spectral_clustering(&g, 3);
+		let blocks = [0..20, 20..45, 45..60];
+		for block in blocks.iter() {
+			assert!(labels[block.clone()].iter().all(|l| *l == labels[block.start]));
+		}
+		assert!(labels[0] != labels[20] && labels[20] != labels[45] && labels[0] != labels[45])
+	}
+
+	#[test]
+	#[should_panic]
+	fn invalid_probability() {
+		let _ = erdos_renyi(5, 1.5, &mut Rng::new(0));
+	}
+
+}
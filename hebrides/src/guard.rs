@@ -0,0 +1,189 @@
+//! Resource guards.
+//!
+//! A [`Budget`] bounds the iterations, allocation size, and wall-clock time
+//! of a heavyweight routine, which reports [`ResourceExceeded`] instead of
+//! running on once a bound is crossed. The guarded routines are the
+//! `_guarded` variants of the iterative solvers, such as
+//! [`sinkhorn_guarded`](crate::optimize::sinkhorn_guarded) and
+//! [`soft_impute_guarded`](crate::linal::soft_impute_guarded).
+//!
+//! Time is read from a caller-supplied clock rather than the system clock,
+//! so that budgets work on targets without one and can be driven
+//! deterministically in tests.
+
+use std::time::Duration;
+
+/// Error type for routines stopped by their [`Budget`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceExceeded {
+	/// More iterations were needed than the budget allows.
+	Iterations {
+		/// The iteration limit.
+		limit: usize
+	},
+	/// An allocation larger than the budget allows was requested.
+	Allocation {
+		/// The number of elements requested.
+		requested: usize,
+		/// The allocation limit, in elements.
+		limit: usize
+	},
+	/// The routine ran past its deadline.
+	Time {
+		/// The elapsed time when the deadline was noticed.
+		elapsed: Duration,
+		/// The time limit.
+		limit: Duration
+	}
+}
+
+impl std::fmt::Display for ResourceExceeded {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ResourceExceeded::Iterations { limit } => write!(f, "Exceeded the limit of {} iterations", limit),
+			ResourceExceeded::Allocation { requested, limit } => {
+				write!(f, "Requested {} elements against a limit of {}", requested, limit)
+			},
+			ResourceExceeded::Time { elapsed, limit } => write!(f, "Ran for {:?} against a limit of {:?}", elapsed, limit)
+		}
+	}
+}
+
+struct Deadline {
+	clock: Box<dyn Fn() -> Duration>,
+	start: Duration,
+	limit: Duration
+}
+
+/// Bounds on the resources a guarded routine may use.
+///
+/// A budget is consumed as the routine runs: each iteration is counted
+/// against the iteration limit, and the deadline is measured from when it
+/// was set. Use a fresh budget for each call.
+///
+/// ```
+/// # use hebrides::guard::{Budget, ResourceExceeded};
+/// let mut budget = Budget::unlimited().with_max_iterations(2);
+/// assert!(budget.step().is_ok() && budget.step().is_ok());
+/// assert_eq!(budget.step(), Err(ResourceExceeded::Iterations { limit: 2 }));
+/// ```
+#[derive(Default)]
+pub struct Budget {
+	max_iterations: Option<usize>,
+	max_elements: Option<usize>,
+	deadline: Option<Deadline>,
+	iterations: usize
+}
+
+impl Budget {
+
+	/// Constructs a [`Budget`] with no limits.
+	pub fn unlimited() -> Budget {
+		Budget::default()
+	}
+
+	/// Limits the number of iterations.
+	pub fn with_max_iterations(mut self, max_iterations: usize) -> Budget {
+		self.max_iterations = Some(max_iterations);
+		self
+	}
+
+	/// Limits the number of elements in any single allocation.
+	pub fn with_max_elements(mut self, max_elements: usize) -> Budget {
+		self.max_elements = Some(max_elements);
+		self
+	}
+
+	/// Limits the running time to `limit` as measured by `clock`, which
+	/// returns the time since any fixed instant. The time is measured from
+	/// this call.
+	///
+	/// ```
+	/// # use std::time::{Duration, Instant};
+	/// # use hebrides::guard::Budget;
+	/// let epoch = Instant::now();
+	/// let budget = Budget::unlimited().with_deadline(move || epoch.elapsed(), Duration::from_millis(50));
+	/// ```
+	pub fn with_deadline(mut self, clock: impl Fn() -> Duration + 'static, limit: Duration) -> Budget {
+		let start = clock();
+		self.deadline = Some(Deadline { clock: Box::new(clock), start, limit });
+		self
+	}
+
+	/// Returns the number of iterations counted so far.
+	pub fn iterations(&self) -> usize {
+		self.iterations
+	}
+
+	/// Counts one iteration, returning an error if this exceeds the iteration
+	/// limit or the deadline has passed.
+	pub fn step(&mut self) -> Result<(), ResourceExceeded> {
+		self.iterations += 1;
+		if let Some(limit) = self.max_iterations.filter(|limit| self.iterations > *limit) {
+			return Err(ResourceExceeded::Iterations { limit });
+		}
+		if let Some(deadline) = &self.deadline {
+			let elapsed = (deadline.clock)().saturating_sub(deadline.start);
+			if elapsed > deadline.limit {
+				return Err(ResourceExceeded::Time { elapsed, limit: deadline.limit });
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns an error if an allocation of `elements` elements exceeds the
+	/// allocation limit.
+	pub fn reserve(&self, elements: usize) -> Result<(), ResourceExceeded> {
+		match self.max_elements {
+			Some(limit) if elements > limit => Err(ResourceExceeded::Allocation { requested: elements, limit }),
+			_ => Ok(())
+		}
+	}
+
+}
+
+impl std::fmt::Debug for Budget {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Budget")
+		 .field("max_iterations", &self.max_iterations)
+		 .field("max_elements", &self.max_elements)
+		 .field("time_limit", &self.deadline.as_ref().map(|d| d.limit))
+		 .field("iterations", &self.iterations)
+		 .finish()
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+	use std::rc::Rc;
+	use std::cell::Cell;
+
+	#[test]
+	fn deadline_uses_supplied_clock() {
+		let now = Rc::new(Cell::new(Duration::from_secs(100)));
+		let clock = Rc::clone(&now);
+		let mut budget = Budget::unlimited().with_deadline(move || clock.get(), Duration::from_secs(5));
+		now.set(Duration::from_secs(104));
+		assert!(budget.step().is_ok());
+		now.set(Duration::from_secs(106));
+		assert_eq!(budget.step(), Err(ResourceExceeded::Time { elapsed: Duration::from_secs(6), limit: Duration::from_secs(5) }))
+	}
+
+	#[test]
+	fn allocation_limit() {
+		let budget = Budget::unlimited().with_max_elements(100);
+		assert!(budget.reserve(100).is_ok());
+		assert_eq!(budget.reserve(101), Err(ResourceExceeded::Allocation { requested: 101, limit: 100 }))
+	}
+
+	#[test]
+	fn unlimited_never_fails() {
+		let mut budget = Budget::unlimited();
+		assert!((0..1000).all(|_| budget.step().is_ok()));
+		assert!(budget.reserve(usize::MAX).is_ok());
+		assert_eq!(budget.iterations(), 1000)
+	}
+
+}
@@ -0,0 +1,423 @@
+//! Interpolation and smoothing.
+//!
+//! Curves fitted to noisy samples: cubic [`smoothing_spline`]s, whose
+//! roughness penalty can be chosen by generalized cross-validation, and
+//! least squares polynomials in a basis of [`orthogonal_polyfit`]
+//! polynomials, which avoids the ill-conditioning of the Vandermonde matrix.
+
+use crate::linal::{Vector, BandedMatrix};
+
+/// A natural cubic smoothing spline, computed by [`smoothing_spline`].
+///
+/// The spline is stored by its values and second derivatives at the knots,
+/// and is extended linearly beyond the first and last knots.
+#[derive(Debug, Clone)]
+pub struct SmoothingSpline {
+	knots: Vec<f64>,
+	values: Vec<f64>,
+	second_derivatives: Vec<f64>,
+	lambda: f64,
+	degrees_of_freedom: f64,
+	gcv: f64
+}
+
+impl SmoothingSpline {
+
+	/// Returns the value of the spline at `x`.
+	pub fn eval(&self, x: f64) -> f64 {
+		let (t, g, gamma) = (&self.knots, &self.values, &self.second_derivatives);
+		let n = t.len();
+		if x <= t[0] {
+			let h = t[1] - t[0];
+			return g[0] + (x - t[0]) * ((g[1] - g[0]) / h - h * gamma[1] / 6.0);
+		}
+		if x >= t[n - 1] {
+			let h = t[n - 1] - t[n - 2];
+			return g[n - 1] + (x - t[n - 1]) * ((g[n - 1] - g[n - 2]) / h + h * gamma[n - 2] / 6.0);
+		}
+		let i = t.partition_point(|knot| *knot <= x) - 1;
+		let h = t[i + 1] - t[i];
+		let (left, right) = (x - t[i], t[i + 1] - x);
+		(left * g[i + 1] + right * g[i]) / h
+			- left * right / 6.0 * ((1.0 + left / h) * gamma[i + 1] + (1.0 + right / h) * gamma[i])
+	}
+
+	/// Returns the sorted, distinct sample locations at which the spline
+	/// has its knots.
+	pub fn knots(&self) -> &[f64] {
+		&self.knots
+	}
+
+	/// Returns the values of the spline at its knots.
+	pub fn fitted(&self) -> &[f64] {
+		&self.values
+	}
+
+	/// Returns the second derivatives of the spline at its knots, which are
+	/// zero at the first and last.
+	pub fn second_derivatives(&self) -> &[f64] {
+		&self.second_derivatives
+	}
+
+	/// Returns the roughness penalty λ the spline was fitted with.
+	pub fn lambda(&self) -> f64 {
+		self.lambda
+	}
+
+	/// Returns the effective degrees of freedom of the fit, the trace of
+	/// its hat matrix, which falls from the number of knots for
+	/// interpolation to 2 for a straight line as λ grows.
+	pub fn degrees_of_freedom(&self) -> f64 {
+		self.degrees_of_freedom
+	}
+
+	/// Returns the generalized cross-validation score n·RSS/(n - df)² of
+	/// the fit, an estimate of its prediction error.
+	pub fn gcv(&self) -> f64 {
+		self.gcv
+	}
+
+}
+
+/// The banded matrices R and QᵀQ of the Reinsch formulation, with Q
+/// stored by its three nonzero entries per column.
+struct Reinsch {
+	q: Vec<[f64; 3]>,
+	r: BandedMatrix<f64>,
+	qtq: BandedMatrix<f64>
+}
+
+impl Reinsch {
+
+	fn new(knots: &[f64]) -> Self {
+		let m = knots.len() - 2;
+		let h: Vec<f64> = knots.windows(2).map(|w| w[1] - w[0]).collect();
+		let q: Vec<[f64; 3]> = (0..m).map(|j| [1.0 / h[j], -1.0 / h[j] - 1.0 / h[j + 1], 1.0 / h[j + 1]]).collect();
+		let mut r = BandedMatrix::zeros(m, 1, 1);
+		let mut qtq = BandedMatrix::zeros(m, 2, 2);
+		for j in 0..m {
+			r.set(j, j, (h[j] + h[j + 1]) / 3.0);
+			if j + 1 < m {
+				r.set(j, j + 1, h[j + 1] / 6.0);
+				r.set(j + 1, j, h[j + 1] / 6.0);
+			}
+			// Column j of Q covers rows j..j + 3, so columns overlap only
+			// within a distance of two.
+			for k in j..(j + 3).min(m) {
+				let dot: f64 = (k..j + 3).map(|row| q[j][row - j] * q[k][row - k]).sum();
+				qtq.set(j, k, dot);
+				qtq.set(k, j, dot);
+			}
+		}
+		Reinsch { q, r, qtq }
+	}
+
+	/// Returns the smoothed values, the second derivatives at the interior
+	/// knots, and the trace of the hat matrix for penalty `lambda`.
+	fn fit(&self, y: &[f64], lambda: f64) -> (Vec<f64>, Vec<f64>, f64) {
+		let m = self.q.len();
+		let mut system = BandedMatrix::zeros(m, 2, 2);
+		for j in 0..m {
+			for k in j.saturating_sub(2)..(j + 3).min(m) {
+				let r = self.r.get(j, k).copied().unwrap_or(0.0);
+				system.set(j, k, r + lambda * self.qtq.get(j, k).unwrap());
+			}
+		}
+		let qty = Vector::from_fn(m, |j| (0..3).map(|k| self.q[j][k] * y[j + k]).sum());
+		let gamma = system.solve(&qty).expect("Smoothing spline system is positive definite");
+		let mut values = y.to_vec();
+		for j in 0..m {
+			for k in 0..3 {
+				values[j + k] -= lambda * self.q[j][k] * gamma[j];
+			}
+		}
+		// tr(I - λQ(R + λQᵀQ)⁻¹Qᵀ) = n - λ tr((R + λQᵀQ)⁻¹QᵀQ).
+		let trace: f64 = (0..m).map(|j| {
+			let column = Vector::from_fn(m, |i| self.qtq.get(i, j).copied().unwrap_or(0.0));
+			system.solve(&column).unwrap()[j]
+		}).sum();
+		(values, gamma.iter().copied().collect(), y.len() as f64 - lambda * trace)
+	}
+
+}
+
+/// Fits a natural cubic smoothing spline to the samples (`x`, `y`),
+/// minimizing Σ(yᵢ - g(xᵢ))² + λ∫g''(t)² dt over twice-differentiable g.
+///
+/// Small λ follows the data closely, approaching the interpolating spline,
+/// while large λ approaches the least squares line. If `lambda` is `None`
+/// it is chosen to minimize the generalized cross-validation score, which
+/// estimates prediction error without refitting on held-out points.
+///
+/// Uses the Reinsch algorithm, solving a banded system in O(n) per fit;
+/// selecting λ costs O(n²) per candidate for the trace of the hat matrix.
+///
+/// ```
+/// # use hebrides::linal::Vector;
+/// # use hebrides::random::Rng;
+/// # use hebrides::interp::smoothing_spline;
+/// let mut rng = Rng::new(7);
+/// let x = Vector::from_fn(40, |i| i as f64 / 39.0);
+/// let y = Vector::from_fn(40, |i| (6.0 * x[i]).sin() + 0.1 * (rng.next_f64() - 0.5));
+/// let spline = smoothing_spline(&x, &y, None);
+/// assert!(((spline.eval(0.5) - 3.0f64.sin())).abs() < 0.05);
+/// assert!(spline.degrees_of_freedom() < 30.0);
+/// ```
+///
+/// # Panics
+/// Panics if `x` and `y` differ in length, if `x` does not contain at least
+/// three distinct values, if `x` contains repeated values, or if `lambda`
+/// is negative.
+pub fn smoothing_spline(x: &Vector<f64>, y: &Vector<f64>, lambda: Option<f64>) -> SmoothingSpline {
+	if x.dim() != y.dim() {
+		panic!("Sample locations and values must have the same length")
+	}
+	if lambda.is_some_and(|l| l < 0.0) {
+		panic!("Smoothing penalty must be nonnegative")
+	}
+	let mut samples: Vec<(f64, f64)> = x.iter().copied().zip(y.iter().copied()).collect();
+	samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+	if samples.windows(2).any(|w| w[0].0 == w[1].0) {
+		panic!("Sample locations must be distinct")
+	}
+	if samples.len() < 3 {
+		panic!("Smoothing spline needs at least three samples")
+	}
+	let (knots, values): (Vec<f64>, Vec<f64>) = samples.into_iter().unzip();
+	let reinsch = Reinsch::new(&knots);
+	let n = knots.len() as f64;
+	let score = |lambda: f64| {
+		let (fitted, gamma, trace) = reinsch.fit(&values, lambda);
+		let rss: f64 = fitted.iter().zip(values.iter()).map(|(g, v)| (g - v) * (g - v)).sum();
+		(n * rss / ((n - trace) * (n - trace)), fitted, gamma, trace)
+	};
+	let lambda = lambda.unwrap_or_else(|| {
+		// Search log λ about the scale at which penalty and fit balance,
+		// first on a coarse grid and then by golden section.
+		let scale = (0..knots.len() - 2).map(|j| *reinsch.r.get(j, j).unwrap()).sum::<f64>()
+			/ (0..knots.len() - 2).map(|j| *reinsch.qtq.get(j, j).unwrap()).sum::<f64>();
+		let gcv = |t: f64| score(scale * t.exp()).0;
+		let best = (-30..=20).map(|t| (f64::from(t), gcv(f64::from(t)))).min_by(|a, b| a.1.total_cmp(&b.1)).unwrap().0;
+		let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+		let (mut low, mut high) = (best - 1.0, best + 1.0);
+		while high - low > 1e-3 {
+			let (left, right) = (high - ratio * (high - low), low + ratio * (high - low));
+			if gcv(left) < gcv(right) { high = right } else { low = left }
+		}
+		scale * ((low + high) / 2.0).exp()
+	});
+	let (gcv, fitted, gamma, trace) = score(lambda);
+	let mut second_derivatives = vec![0.0];
+	second_derivatives.extend(gamma);
+	second_derivatives.push(0.0);
+	SmoothingSpline { knots, values: fitted, second_derivatives, lambda, degrees_of_freedom: trace, gcv }
+}
+
+/// A least squares polynomial expressed in polynomials orthogonal over its
+/// sample locations, computed by [`orthogonal_polyfit`].
+#[derive(Debug, Clone)]
+pub struct OrthogonalPolynomial {
+	alpha: Vec<f64>,
+	beta: Vec<f64>,
+	coefficients: Vec<f64>,
+	rss: f64
+}
+
+impl OrthogonalPolynomial {
+
+	/// Returns the value of the polynomial at `x`, by running the
+	/// three-term recurrence of its basis.
+	pub fn eval(&self, x: f64) -> f64 {
+		let (mut previous, mut current) = (0.0, 1.0);
+		let mut sum = self.coefficients[0];
+		for k in 1..self.coefficients.len() {
+			let next = (x - self.alpha[k - 1]) * current - self.beta[k - 1] * previous;
+			(previous, current) = (current, next);
+			sum += self.coefficients[k] * current;
+		}
+		sum
+	}
+
+	/// Returns the degree of the fit.
+	pub fn degree(&self) -> usize {
+		self.coefficients.len() - 1
+	}
+
+	/// Returns the coefficients of the fit in its orthogonal basis. Because
+	/// the basis is orthogonal, each is unchanged by fitting a higher
+	/// degree to the same data.
+	pub fn coefficients(&self) -> &[f64] {
+		&self.coefficients
+	}
+
+	/// Returns the residual sum of squares of the fit.
+	pub fn rss(&self) -> f64 {
+		self.rss
+	}
+
+	/// Returns the coefficients of the fit in the monomial basis, from the
+	/// constant term up.
+	///
+	/// Monomial coefficients are themselves ill-conditioned for high
+	/// degrees or samples far from the origin; prefer [`eval`](Self::eval).
+	pub fn to_monomial(&self) -> Vec<f64> {
+		let n = self.coefficients.len();
+		let mut result = vec![0.0; n];
+		let (mut previous, mut current) = (vec![0.0; n], vec![0.0; n]);
+		current[0] = 1.0;
+		for k in 0..n {
+			if k > 0 {
+				let mut next = vec![0.0; n];
+				for i in 0..k {
+					next[i + 1] += current[i];
+					next[i] -= self.alpha[k - 1] * current[i] + self.beta[k - 1] * previous[i];
+				}
+				(previous, current) = (current, next);
+			}
+			for (r, c) in result.iter_mut().zip(current.iter()) {
+				*r += self.coefficients[k] * c;
+			}
+		}
+		result
+	}
+
+}
+
+/// Fits a polynomial of degree `degree` to the samples (`x`, `y`) by least
+/// squares, in the basis of polynomials orthogonal over `x`.
+///
+/// Forsythe's method builds the basis by the recurrence
+/// pₖ₊₁(t) = (t - αₖ)pₖ(t) - βₖpₖ₋₁(t), after which each coefficient is an
+/// independent projection of `y`. This sidesteps the normal equations of
+/// the Vandermonde matrix, whose condition number grows exponentially with
+/// the degree.
+///
+/// ```
+/// # use hebrides::linal::Vector;
+/// # use hebrides::interp::orthogonal_polyfit;
+/// // Samples far from the origin defeat a Vandermonde fit in f64.
+/// let x = Vector::from_fn(30, |i| 1000.0 + i as f64 / 29.0);
+/// let y = Vector::from_fn(30, |i| (x[i] - 1000.5).powi(6));
+/// let fit = orthogonal_polyfit(&x, &y, 6);
+/// assert!(fit.rss() < 1e-20);
+/// assert!((fit.eval(1000.25) - 0.25f64.powi(6)).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+/// Panics if `x` and `y` differ in length or if there are not more than
+/// `degree` distinct sample locations.
+pub fn orthogonal_polyfit(x: &Vector<f64>, y: &Vector<f64>, degree: usize) -> OrthogonalPolynomial {
+	if x.dim() != y.dim() {
+		panic!("Sample locations and values must have the same length")
+	}
+	let mut distinct: Vec<f64> = x.iter().copied().collect();
+	distinct.sort_by(f64::total_cmp);
+	distinct.dedup();
+	if distinct.len() <= degree {
+		panic!("Polynomial fit needs more distinct samples than its degree")
+	}
+	let (mut alpha, mut beta, mut coefficients) = (Vec::new(), Vec::new(), Vec::new());
+	let mut previous = vec![0.0; x.dim()];
+	let mut current = vec![1.0; x.dim()];
+	let mut norm: f64 = x.dim() as f64;
+	let mut residual: Vec<f64> = y.iter().copied().collect();
+	for k in 0..=degree {
+		if k > 0 {
+			let a = x.iter().zip(current.iter()).map(|(t, p)| t * p * p).sum::<f64>() / norm;
+			let next: Vec<f64> = (0..x.dim()).map(|i| {
+				(x[i] - a) * current[i] - beta.last().map_or(0.0, |b| b * previous[i])
+			}).collect();
+			let next_norm: f64 = next.iter().map(|p| p * p).sum();
+			alpha.push(a);
+			beta.push(next_norm / norm);
+			(previous, current, norm) = (current, next, next_norm);
+		}
+		// Projecting the running residual rather than y itself is the
+		// modified Gram-Schmidt form, which loses less orthogonality.
+		let c = residual.iter().zip(current.iter()).map(|(r, p)| r * p).sum::<f64>() / norm;
+		for (r, p) in residual.iter_mut().zip(current.iter()) {
+			*r -= c * p;
+		}
+		coefficients.push(c);
+	}
+	// βₖ as pushed is ‖pₖ₊₁‖²/‖pₖ‖², but the recurrence for pₖ₊₁ needs
+	// βₖ = ‖pₖ‖²/‖pₖ₋₁‖², so shift it by one with β₀ = 0.
+	beta.insert(0, 0.0);
+	beta.pop();
+	OrthogonalPolynomial { alpha, beta, coefficients, rss: residual.iter().map(|r| r * r).sum() }
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn spline_interpolates_as_lambda_vanishes() {
+		let x = Vector::new(vec![0.0, 0.5, 1.5, 2.0, 3.0]);
+		let y = Vector::new(vec![1.0, -1.0, 2.0, 0.5, 0.0]);
+		let spline = smoothing_spline(&x, &y, Some(0.0));
+		for (t, v) in x.iter().zip(y.iter()) {
+			assert!((spline.eval(*t) - v).abs() < 1e-12);
+		}
+		assert!((spline.degrees_of_freedom() - 5.0).abs() < 1e-9)
+	}
+
+	#[test]
+	fn spline_approaches_regression_line_as_lambda_grows() {
+		let x = Vector::new(vec![3.0, 0.0, 1.0, 2.0, 4.0]);
+		let y = Vector::new(vec![4.0, 1.0, 0.0, 3.0, 5.0]);
+		let spline = smoothing_spline(&x, &y, Some(1e12));
+		// The least squares line through these points is 0.2 + 1.2x.
+		for t in [-1.0, 0.5, 2.0, 6.0] {
+			assert!((spline.eval(t) - (0.2 + 1.2 * t)).abs() < 1e-6, "{t}");
+		}
+		assert!((spline.degrees_of_freedom() - 2.0).abs() < 1e-6)
+	}
+
+	#[test]
+	fn gcv_smooths_noise() {
+		let x = Vector::from_fn(60, |i| i as f64 / 59.0 * 3.0);
+		let truth = |t: f64| t.cos() + 0.5 * t;
+		let y = Vector::from_fn(60, |i| truth(x[i]) + 0.1 * ((i * i) as f64 * 0.7).sin());
+		let spline = smoothing_spline(&x, &y, None);
+		let error: f64 = x.iter().map(|t| (spline.eval(*t) - truth(*t)).powi(2)).sum::<f64>() / 60.0;
+		let noise: f64 = x.iter().zip(y.iter()).map(|(t, v)| (v - truth(*t)).powi(2)).sum::<f64>() / 60.0;
+		assert!(error < noise / 2.0);
+		assert!(spline.gcv() <= smoothing_spline(&x, &y, Some(spline.lambda() * 100.0)).gcv());
+		assert!(spline.gcv() <= smoothing_spline(&x, &y, Some(spline.lambda() / 100.0)).gcv())
+	}
+
+	#[test]
+	#[should_panic]
+	fn repeated_knots() {
+		smoothing_spline(&Vector::new(vec![0.0, 1.0, 1.0]), &Vector::new(vec![0.0, 1.0, 2.0]), Some(1.0));
+	}
+
+	#[test]
+	fn polyfit_recovers_cubic() {
+		let x = Vector::from_fn(10, |i| i as f64 - 4.0);
+		let y = Vector::from_fn(10, |i| 2.0 - x[i] + 0.5 * x[i].powi(3));
+		let fit = orthogonal_polyfit(&x, &y, 3);
+		let monomial = fit.to_monomial();
+		for (c, expected) in monomial.iter().zip([2.0, -1.0, 0.0, 0.5]) {
+			assert!((c - expected).abs() < 1e-10);
+		}
+		assert!(fit.rss() < 1e-20);
+		assert_eq!(fit.degree(), 3)
+	}
+
+	#[test]
+	fn polyfit_coefficients_are_nested() {
+		let x = Vector::from_fn(12, |i| (i as f64 * 0.37).sin());
+		let y = Vector::from_fn(12, |i| (i as f64).sqrt());
+		let (low, high) = (orthogonal_polyfit(&x, &y, 2), orthogonal_polyfit(&x, &y, 5));
+		for k in 0..3 {
+			assert!((low.coefficients()[k] - high.coefficients()[k]).abs() < 1e-12);
+		}
+		assert!(high.rss() <= low.rss());
+		let line = orthogonal_polyfit(&x, &y, 0);
+		assert!((line.eval(0.3) - y.iter().sum::<f64>() / 12.0).abs() < 1e-12)
+	}
+
+}
@@ -0,0 +1,251 @@
+//! Interval arithmetic.
+//!
+//! Closed intervals of floats whose arithmetic rounds outward, so that the
+//! exact result of every operation on members of the operands is a member of
+//! the result. Intervals plug into [`Vector`] and [`Matrix`] like any other
+//! scalar, and [`krawczyk_solve`] uses them to enclose the solutions of
+//! linear systems with guaranteed bounds.
+
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+use crate::linal::{Vector, Matrix};
+
+/// A closed interval [lo, hi] of floats.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Interval {
+	lo: f64,
+	hi: f64
+}
+
+impl Interval {
+
+	/// Constructs the interval [`lo`, `hi`].
+	///
+	/// # Panics
+	/// Panics if `lo` is greater than `hi` or either endpoint is NaN.
+	pub fn new(lo: f64, hi: f64) -> Self {
+		if lo.is_nan() || hi.is_nan() || lo > hi {
+			panic!("Interval endpoints must be ordered numbers")
+		}
+		Self { lo, hi }
+	}
+
+	/// Constructs the degenerate interval [`x`, `x`].
+	pub fn point(x: f64) -> Self {
+		Self::new(x, x)
+	}
+
+	fn outward(lo: f64, hi: f64) -> Self {
+		Self { lo: lo.next_down(), hi: hi.next_up() }
+	}
+
+	/// Returns the lower endpoint.
+	pub fn lo(&self) -> f64 {
+		self.lo
+	}
+
+	/// Returns the upper endpoint.
+	pub fn hi(&self) -> f64 {
+		self.hi
+	}
+
+	/// Returns the midpoint.
+	pub fn mid(&self) -> f64 {
+		self.lo + (self.hi - self.lo) / 2.0
+	}
+
+	/// Returns the width, hi - lo.
+	pub fn width(&self) -> f64 {
+		self.hi - self.lo
+	}
+
+	/// Returns whether `x` lies in `self`.
+	pub fn contains(&self, x: f64) -> bool {
+		self.lo <= x && x <= self.hi
+	}
+
+	/// Returns whether `self` lies strictly inside `other`, touching neither
+	/// of its endpoints.
+	pub fn is_interior_of(&self, other: &Self) -> bool {
+		other.lo < self.lo && self.hi < other.hi
+	}
+
+	/// Returns the smallest interval containing both `self` and `other`.
+	pub fn hull(&self, other: &Self) -> Self {
+		Self { lo: self.lo.min(other.lo), hi: self.hi.max(other.hi) }
+	}
+
+	/// Returns the intersection of `self` and `other`, or `None` if they are
+	/// disjoint.
+	pub fn intersection(&self, other: &Self) -> Option<Self> {
+		let (lo, hi) = (self.lo.max(other.lo), self.hi.min(other.hi));
+		if lo > hi {
+			return None;
+		}
+		Some(Self { lo, hi })
+	}
+
+}
+
+impl Add<Self> for Interval {
+	type Output = Self;
+	fn add(self, other: Self) -> Self {
+		Interval::outward(self.lo + other.lo, self.hi + other.hi)
+	}
+}
+
+impl Sub<Self> for Interval {
+	type Output = Self;
+	fn sub(self, other: Self) -> Self {
+		Interval::outward(self.lo - other.hi, self.hi - other.lo)
+	}
+}
+
+impl Mul<Self> for Interval {
+	type Output = Self;
+	fn mul(self, other: Self) -> Self {
+		let products = [self.lo * other.lo, self.lo * other.hi, self.hi * other.lo, self.hi * other.hi];
+		let lo = products.iter().copied().fold(f64::INFINITY, f64::min);
+		let hi = products.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+		Interval::outward(lo, hi)
+	}
+}
+
+impl Div<Self> for Interval {
+	type Output = Self;
+	fn div(self, other: Self) -> Self {
+		if other.contains(0.0) {
+			panic!("Cannot divide by an Interval containing zero")
+		}
+		let quotients = [self.lo / other.lo, self.lo / other.hi, self.hi / other.lo, self.hi / other.hi];
+		let lo = quotients.iter().copied().fold(f64::INFINITY, f64::min);
+		let hi = quotients.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+		Interval::outward(lo, hi)
+	}
+}
+
+impl Neg for Interval {
+	type Output = Self;
+	fn neg(self) -> Self {
+		Interval { lo: -self.hi, hi: -self.lo }
+	}
+}
+
+/// Encloses the solutions of Ax = `b` for every A in the interval matrix
+/// `a` and every b in the interval vector `b`, by the Krawczyk method.
+///
+/// With R an approximate inverse of the midpoint of `a` and x̃ an
+/// approximate solution, the operator K(X) = R(b - Ax̃) + (I - RA)X is
+/// iterated on an inflated starting box. Once K maps a box into its own
+/// interior, Brouwer's fixed point theorem guarantees that every A in `a` is
+/// nonsingular and that x̃ + K(X) contains every solution. Returns `None` if
+/// no such box is found, which happens when `a` is singular, contains a
+/// singular matrix, or is too ill-conditioned to verify.
+///
+/// ```
+/// # use hebrides::linal::{Vector, Matrix};
+/// # use hebrides::interval::{Interval, krawczyk_solve};
+/// let a = Matrix::new(vec![vec![4.0, 1.0], vec![1.0, 3.0]]).map(Interval::point);
+/// let b = Vector::new(vec![1.0, 2.0]).map(Interval::point);
+/// let x = krawczyk_solve(&a, &b).unwrap();
+/// assert!(x[0].contains(1.0 / 11.0) && x[1].contains(7.0 / 11.0));
+/// assert!(x[0].width() < 1e-14);
+/// ```
+///
+/// # Panics
+/// Panics if `a` is not square or if `b` does not have as many components
+/// as `a` has rows.
+pub fn krawczyk_solve(a: &Matrix<Interval>, b: &Vector<Interval>) -> Option<Vector<Interval>> {
+	let n = a.dims.num_rows;
+	if !a.is_square() || b.dim() != n {
+		panic!("Right-hand side must match the dimension of the square interval Matrix")
+	}
+	let r = a.map(|e| e.mid()).inverse()?;
+	let approximate = r.clone() * b.map(|e| e.mid());
+	let r = r.map(Interval::point);
+	let approximate = approximate.map(Interval::point);
+	let z = r.clone() * (b.clone() - a.clone() * approximate.clone());
+	let identity = Matrix::new((0..n).map(|i| (0..n).map(|j| Interval::point(if i == j { 1.0 } else { 0.0 })).collect()).collect());
+	let c = identity - r * a.clone();
+	let mut x = z.clone();
+	for _ in 0..20 {
+		let y = x.map(|e| {
+			let margin = 0.1 * e.width() + f64::MIN_POSITIVE;
+			Interval::outward(e.lo - margin, e.hi + margin)
+		});
+		x = z.clone() + c.clone() * y.clone();
+		if (0..n).all(|i| x[i].is_interior_of(&y[i])) {
+			return Some(approximate + x);
+		}
+	}
+	None
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn arithmetic_encloses_exact_results() {
+		let (a, b) = (Interval::new(1.0, 2.0), Interval::new(-3.0, 0.5));
+		let sum = a + b;
+		assert!(sum.contains(-2.0) && sum.contains(2.5));
+		let product = a * b;
+		assert!(product.contains(-6.0) && product.contains(1.0) && !product.contains(1.5));
+		let tenth = Interval::point(1.0) / Interval::point(10.0);
+		assert!(tenth.contains(0.1) && tenth.lo() < tenth.hi());
+		assert_eq!(-a, Interval::new(-2.0, -1.0))
+	}
+
+	#[test]
+	fn hull_and_intersection() {
+		let (a, b) = (Interval::new(0.0, 2.0), Interval::new(1.0, 3.0));
+		assert_eq!(a.hull(&b), Interval::new(0.0, 3.0));
+		assert_eq!(a.intersection(&b), Some(Interval::new(1.0, 2.0)));
+		assert_eq!(a.intersection(&Interval::point(5.0)), None)
+	}
+
+	#[test]
+	fn interval_matrix_vector_product_encloses_point_products() {
+		let a = Matrix::new(vec![
+			vec![Interval::new(1.0, 1.1), Interval::point(-2.0)],
+			vec![Interval::point(0.5), Interval::new(2.9, 3.0)]
+		]);
+		let x = Vector::new(vec![Interval::point(1.0), Interval::new(-1.0, 1.0)]);
+		let y = a * x;
+		for (a00, a11, x1) in [(1.0, 2.9, -1.0), (1.1, 3.0, 1.0), (1.05, 2.95, 0.3)] {
+			assert!(y[0].contains(a00 - 2.0 * x1) && y[1].contains(0.5 + a11 * x1));
+		}
+	}
+
+	#[test]
+	fn krawczyk_encloses_solution_set() {
+		let a = Matrix::new(vec![
+			vec![Interval::new(3.9, 4.1), Interval::point(1.0), Interval::point(0.0)],
+			vec![Interval::point(1.0), Interval::new(4.9, 5.1), Interval::point(-1.0)],
+			vec![Interval::point(0.0), Interval::point(2.0), Interval::new(5.9, 6.1)]
+		]);
+		let b = Vector::new(vec![Interval::point(1.0), Interval::new(1.9, 2.1), Interval::point(3.0)]);
+		let x = krawczyk_solve(&a, &b).unwrap();
+		for (d0, d1, d2, b1) in [(3.9, 4.9, 5.9, 1.9), (4.1, 5.1, 6.1, 2.1), (4.0, 5.0, 6.0, 2.0), (3.9, 5.1, 6.0, 2.1)] {
+			let point = Matrix::new(vec![vec![d0, 1.0, 0.0], vec![1.0, d1, -1.0], vec![0.0, 2.0, d2]]);
+			let solution = point.solve(&Vector::new(vec![1.0, b1, 3.0])).unwrap();
+			assert!((0..3).all(|i| x[i].contains(solution[i])));
+		}
+	}
+
+	#[test]
+	fn singular_system_is_not_verified() {
+		let a = Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).map(Interval::point);
+		let b = Vector::new(vec![1.0, 2.0]).map(Interval::point);
+		assert!(krawczyk_solve(&a, &b).is_none())
+	}
+
+	#[test]
+	#[should_panic]
+	fn division_by_interval_containing_zero() {
+		let _ = Interval::point(1.0) / Interval::new(-1.0, 1.0);
+	}
+
+}
@@ -2,12 +2,34 @@
 //! 
 //! `Real` and `Complex` are Rust implementations of their mathematical
 //! counterparts, and `Vector` and `Matrix` form the basis of the crate's
-//! linear algebra systems.
+//! linear algebra systems. The [`prelude`] re-exports the commonly used
+//! types and traits.
 
 #![deny(rust_2018_idioms, missing_docs)]
 
+pub mod calculus;
+pub mod control;
 pub mod elem;
+pub mod graph;
+pub mod guard;
+pub mod interp;
+pub mod interval;
 pub mod linal;
+pub mod nn;
+pub mod noise;
+pub mod optimize;
+pub mod plot;
+#[cfg(feature = "posit")]
+pub mod posit;
+pub mod prelude;
+pub mod random;
+pub mod signal;
+pub mod stats;
+#[cfg(feature = "strict")]
+pub mod strict;
+pub mod symbolic;
+#[cfg(feature = "tune")]
+pub mod tune;
 
 pub use elem::*;
 pub use linal::*;
@@ -4,7 +4,71 @@
 //! support a wide array of operations in finite-dimensional space and
 //! form the basis of the linear algebra system for `hebrides`.
 
-use std::ops::{Add, Sub, Mul, Div, Neg, Index};
+use std::ops::{Add, Sub, Mul, Div, Neg, Index, IndexMut};
+use std::ops::{AddAssign, SubAssign, MulAssign, DivAssign};
+
+use crate::{ApproxEq, Complex, Conjugate, Float, Semiring, Ring};
+
+mod batched;
+mod completion;
+mod decomp;
+mod format;
+pub mod kernels;
+mod function;
+mod generator;
+mod lowrank;
+mod lstsq;
+mod masked;
+mod orthogonal;
+mod permutation;
+mod polynomial;
+mod quantize;
+mod reduce;
+mod sorted;
+mod space;
+mod sparse;
+mod stochastic;
+mod strassen;
+mod structured;
+mod subspace;
+mod trace;
+mod transform;
+mod typed;
+mod uncertain;
+
+pub use batched::*;
+pub use completion::*;
+pub use decomp::*;
+pub use format::*;
+pub use function::*;
+pub use generator::*;
+pub use lowrank::*;
+pub use lstsq::*;
+pub use masked::*;
+pub use orthogonal::*;
+pub use permutation::*;
+pub use polynomial::*;
+pub use quantize::*;
+pub use reduce::*;
+pub use sorted::*;
+pub use space::*;
+pub use sparse::*;
+pub use strassen::*;
+pub use structured::*;
+pub use subspace::*;
+pub use trace::*;
+pub use typed::*;
+pub use uncertain::*;
+
+/// Error type for data whose length does not fit the requested shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeError;
+
+impl std::fmt::Display for ShapeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "Data does not fit the requested shape")
+	}
+}
 
 /// Implementation for a finite-dimensional vector over T.
 #[derive(Debug)]
@@ -24,32 +88,276 @@ impl<T> Vector<T> {
 		Self { components, dim }
 	}
 
+	/// Constructs a [`Vector`] from a [`Vec`], returning an error instead of
+	/// panicking if `components` is empty.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, ShapeError};
+	/// assert!(Vector::try_new(vec![1, 2]).is_ok());
+	/// assert_eq!(Vector::<i32>::try_new(vec![]), Err(ShapeError));
+	/// ```
+	pub fn try_new(components: Vec<T>) -> Result<Vector<T>, ShapeError> {
+		if components.is_empty() {
+			return Err(ShapeError);
+		}
+		Ok(Vector::new(components))
+	}
+
+	/// Constructs the `n`-dimensional [`Vector`] whose `i`th component is
+	/// `f(i)`.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// assert_eq!(Vector::from_fn(3, |i| i * i), Vector::new(vec![0, 1, 4]));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `n` is zero.
+	pub fn from_fn(n: usize, f: impl FnMut(usize) -> T) -> Vector<T> {
+		Vector::new((0..n).map(f).collect())
+	}
+
+	/// Returns the number of components in `self`.
+	pub fn dim(&self) -> usize {
+		self.dim
+	}
+
 	/// Returns whether or not `a` and `b` are of the same dimension.
 	pub fn same_dim(a: &Self, b: &Self) -> bool {
 		a.dim == b.dim
 	}
 
+	/// Returns the `index`th component of `self`, or `None` if it is out of bounds.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let v = Vector::new(vec![1, 2]);
+	/// assert_eq!(v.get(1), Some(&2));
+	/// assert_eq!(v.get(2), None);
+	/// ```
+	pub fn get(&self, index: usize) -> Option<&T> {
+		self.components.get(index)
+	}
+
+	/// Returns a mutable reference to the `index`th component of `self`, or
+	/// `None` if it is out of bounds.
+	pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+		self.components.get_mut(index)
+	}
+
+	/// Iterates over the components of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let v = Vector::new(vec![1, -2, 3]);
+	/// assert_eq!(v.iter().filter(|e| **e > 0).count(), 2);
+	/// ```
+	pub fn iter(&self) -> std::slice::Iter<'_, T> {
+		self.components.iter()
+	}
+
+	/// Iterates mutably over the components of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let mut v = Vector::new(vec![1, -2, 3]);
+	/// for e in v.iter_mut() {
+	///     *e *= 2;
+	/// }
+	/// assert_eq!(v, Vector::new(vec![2, -4, 6]));
+	/// ```
+	pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+		self.components.iter_mut()
+	}
+
+}
+
+impl<T> Vector<T> where T: From<u8> {
+
+	/// Constructs the `n`-dimensional zero [`Vector`].
+	///
+	/// # Panics
+	/// Panics if `n` is zero.
+	pub fn zeros(n: usize) -> Vector<T> {
+		Vector::from_fn(n, |_| T::from(0))
+	}
+
+	/// Constructs the `n`-dimensional [`Vector`] of all ones.
+	///
+	/// # Panics
+	/// Panics if `n` is zero.
+	pub fn ones(n: usize) -> Vector<T> {
+		Vector::from_fn(n, |_| T::from(1))
+	}
+
+	/// Constructs the `i`th standard basis [`Vector`] of dimension `n`, which
+	/// is one in component `i` and zero elsewhere.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// assert_eq!(Vector::<f64>::basis(3, 1), Vector::new(vec![0.0, 1.0, 0.0]));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `i` is not less than `n`.
+	pub fn basis(n: usize, i: usize) -> Vector<T> {
+		if i >= n {
+			panic!("Basis index must be less than the dimension of the Vector")
+		}
+		Vector::from_fn(n, |j| T::from(u8::from(i == j)))
+	}
+
+}
+
+impl<T> Vector<T> where T: Copy {
+
+	/// Returns the [`Vector`] obtained by applying `f` to every component of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let v = Vector::new(vec![1, -2, 3]);
+	/// assert_eq!(v.map(|x| x as f64 * 0.5), Vector::new(vec![0.5, -1.0, 1.5]));
+	/// ```
+	pub fn map<U>(&self, f: impl FnMut(T) -> U) -> Vector<U> {
+		Vector::new(self.components.iter().copied().map(f).collect())
+	}
+
+	/// Returns the [`Vector`] obtained by applying `f` to corresponding
+	/// components of `self` and `other`.
+	///
+	/// # Panics
+	/// Panics if `self` and `other` are of different dimensions.
+	pub fn zip_with<S, U>(&self, other: &Vector<S>, mut f: impl FnMut(T, S) -> U) -> Vector<U> where S: Copy {
+		if self.dim != other.dim {
+			panic!("Vectors must be of the same dimension to be zipped")
+		}
+		Vector::new(self.components.iter().zip(other.components.iter()).map(|(a, b)| f(*a, *b)).collect())
+	}
+
+
+	fn named_component(&self, index: usize, name: char) -> T {
+		match self.components.get(index) {
+			Some(e) => *e,
+			None => panic!("Vector of dimension {} has no {} component", self.dim, name)
+		}
+	}
+
+	/// Returns the first component of `self`.
+	///
+	/// The named accessors `x`, `y`, `z`, and `w` read the first four
+	/// components, and `xy` and `xyz` extract the leading two and three
+	/// components as a [`Vector`].
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let p = Vector::new(vec![1.0, 2.0, 3.0, 1.0]);
+	/// assert_eq!((p.x(), p.y(), p.z(), p.w()), (1.0, 2.0, 3.0, 1.0));
+	/// assert_eq!(p.xyz(), Vector::new(vec![1.0, 2.0, 3.0]));
+	/// ```
+	///
+	/// ```should_panic
+	/// # use hebrides::linal::Vector;
+	/// let _ = Vector::new(vec![1.0, 2.0]).z(); // panics!
+	/// ```
+	pub fn x(&self) -> T {
+		self.named_component(0, 'x')
+	}
+
+	/// Returns the second component of `self`.
+	///
+	/// # Panics
+	/// Panics if `self` has fewer than two components.
+	pub fn y(&self) -> T {
+		self.named_component(1, 'y')
+	}
+
+	/// Returns the third component of `self`.
+	///
+	/// # Panics
+	/// Panics if `self` has fewer than three components.
+	pub fn z(&self) -> T {
+		self.named_component(2, 'z')
+	}
+
+	/// Returns the fourth component of `self`.
+	///
+	/// # Panics
+	/// Panics if `self` has fewer than four components.
+	pub fn w(&self) -> T {
+		self.named_component(3, 'w')
+	}
+
+	/// Returns the first two components of `self`.
+	///
+	/// # Panics
+	/// Panics if `self` has fewer than two components.
+	pub fn xy(&self) -> Self {
+		Vector::new(vec![self.x(), self.y()])
+	}
+
+	/// Returns the first three components of `self`.
+	///
+	/// # Panics
+	/// Panics if `self` has fewer than three components.
+	pub fn xyz(&self) -> Self {
+		Vector::new(vec![self.x(), self.y(), self.z()])
+	}
+
 }
 
-impl<T> Vector<T> where T: Copy + Mul<Output=T> + Add<Output=T> + Default {
+impl<T> Vector<T> where T: Semiring + Conjugate {
 
 	/// Implements a dot product.
 	///
+	/// This is the Hermitian inner product Σ aᵢ conj(bᵢ), linear in `self`
+	/// and conjugate-linear in `other`; for real scalars the conjugation does
+	/// nothing.
+	///
 	/// This method differs from the implementation offered by Mul<Vector<T>> in that
 	/// it provides for both arguments being borrowed so that ownership of the
 	/// [`Vector`]s involved does not have to be given up to get their product.
+	///
+	/// ```
+	/// # use hebrides::{Complex, linal::Vector};
+	/// let v = Vector::new(vec![Complex::I, Complex::ONE]);
+	/// assert_eq!(v.dot(&v), Complex::new(2.0, 0.0));
+	/// ```
 	pub fn dot(&self, other: &Self) -> T {
+		hermitian_dot(&self.components, &other.components)
+	}
+
+}
+
+impl<T> Vector<T> where T: Copy + Mul<Output=T> {
+
+	/// Returns the outer product of `self` with `other`, the [`Matrix`] whose
+	/// (i, j) entry is `self[i] * other[j]`.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, Matrix};
+	/// let a = Vector::new(vec![1, 2]);
+	/// let b = Vector::new(vec![3, 4, 5]);
+	/// assert_eq!(a.outer(&b), Matrix::new(vec![vec![3, 4, 5], vec![6, 8, 10]]));
+	/// ```
+	pub fn outer(&self, other: &Self) -> Matrix<T> {
+		Matrix::new(self.components.iter().map(|a| other.components.iter().map(|b| *a * *b).collect()).collect())
+	}
+
+	/// Returns the Hadamard (componentwise) product of `self` with `other`.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let weights = Vector::new(vec![0.5, 0.0, 2.0]);
+	/// assert_eq!(Vector::new(vec![4.0, 3.0, 1.5]).hadamard(&weights), Vector::new(vec![2.0, 0.0, 3.0]));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` and `other` are of different dimensions.
+	pub fn hadamard(&self, other: &Self) -> Self {
 		if self.dim != other.dim {
-			panic!("Vectors must be of the same dimension to have their dot product taken")
+			panic!("Vectors must be of the same dimension to have their Hadamard product taken")
 		}
-		let mut sum = self.components[0] * other.components[0];
-		for (i, (a, b)) in self.components.iter().zip(other.components.iter()).enumerate() {
-			if i == 0 {
-				continue;
-			}
-			sum = sum + *a * *b;
-		}
-		sum
+		Vector::new(self.components.iter().zip(other.components.iter()).map(|(a, b)| *a * *b).collect())
 	}
 
 }
@@ -70,22 +378,77 @@ impl<T> Vector<T> where T: Copy + Mul<Output=T> + Sub<Output=T> {
 		Vector::new(vec![x, y, z])
 	}
 
+	/// Returns the two-dimensional cross product of `self` with `other`, the
+	/// scalar `self[0] * other[1] - self[1] * other[0]`.
+	///
+	/// This is positive when `other` lies counterclockwise of `self`,
+	/// negative when it lies clockwise, and zero when the two are parallel.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let (a, b) = (Vector::new(vec![1, 0]), Vector::new(vec![1, 1]));
+	/// assert_eq!(a.cross_2d(&b), 1);
+	/// assert_eq!(b.cross_2d(&a), -1);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` and `other` are not both two-dimensional.
+	pub fn cross_2d(&self, other: &Self) -> T {
+		if self.dim != 2 || other.dim != 2 {
+			panic!("Vectors must be two-dimensional to have a two-dimensional cross product")
+		}
+		self.components[0]*other.components[1] - self.components[1]*other.components[0]
+	}
+
+	/// Returns the scalar triple product `a` · (`b` × `c`), the signed volume
+	/// of the parallelepiped spanned by the three vectors.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let a = Vector::new(vec![1, 0, 0]);
+	/// let b = Vector::new(vec![0, 2, 0]);
+	/// let c = Vector::new(vec![0, 0, 3]);
+	/// assert_eq!(Vector::triple(&a, &b, &c), 6);
+	/// assert_eq!(Vector::triple(&a, &c, &b), -6);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if the vectors are not all three-dimensional.
+	pub fn triple(a: &Self, b: &Self, c: &Self) -> T where T: Add<Output=T> {
+		if a.dim != 3 {
+			panic!("Vectors must be three-dimensional to have valid triple products")
+		}
+		let bc = b.cross(c);
+		a.components[0]*bc.components[0] + a.components[1]*bc.components[1] + a.components[2]*bc.components[2]
+	}
+
 }
 
-impl<T> Vector<T> where T: Copy + Add<Output=T> + std::iter::Sum<T> {
+impl<T> Vector<T> where T: Copy + Mul<Output=T> + std::iter::Sum<T> {
 
 	/// Returns the squared norm of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// assert_eq!(Vector::new(vec![3, -4]).square_norm(), 25);
+	/// ```
 	pub fn square_norm(&self) -> T {
-		self.components.iter().map(|e| *e).sum::<T>()
+		self.components.iter().map(|e| *e * *e).sum::<T>()
 	}
 
 }
 
-impl Vector<f32> {
+impl<T> Vector<T> where T: Float {
 
 	/// Returns the norm of `self`.
-	pub fn norm(&self) -> f32 {
-		self.square_norm().powf(0.5)
+	///
+	/// ```
+	/// # use hebrides::{Real, linal::Vector};
+	/// let v = Vector::new(vec![Real::new(3.0), Real::new(-4.0)]);
+	/// assert_eq!(v.norm(), Real::new(5.0));
+	/// ```
+	pub fn norm(&self) -> T {
+		self.components.iter().fold(T::ZERO, |sum, e| sum + *e * *e).sqrt()
 	}
 
 	/// Normalized version of `self`.
@@ -96,21 +459,187 @@ impl Vector<f32> {
 
 }
 
-impl Vector<f64> {
+/// Distance metrics between [`Vector`]s.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Metric {
+	/// The 2-norm of the difference.
+	Euclidean,
+	/// The 1-norm of the difference, also known as the taxicab distance.
+	Manhattan,
+	/// The infinity-norm of the difference, the largest componentwise gap.
+	Chebyshev
+}
 
-	/// Returns the norm of `self`.
-	pub fn norm(&self) -> f64 {
-		self.square_norm().powf(0.5)
-	}
+macro_rules! impl_float_vector {
+	($t:ty) => {
+		impl Vector<$t> {
+
+			/// Returns the `p`-norm of `self`, the `p`th root of the sum of the
+			/// `p`th powers of the absolute values of its components.
+			///
+			/// An infinite `p` gives [`norm_inf`](Self::norm_inf).
+			///
+			/// ```
+			/// # use hebrides::linal::Vector;
+			#[doc = concat!("let v = Vector::new(vec![3.0_", stringify!($t), ", -4.0]);")]
+			/// assert_eq!(v.p_norm(1.0), 7.0);
+			/// assert_eq!(v.p_norm(2.0), 5.0);
+			#[doc = concat!("assert_eq!(v.p_norm(", stringify!($t), "::INFINITY), 4.0);")]
+			/// ```
+			///
+			/// # Panics
+			/// Panics if `p` is not positive.
+			pub fn p_norm(&self, p: $t) -> $t {
+				if p.is_nan() || p <= 0.0 {
+					panic!("Norm order must be positive")
+				}
+				if p.is_infinite() {
+					return self.norm_inf();
+				}
+				if p == 1.0 {
+					return self.components.iter().map(|e| e.abs()).sum();
+				}
+				if p == 2.0 {
+					return self.norm();
+				}
+				self.components.iter().map(|e| e.abs().powf(p)).sum::<$t>().powf(1.0 / p)
+			}
 
-	/// Normalized version of `self`.
-	pub fn normalized(&self) -> Self {
-		let norm = self.norm();
-		Vector::new(self.components.iter().map(|e| *e / norm).collect())
-	}
+			/// Returns the infinity-norm of `self`, the largest absolute value of
+			/// its components.
+			pub fn norm_inf(&self) -> $t {
+				self.components.iter().fold(0.0, |max: $t, e| max.max(e.abs()))
+			}
 
+			/// Returns the distance between `self` and `other` under `metric`.
+			///
+			/// ```
+			/// # use hebrides::linal::{Vector, Metric};
+			#[doc = concat!("let a = Vector::new(vec![0.0_", stringify!($t), ", 0.0]);")]
+			/// let b = Vector::new(vec![3.0, 4.0]);
+			/// assert_eq!(a.distance(&b, Metric::Euclidean), 5.0);
+			/// assert_eq!(a.distance(&b, Metric::Manhattan), 7.0);
+			/// assert_eq!(a.distance(&b, Metric::Chebyshev), 4.0);
+			/// ```
+			///
+			/// # Panics
+			/// Panics if `self` and `other` are of different dimensions.
+			pub fn distance(&self, other: &Self, metric: Metric) -> $t {
+				if self.dim != other.dim {
+					panic!("Vectors must be of the same dimension to measure the distance between them")
+				}
+				let difference = self.zip_with(other, |a, b| a - b);
+				match metric {
+					Metric::Euclidean => difference.norm(),
+					Metric::Manhattan => difference.p_norm(1.0),
+					Metric::Chebyshev => difference.norm_inf()
+				}
+			}
+
+			/// Returns the projection of `self` onto the line spanned by `other`.
+			///
+			/// ```
+			/// # use hebrides::linal::Vector;
+			#[doc = concat!("let v = Vector::new(vec![2.0_", stringify!($t), ", 3.0]);")]
+			/// let x = Vector::new(vec![4.0, 0.0]);
+			/// assert_eq!(v.project_onto(&x), Vector::new(vec![2.0, 0.0]));
+			/// assert_eq!(v.reject_from(&x), Vector::new(vec![0.0, 3.0]));
+			/// ```
+			///
+			/// # Panics
+			/// Panics if `self` and `other` are of different dimensions or if `other`
+			/// is zero.
+			pub fn project_onto(&self, other: &Self) -> Self {
+				let square_norm = other.square_norm();
+				if square_norm == 0.0 {
+					panic!("Cannot project onto the zero Vector")
+				}
+				other.clone() * (self.dot(other) / square_norm)
+			}
+
+			/// Returns the component of `self` orthogonal to `other`, so that `self`
+			/// is the sum of its projection onto and rejection from `other`.
+			///
+			/// # Panics
+			/// Panics if `self` and `other` are of different dimensions or if `other`
+			/// is zero.
+			pub fn reject_from(&self, other: &Self) -> Self {
+				self.clone() - self.project_onto(other)
+			}
+
+			/// Returns the reflection of `self` off a surface with normal `normal`,
+			/// v - 2(v·n)n for the unit normal n.
+			///
+			/// `normal` is normalized first, and may point to either side of the
+			/// surface.
+			///
+			/// ```
+			/// # use hebrides::linal::Vector;
+			#[doc = concat!("let ray = Vector::new(vec![1.0_", stringify!($t), ", -1.0]);")]
+			/// let floor = Vector::new(vec![0.0, 2.0]);
+			/// assert_eq!(ray.reflect(&floor), Vector::new(vec![1.0, 1.0]));
+			/// ```
+			///
+			/// # Panics
+			/// Panics if `self` and `normal` are of different dimensions or if
+			/// `normal` is zero.
+			pub fn reflect(&self, normal: &Self) -> Self {
+				let n = Self::surface_normal(normal);
+				self.clone() - n.clone() * (2.0 * self.dot(&n))
+			}
+
+			/// Returns the unit direction of `self` after refraction through a
+			/// surface with normal `normal`, by Snell's law with `eta` the ratio
+			/// n₁/n₂ of the refractive indices on the incoming and outgoing sides.
+			///
+			/// `self` is the direction of travel and need not be a unit vector;
+			/// `normal` is normalized first and may point to either side of the
+			/// surface. Returns `None` on total internal reflection.
+			///
+			/// ```
+			/// # use hebrides::linal::Vector;
+			#[doc = concat!("let ray = Vector::new(vec![1.0_", stringify!($t), ", -1.0]);")]
+			/// let surface = Vector::new(vec![0.0, 1.0]);
+			#[doc = concat!("let bent = ray.refract(&surface, 1.0 / 1.5).unwrap();")]
+			#[doc = concat!("assert!((bent[0] - (0.5_", stringify!($t), ".sqrt() / 1.5)).abs() < 1e-6);")]
+			/// assert!(ray.refract(&surface, 1.5).is_none());
+			/// ```
+			///
+			/// # Panics
+			/// Panics if `self` and `normal` are of different dimensions or if
+			/// either is zero.
+			pub fn refract(&self, normal: &Self, eta: $t) -> Option<Self> {
+				if self.square_norm() == 0.0 {
+					panic!("Cannot refract the zero Vector")
+				}
+				let incident = self.normalized();
+				let mut n = Self::surface_normal(normal);
+				let mut cos_in = -incident.dot(&n);
+				if cos_in < 0.0 {
+					n = -n;
+					cos_in = -cos_in;
+				}
+				let k = 1.0 - eta * eta * (1.0 - cos_in * cos_in);
+				if k < 0.0 {
+					return None;
+				}
+				Some(incident * eta + n * (eta * cos_in - k.sqrt()))
+			}
+
+			fn surface_normal(normal: &Self) -> Self {
+				if normal.square_norm() == 0.0 {
+					panic!("Surface normal must be nonzero")
+				}
+				normal.normalized()
+			}
+
+		}
+	}
 }
 
+impl_float_vector![f32];
+impl_float_vector![f64];
+
 impl<T> Clone for Vector<T> where T: Clone {
 	fn clone(&self) -> Vector<T> {
 		Vector::new(self.components.to_vec())
@@ -128,6 +657,24 @@ impl<T> PartialEq for Vector<T> where T: PartialEq {
 	}
 }
 
+impl<T> ApproxEq for Vector<T> where T: ApproxEq {
+	fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+		Vector::same_dim(self, other) && self.components.iter()
+			.zip(other.components.iter())
+			.all(|(a, b)| a.abs_diff_eq(b, epsilon))
+	}
+	fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool {
+		Vector::same_dim(self, other) && self.components.iter()
+			.zip(other.components.iter())
+			.all(|(a, b)| a.relative_eq(b, epsilon, max_relative))
+	}
+	fn ulps_eq(&self, other: &Self, max_ulps: u64) -> bool {
+		Vector::same_dim(self, other) && self.components.iter()
+			.zip(other.components.iter())
+			.all(|(a, b)| a.ulps_eq(b, max_ulps))
+	}
+}
+
 impl<T> Index<usize> for Vector<T> {
 	type Output = T;
 	fn index(&self, index: usize) -> &T {
@@ -135,47 +682,124 @@ impl<T> Index<usize> for Vector<T> {
 	}
 } 
 
+impl<T> IndexMut<usize> for Vector<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T {
+		&mut self.components[index]
+	}
+}
+
+impl<T> IntoIterator for Vector<T> {
+	type Item = T;
+	type IntoIter = std::vec::IntoIter<T>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.components.into_iter()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a Vector<T> {
+	type Item = &'a T;
+	type IntoIter = std::slice::Iter<'a, T>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.components.iter()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a mut Vector<T> {
+	type Item = &'a mut T;
+	type IntoIter = std::slice::IterMut<'a, T>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.components.iter_mut()
+	}
+}
+
+/// Collects an iterator into a [`Vector`].
+///
+/// ```
+/// # use hebrides::linal::Vector;
+/// let squares: Vector<i32> = (1..4).map(|i| i * i).collect();
+/// assert_eq!(squares, Vector::new(vec![1, 4, 9]));
+/// ```
+///
+/// # Panics
+/// Panics if the iterator is empty.
+impl<T> FromIterator<T> for Vector<T> {
+	fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+		Vector::new(iter.into_iter().collect())
+	}
+}
+
 impl<T> Add<Self> for Vector<T> where T: Copy + Add<Output=T> {
 	type Output = Self;
 	fn add(mut self, other: Self) -> Self {
-		if !Vector::same_dim(&self, &other) {
+		self += &other;
+		self
+	}
+}
+
+/// Adds in place, without moving or rebuilding `self`.
+///
+/// ```
+/// # use hebrides::linal::Vector;
+/// let mut total = Vector::new(vec![0.0; 2]);
+/// for v in [Vector::new(vec![1.0, 2.0]), Vector::new(vec![3.0, 4.0])] {
+///     total += &v;
+/// }
+/// assert_eq!(total, Vector::new(vec![4.0, 6.0]));
+/// ```
+impl<T> AddAssign<&Self> for Vector<T> where T: Copy + Add<Output=T> {
+	fn add_assign(&mut self, other: &Self) {
+		if !Vector::same_dim(self, other) {
 			panic!("Sums can only be taken between Vectors of the same dimension")
 		}
-		for (i, component) in other.components.iter().enumerate() {
-			self.components[i] = self.components[i] + *component;
+		for (a, b) in self.components.iter_mut().zip(other.components.iter()) {
+			*a = *a + *b;
 		}
-		self
+	}
+}
+
+impl<T> AddAssign<Self> for Vector<T> where T: Copy + Add<Output=T> {
+	fn add_assign(&mut self, other: Self) {
+		*self += &other;
 	}
 }
 
 impl<T> Sub<Self> for Vector<T> where T: Copy + Sub<Output=T> {
 	type Output = Self;
 	fn sub(mut self, other: Self) -> Self {
-		if !Vector::same_dim(&self, &other) {
+		self -= &other;
+		self
+	}
+}
+
+impl<T> SubAssign<&Self> for Vector<T> where T: Copy + Sub<Output=T> {
+	fn sub_assign(&mut self, other: &Self) {
+		if !Vector::same_dim(self, other) {
 			panic!("Differences can only be taken between Vectors of the same dimension")
 		}
-		for (i, component) in other.components.iter().enumerate() {
-			self.components[i] = self.components[i] - *component;
+		for (a, b) in self.components.iter_mut().zip(other.components.iter()) {
+			*a = *a - *b;
 		}
-		self
 	}
 }
 
-/// Implements a dot product.
-impl<T> Mul<Self> for Vector<T> where T: Copy + Mul<Output=T> + Add<Output=T> {
+impl<T> SubAssign<Self> for Vector<T> where T: Copy + Sub<Output=T> {
+	fn sub_assign(&mut self, other: Self) {
+		*self -= &other;
+	}
+}
+
+fn hermitian_dot<T>(a: &[T], b: &[T]) -> T where T: Semiring + Conjugate {
+	if a.len() != b.len() {
+		panic!("Vectors must be of the same dimension to have their dot product taken")
+	}
+	T::sum_of(a.iter().zip(b.iter()).map(|(x, y)| *x * y.conjugate()))
+}
+
+/// Implements a dot product; see [`Vector::dot`].
+impl<T> Mul<Self> for Vector<T> where T: Semiring + Conjugate {
 	type Output = T;
 	fn mul(self, other: Self) -> T {
-		if self.dim != other.dim {
-			panic!("Vectors must be of the same dimension to have their dot product taken")
-		}
-		let mut sum = self.components[0] * other.components[0];
-		for (i, (a, b)) in self.components.iter().zip(other.components.iter()).enumerate() {
-			if i == 0 {
-				continue;
-			}
-			sum = sum + *a * *b;
-		}
-		sum
+		hermitian_dot(&self.components, &other.components)
 	}
 }
 
@@ -186,16 +810,30 @@ impl<T> Mul<T> for Vector<T> where T: Copy + Mul<Output=T> {
 	}
 }
 
+impl<T> MulAssign<T> for Vector<T> where T: Copy + Mul<Output=T> {
+	fn mul_assign(&mut self, other: T) {
+		for e in self.components.iter_mut() {
+			*e = *e * other;
+		}
+	}
+}
+
 impl<T> Div<T> for Vector<T> where T: Copy + Div<Output=T> {
 	type Output = Self;
 	fn div(mut self, other: T) -> Self {
-		for i in 0..self.dim {
-			self.components[i] = self.components[i] / other;
-		}
+		self /= other;
 		self
 	}
 }
 
+impl<T> DivAssign<T> for Vector<T> where T: Copy + Div<Output=T> {
+	fn div_assign(&mut self, other: T) {
+		for e in self.components.iter_mut() {
+			*e = *e / other;
+		}
+	}
+}
+
 impl<T> Neg for Vector<T> where T: Copy + Neg<Output=T> {
 	type Output = Self;
 	fn neg(self) -> Self {
@@ -208,7 +846,7 @@ impl<T> Neg for Vector<T> where T: Copy + Neg<Output=T> {
 }
 
 /// Helper struct carrying the dimensions of a [`Matrix`].
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct MatrixDimensions {
 	/// Number of rows in the given `Matrix`
 	pub num_rows: usize,
@@ -241,34 +879,176 @@ pub struct Matrix<T> {
 	pub dims: MatrixDimensions
 }
 
-impl<T> Matrix<T> {
+/// Mutable handle to a single entry of a [`Matrix`], keeping its row and
+/// column storage in agreement.
+pub struct ElementMut<'a, T> {
+	in_row: &'a mut T,
+	in_col: &'a mut T
+}
 
-	/// Returns whether or not all containers within the provided [`Vec`] have
-	/// equal length.
-	fn have_equal_length(rows: &Vec<Vec<T>>) -> bool {
-		rows.iter().all(|row| row.len() == rows[0].len())
+impl<T> ElementMut<'_, T> {
+
+	/// Returns the current value of the entry.
+	pub fn get(&self) -> &T {
+		self.in_row
 	}
 
 }
 
-impl<T> Matrix<T> where T: Copy {
+impl<T> ElementMut<'_, T> where T: Clone {
 
-	/// Takes a [`Vec`] describing a matrix in rows and returns a [`Vec`]
-	/// describing the same matrix in terms of columns.
-	fn to_columns(rows: &Vec<Vec<T>>) -> Vec<Vec<T>> {
-		let mut cols: Vec<Vec<T>> = vec![];
-		for i in 0..rows[0].len() {
-			let mut col: Vec<T> = vec![];
-			for j in 0..rows.len() {
-				col.push(rows[j][i])
-			}
-			cols.push(col);
-		}
-		cols
+	/// Overwrites the entry with `value`.
+	pub fn set(&mut self, value: T) {
+		*self.in_col = value.clone();
+		*self.in_row = value;
 	}
 
-	/// Constructs a new [`Matrix`] from a nested [`Vec`].
-	///
+}
+
+/// Mutable row-major iterator over the entries of a [`Matrix`], created by
+/// [`Matrix::iter_mut`].
+pub struct IterMut<'a, T> {
+	rows: std::iter::Enumerate<std::slice::IterMut<'a, Vec<T>>>,
+	current: Option<(usize, std::iter::Enumerate<std::slice::IterMut<'a, T>>)>,
+	cols: Vec<std::slice::IterMut<'a, T>>
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+	type Item = ((usize, usize), ElementMut<'a, T>);
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some((i, row)) = &mut self.current {
+				if let Some((j, in_row)) = row.next() {
+					let in_col = self.cols[j].next().unwrap();
+					return Some(((*i, j), ElementMut { in_row, in_col }));
+				}
+			}
+			let (i, row) = self.rows.next()?;
+			self.current = Some((i, row.iter_mut().enumerate()));
+		}
+	}
+}
+
+impl<T> Matrix<T> {
+
+	/// Returns whether or not all containers within the provided [`Vec`] have
+	/// equal length.
+	fn have_equal_length(rows: &[Vec<T>]) -> bool {
+		rows.iter().all(|row| row.len() == rows[0].len())
+	}
+
+	/// Returns whether or not `self` has as many rows as columns.
+	pub fn is_square(&self) -> bool {
+		self.dims.num_rows == self.dims.num_cols
+	}
+
+	/// Returns the entry at (`row`, `col`), or `None` if it is out of bounds.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let m = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+	/// assert_eq!(m.get(1, 0), Some(&3));
+	/// assert_eq!(m.get(0, 2), None);
+	/// ```
+	pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+		self.rows.get(row)?.get(col)
+	}
+
+	/// Returns a handle to the entry at (`row`, `col`), or `None` if it is out
+	/// of bounds.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let mut m = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+	/// m.get_mut(0, 1).unwrap().set(5);
+	/// assert_eq!(m.cols[1], vec![5, 4]);
+	/// assert!(m.get_mut(2, 0).is_none());
+	/// ```
+	pub fn get_mut(&mut self, row: usize, col: usize) -> Option<ElementMut<'_, T>> {
+		if row >= self.dims.num_rows || col >= self.dims.num_cols {
+			return None;
+		}
+		Some(ElementMut { in_row: &mut self.rows[row][col], in_col: &mut self.cols[col][row] })
+	}
+
+	/// Returns the `row`th row of `self`, or `None` if it is out of bounds.
+	pub fn get_row(&self, row: usize) -> Option<&[T]> {
+		self.rows.get(row).map(|r| r.as_slice())
+	}
+
+	/// Returns the `col`th column of `self`, or `None` if it is out of bounds.
+	pub fn get_col(&self, col: usize) -> Option<&[T]> {
+		self.cols.get(col).map(|c| c.as_slice())
+	}
+
+	/// Iterates over the rows of `self`.
+	pub fn iter_rows(&self) -> impl Iterator<Item=&[T]> {
+		self.rows.iter().map(|row| row.as_slice())
+	}
+
+	/// Iterates over the columns of `self`.
+	pub fn iter_cols(&self) -> impl Iterator<Item=&[T]> {
+		self.cols.iter().map(|col| col.as_slice())
+	}
+
+	/// Iterates over the entries of `self` in row-major order, paired with
+	/// their (row, column) indices.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let m = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+	/// let off_diagonal: i32 = m.iter().filter(|((i, j), _)| i != j).map(|(_, e)| e).sum();
+	/// assert_eq!(off_diagonal, 5);
+	/// ```
+	pub fn iter(&self) -> impl Iterator<Item=((usize, usize), &T)> {
+		self.rows.iter()
+				 .enumerate()
+				 .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, e)| ((i, j), e)))
+	}
+
+	/// Iterates mutably over the entries of `self` in row-major order, paired
+	/// with their (row, column) indices.
+	///
+	/// Entries are yielded as [`ElementMut`]s so that writes reach both the
+	/// rows and the columns of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let mut m = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+	/// for ((i, j), mut e) in m.iter_mut() {
+	///     e.set(*e.get() * 10 + (i + j) as i32);
+	/// }
+	/// assert_eq!(m, Matrix::new(vec![vec![10, 21], vec![31, 42]]));
+	/// assert_eq!(m.cols[1], vec![21, 42]);
+	/// ```
+	pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+		IterMut {
+			rows: self.rows.iter_mut().enumerate(),
+			current: None,
+			cols: self.cols.iter_mut().map(|col| col.iter_mut()).collect()
+		}
+	}
+
+}
+
+impl<T> Matrix<T> where T: Clone {
+
+	/// Takes a [`Vec`] describing a matrix in rows and returns a [`Vec`]
+	/// describing the same matrix in terms of columns.
+	fn to_columns(rows: &[Vec<T>]) -> Vec<Vec<T>> {
+		let mut cols: Vec<Vec<T>> = vec![];
+		for i in 0..rows[0].len() {
+			let mut col: Vec<T> = vec![];
+			for row in rows {
+				col.push(row[i].clone())
+			}
+			cols.push(col);
+		}
+		cols
+	}
+
+	/// Constructs a new [`Matrix`] from a nested [`Vec`].
+	///
 	/// # Panics
 	/// This constructor panics in two scenarios. If the provided `Vec` has no 
 	/// elements, as depicted below,
@@ -288,7 +1068,7 @@ impl<T> Matrix<T> where T: Copy {
 	/// let _ = Matrix::new(vec![vec![1, 0], vec![0]]); // panics!
 	/// ```
 	pub fn new(rows: Vec<Vec<T>>) -> Matrix<T> {
-		if rows.len() == 0 {
+		if rows.is_empty() {
 			panic!("Matrix must be non-empty")
 		}
 		if !Matrix::have_equal_length(&rows) {
@@ -299,41 +1079,462 @@ impl<T> Matrix<T> where T: Copy {
 		Self { rows, cols, dims }
 	}
 
+	/// Constructs a new [`Matrix`] from a nested [`Vec`], returning an error
+	/// instead of panicking if `rows` is empty or ragged.
+	pub fn try_new(rows: Vec<Vec<T>>) -> Result<Matrix<T>, ShapeError> {
+		if rows.is_empty() || rows[0].is_empty() || !Matrix::have_equal_length(&rows) {
+			return Err(ShapeError);
+		}
+		Ok(Matrix::new(rows))
+	}
+
 }
 
-/// Matrix type specifying a Matrix of [`f32`]s.
-pub type Matrix32 = Matrix<f32>;
+impl<T> Matrix<T> where T: Copy {
+
+	/// Constructs a `num_rows`x`num_cols` [`Matrix`] from entries listed in
+	/// row-major order.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let m = Matrix::from_flat(2, 3, vec![1, 2, 3, 4, 5, 6]).unwrap();
+	/// assert_eq!(m, Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]));
+	/// assert!(Matrix::from_flat(2, 2, vec![1, 2, 3]).is_err());
+	/// ```
+	///
+	/// # Errors
+	/// Returns a [`ShapeError`] if either dimension is zero or if `entries`
+	/// does not hold exactly `num_rows * num_cols` entries.
+	pub fn from_flat(num_rows: usize, num_cols: usize, entries: Vec<T>) -> Result<Matrix<T>, ShapeError> {
+		if num_rows == 0 || num_cols == 0 || num_rows.checked_mul(num_cols) != Some(entries.len()) {
+			return Err(ShapeError);
+		}
+		Ok(Matrix::new(entries.chunks(num_cols).map(|row| row.to_vec()).collect()))
+	}
 
-impl Matrix32{
+	/// Returns the entries of `self` in row-major order.
+	pub fn to_flat(&self) -> Vec<T> {
+		self.rows.iter().flatten().copied().collect()
+	}
+
+	/// Returns the entries of `self` rearranged, in row-major order, into a
+	/// `num_rows`x`num_cols` [`Matrix`].
+	///
+	/// # Errors
+	/// Returns a [`ShapeError`] if the new shape does not hold exactly as many
+	/// entries as `self`.
+	pub fn reshape(&self, num_rows: usize, num_cols: usize) -> Result<Matrix<T>, ShapeError> {
+		Matrix::from_flat(num_rows, num_cols, self.to_flat())
+	}
+
+	/// Returns the transpose of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let m = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+	/// assert_eq!(m.transpose(), Matrix::new(vec![vec![1, 4], vec![2, 5], vec![3, 6]]));
+	/// ```
+	pub fn transpose(&self) -> Matrix<T> {
+		Self {
+			rows: self.cols.clone(),
+			cols: self.rows.clone(),
+			dims: MatrixDimensions::new(self.dims.num_cols, self.dims.num_rows)
+		}
+	}
 
-	/// Creates a homogenous transformation matrix that will cause a vector to point at 
-	/// `dir`, using `up` for orientation.
-	pub fn look_at_rh(eye: &Vector<f32>, dir: &Vector<f32>, up: &Vector<f32>) -> Matrix32 {
-		let f = dir.normalized();
-		let s = f.cross(&up).normalized();
-		let u = s.cross(&f);
+	/// Returns the main diagonal of `self` as a [`Vector`].
+	///
+	/// For non-square matrices this is the diagonal starting at the top-left
+	/// entry, which has as many components as the smaller dimension of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, Matrix};
+	/// let m = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+	/// assert_eq!(m.diagonal(), Vector::new(vec![1, 5]));
+	/// ```
+	pub fn diagonal(&self) -> Vector<T> {
+		let n = self.dims.num_rows.min(self.dims.num_cols);
+		Vector::new((0..n).map(|i| self.rows[i][i]).collect())
+	}
 
-		Matrix::new(vec![
-			vec![s[0], u[0], -f[0], 0.0],
-			vec![s[1], u[1], -f[1], 0.0],
-			vec![s[2], u[2], -f[2], 0.0],
-			vec![-eye.dot(&s), -eye.dot(&u), eye.dot(&f), 0.0]
-		])
+	/// Swaps the `a`th and `b`th rows of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let mut m = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+	/// m.swap_rows(0, 1);
+	/// assert_eq!(m, Matrix::new(vec![vec![3, 4], vec![1, 2]]));
+	/// ```
+	pub fn swap_rows(&mut self, a: usize, b: usize) {
+		if a >= self.dims.num_rows || b >= self.dims.num_rows {
+			panic!("Row index out of bounds for Matrix")
+		}
+		self.rows.swap(a, b);
+		for col in self.cols.iter_mut() {
+			col.swap(a, b);
+		}
 	}
 
-	/// Creates a perspective matrix from the given parameters.
-	pub fn perspective(fovy: &f32, aspect: &f32, znear: &f32, zfar: &f32) -> Matrix32 {
-		let f = 1.0 / (fovy / 2.0).tan();
-		Matrix::new(vec![
-			vec![f / aspect, 0.0, 0.0, 0.0],
-			vec![0.0, f, 0.0, 0.0],
-			vec![0.0, 0.0, (zfar + znear) / (znear - zfar), 2.0*zfar*znear / (znear - zfar)],
-			vec![0.0, 0.0, -1.0, 0.0]
-		])
+	/// Swaps the `a`th and `b`th columns of `self`.
+	pub fn swap_cols(&mut self, a: usize, b: usize) {
+		if a >= self.dims.num_cols || b >= self.dims.num_cols {
+			panic!("Column index out of bounds for Matrix")
+		}
+		self.cols.swap(a, b);
+		for row in self.rows.iter_mut() {
+			row.swap(a, b);
+		}
+	}
+
+	/// Returns the [`Matrix`] obtained by applying `f` to every entry of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let m = Matrix::new(vec![vec![-1.5_f64, 2.0], vec![0.5, -3.0]]);
+	/// assert_eq!(m.map(|x| x.max(0.0)), Matrix::new(vec![vec![0.0, 2.0], vec![0.5, 0.0]]));
+	/// assert_eq!(m.map(|x| x < 0.0), Matrix::new(vec![vec![true, false], vec![false, true]]));
+	/// ```
+	pub fn map<U>(&self, mut f: impl FnMut(T) -> U) -> Matrix<U> where U: Copy {
+		Matrix::new(self.rows.iter().map(|row| row.iter().map(|e| f(*e)).collect()).collect())
+	}
+
+	/// Returns the [`Matrix`] obtained by applying `f` to corresponding entries
+	/// of `self` and `other`.
+	///
+	/// # Panics
+	/// Panics if `self` and `other` have different dimensions.
+	pub fn zip_with<S, U>(&self, other: &Matrix<S>, mut f: impl FnMut(T, S) -> U) -> Matrix<U> where S: Copy, U: Copy {
+		if self.dims != other.dims {
+			panic!("Matrices must have the same dimensions to be zipped")
+		}
+		let rows = self.rows.iter()
+							.zip(other.rows.iter())
+							.map(|(a, b)| a.iter().zip(b.iter()).map(|(x, y)| f(*x, *y)).collect())
+							.collect();
+		Matrix::new(rows)
 	}
 
 }
 
+impl<T> Matrix<T> where T: Copy + Default {
+
+	/// Constructs a square [`Matrix`] with `diagonal` along its main diagonal
+	/// and `T::default()` everywhere else.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, Matrix};
+	/// let m = Matrix::from_diagonal(&Vector::new(vec![1, 2]));
+	/// assert_eq!(m, Matrix::new(vec![vec![1, 0], vec![0, 2]]));
+	/// ```
+	pub fn from_diagonal(diagonal: &Vector<T>) -> Matrix<T> {
+		let mut rows = vec![vec![T::default(); diagonal.dim]; diagonal.dim];
+		for (i, row) in rows.iter_mut().enumerate() {
+			row[i] = diagonal[i];
+		}
+		Matrix::new(rows)
+	}
+
+}
+
+impl<T> Matrix<T> where T: Copy + Mul<Output=T> {
+
+	/// Multiplies every entry in the `row`th row of `self` by `factor`.
+	pub fn scale_row(&mut self, row: usize, factor: T) {
+		if row >= self.dims.num_rows {
+			panic!("Row index out of bounds for Matrix")
+		}
+		for (j, component) in self.rows[row].iter_mut().enumerate() {
+			*component = *component * factor;
+			self.cols[j][row] = *component;
+		}
+	}
+
+	/// Multiplies every entry in the `col`th column of `self` by `factor`.
+	pub fn scale_col(&mut self, col: usize, factor: T) {
+		if col >= self.dims.num_cols {
+			panic!("Column index out of bounds for Matrix")
+		}
+		for (i, component) in self.cols[col].iter_mut().enumerate() {
+			*component = *component * factor;
+			self.rows[i][col] = *component;
+		}
+	}
+
+}
+
+impl<T> Matrix<T> where T: Copy + Mul<Output=T> + Add<Output=T> {
+
+	/// Adds `factor` times the `source`th row of `self` to its `target`th row.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let mut m = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+	/// m.add_scaled_row(1, 0, -3);
+	/// assert_eq!(m, Matrix::new(vec![vec![1, 2], vec![0, -2]]));
+	/// ```
+	pub fn add_scaled_row(&mut self, target: usize, source: usize, factor: T) {
+		if target >= self.dims.num_rows || source >= self.dims.num_rows {
+			panic!("Row index out of bounds for Matrix")
+		}
+		for j in 0..self.dims.num_cols {
+			let component = self.rows[target][j] + factor * self.rows[source][j];
+			self.rows[target][j] = component;
+			self.cols[j][target] = component;
+		}
+	}
+
+	/// Adds `factor` times the `source`th column of `self` to its `target`th column.
+	pub fn add_scaled_col(&mut self, target: usize, source: usize, factor: T) {
+		if target >= self.dims.num_cols || source >= self.dims.num_cols {
+			panic!("Column index out of bounds for Matrix")
+		}
+		for i in 0..self.dims.num_rows {
+			let component = self.cols[target][i] + factor * self.cols[source][i];
+			self.cols[target][i] = component;
+			self.rows[i][target] = component;
+		}
+	}
+
+}
+
+impl<T> Matrix<T> where T: Ring {
+
+	/// Returns the determinant of `self` by cofactor expansion along the
+	/// first row.
+	///
+	/// Uses only ring operations, so the result is exact for integer entries,
+	/// but takes O(n!) time; prefer [`Matrix::determinant`] for float matrices
+	/// of any real size.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let m: Matrix<i64> = Matrix::new(vec![vec![2, 0, 1], vec![1, 3, 2], vec![1, 1, 2]]);
+	/// assert_eq!(m.cofactor_determinant(), 6);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square.
+	pub fn cofactor_determinant(&self) -> T {
+		if !self.is_square() {
+			panic!("Matrix must be square to have a determinant")
+		}
+		let cols: Vec<usize> = (0..self.dims.num_cols).collect();
+		self.minor_determinant(0, &cols)
+	}
+
+	/// Returns the determinant of the submatrix of `self` made of the rows
+	/// from `row` down and the given `cols`.
+	fn minor_determinant(&self, row: usize, cols: &[usize]) -> T {
+		if cols.len() == 1 {
+			return self.rows[row][cols[0]];
+		}
+		let mut determinant = T::default();
+		for (k, col) in cols.iter().enumerate() {
+			let remaining: Vec<usize> = cols.iter().copied().filter(|c| c != col).collect();
+			let term = self.rows[row][*col] * self.minor_determinant(row + 1, &remaining);
+			determinant = if k % 2 == 0 { determinant + term } else { determinant - term };
+		}
+		determinant
+	}
+
+}
+
+impl<T> Matrix<T> where T: PartialEq {
+
+	/// Returns whether or not `self` is equal to its transpose.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// assert!(Matrix::new(vec![vec![1, 2], vec![2, 1]]).is_symmetric());
+	/// assert!(!Matrix::new(vec![vec![1, 2], vec![3, 1]]).is_symmetric());
+	/// ```
+	pub fn is_symmetric(&self) -> bool {
+		if !self.is_square() {
+			return false;
+		}
+		(0..self.dims.num_rows).all(|i| (0..i).all(|j| self.rows[i][j] == self.rows[j][i]))
+	}
+
+}
+
+impl<T> Matrix<T> where T: PartialEq + Default {
+
+	/// Returns whether or not every entry of `self` off its main diagonal is
+	/// `T::default()`.
+	///
+	/// Non-square matrices may be diagonal; only the entries whose row and
+	/// column indices coincide are allowed to be nonzero.
+	pub fn is_diagonal(&self) -> bool {
+		let zero = T::default();
+		self.rows.iter()
+				 .enumerate()
+				 .all(|(i, row)| row.iter().enumerate().all(|(j, e)| i == j || *e == zero))
+	}
+
+}
+
+impl<T> Matrix<T> where T: Semiring + PartialEq + From<u8> {
+
+	/// Returns whether or not the transpose of `self` is its inverse.
+	///
+	/// Entries are compared with `PartialEq`, so this is only meaningful for
+	/// exactly representable entries; float matrices should use
+	/// `is_orthogonal_within` instead.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// assert!(Matrix::new(vec![vec![0, 1], vec![1, 0]]).is_orthogonal());
+	/// assert!(!Matrix::new(vec![vec![1, 1], vec![0, 1]]).is_orthogonal());
+	/// ```
+	pub fn is_orthogonal(&self) -> bool {
+		if !self.is_square() {
+			return false;
+		}
+		let (zero, one) = (T::from(0), T::from(1));
+		let n = self.dims.num_cols;
+		(0..n).all(|i| (0..n).all(|j| {
+			let dot = self.cols[i].iter()
+								  .zip(self.cols[j].iter())
+								  .fold(zero, |sum, (a, b)| sum + *a * *b);
+			dot == if i == j { one } else { zero }
+		}))
+	}
+
+}
+
+macro_rules! impl_float_predicates {
+	($t:ty) => {
+		impl Matrix<$t> {
+
+			/// Returns whether or not `self` is within `epsilon` of its transpose
+			/// in every entry.
+			pub fn is_symmetric_within(&self, epsilon: $t) -> bool {
+				if !self.is_square() {
+					return false;
+				}
+				(0..self.dims.num_rows).all(|i| (0..i).all(|j| (self.rows[i][j] - self.rows[j][i]).abs() <= epsilon))
+			}
+
+			/// Returns whether or not every entry of `self` off its main diagonal
+			/// is within `epsilon` of zero.
+			pub fn is_diagonal_within(&self, epsilon: $t) -> bool {
+				self.rows.iter()
+						 .enumerate()
+						 .all(|(i, row)| row.iter().enumerate().all(|(j, e)| i == j || e.abs() <= epsilon))
+			}
+
+			/// Returns whether or not the product of the transpose of `self` with
+			/// `self` is within `epsilon` of the identity in every entry.
+			pub fn is_orthogonal_within(&self, epsilon: $t) -> bool {
+				if !self.is_square() {
+					return false;
+				}
+				let n = self.dims.num_cols;
+				(0..n).all(|i| (0..n).all(|j| {
+					let dot: $t = self.cols[i].iter().zip(self.cols[j].iter()).map(|(a, b)| a * b).sum();
+					let expected = if i == j { 1.0 } else { 0.0 };
+					(dot - expected).abs() <= epsilon
+				}))
+			}
+
+		}
+	}
+}
+
+impl_float_predicates![f32];
+impl_float_predicates![f64];
+
+impl<T> Matrix<T> where T: Copy + Conjugate {
+
+	/// Returns the conjugate transpose (or Hermitian adjoint, "dagger") of
+	/// `self`, which is its transpose for real matrices.
+	///
+	/// ```
+	/// # use hebrides::{Complex, linal::Matrix};
+	/// let i = Complex::new(0.0, 1.0);
+	/// let m = Matrix::new(vec![vec![i, Complex::new(2.0, 0.0)]]);
+	/// assert_eq!(m.conjugate_transpose(), Matrix::new(vec![vec![-i], vec![Complex::new(2.0, 0.0)]]));
+	/// ```
+	pub fn conjugate_transpose(&self) -> Matrix<T> {
+		Matrix::new(self.cols.iter().map(|col| col.iter().map(|e| e.conjugate()).collect()).collect())
+	}
+
+}
+
+impl<T> Matrix<T> where T: Copy + Conjugate + PartialEq {
+
+	/// Returns whether or not `self` is equal to its conjugate transpose,
+	/// which for real matrices is whether or not it is symmetric.
+	///
+	/// Entries are compared with `PartialEq`; complex matrices with rounded
+	/// entries should use `is_hermitian_within` instead.
+	pub fn is_hermitian(&self) -> bool {
+		if !self.is_square() {
+			return false;
+		}
+		(0..self.dims.num_rows).all(|i| (0..=i).all(|j| self.rows[i][j] == self.rows[j][i].conjugate()))
+	}
+
+}
+
+impl Matrix<Complex> {
+
+	/// Returns whether or not the conjugate transpose of `self` is its
+	/// inverse.
+	///
+	/// Entries are compared with `PartialEq`, so this is only meaningful for
+	/// exactly representable entries, such as those of the Pauli matrices;
+	/// others should use `is_unitary_within` instead.
+	///
+	/// ```
+	/// # use hebrides::{Complex, linal::Matrix};
+	/// let (zero, i) = (Complex::new(0.0, 0.0), Complex::new(0.0, 1.0));
+	/// let pauli_y = Matrix::new(vec![vec![zero, -i], vec![i, zero]]);
+	/// assert!(pauli_y.is_hermitian() && pauli_y.is_unitary());
+	/// ```
+	pub fn is_unitary(&self) -> bool {
+		self.is_unitary_within(0.0)
+	}
+
+	/// Returns whether or not `self` is within `epsilon` of its conjugate
+	/// transpose in the real and imaginary parts of every entry.
+	pub fn is_hermitian_within(&self, epsilon: f64) -> bool {
+		if !self.is_square() {
+			return false;
+		}
+		(0..self.dims.num_rows).all(|i| (0..=i).all(|j| self.rows[i][j].abs_diff_eq(&self.rows[j][i].conjugate(), epsilon)))
+	}
+
+	/// Returns whether or not the product of the conjugate transpose of
+	/// `self` with `self` is within `epsilon` of the identity in the real and
+	/// imaginary parts of every entry.
+	///
+	/// ```
+	/// # use hebrides::{Complex, linal::Matrix};
+	/// let h = Complex::new(std::f64::consts::FRAC_1_SQRT_2, 0.0);
+	/// let hadamard = Matrix::new(vec![vec![h, h], vec![h, -h]]);
+	/// assert!(hadamard.is_unitary_within(1e-15));
+	/// ```
+	pub fn is_unitary_within(&self, epsilon: f64) -> bool {
+		if !self.is_square() {
+			return false;
+		}
+		let n = self.dims.num_cols;
+		(0..n).all(|i| (0..n).all(|j| {
+			let dot = hermitian_dot(&self.cols[j], &self.cols[i]);
+			let expected = Complex::new(if i == j { 1.0 } else { 0.0 }, 0.0);
+			dot.abs_diff_eq(&expected, epsilon)
+		}))
+	}
+
+}
+
+/// Matrix type specifying a Matrix of [`f32`]s.
+pub type Matrix32 = Matrix<f32>;
+
+/// Matrix type specifying a Matrix of [`f64`]s.
+pub type Matrix64 = Matrix<f64>;
+
 impl<T> std::fmt::Debug for Matrix<T> where T: std::fmt::Display {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
 		let mut repr = "[".to_owned();
@@ -347,7 +1548,7 @@ impl<T> std::fmt::Debug for Matrix<T> where T: std::fmt::Display {
 				row_repr.push_str(&format!("{}, ", component));
 			}
 			if i == self.rows.len() - 1 {
-				row_repr.push_str("]");
+				row_repr.push(']');
 				repr.push_str(&row_repr);
 				continue;
 			}
@@ -358,12 +1559,39 @@ impl<T> std::fmt::Debug for Matrix<T> where T: std::fmt::Display {
 	}
 }
 
+impl<T> Clone for Matrix<T> where T: Clone {
+	fn clone(&self) -> Matrix<T> {
+		Self {
+			rows: self.rows.clone(),
+			cols: self.cols.clone(),
+			dims: self.dims
+		}
+	}
+}
+
 impl<T> PartialEq for Matrix<T> where T: PartialEq {
 	fn eq(&self, other: &Self) -> bool {
 		self.rows.iter()
 				 .zip(other.rows.iter())
-				 .map(|pair| *pair.0 == *pair.1)
-				 .all(|e| e)
+				 .all(|pair| *pair.0 == *pair.1)
+	}
+}
+
+impl<T> ApproxEq for Matrix<T> where T: ApproxEq {
+	fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+		self.dims == other.dims && self.rows.iter().flatten()
+			.zip(other.rows.iter().flatten())
+			.all(|(a, b)| a.abs_diff_eq(b, epsilon))
+	}
+	fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool {
+		self.dims == other.dims && self.rows.iter().flatten()
+			.zip(other.rows.iter().flatten())
+			.all(|(a, b)| a.relative_eq(b, epsilon, max_relative))
+	}
+	fn ulps_eq(&self, other: &Self, max_ulps: u64) -> bool {
+		self.dims == other.dims && self.rows.iter().flatten()
+			.zip(other.rows.iter().flatten())
+			.all(|(a, b)| a.ulps_eq(b, max_ulps))
 	}
 }
 
@@ -374,33 +1602,108 @@ impl<T> Index<usize> for Matrix<T> where T: Clone {
 	}
 } 
 
+/// Indexes a single entry by its (row, column) position.
+///
+/// There is deliberately no matching `IndexMut`: a [`Matrix`] stores each
+/// entry in both its rows and its columns, and a bare `&mut T` could not
+/// keep the two in sync. Write entries through [`Matrix::get_mut`] or
+/// [`Matrix::iter_mut`] instead.
+///
+/// ```
+/// # use hebrides::linal::Matrix;
+/// let mut m = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+/// m.get_mut(1, 0).unwrap().set(5);
+/// assert_eq!(m[(1, 0)], 5);
+/// assert_eq!(m.cols[0], vec![1, 5]);
+/// ```
+impl<T> Index<(usize, usize)> for Matrix<T> {
+	type Output = T;
+	fn index(&self, (row, col): (usize, usize)) -> &T {
+		&self.rows[row][col]
+	}
+}
+
+
+impl<T> Matrix<T> where T: Copy {
+
+	/// Combines each entry of `self` with the matching entry of `other` in
+	/// place, keeping `rows` and `cols` in sync.
+	fn zip_assign(&mut self, other: &Self, op: impl Fn(T, T) -> T, verb: &str) {
+		if self.dims != other.dims {
+			panic!("Matrices must have the same dimensions to be {}", verb)
+		}
+		for (row, other_row) in self.rows.iter_mut().zip(other.rows.iter()) {
+			for (a, b) in row.iter_mut().zip(other_row.iter()) {
+				*a = op(*a, *b);
+			}
+		}
+		for (col, other_col) in self.cols.iter_mut().zip(other.cols.iter()) {
+			for (a, b) in col.iter_mut().zip(other_col.iter()) {
+				*a = op(*a, *b);
+			}
+		}
+	}
+
+	/// Applies `op` to each entry of `self` in place, keeping `rows` and
+	/// `cols` in sync.
+	fn apply_assign(&mut self, op: impl Fn(T) -> T) {
+		for e in self.rows.iter_mut().chain(self.cols.iter_mut()).flatten() {
+			*e = op(*e);
+		}
+	}
+
+}
 
 impl<T> Add<Self> for Matrix<T> where T: Copy + Add<Output=T> {
 	type Output = Self;
 	fn add(mut self, other: Self) -> Self {
-		for (i, row) in other.rows.iter().enumerate() {
-			for (j, component) in row.iter().enumerate() {
-				self.rows[i][j] = self.rows[i][j] + *component;
-			}
-		}
+		self += &other;
 		self
 	}
 }
 
+/// Adds in place, updating the entries of `self` without rebuilding it.
+///
+/// ```
+/// # use hebrides::linal::Matrix;
+/// let mut m = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+/// m += &Matrix::new(vec![vec![1, 1], vec![1, 1]]);
+/// assert_eq!(m.cols[1], vec![3, 5]);
+/// ```
+impl<T> AddAssign<&Self> for Matrix<T> where T: Copy + Add<Output=T> {
+	fn add_assign(&mut self, other: &Self) {
+		self.zip_assign(other, |a, b| a + b, "added");
+	}
+}
+
+impl<T> AddAssign<Self> for Matrix<T> where T: Copy + Add<Output=T> {
+	fn add_assign(&mut self, other: Self) {
+		*self += &other;
+	}
+}
+
 impl<T> Sub<Self> for Matrix<T> where T: Copy + Sub<Output=T> {
 	type Output = Self;
 	fn sub(mut self, other: Self) -> Self {
-		for (i, row) in other.rows.iter().enumerate() {
-			for (j, component) in row.iter().enumerate() {
-				self.rows[i][j] = self.rows[i][j] - *component;
-			}
-		}
+		self -= &other;
 		self
 	}
 }
 
+impl<T> SubAssign<&Self> for Matrix<T> where T: Copy + Sub<Output=T> {
+	fn sub_assign(&mut self, other: &Self) {
+		self.zip_assign(other, |a, b| a - b, "subtracted");
+	}
+}
+
+impl<T> SubAssign<Self> for Matrix<T> where T: Copy + Sub<Output=T> {
+	fn sub_assign(&mut self, other: Self) {
+		*self -= &other;
+	}
+}
+
 fn into_chunks<T>(v: Vec<T>, n: usize) -> Vec<Vec<T>> where T: Copy {
-	if v.len() % n != 0 {
+	if !v.len().is_multiple_of(n) {
 		panic!("Provided Vec cannot be evenly distributed into chunks of size {}", n)
 	}
 	let mut counter: usize = 0;
@@ -420,7 +1723,7 @@ fn into_chunks<T>(v: Vec<T>, n: usize) -> Vec<Vec<T>> where T: Copy {
 	chunks
 }
 
-impl<T> Mul<Self> for Matrix<T> where T: Copy + Default + Mul<Output=T> + Add<Output=T> {
+impl<T> Mul<Self> for Matrix<T> where T: Semiring {
 	type Output = Self;
 	fn mul(self, other: Self) -> Self {
 		if !MatrixDimensions::are_compatible(self.dims, other.dims) {
@@ -442,7 +1745,7 @@ impl<T> Mul<Self> for Matrix<T> where T: Copy + Default + Mul<Output=T> + Add<Ou
 	}
 }
 
-impl<T> Mul<Vector<T>> for Matrix<T> where T: Copy + Default + Mul<Output=T> + Add<Output=T> {
+impl<T> Mul<Vector<T>> for Matrix<T> where T: Semiring {
 	type Output = Vector<T>;
 	fn mul(self, other: Vector<T>) -> Vector<T> {
 		if self.dims.num_cols != other.dim {
@@ -483,6 +1786,42 @@ impl<T> Div<T> for Matrix<T> where T: Copy + Div<Output=T> {
 	}
 }
 
+impl<T> MulAssign<T> for Matrix<T> where T: Copy + Mul<Output=T> {
+	fn mul_assign(&mut self, other: T) {
+		self.apply_assign(|e| other * e);
+	}
+}
+
+impl<T> DivAssign<T> for Matrix<T> where T: Copy + Div<Output=T> {
+	fn div_assign(&mut self, other: T) {
+		self.apply_assign(|e| e / other);
+	}
+}
+
+/// Implements scalar-on-the-left multiplication, `2.0 * v`, for the
+/// primitive numeric types.
+macro_rules! impl_left_scalar_mul {
+	($($t:ty),*) => {
+		$(
+			impl Mul<Vector<$t>> for $t {
+				type Output = Vector<$t>;
+				fn mul(self, other: Vector<$t>) -> Vector<$t> {
+					other * self
+				}
+			}
+
+			impl Mul<Matrix<$t>> for $t {
+				type Output = Matrix<$t>;
+				fn mul(self, other: Matrix<$t>) -> Matrix<$t> {
+					other * self
+				}
+			}
+		)*
+	};
+}
+
+impl_left_scalar_mul![i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64];
+
 #[cfg(test)]
 mod test {
 
@@ -490,99 +1829,470 @@ mod test {
 
 	mod vector {
 
-		use super::*;
+		use super::*;
+
+		mod constructors {
+
+			use super::*;
+
+			#[test]
+			fn zeros_and_ones() {
+				assert_eq!(Vector::<i32>::zeros(3), Vector::new(vec![0, 0, 0]));
+				assert_eq!(Vector::<f64>::ones(2), Vector::new(vec![1.0, 1.0]))
+			}
+
+			#[test]
+			fn basis_vectors_are_orthonormal() {
+				for i in 0..4 {
+					for j in 0..4 {
+						assert_eq!(Vector::<i32>::basis(4, i).dot(&Vector::basis(4, j)), i32::from(i == j));
+					}
+				}
+			}
+
+			#[test]
+			fn from_fn_matches_explicit_components() {
+				assert_eq!(Vector::from_fn(4, |i| 2.0 * i as f64), Vector::new(vec![0.0, 2.0, 4.0, 6.0]))
+			}
+
+			#[test]
+			#[should_panic]
+			fn basis_index_out_of_range() {
+				let _ = Vector::<f64>::basis(3, 3);
+			}
+
+			#[test]
+			#[should_panic]
+			fn zero_dimensional() {
+				let _ = Vector::<f64>::zeros(0);
+			}
+
+		}
+
+		mod indexing {
+
+			use super::*;
+
+			#[test]
+			fn write_through_index() {
+				let mut v = Vector::new(vec![1.0, 2.0, 3.0]);
+				v[2] = 5.0;
+				v[0] += 1.0;
+				assert_eq!(v, Vector::new(vec![2.0, 2.0, 5.0]))
+			}
+
+			#[test]
+			#[should_panic]
+			fn out_of_bounds() {
+				let mut v = Vector::new(vec![1, 2]);
+				v[2] = 3;
+			}
+
+		}
+
+		mod swizzles {
+
+			use super::*;
+
+			#[test]
+			fn named_components() {
+				let v = Vector::new(vec![4, 5, 6]);
+				assert_eq!([v.x(), v.y(), v.z()], [4, 5, 6]);
+				assert_eq!(v.xy(), Vector::new(vec![4, 5]));
+				assert_eq!(v.xyz(), v)
+			}
+
+			#[test]
+			#[should_panic]
+			fn missing_component() {
+				let _ = Vector::new(vec![4, 5, 6]).w();
+			}
+
+		}
+
+		mod iteration {
+
+			use super::*;
+
+			#[test]
+			fn borrowed_and_owned_iteration_agree() {
+				let v = Vector::new(vec![3, 1, 4, 1, 5]);
+				let borrowed: Vec<i32> = (&v).into_iter().copied().collect();
+				assert_eq!(borrowed, v.iter().copied().collect::<Vec<i32>>());
+				assert_eq!(v.clone().into_iter().collect::<Vec<i32>>(), borrowed)
+			}
+
+			#[test]
+			fn mutable_iteration_writes_components() {
+				let mut v = Vector::new(vec![1.0, 2.0, 3.0]);
+				for e in &mut v {
+					*e = -*e;
+				}
+				assert_eq!(v, Vector::new(vec![-1.0, -2.0, -3.0]))
+			}
+
+			#[test]
+			fn collect_round_trips() {
+				let v = Vector::new(vec![1, 2, 3]);
+				let doubled: Vector<i32> = v.iter().map(|e| 2 * e).collect();
+				assert_eq!(doubled, v.clone() * 2);
+				assert_eq!(doubled.dim(), 3)
+			}
+
+			#[test]
+			#[should_panic]
+			fn collect_empty() {
+				let _: Vector<i32> = std::iter::empty().collect();
+			}
+
+		}
+
+		mod addition {
+
+			use super::*;
+
+			#[test]
+			fn left_identity() {
+				let zero = Vector::new(vec![0, 0, 0]);
+				let a = Vector::new(vec![1, 2, 3]);
+				assert_eq!(zero + a, Vector::new(vec![1, 2, 3]))
+			}
+
+			#[test]
+			fn right_identity() {
+				let zero = Vector::new(vec![0, 0, 0]);
+				let a = Vector::new(vec![1, 2, 3]);
+				assert_eq!(a + zero, Vector::new(vec![1, 2, 3]))
+			}
+
+			#[test]
+			fn standard() {
+				let a = Vector::new(vec![1, 2, 3]);
+				let b = Vector::new(vec![4, 5, 6]);
+				assert_eq!(a + b, Vector::new(vec![5, 7, 9]))
+			}
+
+		}
+
+		mod subtraction {
+
+			use super::*;
+
+			#[test]
+			fn left_identity() {
+				let zero = Vector::new(vec![0; 3]);
+				let a = Vector::new(vec![1, 2, 3]);
+				assert_eq!(zero - a, Vector::new(vec![-1, -2, -3]))
+			}
+
+			#[test]
+			fn right_identity() {
+				let zero = Vector::new(vec![0; 3]);
+				let a = Vector::new(vec![1, 2, 3]);
+				assert_eq!(a - zero, Vector::new(vec![1, 2, 3]))
+			}
+
+			#[test]
+			fn standard() {
+				let a = Vector::new(vec![1, 2, 3]);
+				let b = Vector::new(vec![4, 5, 6]);
+				assert_eq!(a - b, Vector::new(vec![-3, -3, -3]))
+			}
+
+		}
+
+		mod dot_product {
+
+			use super::*;
+
+			#[test]
+			fn left_identity() {
+				let zero = Vector::new(vec![0; 3]);
+				let a = Vector::new(vec![1, 2, 3]);
+				assert_eq!(zero * a, 0)
+			}
+
+			#[test]
+			fn right_identity() {
+				let zero = Vector::new(vec![0; 3]);
+				let a = Vector::new(vec![1, 2, 3]);
+				assert_eq!(a * zero, 0)
+			}
+
+			#[test]
+			fn square() {
+				let a = Vector::new(vec![1, 2, 3]);
+				let b = Vector::new(vec![1, 2, 3]);
+				assert_eq!(a * b, 14)
+			}
+
+			#[test]
+			fn orthogonal() {
+				let k_hat = Vector::new(vec![0, 0, 1]);
+				let j_hat = Vector::new(vec![0, 1, 0]);
+				assert_eq!(k_hat * j_hat, 0)
+			}
+
+			#[test]
+			fn standard() {
+				let a = Vector::new(vec![1, 2, 3]);
+				let b = Vector::new(vec![4, 5, 6]);
+				assert_eq!(a * b, 32)
+			}
+
+			#[test]
+			fn hermitian() {
+				use crate::Complex;
+				let a = Vector::new(vec![Complex::new(1.0, 2.0), Complex::new(0.0, -1.0)]);
+				let b = Vector::new(vec![Complex::new(3.0, -1.0), Complex::new(2.0, 2.0)]);
+				assert_eq!(a.dot(&b), Complex::new(-1.0, 5.0));
+				assert_eq!(b.dot(&a), Complex::new(-1.0, -5.0));
+				assert_eq!(a.clone() * a, Complex::new(6.0, 0.0))
+			}
+
+		}
+
+		mod projection {
+
+			use super::*;
+
+			#[test]
+			fn projection_and_rejection_decompose() {
+				let v = Vector::new(vec![1.0_f64, 2.0, 3.0]);
+				let onto = Vector::new(vec![1.0, 1.0, 0.0]);
+				let (p, r) = (v.project_onto(&onto), v.reject_from(&onto));
+				assert_eq!(p.clone() + r.clone(), v);
+				assert_eq!(r.dot(&onto), 0.0);
+				assert_eq!(p, Vector::new(vec![1.5, 1.5, 0.0]))
+			}
+
+			#[test]
+			fn single_precision() {
+				let v = Vector::new(vec![3.0_f32, 4.0]);
+				assert_eq!(v.project_onto(&Vector::new(vec![0.0, 2.0])), Vector::new(vec![0.0, 4.0]))
+			}
+
+			#[test]
+			#[should_panic]
+			fn onto_zero() {
+				let _ = Vector::new(vec![1.0_f64, 2.0]).project_onto(&Vector::new(vec![0.0, 0.0]));
+			}
+
+		}
+
+		mod norms {
+
+			use super::*;
+
+			#[test]
+			fn generic_norm_over_real() {
+				use crate::Real;
+				let v = Vector::new(vec![Real::new(1.0), Real::new(2.0), Real::new(-2.0)]);
+				assert_eq!(v.norm(), Real::new(3.0));
+				let unit = v.normalized();
+				assert_eq!(unit.norm(), Real::ONE);
+				assert_eq!(unit[2], Real::new(-2.0 / 3.0))
+			}
+
+			#[test]
+			fn generic_norm_agrees_across_precisions() {
+				let (single, double) = (Vector::new(vec![0.3_f32, -1.2, 2.5]), Vector::new(vec![0.3_f64, -1.2, 2.5]));
+				assert!((single.norm() as f64 - double.norm()).abs() < 1e-6)
+			}
+
+			#[test]
+			fn p_norms_decrease_in_p() {
+				let v = Vector::new(vec![1.0_f64, -2.0, 3.0, -0.5]);
+				let norms: Vec<f64> = [1.0, 1.5, 2.0, 3.0, 10.0].iter().map(|p| v.p_norm(*p)).collect();
+				assert!(norms.windows(2).all(|w| w[0] >= w[1]));
+				assert!(norms[4] >= v.norm_inf() && (v.p_norm(2.0) - v.norm()).abs() < 1e-15)
+			}
+
+			#[test]
+			fn distances_are_symmetric() {
+				let a = Vector::new(vec![1.0_f32, 5.0, -2.0]);
+				let b = Vector::new(vec![-1.0, 2.0, 4.0]);
+				for metric in [Metric::Euclidean, Metric::Manhattan, Metric::Chebyshev] {
+					assert_eq!(a.distance(&b, metric), b.distance(&a, metric));
+					assert_eq!(a.distance(&a, metric), 0.0);
+				}
+				assert_eq!(a.distance(&b, Metric::Manhattan), 11.0)
+			}
+
+			#[test]
+			#[should_panic]
+			fn non_positive_order() {
+				let _ = Vector::new(vec![1.0_f64]).p_norm(0.0);
+			}
+
+		}
+
+		mod outer_product {
+
+			use super::*;
+
+			#[test]
+			fn rank_one() {
+				let a = Vector::new(vec![1, -1, 2]);
+				let outer = a.outer(&a);
+				assert!(outer.is_symmetric());
+				assert_eq!(outer.cols[1], vec![-1, 1, -2])
+			}
+
+			#[test]
+			fn trace_is_dot_product() {
+				let a = Vector::new(vec![1.5, 2.0, -3.0]);
+				let b = Vector::new(vec![4.0, 0.5, 1.0]);
+				let trace: f64 = (0..3).map(|i| a.outer(&b)[i][i]).sum();
+				assert_eq!(trace, a.dot(&b))
+			}
+
+		}
+
+		mod cross_product {
+
+			use super::*;
+
+			#[test]
+			fn cross_2d_is_z_component_of_embedded_cross() {
+				let (a, b) = (Vector::new(vec![3.0, -1.0]), Vector::new(vec![2.0, 5.0]));
+				let embedded = Vector::new(vec![3.0, -1.0, 0.0]).cross(&Vector::new(vec![2.0, 5.0, 0.0]));
+				assert_eq!(a.cross_2d(&b), embedded[2])
+			}
+
+			#[test]
+			fn triple_product_is_determinant() {
+				let rows = vec![vec![2.0, 1.0, -1.0], vec![0.0, 3.0, 4.0], vec![1.0, -2.0, 5.0]];
+				let (a, b, c) = (Vector::new(rows[0].clone()), Vector::new(rows[1].clone()), Vector::new(rows[2].clone()));
+				assert_eq!(Vector::triple(&a, &b, &c), Matrix::new(rows).lu().determinant())
+			}
+
+			#[test]
+			#[should_panic]
+			fn cross_2d_of_three_dimensional() {
+				let a = Vector::new(vec![1, 2, 3]);
+				let _ = a.cross_2d(&a);
+			}
+
+		}
+
+		mod hadamard_product {
+
+			use super::*;
+
+			#[test]
+			fn standard() {
+				let a = Vector::new(vec![1, -2, 3]);
+				let b = Vector::new(vec![4, 5, 0]);
+				assert_eq!(a.hadamard(&b), Vector::new(vec![4, -10, 0]))
+			}
+
+			#[test]
+			#[should_panic]
+			fn different_dimensions() {
+				let _ = Vector::new(vec![1, 2]).hadamard(&Vector::new(vec![1, 2, 3]));
+			}
+
+		}
 
-		mod addition {
+		mod map {
 
 			use super::*;
 
 			#[test]
-			fn left_identity() {
-				let zero = Vector::new(vec![0, 0, 0]);
-				let a = Vector::new(vec![1, 2, 3]);
-				assert_eq!(zero + a, Vector::new(vec![1, 2, 3]))
-			}
-
-			#[test]
-			fn right_identity() {
-				let zero = Vector::new(vec![0, 0, 0]);
-				let a = Vector::new(vec![1, 2, 3]);
-				assert_eq!(a + zero, Vector::new(vec![1, 2, 3]))
+			fn zip_with() {
+				let a = Vector::new(vec![1, 5, 3]);
+				let b = Vector::new(vec![4, 2, 6]);
+				assert_eq!(a.zip_with(&b, |x, y| x.max(y)), Vector::new(vec![4, 5, 6]))
 			}
 
 			#[test]
-			fn standard() {
-				let a = Vector::new(vec![1, 2, 3]);
-				let b = Vector::new(vec![4, 5, 6]);
-				assert_eq!(a + b, Vector::new(vec![5, 7, 9]))
+			#[should_panic]
+			fn zip_with_mismatched_dimensions() {
+				let _ = Vector::new(vec![1, 2]).zip_with(&Vector::new(vec![1]), |x, y| x + y);
 			}
 
 		}
 
-		mod subtraction {
+		mod scalar_multiplication {
 
 			use super::*;
 
 			#[test]
-			fn left_identity() {
-				let zero = Vector::new(vec![0; 3]);
-				let a = Vector::new(vec![1, 2, 3]);
-				assert_eq!(zero - a, Vector::new(vec![-1, -2, -3]))
-			}
-
-			#[test]
-			fn right_identity() {
-				let zero = Vector::new(vec![0; 3]);
-				let a = Vector::new(vec![1, 2, 3]);
-				assert_eq!(a - zero, Vector::new(vec![1, 2, 3]))
+			fn right_standard() {
+				assert_eq!(Vector::new(vec![1, -2, 3]) * 3, Vector::new(vec![3, -6, 9]))
 			}
 
 			#[test]
-			fn standard() {
-				let a = Vector::new(vec![1, 2, 3]);
-				let b = Vector::new(vec![4, 5, 6]);
-				assert_eq!(a - b, Vector::new(vec![-3, -3, -3]))
+			fn left_matches_right() {
+				let v = Vector::new(vec![1.5, -2.0, 0.25]);
+				assert_eq!(2.0 * v.clone(), v * 2.0)
 			}
 
 		}
 
-		mod dot_product {
+		mod reflection {
 
 			use super::*;
 
 			#[test]
-			fn left_identity() {
-				let zero = Vector::new(vec![0; 3]);
-				let a = Vector::new(vec![1, 2, 3]);
-				assert_eq!(zero * a, 0)
+			fn reflect_preserves_length_and_tangent() {
+				let v = Vector::new(vec![3.0_f64, -2.0, 1.0]);
+				let n = Vector::new(vec![0.0, 0.0, -5.0]);
+				assert_eq!(v.reflect(&n), Vector::new(vec![3.0, -2.0, -1.0]));
+				assert_eq!(v.reflect(&n).reflect(&n), v)
 			}
 
 			#[test]
-			fn right_identity() {
-				let zero = Vector::new(vec![0; 3]);
-				let a = Vector::new(vec![1, 2, 3]);
-				assert_eq!(a * zero, 0)
+			fn refraction_obeys_snells_law() {
+				let v: Vector<f64> = Vector::new(vec![0.6, -0.8, 0.0]);
+				let n = Vector::new(vec![0.0, 1.0, 0.0]);
+				let eta = 1.0 / 1.33;
+				let t = v.refract(&n, eta).unwrap();
+				assert!((t.norm() - 1.0).abs() < 1e-12);
+				assert!((t[0] - eta * 0.6).abs() < 1e-12);
+				assert!(t[1] < 0.0 && t[2] == 0.0);
+				let flipped = v.refract(&-n, eta).unwrap();
+				assert!(flipped.distance(&t, Metric::Euclidean) < 1e-12)
 			}
 
 			#[test]
-			fn square() {
-				let a = Vector::new(vec![1, 2, 3]);
-				let b = Vector::new(vec![1, 2, 3]);
-				assert_eq!(a * b, 14)
+			fn matching_media_do_not_bend() {
+				let v: Vector<f64> = Vector::new(vec![2.0, -1.0]);
+				let t = v.refract(&Vector::new(vec![0.0, 1.0]), 1.0).unwrap();
+				assert!(t.distance(&v.normalized(), Metric::Euclidean) < 1e-12)
 			}
 
 			#[test]
-			fn orthogonal() {
-				let k_hat = Vector::new(vec![0, 0, 1]);
-				let j_hat = Vector::new(vec![0, 1, 0]);
-				assert_eq!(k_hat * j_hat, 0)
+			#[should_panic]
+			fn zero_normal() {
+				let _ = Vector::new(vec![1.0_f64, 0.0]).reflect(&Vector::new(vec![0.0, 0.0]));
 			}
 
+		}
+
+		mod compound_assignment {
+
+			use super::*;
+
 			#[test]
-			fn standard() {
-				let a = Vector::new(vec![1, 2, 3]);
-				let b = Vector::new(vec![4, 5, 6]);
-				assert_eq!(a * b, 32)
+			fn matches_binary_operators() {
+				let (a, b) = (Vector::new(vec![1, -2, 3]), Vector::new(vec![4, 5, 6]));
+				let mut v = a.clone();
+				v += b.clone();
+				assert_eq!(v, a.clone() + b.clone());
+				v -= &b;
+				v *= 4;
+				assert_eq!(v, a.clone() * 4);
+				v /= 2;
+				assert_eq!(v, Vector::new(vec![2, -4, 6]))
+			}
+
+			#[test]
+			#[should_panic]
+			fn mismatched_dimensions() {
+				let mut v = Vector::new(vec![1, 2]);
+				v += &Vector::new(vec![1, 2, 3]);
 			}
 
 		}
@@ -618,6 +2328,15 @@ mod test {
 
 		use super::*;
 
+		#[test]
+		fn approximate_equality() {
+			let m = Matrix::new(vec![vec![4.0, 1.0], vec![2.0, 3.0]]);
+			let roundtrip = m.inverse().unwrap().inverse().unwrap();
+			assert!(roundtrip.relative_eq(&m, 1e-12, 1e-12));
+			assert!(!m.abs_diff_eq(&Matrix::new(vec![vec![4.0, 1.0]]), f64::INFINITY));
+			assert!(!m.ulps_eq(&Matrix::new(vec![vec![4.0, 1.0], vec![2.0, 3.1]]), 1000))
+		}
+
 		#[test]
 		fn rows_to_columns_works() {
 			let m = Matrix::new(vec![
@@ -887,6 +2606,42 @@ mod test {
 				assert_eq!(a * 4, expected_product)
 			}
 
+			#[test]
+			fn left_standard() {
+				let a = Matrix::new(vec![
+					vec![1, 2, 3],
+					vec![4, 5, 6]
+				]);
+				assert_eq!(4 * a.clone(), a * 4)
+			}
+
+		}
+
+		mod compound_assignment {
+
+			use super::*;
+
+			#[test]
+			fn keeps_columns_in_sync() {
+				let p = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+				let q = Matrix::new(vec![vec![6, 5, 4], vec![3, 2, 1]]);
+				let mut m = p.clone();
+				m += &q;
+				assert_eq!(m.cols, (p.clone() + q.clone()).transpose().rows);
+				m -= q;
+				m *= 3;
+				m /= 3;
+				assert_eq!(m.rows, p.rows);
+				assert_eq!(m.cols, p.cols)
+			}
+
+			#[test]
+			#[should_panic]
+			fn mismatched_dimensions() {
+				let mut m = Matrix::new(vec![vec![1, 2]]);
+				m -= Matrix::new(vec![vec![1], vec![2]]);
+			}
+
 		}
 
 		mod scalar_division {
@@ -921,6 +2676,363 @@ mod test {
 
 		}
 
+		#[test]
+		fn transpose() {
+			let m = Matrix::new(vec![
+				vec![1, 2, 3],
+				vec![4, 5, 6]
+			]);
+			let t = m.transpose();
+			assert_eq!(t.rows, m.cols);
+			assert_eq!(t.cols, m.rows);
+			assert_eq!(t.dims.num_rows, 3);
+			assert_eq!(t.transpose(), m)
+		}
+
+		mod iteration {
+
+			use super::*;
+
+			#[test]
+			fn rows_and_cols() {
+				let m = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+				assert_eq!(m.iter_rows().map(|row| row.iter().sum::<i32>()).collect::<Vec<_>>(), vec![6, 15]);
+				assert_eq!(m.iter_cols().collect::<Vec<_>>(), vec![&[1, 4][..], &[2, 5], &[3, 6]])
+			}
+
+			#[test]
+			fn elements_are_row_major() {
+				let m = Matrix::new(vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+				let indices: Vec<(usize, usize)> = m.iter().map(|(index, _)| index).collect();
+				assert_eq!(indices, vec![(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)]);
+				assert_eq!(m.iter().map(|(_, e)| *e).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6])
+			}
+
+			#[test]
+			fn iter_mut_updates_rows_and_cols() {
+				let mut m = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+				for ((i, _), mut e) in m.iter_mut() {
+					if i == 1 {
+						e.set(-*e.get());
+					}
+				}
+				let expected = Matrix::new(vec![vec![1, 2, 3], vec![-4, -5, -6]]);
+				assert_eq!(m.rows, expected.rows);
+				assert_eq!(m.cols, expected.cols)
+			}
+
+		}
+
+		mod checked {
+
+			use super::*;
+
+			#[test]
+			fn get_out_of_bounds() {
+				let m = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+				assert_eq!(m.get(1, 2), Some(&6));
+				assert_eq!(m.get(2, 0), None);
+				assert_eq!(m.get_row(1), Some(&[4, 5, 6][..]));
+				assert_eq!(m.get_row(2), None);
+				assert_eq!(m.get_col(2), Some(&[3, 6][..]));
+				assert_eq!(m.get_col(3), None)
+			}
+
+			#[test]
+			fn get_mut_keeps_cols_consistent() {
+				let mut m = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+				m.get_mut(1, 0).unwrap().set(7);
+				assert_eq!(m.rows, vec![vec![1, 2], vec![7, 4]]);
+				assert_eq!(m.cols, vec![vec![1, 7], vec![2, 4]]);
+				assert!(m.get_mut(0, 2).is_none())
+			}
+
+			#[test]
+			fn indexed_write_reaches_transpose_and_columns() {
+				let mut m = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+				m.get_mut(0, 0).unwrap().set(9);
+				assert_eq!(m[(0, 0)], 9);
+				assert_eq!(m.transpose(), Matrix::new(vec![vec![9, 3], vec![2, 4]]));
+				assert_eq!(m.get_col(0), Some(&[9, 3][..]))
+			}
+
+			#[test]
+			fn reshape() {
+				let m = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+				assert_eq!(m.reshape(3, 2).unwrap(), Matrix::new(vec![vec![1, 2], vec![3, 4], vec![5, 6]]));
+				assert_eq!(m.reshape(4, 2), Err(ShapeError));
+				assert_eq!(m.reshape(0, 6), Err(ShapeError))
+			}
+
+			#[test]
+			fn from_flat_rejects_overflowing_shapes() {
+				assert_eq!(Matrix::from_flat(usize::MAX, 2, vec![1, 2]), Err(ShapeError))
+			}
+
+			#[test]
+			fn try_new_rejects_ragged_rows() {
+				assert_eq!(Matrix::try_new(vec![vec![1, 2], vec![3]]), Err(ShapeError));
+				assert_eq!(Matrix::<i32>::try_new(vec![]), Err(ShapeError));
+				assert!(Matrix::try_new(vec![vec![1]]).is_ok())
+			}
+
+		}
+
+		mod map {
+
+			use super::*;
+
+			#[test]
+			fn map_keeps_cols_consistent() {
+				let m = Matrix::new(vec![vec![1, 2], vec![3, 4]]).map(|x| x * x);
+				assert_eq!(m.cols, vec![vec![1, 9], vec![4, 16]])
+			}
+
+			#[test]
+			fn zip_with_changes_type() {
+				let a = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+				let b = Matrix::new(vec![vec![0.5, 1.0], vec![1.5, 2.0]]);
+				assert_eq!(a.zip_with(&b, |x, y| x as f64 * y), Matrix::new(vec![vec![0.5, 2.0], vec![4.5, 8.0]]))
+			}
+
+			#[test]
+			#[should_panic]
+			fn zip_with_mismatched_dimensions() {
+				let a = Matrix::new(vec![vec![1, 2]]);
+				let _ = a.zip_with(&a.transpose(), |x, y| x + y);
+			}
+
+		}
+
+		mod diagonal {
+
+			use super::*;
+
+			#[test]
+			fn from_diagonal() {
+				let m = Matrix::from_diagonal(&Vector::new(vec![1.0, 2.0, 3.0]));
+				let expected = Matrix::new(vec![
+					vec![1.0, 0.0, 0.0],
+					vec![0.0, 2.0, 0.0],
+					vec![0.0, 0.0, 3.0]
+				]);
+				assert_eq!(m, expected);
+				assert_eq!(m.cols, expected.cols)
+			}
+
+			#[test]
+			fn extraction() {
+				let m = Matrix::new(vec![
+					vec![1, 2],
+					vec![3, 4],
+					vec![5, 6]
+				]);
+				assert_eq!(m.diagonal(), Vector::new(vec![1, 4]))
+			}
+
+			#[test]
+			fn round_trip() {
+				let d = Vector::new(vec![7, -1, 4]);
+				assert_eq!(Matrix::from_diagonal(&d).diagonal(), d)
+			}
+
+		}
+
+		mod predicates {
+
+			use super::*;
+
+			#[test]
+			fn square() {
+				assert!(Matrix::new(vec![vec![1, 2], vec![3, 4]]).is_square());
+				assert!(!Matrix::new(vec![vec![1, 2]]).is_square())
+			}
+
+			#[test]
+			fn symmetric() {
+				let m = Matrix::new(vec![
+					vec![1, 7, 3],
+					vec![7, 4, 5],
+					vec![3, 5, 6]
+				]);
+				assert!(m.is_symmetric());
+				assert!(!Matrix::new(vec![vec![1, 2, 3]]).is_symmetric())
+			}
+
+			#[test]
+			fn diagonal() {
+				assert!(Matrix::new(vec![vec![1, 0], vec![0, 2], vec![0, 0]]).is_diagonal());
+				assert!(!Matrix::new(vec![vec![1, 0], vec![3, 2]]).is_diagonal())
+			}
+
+			#[test]
+			fn orthogonal() {
+				let permutation = Matrix::new(vec![
+					vec![0, 0, 1],
+					vec![1, 0, 0],
+					vec![0, 1, 0]
+				]);
+				assert!(permutation.is_orthogonal());
+				assert!(!Matrix::new(vec![vec![2, 0], vec![0, 1]]).is_orthogonal())
+			}
+
+			#[test]
+			fn float_tolerances() {
+				let theta: f64 = 0.3;
+				let rotation = Matrix::new(vec![
+					vec![theta.cos(), -theta.sin()],
+					vec![theta.sin(), theta.cos()]
+				]);
+				assert!(rotation.is_orthogonal_within(1e-12));
+				assert!(!(rotation * 2.0).is_orthogonal_within(1e-12));
+				let nearly_symmetric = Matrix::new(vec![vec![1.0_f64, 2.0], vec![2.0 + 1e-10, 1.0]]);
+				assert!(nearly_symmetric.is_symmetric_within(1e-9));
+				assert!(!nearly_symmetric.is_symmetric_within(1e-11));
+				let nearly_diagonal = Matrix::new(vec![vec![1.0_f32, 1e-7], vec![0.0, 1.0]]);
+				assert!(nearly_diagonal.is_diagonal_within(1e-6));
+				assert!(!nearly_diagonal.is_diagonal());
+			}
+
+			#[test]
+			fn hermitian_and_unitary() {
+				let c = |re: f64, im: f64| Complex::new(re, im);
+				let h = Matrix::new(vec![vec![c(2.0, 0.0), c(1.0, -1.0)], vec![c(1.0, 1.0), c(3.0, 0.0)]]);
+				assert!(h.is_hermitian());
+				assert_eq!(h.conjugate_transpose(), h);
+				assert!(!Matrix::new(vec![vec![c(0.0, 1.0)]]).is_hermitian());
+				let phase = Matrix::new(vec![vec![c(1.0, 0.0), c(0.0, 0.0)], vec![c(0.0, 0.0), c(0.0, 1.0)]]);
+				assert!(phase.is_unitary() && !phase.is_hermitian());
+				assert!(!h.is_unitary_within(1e-9));
+				let theta: f64 = 0.7;
+				let rotation = Matrix::new(vec![
+					vec![c(theta.cos(), 0.0), c(0.0, -theta.sin())],
+					vec![c(0.0, -theta.sin()), c(theta.cos(), 0.0)]
+				]);
+				assert!(rotation.is_unitary_within(1e-15));
+				assert!(Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 1.0]]).is_hermitian())
+			}
+
+		}
+
+		mod elementary_operations {
+
+			use super::*;
+
+			#[test]
+			fn swap_rows_keeps_columns_consistent() {
+				let mut m = Matrix::new(vec![
+					vec![1, 2],
+					vec![3, 4]
+				]);
+				m.swap_rows(0, 1);
+				assert_eq!(m.rows, vec![vec![3, 4], vec![1, 2]]);
+				assert_eq!(m.cols, vec![vec![3, 1], vec![4, 2]])
+			}
+
+			#[test]
+			fn swap_cols_keeps_rows_consistent() {
+				let mut m = Matrix::new(vec![
+					vec![1, 2],
+					vec![3, 4]
+				]);
+				m.swap_cols(0, 1);
+				assert_eq!(m.rows, vec![vec![2, 1], vec![4, 3]]);
+				assert_eq!(m.cols, vec![vec![2, 4], vec![1, 3]])
+			}
+
+			#[test]
+			fn scale_row() {
+				let mut m = Matrix::new(vec![
+					vec![1, 2],
+					vec![3, 4]
+				]);
+				m.scale_row(1, 2);
+				assert_eq!(m.rows, vec![vec![1, 2], vec![6, 8]]);
+				assert_eq!(m.cols, vec![vec![1, 6], vec![2, 8]])
+			}
+
+			#[test]
+			fn scale_col() {
+				let mut m = Matrix::new(vec![
+					vec![1, 2],
+					vec![3, 4]
+				]);
+				m.scale_col(0, -1);
+				assert_eq!(m.rows, vec![vec![-1, 2], vec![-3, 4]]);
+				assert_eq!(m.cols, vec![vec![-1, -3], vec![2, 4]])
+			}
+
+			#[test]
+			fn add_scaled_row() {
+				let mut m = Matrix::new(vec![
+					vec![1, 2, 3],
+					vec![2, 5, 7]
+				]);
+				m.add_scaled_row(1, 0, -2);
+				assert_eq!(m.rows, vec![vec![1, 2, 3], vec![0, 1, 1]]);
+				assert_eq!(m.cols, vec![vec![1, 0], vec![2, 1], vec![3, 1]])
+			}
+
+			#[test]
+			fn add_scaled_col() {
+				let mut m = Matrix::new(vec![
+					vec![1, 2],
+					vec![3, 4]
+				]);
+				m.add_scaled_col(1, 0, 1);
+				assert_eq!(m.rows, vec![vec![1, 3], vec![3, 7]]);
+				assert_eq!(m.cols, vec![vec![1, 3], vec![3, 7]])
+			}
+
+			#[test]
+			#[should_panic]
+			fn out_of_bounds() {
+				let mut m = Matrix::new(vec![
+					vec![1, 2],
+					vec![3, 4]
+				]);
+				m.swap_rows(0, 2);
+			}
+
+		}
+
+		mod projection {
+
+			use super::*;
+
+			fn depth(p: &Matrix32, z: f32) -> f32 {
+				let clip = p.clone() * Vector::new(vec![0.0, 0.0, z, 1.0]);
+				clip[2] / clip[3]
+			}
+
+			#[test]
+			fn depth_ranges() {
+				let (fovy, aspect, near, far) = (1.2, 1.5, 0.5, 50.0);
+				let rh = Matrix32::perspective(&fovy, &aspect, &near, &far);
+				let lh = Matrix32::perspective_lh(&fovy, &aspect, &near, &far);
+				let rh_zo = Matrix32::perspective_zo(&fovy, &aspect, &near, &far);
+				let lh_zo = Matrix32::perspective_lh_zo(&fovy, &aspect, &near, &far);
+				let close = |a: f32, b: f32| (a - b).abs() < 1e-5;
+				assert!(close(depth(&rh, -near), -1.0) && close(depth(&rh, -far), 1.0));
+				assert!(close(depth(&lh, near), -1.0) && close(depth(&lh, far), 1.0));
+				assert!(close(depth(&rh_zo, -near), 0.0) && close(depth(&rh_zo, -far), 1.0));
+				assert!(close(depth(&lh_zo, near), 0.0) && close(depth(&lh_zo, far), 1.0))
+			}
+
+			#[test]
+			fn view_matrices_look_down_opposite_axes() {
+				let eye = Vector::new(vec![1.0, 2.0, 3.0]);
+				let dir = Vector::new(vec![0.0, 0.0, -2.0]);
+				let up = Vector::new(vec![0.0, 1.0, 0.0]);
+				let target = Vector::new(vec![1.0, 2.0, -2.0, 1.0]);
+				let rh = Matrix32::look_at_rh(&eye, &dir, &up) * target.clone();
+				let lh = Matrix32::look_at_lh(&eye, &dir, &up) * target;
+				assert_eq!(rh, Vector::new(vec![0.0, 0.0, -5.0, 1.0]));
+				assert_eq!(lh, Vector::new(vec![0.0, 0.0, 5.0, 1.0]))
+			}
+
+		}
+
 	}
 	
 
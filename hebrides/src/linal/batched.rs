@@ -0,0 +1,288 @@
+//! Stacks of equally sized matrices.
+//!
+//! [`BatchedMatrix`] applies the same operation to every matrix in a batch,
+//! optionally spreading the work across threads.
+
+use std::ops::Index;
+
+use super::{Vector, Matrix, MatrixDimensions};
+use crate::Semiring;
+
+/// Applies `f` to every item of `items`, splitting the work across the
+/// available threads and preserving order.
+fn par_map<A, R>(items: &[A], f: impl Fn(&A) -> R + Sync) -> Vec<R> where A: Sync, R: Send {
+	let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+	let chunk_size = items.len().div_ceil(threads).max(1);
+	std::thread::scope(|scope| {
+		let handles: Vec<_> = items.chunks(chunk_size)
+								   .map(|chunk| scope.spawn(|| chunk.iter().map(&f).collect::<Vec<R>>()))
+								   .collect();
+		handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+	})
+}
+
+/// Three-dimensional stack of [`Matrix`]es sharing the same dimensions.
+pub struct BatchedMatrix<T> {
+	matrices: Vec<Matrix<T>>,
+	dims: MatrixDimensions
+}
+
+impl<T> BatchedMatrix<T> {
+
+	/// Constructs a [`BatchedMatrix`] from a [`Vec`] of matrices.
+	///
+	/// # Panics
+	/// Panics if `matrices` is empty or if its matrices do not all share the
+	/// same dimensions.
+	pub fn new(matrices: Vec<Matrix<T>>) -> BatchedMatrix<T> {
+		if matrices.is_empty() {
+			panic!("BatchedMatrix must be non-empty")
+		}
+		let dims = matrices[0].dims;
+		if matrices.iter().any(|m| m.dims != dims) {
+			panic!("All matrices of a BatchedMatrix must have the same dimensions")
+		}
+		Self { matrices, dims }
+	}
+
+	/// Returns the number of matrices in `self`.
+	pub fn len(&self) -> usize {
+		self.matrices.len()
+	}
+
+	/// Always false, as a [`BatchedMatrix`] cannot be empty.
+	pub fn is_empty(&self) -> bool {
+		self.matrices.is_empty()
+	}
+
+	/// Returns the dimensions shared by every matrix in `self`.
+	pub fn dims(&self) -> MatrixDimensions {
+		self.dims
+	}
+
+	/// Returns the `index`th matrix of `self`, or `None` if it is out of bounds.
+	pub fn get(&self, index: usize) -> Option<&Matrix<T>> {
+		self.matrices.get(index)
+	}
+
+	/// Returns the matrices of `self`.
+	pub fn matrices(&self) -> &[Matrix<T>] {
+		&self.matrices
+	}
+
+	/// Consumes `self`, returning its matrices.
+	pub fn into_matrices(self) -> Vec<Matrix<T>> {
+		self.matrices
+	}
+
+}
+
+impl<T> Index<usize> for BatchedMatrix<T> {
+	type Output = Matrix<T>;
+	fn index(&self, index: usize) -> &Matrix<T> {
+		&self.matrices[index]
+	}
+}
+
+impl<T> BatchedMatrix<T> where T: Semiring {
+
+	/// Panics if the matrices of `self` cannot be multiplied by those of `other`.
+	fn check_product(&self, other: &Self) {
+		if self.len() != other.len() {
+			panic!("Batches must be of the same size to be multiplied")
+		}
+		if !MatrixDimensions::are_compatible(self.dims, other.dims) {
+			panic!("Matrices must have compatible dimensions to be multiplied")
+		}
+	}
+
+	/// Panics if the matrices of `self` cannot transform `vectors`.
+	fn check_transform(&self, vectors: &[Vector<T>]) {
+		if self.len() != vectors.len() {
+			panic!("Must provide one Vector per matrix in the batch")
+		}
+		if vectors.iter().any(|v| v.dim != self.dims.num_cols) {
+			panic!("Vector must be compatible with matrix to be transformed by it")
+		}
+	}
+
+	/// Multiplies each matrix of `self` by the corresponding matrix of `other`.
+	///
+	/// ```
+	/// # use hebrides::linal::{Matrix, BatchedMatrix};
+	/// let a = BatchedMatrix::new(vec![
+	///     Matrix::new(vec![vec![1, 0], vec![0, 1]]),
+	///     Matrix::new(vec![vec![2, 0], vec![0, 2]])
+	/// ]);
+	/// let b = BatchedMatrix::new(vec![
+	///     Matrix::new(vec![vec![1, 2], vec![3, 4]]),
+	///     Matrix::new(vec![vec![1, 2], vec![3, 4]])
+	/// ]);
+	/// let product = a.matmul(&b);
+	/// assert_eq!(product[1], Matrix::new(vec![vec![2, 4], vec![6, 8]]));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if the batches are of different sizes or if their matrices have
+	/// incompatible dimensions.
+	pub fn matmul(&self, other: &Self) -> Self {
+		self.check_product(other);
+		let matrices = self.matrices.iter()
+									.zip(other.matrices.iter())
+									.map(|(a, b)| a.clone() * b.clone())
+									.collect();
+		BatchedMatrix::new(matrices)
+	}
+
+	/// Multiplies every matrix of `self` by the same `other` matrix.
+	///
+	/// # Panics
+	/// Panics if the matrices of `self` are not compatible with `other`.
+	pub fn matmul_broadcast(&self, other: &Matrix<T>) -> Self {
+		if !MatrixDimensions::are_compatible(self.dims, other.dims) {
+			panic!("Matrices must have compatible dimensions to be multiplied")
+		}
+		BatchedMatrix::new(self.matrices.iter().map(|a| a.clone() * other.clone()).collect())
+	}
+
+	/// Transforms each of `vectors` by the corresponding matrix of `self`.
+	///
+	/// # Panics
+	/// Panics if there is not one [`Vector`] per matrix, or if any of them is
+	/// incompatible with the matrices of `self`.
+	pub fn transform(&self, vectors: &[Vector<T>]) -> Vec<Vector<T>> {
+		self.check_transform(vectors);
+		self.matrices.iter()
+					 .zip(vectors.iter())
+					 .map(|(m, v)| m.clone() * v.clone())
+					 .collect()
+	}
+
+}
+
+impl<T> BatchedMatrix<T> where T: Semiring + Send + Sync {
+
+	/// Parallel version of [`BatchedMatrix::matmul`].
+	pub fn par_matmul(&self, other: &Self) -> Self {
+		self.check_product(other);
+		let pairs: Vec<(&Matrix<T>, &Matrix<T>)> = self.matrices.iter().zip(other.matrices.iter()).collect();
+		BatchedMatrix::new(par_map(&pairs, |(a, b)| (*a).clone() * (*b).clone()))
+	}
+
+	/// Parallel version of [`BatchedMatrix::transform`].
+	pub fn par_transform(&self, vectors: &[Vector<T>]) -> Vec<Vector<T>> {
+		self.check_transform(vectors);
+		let pairs: Vec<(&Matrix<T>, &Vector<T>)> = self.matrices.iter().zip(vectors.iter()).collect();
+		par_map(&pairs, |(m, v)| (*m).clone() * (*v).clone())
+	}
+
+}
+
+impl BatchedMatrix<f64> {
+
+	/// Solves the system given by each matrix of `self` against the
+	/// corresponding right-hand side in `rhs`.
+	///
+	/// Each solution is `None` if its matrix is singular.
+	///
+	/// # Panics
+	/// Panics if the matrices of `self` are not square, if there is not one
+	/// right-hand side per matrix, or if any right-hand side has the wrong
+	/// dimension.
+	pub fn solve(&self, rhs: &[Vector<f64>]) -> Vec<Option<Vector<f64>>> {
+		self.check_solve(rhs);
+		self.matrices.iter().zip(rhs.iter()).map(|(a, b)| a.solve(b)).collect()
+	}
+
+	/// Parallel version of [`BatchedMatrix::solve`].
+	pub fn par_solve(&self, rhs: &[Vector<f64>]) -> Vec<Option<Vector<f64>>> {
+		self.check_solve(rhs);
+		let pairs: Vec<(&Matrix<f64>, &Vector<f64>)> = self.matrices.iter().zip(rhs.iter()).collect();
+		par_map(&pairs, |(a, b)| a.solve(b))
+	}
+
+	/// Panics if the matrices of `self` cannot be solved against `rhs`.
+	fn check_solve(&self, rhs: &[Vector<f64>]) {
+		if self.dims.num_rows != self.dims.num_cols {
+			panic!("Matrix must be square to be LU factorized")
+		}
+		if self.len() != rhs.len() {
+			panic!("Must provide one right-hand side per matrix in the batch")
+		}
+		if rhs.iter().any(|b| b.dim != self.dims.num_rows) {
+			panic!("Right-hand side must have as many components as the Matrix has rows")
+		}
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn batch(n: usize) -> BatchedMatrix<f64> {
+		BatchedMatrix::new((0..n).map(|k| {
+			let k = k as f64;
+			Matrix::new(vec![
+				vec![2.0 + k, 1.0],
+				vec![1.0, 3.0 + k]
+			])
+		}).collect())
+	}
+
+	#[test]
+	#[should_panic]
+	fn mismatched_dimensions() {
+		let _ = BatchedMatrix::new(vec![
+			Matrix::new(vec![vec![1, 2]]),
+			Matrix::new(vec![vec![1], vec![2]])
+		]);
+	}
+
+	#[test]
+	fn parallel_matmul_matches_serial() {
+		let a = batch(37);
+		let b = batch(37);
+		let serial = a.matmul(&b);
+		let parallel = a.par_matmul(&b);
+		assert!((0..37).all(|i| serial[i] == parallel[i]))
+	}
+
+	#[test]
+	fn matmul_broadcast() {
+		let a = batch(3);
+		let identity = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+		let product = a.matmul_broadcast(&identity);
+		assert!((0..3).all(|i| product[i] == a[i]))
+	}
+
+	#[test]
+	fn transform() {
+		let a = batch(2);
+		let vectors = vec![Vector::new(vec![1.0, 0.0]), Vector::new(vec![0.0, 1.0])];
+		let expected = vec![Vector::new(vec![2.0, 1.0]), Vector::new(vec![1.0, 4.0])];
+		assert_eq!(a.transform(&vectors), expected);
+		assert_eq!(a.par_transform(&vectors), expected)
+	}
+
+	#[test]
+	fn solve_recovers_solutions() {
+		let a = batch(20);
+		let x: Vec<Vector<f64>> = (0..20).map(|k| Vector::new(vec![k as f64, 1.0])).collect();
+		let b = a.transform(&x);
+		for solution in [a.solve(&b), a.par_solve(&b)] {
+			for (found, expected) in solution.iter().zip(x.iter()) {
+				let found = found.clone().unwrap();
+				assert!((found[0] - expected[0]).abs() < 1e-12 && (found[1] - expected[1]).abs() < 1e-12);
+			}
+		}
+	}
+
+	#[test]
+	#[should_panic]
+	fn wrong_batch_size() {
+		let _ = batch(2).matmul(&batch(3));
+	}
+
+}
@@ -0,0 +1,252 @@
+//! Matrix completion.
+//!
+//! Recovering a low-rank matrix from a [`MaskedMatrix`] of partial
+//! observations, by singular value shrinkage or by alternating least squares
+//! on a factorization. The `_guarded` variants bound the work done by a
+//! [`Budget`].
+
+use super::{Vector, Matrix, MaskedMatrix};
+use crate::guard::{Budget, ResourceExceeded};
+
+/// A completed matrix along with the history of its fit.
+#[derive(Debug, Clone)]
+pub struct Completion {
+	matrix: Matrix<f64>,
+	history: Vec<f64>
+}
+
+impl Completion {
+
+	/// Returns the low-rank reconstruction, with every entry filled in.
+	pub fn matrix(&self) -> &Matrix<f64> {
+		&self.matrix
+	}
+
+	/// Returns the root mean squared error of the reconstruction on the
+	/// observed entries after each iteration.
+	pub fn history(&self) -> &[f64] {
+		&self.history
+	}
+
+}
+
+fn observed_rmse(observed: &MaskedMatrix<f64>, estimate: &[Vec<f64>]) -> f64 {
+	let data = &observed.data().rows;
+	let (mut total, mut count) = (0.0, 0);
+	for (i, row) in estimate.iter().enumerate() {
+		for (j, e) in row.iter().enumerate() {
+			if observed.is_valid(i, j) {
+				total += (e - data[i][j]).powi(2);
+				count += 1;
+			}
+		}
+	}
+	(total / count as f64).sqrt()
+}
+
+fn check_observed(observed: &MaskedMatrix<f64>) {
+	if observed.count() == 0 {
+		panic!("Matrix completion needs at least one observed entry")
+	}
+}
+
+/// Completes `observed` by soft-impute, repeatedly filling the missing
+/// entries from the current estimate and shrinking the singular values of
+/// the result by `shrinkage`.
+///
+/// This solves the nuclear-norm regularized problem of minimizing
+/// ½‖P(X - Z)‖² + `shrinkage`·‖Z‖* over Z, where P keeps the observed
+/// entries; larger `shrinkage` gives lower-rank reconstructions. Iteration
+/// stops after `max_iterations` or once the relative change in the estimate
+/// falls below `tolerance`.
+///
+/// # Panics
+/// Panics if `observed` has no valid entries.
+pub fn soft_impute(observed: &MaskedMatrix<f64>, shrinkage: f64, max_iterations: usize, tolerance: f64) -> Completion {
+	soft_impute_guarded(observed, shrinkage, max_iterations, tolerance, &mut Budget::unlimited()).expect("Unlimited Budget was exceeded")
+}
+
+/// Completes `observed` as [`soft_impute`] does, counting each iteration
+/// and the estimate's allocation against `budget`.
+///
+/// # Errors
+/// Returns [`ResourceExceeded`] if `budget` runs out before the iteration
+/// stops.
+///
+/// # Panics
+/// Panics if `observed` has no valid entries.
+pub fn soft_impute_guarded(observed: &MaskedMatrix<f64>, shrinkage: f64, max_iterations: usize, tolerance: f64, budget: &mut Budget) -> Result<Completion, ResourceExceeded> {
+	check_observed(observed);
+	let data = observed.data();
+	let (m, n) = (data.dims.num_rows, data.dims.num_cols);
+	budget.reserve(m * n)?;
+	let mut estimate = vec![vec![0.0; n]; m];
+	let mut history = Vec::new();
+	for _ in 0..max_iterations {
+		budget.step()?;
+		let filled = Matrix::new((0..m).map(|i| (0..n).map(|j| {
+			if observed.is_valid(i, j) { data.rows[i][j] } else { estimate[i][j] }
+		}).collect()).collect());
+		let svd = filled.svd();
+		let shrunk: Vec<f64> = svd.singular_values().iter().map(|s| (s - shrinkage).max(0.0)).collect();
+		let next: Vec<Vec<f64>> = (0..m).map(|i| (0..n).map(|j| {
+			shrunk.iter().enumerate().map(|(k, s)| svd.u().rows[i][k] * s * svd.v().rows[j][k]).sum()
+		}).collect()).collect();
+		let change: f64 = next.iter().flatten().zip(estimate.iter().flatten()).map(|(a, b)| (a - b).powi(2)).sum();
+		let size: f64 = estimate.iter().flatten().map(|e| e * e).sum();
+		estimate = next;
+		history.push(observed_rmse(observed, &estimate));
+		if change <= tolerance * tolerance * size {
+			break;
+		}
+	}
+	Ok(Completion { matrix: Matrix::new(estimate), history })
+}
+
+/// Completes `observed` with a rank `rank` factorization UVᵀ fit by
+/// alternating least squares, each row of U and V solving a ridge
+/// regression with penalty `regularization` against the observed entries.
+///
+/// The factors start from the truncated [SVD](Matrix::svd) of the
+/// zero-filled observations. Iteration stops after `max_iterations` or once
+/// the observed error improves by less than a relative `tolerance`.
+///
+/// ```
+/// # use hebrides::linal::{Matrix, MaskedMatrix, als_complete};
+/// let data = Matrix::new(vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 0.0], vec![3.0, 6.0, 9.0]]);
+/// let mask = vec![vec![true, true, true], vec![true, true, false], vec![true, true, true]];
+/// let completion = als_complete(&MaskedMatrix::new(data, mask), 1, 1e-12, 100, 1e-12);
+/// assert!((completion.matrix().rows[1][2] - 6.0).abs() < 1e-6);
+/// ```
+///
+/// # Panics
+/// Panics if `observed` has no valid entries or if `rank` is zero or exceeds
+/// either dimension of `observed`.
+pub fn als_complete(observed: &MaskedMatrix<f64>, rank: usize, regularization: f64, max_iterations: usize, tolerance: f64) -> Completion {
+	als_complete_guarded(observed, rank, regularization, max_iterations, tolerance, &mut Budget::unlimited()).expect("Unlimited Budget was exceeded")
+}
+
+/// Completes `observed` as [`als_complete`] does, counting each alternating
+/// sweep and the reconstruction's allocation against `budget`.
+///
+/// ```
+/// # use hebrides::linal::{Matrix, MaskedMatrix, als_complete_guarded};
+/// # use hebrides::guard::{Budget, ResourceExceeded};
+/// let data = Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 0.0]]);
+/// let observed = MaskedMatrix::new(data, vec![vec![true, true], vec![true, false]]);
+/// let mut budget = Budget::unlimited().with_max_elements(3);
+/// let result = als_complete_guarded(&observed, 1, 0.0, 10, 1e-9, &mut budget);
+/// assert_eq!(result.unwrap_err(), ResourceExceeded::Allocation { requested: 4, limit: 3 });
+/// ```
+///
+/// # Errors
+/// Returns [`ResourceExceeded`] if `budget` runs out before the iteration
+/// stops.
+///
+/// # Panics
+/// Panics under the same conditions as [`als_complete`].
+pub fn als_complete_guarded(observed: &MaskedMatrix<f64>, rank: usize, regularization: f64, max_iterations: usize, tolerance: f64, budget: &mut Budget) -> Result<Completion, ResourceExceeded> {
+	check_observed(observed);
+	let data = observed.data();
+	let (m, n) = (data.dims.num_rows, data.dims.num_cols);
+	if rank == 0 || rank > m.min(n) {
+		panic!("Rank must be positive and at most the smaller dimension of the Matrix")
+	}
+	budget.reserve(m * n)?;
+	let svd = observed.fill(0.0).svd();
+	let scale: Vec<f64> = svd.singular_values().iter().take(rank).map(|s| s.sqrt()).collect();
+	let mut u: Vec<Vec<f64>> = svd.u().rows.iter().map(|row| (0..rank).map(|k| row[k] * scale[k]).collect()).collect();
+	let mut v: Vec<Vec<f64>> = svd.v().rows.iter().map(|row| (0..rank).map(|k| row[k] * scale[k]).collect()).collect();
+	// Solves for the factor row minimizing the squared error over `entries`
+	// plus the ridge penalty; an unobserved row is set to zero.
+	let ridge = |others: &[Vec<f64>], entries: Vec<(usize, f64)>| -> Vec<f64> {
+		if entries.is_empty() {
+			return vec![0.0; rank];
+		}
+		let gram = Matrix::new((0..rank).map(|p| (0..rank).map(|q| {
+			entries.iter().map(|(j, _)| others[*j][p] * others[*j][q]).sum::<f64>() + if p == q { regularization } else { 0.0 }
+		}).collect()).collect());
+		let rhs = Vector::new((0..rank).map(|p| entries.iter().map(|(j, x)| others[*j][p] * x).sum()).collect());
+		gram.solve(&rhs).map(|x| x.iter().copied().collect()).unwrap_or_else(|| vec![0.0; rank])
+	};
+	let product = |u: &[Vec<f64>], v: &[Vec<f64>]| -> Vec<Vec<f64>> {
+		u.iter().map(|a| v.iter().map(|b| a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()).collect()).collect()
+	};
+	let mut history: Vec<f64> = Vec::new();
+	for _ in 0..max_iterations {
+		budget.step()?;
+		u = (0..m).map(|i| ridge(&v, (0..n).filter(|j| observed.is_valid(i, *j)).map(|j| (j, data.rows[i][j])).collect())).collect();
+		v = (0..n).map(|j| ridge(&u, (0..m).filter(|i| observed.is_valid(*i, j)).map(|i| (i, data.rows[i][j])).collect())).collect();
+		let error = observed_rmse(observed, &product(&u, &v));
+		let previous = history.last().copied();
+		history.push(error);
+		if previous.is_some_and(|p| p - error <= tolerance * p) {
+			break;
+		}
+	}
+	Ok(Completion { matrix: Matrix::new(product(&u, &v)), history })
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn low_rank() -> (Matrix<f64>, MaskedMatrix<f64>) {
+		let left = Matrix::new(vec![vec![1.0, 0.5], vec![2.0, -1.0], vec![0.0, 1.5], vec![1.5, 1.0], vec![-1.0, 2.0], vec![0.5, 0.5]]);
+		let right = Matrix::new(vec![vec![1.0, 2.0, 0.0, -1.0, 1.0], vec![0.5, -1.0, 2.0, 1.0, 1.5]]);
+		let full = left * right;
+		let mask: Vec<Vec<bool>> = (0..6).map(|i| (0..5).map(|j| (3 * i + 2 * j) % 7 != 0).collect()).collect();
+		(full.clone(), MaskedMatrix::new(full, mask))
+	}
+
+	fn missing_error(full: &Matrix<f64>, observed: &MaskedMatrix<f64>, estimate: &Matrix<f64>) -> f64 {
+		(0..6).flat_map(|i| (0..5).map(move |j| (i, j)))
+			  .filter(|(i, j)| !observed.is_valid(*i, *j))
+			  .map(|(i, j)| (full.rows[i][j] - estimate.rows[i][j]).abs())
+			  .fold(0.0, f64::max)
+	}
+
+	#[test]
+	fn als_recovers_low_rank_matrix() {
+		let (full, observed) = low_rank();
+		let completion = als_complete(&observed, 2, 1e-10, 500, 1e-14);
+		assert!(missing_error(&full, &observed, completion.matrix()) < 1e-4);
+		assert!(completion.history().windows(2).all(|w| w[1] <= w[0] + 1e-12))
+	}
+
+	#[test]
+	fn soft_impute_beats_zero_filling() {
+		let (full, observed) = low_rank();
+		let completion = soft_impute(&observed, 0.1, 500, 1e-10);
+		let zero_filled = missing_error(&full, &observed, &observed.fill(0.0));
+		assert!(missing_error(&full, &observed, completion.matrix()) < 0.1 * zero_filled);
+		assert!(*completion.history().last().unwrap() < 0.1)
+	}
+
+	#[test]
+	fn heavy_shrinkage_gives_zero() {
+		let (_, observed) = low_rank();
+		let completion = soft_impute(&observed, 1e6, 10, 1e-10);
+		assert!(completion.matrix().rows.iter().flatten().all(|e| *e == 0.0))
+	}
+
+	#[test]
+	fn guarded_matches_unguarded_within_budget() {
+		let (_, observed) = low_rank();
+		let unguarded = soft_impute(&observed, 0.1, 50, 1e-10);
+		let mut budget = Budget::unlimited().with_max_iterations(50).with_max_elements(30);
+		let guarded = soft_impute_guarded(&observed, 0.1, 50, 1e-10, &mut budget).unwrap();
+		assert_eq!(guarded.history(), unguarded.history());
+		let mut short = Budget::unlimited().with_max_iterations(5);
+		assert_eq!(soft_impute_guarded(&observed, 0.1, 50, 1e-10, &mut short).unwrap_err(), ResourceExceeded::Iterations { limit: 5 })
+	}
+
+	#[test]
+	#[should_panic]
+	fn rank_too_large() {
+		let (_, observed) = low_rank();
+		let _ = als_complete(&observed, 6, 0.0, 10, 1e-9);
+	}
+
+}
@@ -0,0 +1,1511 @@
+//! Matrix decompositions.
+//!
+//! Factorizations of float matrices, along with the solvers built on them.
+
+use super::{Vector, Matrix};
+use super::kernels::Dispatch;
+use crate::Complex;
+
+/// LU factorization with partial pivoting, PA = LU.
+///
+/// `L` is unit lower triangular and `U` is upper triangular; both are stored
+/// packed into a single square array.
+#[derive(Debug, Clone)]
+pub struct LU {
+	factors: Vec<Vec<f64>>,
+	permutation: Vec<usize>,
+	swaps: usize,
+	singular: bool
+}
+
+impl LU {
+
+	/// Returns the dimension of the factorized matrix.
+	pub fn dim(&self) -> usize {
+		self.factors.len()
+	}
+
+	/// Returns whether or not the factorized matrix is (numerically) singular.
+	pub fn is_singular(&self) -> bool {
+		self.singular
+	}
+
+	/// Returns the unit lower triangular factor `L`.
+	pub fn l(&self) -> Matrix<f64> {
+		let n = self.dim();
+		Matrix::new((0..n).map(|i| (0..n).map(|j| match i.cmp(&j) {
+			std::cmp::Ordering::Greater => self.factors[i][j],
+			std::cmp::Ordering::Equal => 1.0,
+			std::cmp::Ordering::Less => 0.0
+		}).collect()).collect())
+	}
+
+	/// Returns the upper triangular factor `U`.
+	pub fn u(&self) -> Matrix<f64> {
+		let n = self.dim();
+		Matrix::new((0..n).map(|i| (0..n).map(|j| if j >= i { self.factors[i][j] } else { 0.0 }).collect()).collect())
+	}
+
+	/// Returns the row permutation `P` as a list in which the `i`th entry is
+	/// the row of the original matrix that ended up in row `i`.
+	pub fn permutation(&self) -> &[usize] {
+		&self.permutation
+	}
+
+	/// Returns the number of row swaps performed while pivoting.
+	pub fn swaps(&self) -> usize {
+		self.swaps
+	}
+
+	/// Returns the determinant of the factorized matrix, the signed product
+	/// of the diagonal of `U`.
+	///
+	/// This is exactly zero only when a pivot is; a matrix that
+	/// [`LU::is_singular`] flags can still have a tiny nonzero determinant.
+	pub fn determinant(&self) -> f64 {
+		let product: f64 = (0..self.dim()).map(|i| self.factors[i][i]).product();
+		if self.swaps.is_multiple_of(2) { product } else { -product }
+	}
+
+	/// Solves Ax = `b` for x, where A is the factorized matrix.
+	///
+	/// Returns `None` if A is singular.
+	///
+	/// # Panics
+	/// Panics if `b` does not have as many components as A has rows.
+	pub fn solve(&self, b: &Vector<f64>) -> Option<Vector<f64>> {
+		let n = self.dim();
+		if b.dim != n {
+			panic!("Right-hand side must have as many components as the Matrix has rows")
+		}
+		if self.singular {
+			return None;
+		}
+		let kernels = f64::kernels();
+		let mut x: Vec<f64> = self.permutation.iter().map(|i| b[*i]).collect();
+		for i in 0..n {
+			x[i] -= kernels.dot(&self.factors[i][..i], &x[..i]);
+		}
+		for i in (0..n).rev() {
+			x[i] -= kernels.dot(&self.factors[i][i + 1..], &x[i + 1..]);
+			x[i] /= self.factors[i][i];
+		}
+		Some(Vector::new(x))
+	}
+
+}
+
+/// Cholesky factorization of a symmetric positive definite matrix, A = LLᵀ.
+///
+/// `L` is lower triangular with a positive diagonal.
+#[derive(Debug, Clone)]
+pub struct Cholesky {
+	factor: Vec<Vec<f64>>
+}
+
+impl Cholesky {
+
+	/// Returns the dimension of the factorized matrix.
+	pub fn dim(&self) -> usize {
+		self.factor.len()
+	}
+
+	/// Returns the lower triangular factor `L`.
+	pub fn l(&self) -> Matrix<f64> {
+		Matrix::new(self.factor.clone())
+	}
+
+	/// Solves Ax = `b` for x, where A is the factorized matrix.
+	///
+	/// # Panics
+	/// Panics if `b` does not have as many components as A has rows.
+	pub fn solve(&self, b: &Vector<f64>) -> Vector<f64> {
+		let n = self.dim();
+		if b.dim != n {
+			panic!("Right-hand side must have as many components as the Matrix has rows")
+		}
+		let mut x = b.components.clone();
+		for i in 0..n {
+			let partial: f64 = (0..i).map(|j| self.factor[i][j] * x[j]).sum();
+			x[i] = (x[i] - partial) / self.factor[i][i];
+		}
+		for i in (0..n).rev() {
+			let partial: f64 = (i + 1..n).map(|j| self.factor[j][i] * x[j]).sum();
+			x[i] = (x[i] - partial) / self.factor[i][i];
+		}
+		Vector::new(x)
+	}
+
+	/// Updates the factorization of A to one of A + `x` `x`ᵀ in O(n²) time.
+	///
+	/// # Panics
+	/// Panics if `x` does not have as many components as A has rows.
+	pub fn update(&mut self, x: &Vector<f64>) {
+		self.rotate(x, 1.0);
+	}
+
+	/// Updates the factorization of A to one of A - `x` `x`ᵀ in O(n²) time.
+	///
+	/// Returns `false`, leaving the factorization unchanged, if the downdated
+	/// matrix is not positive definite.
+	///
+	/// # Panics
+	/// Panics if `x` does not have as many components as A has rows.
+	pub fn downdate(&mut self, x: &Vector<f64>) -> bool {
+		let previous = self.factor.clone();
+		if !self.rotate(x, -1.0) {
+			self.factor = previous;
+			return false;
+		}
+		true
+	}
+
+	fn rotate(&mut self, x: &Vector<f64>, sign: f64) -> bool {
+		let n = self.dim();
+		if x.dim != n {
+			panic!("Vector must have as many components as the Matrix has rows")
+		}
+		let mut x = x.components.clone();
+		for k in 0..n {
+			let diagonal = self.factor[k][k];
+			let squared = diagonal * diagonal + sign * x[k] * x[k];
+			if squared <= 0.0 {
+				return false;
+			}
+			let r = squared.sqrt();
+			let (c, s) = (r / diagonal, x[k] / diagonal);
+			self.factor[k][k] = r;
+			for (row, xi) in self.factor.iter_mut().zip(x.iter_mut()).skip(k + 1) {
+				row[k] = (row[k] + sign * s * *xi) / c;
+				*xi = c * *xi - s * row[k];
+			}
+		}
+		true
+	}
+
+}
+
+/// A factorized matrix A that can solve Ax = b for many right-hand sides.
+pub trait Solver {
+
+	/// Returns the dimension of A.
+	fn dim(&self) -> usize;
+
+	/// Solves Ax = `b` for x, returning `None` if A is singular.
+	///
+	/// # Panics
+	/// Panics if `b` does not have as many components as A has rows.
+	fn solve(&self, b: &Vector<f64>) -> Option<Vector<f64>>;
+
+}
+
+impl Solver for LU {
+	fn dim(&self) -> usize {
+		LU::dim(self)
+	}
+	fn solve(&self, b: &Vector<f64>) -> Option<Vector<f64>> {
+		LU::solve(self, b)
+	}
+}
+
+impl Solver for Cholesky {
+	fn dim(&self) -> usize {
+		Cholesky::dim(self)
+	}
+	fn solve(&self, b: &Vector<f64>) -> Option<Vector<f64>> {
+		Some(Cholesky::solve(self, b))
+	}
+}
+
+/// Householder QR factorization, A = QR.
+///
+/// For an m x n matrix, `Q` is m x m orthogonal and `R` is m x n upper
+/// triangular.
+#[derive(Debug, Clone)]
+pub struct QR {
+	q: Vec<Vec<f64>>,
+	r: Vec<Vec<f64>>
+}
+
+/// Returns the rotation (c, s) taking (`a`, `b`) to (r, 0).
+fn givens(a: f64, b: f64) -> (f64, f64) {
+	if b == 0.0 {
+		return (1.0, 0.0);
+	}
+	let r = a.hypot(b);
+	(a / r, b / r)
+}
+
+impl QR {
+
+	/// Returns the orthogonal factor `Q`.
+	pub fn q(&self) -> Matrix<f64> {
+		Matrix::new(self.q.clone())
+	}
+
+	/// Returns the upper triangular factor `R`.
+	pub fn r(&self) -> Matrix<f64> {
+		Matrix::new(self.r.clone())
+	}
+
+	/// Updates the factorization of A to one of A + `u` `v`ᵀ in O(m² + mn)
+	/// time using Givens rotations.
+	///
+	/// # Panics
+	/// Panics if `u` does not have as many components as A has rows or `v`
+	/// as many as A has columns.
+	pub fn update(&mut self, u: &Vector<f64>, v: &Vector<f64>) {
+		let (m, n) = (self.r.len(), self.r[0].len());
+		if u.dim != m || v.dim != n {
+			panic!("Vectors must match the dimensions of the factorized Matrix")
+		}
+		let mut w: Vec<f64> = (0..m).map(|j| (0..m).map(|i| self.q[i][j] * u[i]).sum()).collect();
+		for k in (1..m).rev() {
+			let (c, s) = givens(w[k - 1], w[k]);
+			w[k - 1] = c * w[k - 1] + s * w[k];
+			w[k] = 0.0;
+			self.rotate(k - 1, (c, s));
+		}
+		for (e, vj) in self.r[0].iter_mut().zip(v.components.iter()) {
+			*e += w[0] * vj;
+		}
+		for k in 0..n.min(m - 1) {
+			let (c, s) = givens(self.r[k][k], self.r[k + 1][k]);
+			self.rotate(k, (c, s));
+			self.r[k + 1][k] = 0.0;
+		}
+	}
+
+	/// Applies the rotation `(c, s)` to rows `k` and `k` + 1 of `R`, and the
+	/// inverse to the matching columns of `Q`.
+	fn rotate(&mut self, k: usize, (c, s): (f64, f64)) {
+		let (top, bottom) = self.r.split_at_mut(k + 1);
+		for (a, b) in top[k].iter_mut().zip(bottom[0].iter_mut()) {
+			let (x, y) = (*a, *b);
+			*a = c * x + s * y;
+			*b = c * y - s * x;
+		}
+		for row in self.q.iter_mut() {
+			let (x, y) = (row[k], row[k + 1]);
+			row[k] = c * x + s * y;
+			row[k + 1] = c * y - s * x;
+		}
+	}
+
+}
+
+/// Eigendecomposition of a symmetric matrix, A = VΛVᵀ.
+///
+/// The eigenvalues are sorted in increasing order and the columns of `V` are
+/// the matching orthonormal eigenvectors.
+#[derive(Debug, Clone)]
+pub struct SymmetricEigen {
+	values: Vec<f64>,
+	vectors: Matrix<f64>
+}
+
+impl SymmetricEigen {
+
+	/// Returns the eigenvalues in increasing order.
+	pub fn values(&self) -> &[f64] {
+		&self.values
+	}
+
+	/// Returns the orthonormal eigenvectors as the columns of a [`Matrix`].
+	pub fn vectors(&self) -> &Matrix<f64> {
+		&self.vectors
+	}
+
+}
+
+/// Eigendecomposition of a general square matrix.
+///
+/// The eigenvalues may be complex; complex eigenvalues of a real matrix come
+/// in conjugate pairs, which are stored next to each other. Each eigenvector
+/// has unit Euclidean norm and its largest component is real.
+#[derive(Debug, Clone)]
+pub struct Eigen {
+	values: Vec<Complex>,
+	vectors: Vec<Vector<Complex>>
+}
+
+impl Eigen {
+
+	/// Returns the eigenvalues.
+	pub fn values(&self) -> &[Complex] {
+		&self.values
+	}
+
+	/// Returns the eigenvectors, in the same order as the eigenvalues.
+	pub fn vectors(&self) -> &[Vector<Complex>] {
+		&self.vectors
+	}
+
+}
+
+/// Thin singular value decomposition, A = U Σ Vᵀ.
+///
+/// For an m x n matrix with k = min(m, n), `U` is m x k and `V` is n x k,
+/// both with orthonormal columns, and the k singular values are sorted in
+/// decreasing order.
+#[derive(Debug, Clone)]
+pub struct SVD {
+	u: Matrix<f64>,
+	singular_values: Vec<f64>,
+	v: Matrix<f64>
+}
+
+impl SVD {
+
+	/// Returns the left singular vectors as the columns of a [`Matrix`].
+	pub fn u(&self) -> &Matrix<f64> {
+		&self.u
+	}
+
+	/// Returns the singular values in decreasing order.
+	pub fn singular_values(&self) -> &[f64] {
+		&self.singular_values
+	}
+
+	/// Returns the right singular vectors as the columns of a [`Matrix`].
+	pub fn v(&self) -> &Matrix<f64> {
+		&self.v
+	}
+
+	/// Returns the tolerance below which singular values are treated as zero
+	/// by default: the largest singular value scaled by the larger dimension
+	/// and machine epsilon.
+	pub fn default_tolerance(&self) -> f64 {
+		let largest = self.singular_values.first().copied().unwrap_or(0.0);
+		largest * self.u.dims.num_rows.max(self.v.dims.num_rows) as f64 * f64::EPSILON
+	}
+
+	/// Returns the number of singular values greater than `tol`.
+	pub fn rank(&self, tol: f64) -> usize {
+		self.singular_values.iter().filter(|sigma| **sigma > tol).count()
+	}
+
+	/// Returns V Σ⁺ Uᵀ, where Σ⁺ inverts the singular values greater than
+	/// `tol` and zeroes the rest.
+	fn pseudo_inverse(&self, tol: f64) -> Matrix<f64> {
+		let (m, n) = (self.u.dims.num_rows, self.v.dims.num_rows);
+		let rank = self.rank(tol);
+		Matrix::new((0..n).map(|i| (0..m).map(|j| {
+			(0..rank).map(|k| self.v.rows[i][k] * self.u.rows[j][k] / self.singular_values[k]).sum()
+		}).collect()).collect())
+	}
+
+}
+
+/// One-sided Jacobi on the columns of a tall (or square) matrix, returning
+/// the orthogonalized columns and the accumulated rotations as columns.
+fn jacobi_columns(mut a: Vec<Vec<f64>>) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+	let n = a.len();
+	let mut v: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+	let dot = |x: &[f64], y: &[f64]| -> f64 { x.iter().zip(y.iter()).map(|(a, b)| a * b).sum() };
+	for _ in 0..60 {
+		let mut rotated = false;
+		for p in 0..n {
+			for q in p + 1..n {
+				let (alpha, beta, gamma) = (dot(&a[p], &a[p]), dot(&a[q], &a[q]), dot(&a[p], &a[q]));
+				if gamma == 0.0 || gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() {
+					continue;
+				}
+				rotated = true;
+				let zeta = (beta - alpha) / (2.0 * gamma);
+				let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+				let c = 1.0 / (1.0 + t * t).sqrt();
+				let s = c * t;
+				for columns in [&mut a, &mut v] {
+					let (left, right) = columns.split_at_mut(q);
+					for (x, y) in left[p].iter_mut().zip(right[0].iter_mut()) {
+						let (xp, yq) = (*x, *y);
+						*x = c * xp - s * yq;
+						*y = s * xp + c * yq;
+					}
+				}
+			}
+		}
+		if !rotated {
+			break;
+		}
+	}
+	(a, v)
+}
+
+/// Reduces a square matrix to upper Hessenberg form by an orthogonal
+/// similarity built from Householder reflections.
+fn hessenberg(mut a: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+	let n = a.len();
+	for k in 0..n.saturating_sub(2) {
+		let mut v: Vec<f64> = a[k + 1..].iter().map(|row| row[k]).collect();
+		let length = v.iter().map(|e| e * e).sum::<f64>().sqrt();
+		if length == 0.0 {
+			continue;
+		}
+		v[0] += length.copysign(v[0]);
+		let size: f64 = v.iter().map(|e| e * e).sum();
+		for j in k..n {
+			let s = 2.0 * v.iter().zip(a[k + 1..].iter()).map(|(x, row)| x * row[j]).sum::<f64>() / size;
+			for (x, row) in v.iter().zip(a[k + 1..].iter_mut()) {
+				row[j] -= s * x;
+			}
+		}
+		for row in a.iter_mut() {
+			let s = 2.0 * v.iter().zip(row[k + 1..].iter()).map(|(x, e)| x * e).sum::<f64>() / size;
+			for (x, e) in v.iter().zip(row[k + 1..].iter_mut()) {
+				*e -= s * x;
+			}
+		}
+		for row in a[k + 2..].iter_mut() {
+			row[k] = 0.0;
+		}
+	}
+	a
+}
+
+/// Finds the eigenvalues of an upper Hessenberg matrix, as (real, imaginary)
+/// pairs, by Francis double-shift QR iteration. Returns `None` if some
+/// eigenvalue fails to converge.
+fn hessenberg_eigenvalues(mut a: Vec<Vec<f64>>) -> Option<Vec<(f64, f64)>> {
+	let n = a.len();
+	let mut values = vec![(0.0, 0.0); n];
+	let norm: f64 = a.iter().enumerate().map(|(i, row)| row[i.saturating_sub(1)..].iter().map(|e| e.abs()).sum::<f64>()).sum();
+	let mut shift = 0.0;
+	let mut remaining = n;
+	while remaining > 0 {
+		let last = remaining - 1;
+		let mut its = 0;
+		loop {
+			let mut l = last;
+			while l > 0 {
+				let mut s = a[l - 1][l - 1].abs() + a[l][l].abs();
+				if s == 0.0 {
+					s = norm;
+				}
+				if a[l][l - 1].abs() + s == s {
+					a[l][l - 1] = 0.0;
+					break;
+				}
+				l -= 1;
+			}
+			let mut x = a[last][last];
+			if l == last {
+				values[last] = (x + shift, 0.0);
+				remaining -= 1;
+				break;
+			}
+			let mut y = a[last - 1][last - 1];
+			let mut w = a[last][last - 1] * a[last - 1][last];
+			if l + 1 == last {
+				let p = 0.5 * (y - x);
+				let q = p * p + w;
+				let z = q.abs().sqrt();
+				x += shift;
+				if q >= 0.0 {
+					let z = p + z.copysign(p);
+					values[last - 1] = (x + z, 0.0);
+					values[last] = (if z != 0.0 { x - w / z } else { x + z }, 0.0);
+				} else {
+					values[last - 1] = (x + p, -z);
+					values[last] = (x + p, z);
+				}
+				remaining -= 2;
+				break;
+			}
+			if its == 60 {
+				return None;
+			}
+			if its == 10 || its == 20 {
+				shift += x;
+				for (i, row) in a[..=last].iter_mut().enumerate() {
+					row[i] -= x;
+				}
+				let s = a[last][last - 1].abs() + a[last - 1][last - 2].abs();
+				x = 0.75 * s;
+				y = x;
+				w = -0.4375 * s * s;
+			}
+			its += 1;
+			let mut m = last - 2;
+			let (mut p, mut q, mut r);
+			loop {
+				let z = a[m][m];
+				let (rx, sy) = (x - z, y - z);
+				p = (rx * sy - w) / a[m + 1][m] + a[m][m + 1];
+				q = a[m + 1][m + 1] - z - rx - sy;
+				r = a[m + 2][m + 1];
+				let s = p.abs() + q.abs() + r.abs();
+				p /= s;
+				q /= s;
+				r /= s;
+				if m == l {
+					break;
+				}
+				let u = a[m][m - 1].abs() * (q.abs() + r.abs());
+				let v = p.abs() * (a[m - 1][m - 1].abs() + z.abs() + a[m + 1][m + 1].abs());
+				if u + v == v {
+					break;
+				}
+				m -= 1;
+			}
+			for i in m + 2..=last {
+				a[i][i - 2] = 0.0;
+				if i != m + 2 {
+					a[i][i - 3] = 0.0;
+				}
+			}
+			for k in m..last {
+				if k != m {
+					p = a[k][k - 1];
+					q = a[k + 1][k - 1];
+					r = if k + 1 != last { a[k + 2][k - 1] } else { 0.0 };
+					x = p.abs() + q.abs() + r.abs();
+					if x != 0.0 {
+						p /= x;
+						q /= x;
+						r /= x;
+					}
+				}
+				let s = (p * p + q * q + r * r).sqrt().copysign(p);
+				if s == 0.0 {
+					continue;
+				}
+				if k != m {
+					a[k][k - 1] = -s * x;
+				} else if l != m {
+					a[k][k - 1] = -a[k][k - 1];
+				}
+				p += s;
+				let (px, py, pz) = (p / s, q / s, r / s);
+				q /= p;
+				r /= p;
+				let (upper, lower) = a.split_at_mut(k + 1);
+				let (middle, rest) = lower.split_at_mut(1);
+				let mut third = rest.first_mut().filter(|_| k + 1 != last).map(|row| row[k..=last].iter_mut());
+				for (x0, x1) in upper[k][k..=last].iter_mut().zip(middle[0][k..=last].iter_mut()) {
+					let mut t = *x0 + q * *x1;
+					if let Some(x2) = third.as_mut().and_then(|entries| entries.next()) {
+						t += r * *x2;
+						*x2 -= t * pz;
+					}
+					*x1 -= t * py;
+					*x0 -= t * px;
+				}
+				for row in a[l..=last.min(k + 3)].iter_mut() {
+					let mut t = px * row[k] + py * row[k + 1];
+					if k + 1 != last {
+						t += pz * row[k + 2];
+						row[k + 2] -= t * r;
+					}
+					row[k + 1] -= t * q;
+					row[k] -= t;
+				}
+			}
+		}
+	}
+	Some(values)
+}
+
+/// Approximates an eigenvector of `a` for the (real, imaginary) eigenvalue
+/// `lambda` by inverse iteration in complex arithmetic.
+fn inverse_iteration(a: &[Vec<f64>], lambda: (f64, f64), norm: f64) -> Vec<(f64, f64)> {
+	let mul = |x: (f64, f64), y: (f64, f64)| (x.0 * y.0 - x.1 * y.1, x.0 * y.1 + x.1 * y.0);
+	let div = |x: (f64, f64), y: (f64, f64)| {
+		let size = y.0 * y.0 + y.1 * y.1;
+		((x.0 * y.0 + x.1 * y.1) / size, (x.1 * y.0 - x.0 * y.1) / size)
+	};
+	let modulus = |x: (f64, f64)| x.0.hypot(x.1);
+	let n = a.len();
+	let tiny = f64::EPSILON * norm.max(f64::MIN_POSITIVE);
+	let mut lu: Vec<Vec<(f64, f64)>> = a.iter().enumerate().map(|(i, row)| row.iter().enumerate().map(|(j, e)| {
+		if i == j { (e - lambda.0, -lambda.1) } else { (*e, 0.0) }
+	}).collect()).collect();
+	let mut order: Vec<usize> = (0..n).collect();
+	for k in 0..n {
+		let pivot = (k..n).max_by(|i, j| modulus(lu[*i][k]).total_cmp(&modulus(lu[*j][k]))).unwrap();
+		lu.swap(k, pivot);
+		order.swap(k, pivot);
+		if modulus(lu[k][k]) < tiny {
+			lu[k][k] = (tiny, 0.0);
+		}
+		let (upper, lower) = lu.split_at_mut(k + 1);
+		for row in lower.iter_mut() {
+			let factor = div(row[k], upper[k][k]);
+			row[k] = factor;
+			for (e, u) in row[k + 1..].iter_mut().zip(upper[k][k + 1..].iter()) {
+				let t = mul(factor, *u);
+				*e = (e.0 - t.0, e.1 - t.1);
+			}
+		}
+	}
+	let mut x = vec![(1.0, 0.0); n];
+	for _ in 0..3 {
+		let mut y: Vec<(f64, f64)> = order.iter().map(|i| x[*i]).collect();
+		for i in 0..n {
+			for j in 0..i {
+				let t = mul(lu[i][j], y[j]);
+				y[i] = (y[i].0 - t.0, y[i].1 - t.1);
+			}
+		}
+		for i in (0..n).rev() {
+			for j in i + 1..n {
+				let t = mul(lu[i][j], y[j]);
+				y[i] = (y[i].0 - t.0, y[i].1 - t.1);
+			}
+			y[i] = div(y[i], lu[i][i]);
+		}
+		let largest = y.iter().map(|z| modulus(*z)).fold(0.0, f64::max);
+		x = y.iter().map(|z| (z.0 / largest, z.1 / largest)).collect();
+	}
+	x
+}
+
+impl Matrix<f64> {
+
+	/// Computes the [`LU`] factorization of `self` with partial pivoting.
+	///
+	/// Pivots smaller than a tolerance relative to the largest entry of their
+	/// column in `self` mark the factorization as singular rather than failing
+	/// outright. Elimination still carries on past them, so `U` keeps the
+	/// determinant of `self` on its diagonal.
+	///
+	/// # Panics
+	/// Panics if `self` is not square.
+	pub fn lu(&self) -> LU {
+		if !self.is_square() {
+			panic!("Matrix must be square to be LU factorized")
+		}
+		let n = self.dims.num_rows;
+		let mut factors = self.rows.clone();
+		let mut permutation: Vec<usize> = (0..n).collect();
+		let mut swaps = 0;
+		let mut singular = false;
+		let tolerances: Vec<f64> = (0..n).map(|k| {
+			let scale = self.cols[k].iter().fold(0.0_f64, |max, e| max.max(e.abs()));
+			scale * n as f64 * f64::EPSILON
+		}).collect();
+		let kernels = f64::kernels();
+		for k in 0..n {
+			let pivot = (k..n).max_by(|a, b| factors[*a][k].abs().total_cmp(&factors[*b][k].abs())).unwrap();
+			if pivot != k {
+				factors.swap(k, pivot);
+				permutation.swap(k, pivot);
+				swaps += 1;
+			}
+			if factors[k][k].abs() <= tolerances[k] {
+				singular = true;
+			}
+			if factors[k][k] == 0.0 {
+				continue;
+			}
+			let (upper, lower) = factors.split_at_mut(k + 1);
+			let pivot_row = &upper[k];
+			for row in lower.iter_mut() {
+				let factor = row[k] / pivot_row[k];
+				row[k] = factor;
+				kernels.axpy(-factor, &pivot_row[k + 1..], &mut row[k + 1..]);
+			}
+		}
+		LU { factors, permutation, swaps, singular }
+	}
+
+	/// Computes the [`Cholesky`] factorization of `self`.
+	///
+	/// Returns `None` if `self` is not (numerically) positive definite. Only
+	/// the lower triangle of `self` is read.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let a = Matrix::new(vec![vec![4.0, 2.0], vec![2.0, 5.0]]);
+	/// assert_eq!(a.cholesky().unwrap().l(), Matrix::new(vec![vec![2.0, 0.0], vec![1.0, 2.0]]));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square.
+	pub fn cholesky(&self) -> Option<Cholesky> {
+		if !self.is_square() {
+			panic!("Matrix must be square to be Cholesky factorized")
+		}
+		let n = self.dims.num_rows;
+		let mut factor = vec![vec![0.0; n]; n];
+		for i in 0..n {
+			for j in 0..=i {
+				let partial: f64 = (0..j).map(|k| factor[i][k] * factor[j][k]).sum();
+				if i == j {
+					let squared = self.rows[i][i] - partial;
+					if squared <= 0.0 {
+						return None;
+					}
+					factor[i][i] = squared.sqrt();
+				} else {
+					factor[i][j] = (self.rows[i][j] - partial) / factor[j][j];
+				}
+			}
+		}
+		Some(Cholesky { factor })
+	}
+
+	/// Computes the [`QR`] factorization of `self` by Householder
+	/// reflections.
+	pub fn qr(&self) -> QR {
+		let (m, n) = (self.dims.num_rows, self.dims.num_cols);
+		let mut r = self.rows.clone();
+		let mut q: Vec<Vec<f64>> = (0..m).map(|i| (0..m).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+		for k in 0..n.min(m.saturating_sub(1)) {
+			let norm = (k..m).map(|i| r[i][k] * r[i][k]).sum::<f64>().sqrt();
+			if norm == 0.0 {
+				continue;
+			}
+			let alpha = if r[k][k] > 0.0 { -norm } else { norm };
+			let mut h: Vec<f64> = (k..m).map(|i| r[i][k]).collect();
+			h[0] -= alpha;
+			let h_norm = h.iter().map(|e| e * e).sum::<f64>();
+			if h_norm == 0.0 {
+				continue;
+			}
+			for j in 0..n {
+				let projection = 2.0 * h.iter().zip(r[k..].iter()).map(|(hi, row)| hi * row[j]).sum::<f64>() / h_norm;
+				for (hi, row) in h.iter().zip(r[k..].iter_mut()) {
+					row[j] -= projection * hi;
+				}
+			}
+			for row in q.iter_mut() {
+				let projection = 2.0 * (k..m).map(|i| h[i - k] * row[i]).sum::<f64>() / h_norm;
+				for i in k..m {
+					row[i] -= projection * h[i - k];
+				}
+			}
+			for row in r.iter_mut().skip(k + 1) {
+				row[k] = 0.0;
+			}
+		}
+		QR { q, r }
+	}
+
+	/// Computes the [`SymmetricEigen`] decomposition of `self` by cyclic
+	/// Jacobi rotations.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let a = Matrix::new(vec![vec![2.0, 1.0], vec![1.0, 2.0]]);
+	/// let eigen = a.symmetric_eigen();
+	/// assert!((eigen.values()[0] - 1.0).abs() < 1e-15 && (eigen.values()[1] - 3.0).abs() < 1e-15);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not symmetric.
+	pub fn symmetric_eigen(&self) -> SymmetricEigen {
+		if !self.is_symmetric() {
+			panic!("Matrix must be symmetric to take its symmetric eigendecomposition")
+		}
+		let n = self.dims.num_rows;
+		let mut a = self.rows.clone();
+		let mut v: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+		let total: f64 = a.iter().flatten().map(|e| e * e).sum();
+		for _ in 0..100 {
+			let off: f64 = (0..n).map(|i| (0..n).filter(|j| *j != i).map(|j| a[i][j] * a[i][j]).sum::<f64>()).sum();
+			if off <= f64::EPSILON * f64::EPSILON * total {
+				break;
+			}
+			for p in 0..n {
+				for q in p + 1..n {
+					if a[p][q] == 0.0 {
+						continue;
+					}
+					let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+					let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+					let c = 1.0 / (t * t + 1.0).sqrt();
+					let s = t * c;
+					for row in a.iter_mut().chain(v.iter_mut()) {
+						let (x, y) = (row[p], row[q]);
+						row[p] = c * x - s * y;
+						row[q] = s * x + c * y;
+					}
+					let (row_p, row_q) = (a[p].clone(), a[q].clone());
+					for k in 0..n {
+						a[p][k] = c * row_p[k] - s * row_q[k];
+						a[q][k] = s * row_p[k] + c * row_q[k];
+					}
+				}
+			}
+		}
+		let mut order: Vec<usize> = (0..n).collect();
+		order.sort_by(|i, j| a[*i][*i].total_cmp(&a[*j][*j]));
+		SymmetricEigen {
+			values: order.iter().map(|i| a[*i][*i]).collect(),
+			vectors: Matrix::new(v.iter().map(|row| order.iter().map(|j| row[*j]).collect()).collect())
+		}
+	}
+
+	/// Balances `self` by a diagonal similarity, returning the balanced
+	/// matrix B = D⁻¹AD together with the diagonal of D.
+	///
+	/// Rows and columns are rescaled by powers of two until each row has
+	/// roughly the same norm as the matching column, which leaves the
+	/// eigenvalues unchanged (exactly, since no rounding is introduced) while
+	/// shrinking the norm that bounds their rounding errors. If w is an
+	/// eigenvector of B then Dw is an eigenvector of `self`. [`Matrix::eigen`]
+	/// balances automatically.
+	///
+	/// A `self` with any infinite or NaN entry is returned unchanged, with D
+	/// the identity.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let a = Matrix::new(vec![vec![1.0, 1e4], vec![1e-4, 1.0]]);
+	/// let (b, d) = a.balance();
+	/// assert!(b.rows[0][1] < 2.0 && b.rows[1][0] < 2.0);
+	/// assert_eq!(b.rows[0][1], a.rows[0][1] * d[1] / d[0]);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square.
+	pub fn balance(&self) -> (Matrix<f64>, Vector<f64>) {
+		if !self.is_square() {
+			panic!("Matrix must be square to be balanced")
+		}
+		let n = self.dims.num_rows;
+		let mut a = self.rows.clone();
+		let mut scaling = vec![1.0; n];
+		if !a.iter().flatten().all(|e| e.is_finite()) {
+			return (Matrix::new(a), Vector::new(scaling));
+		}
+		let mut converged = false;
+		while !converged {
+			converged = true;
+			for i in 0..n {
+				let mut column: f64 = (0..n).filter(|j| *j != i).map(|j| a[j][i].abs()).sum();
+				let row: f64 = a[i].iter().enumerate().filter(|(j, _)| *j != i).map(|(_, e)| e.abs()).sum();
+				if column == 0.0 || row == 0.0 {
+					continue;
+				}
+				let total = column + row;
+				let mut f = 1.0;
+				while column < row / 2.0 {
+					f *= 2.0;
+					column *= 4.0;
+				}
+				while column > row * 2.0 {
+					f /= 2.0;
+					column /= 4.0;
+				}
+				if (column + row) / f < 0.95 * total {
+					converged = false;
+					scaling[i] *= f;
+					for e in a[i].iter_mut() {
+						*e /= f;
+					}
+					for r in a.iter_mut() {
+						r[i] *= f;
+					}
+				}
+			}
+		}
+		(Matrix::new(a), Vector::new(scaling))
+	}
+
+	/// Computes the [`Eigen`] decomposition of a general square matrix.
+	///
+	/// `self` is [balanced](Matrix::balance) and reduced to Hessenberg form,
+	/// its eigenvalues are found by Francis double-shift QR iteration, and the
+	/// eigenvectors by inverse iteration on the balanced matrix, transformed
+	/// back afterwards. Returns `None` if the QR iteration fails to converge.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// # use hebrides::Complex;
+	/// let rotation = Matrix::new(vec![vec![0.0, -1.0], vec![1.0, 0.0]]);
+	/// let eigen = rotation.eigen().unwrap();
+	/// assert_eq!(eigen.values(), &[Complex::new(0.0, -1.0), Complex::new(0.0, 1.0)]);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square.
+	pub fn eigen(&self) -> Option<Eigen> {
+		if !self.is_square() {
+			panic!("Matrix must be square to take its eigendecomposition")
+		}
+		let (balanced, scaling) = self.balance();
+		let values = hessenberg_eigenvalues(hessenberg(balanced.rows.clone()))?;
+		let norm: f64 = balanced.rows.iter().flatten().map(|e| e.abs()).sum();
+		let vectors = values.iter().map(|lambda| {
+			let w = inverse_iteration(&balanced.rows, *lambda, norm);
+			let v: Vec<(f64, f64)> = w.iter().zip(scaling.components.iter()).map(|(z, d)| (z.0 * d, z.1 * d)).collect();
+			let (top_re, top_im) = *v.iter().max_by(|x, y| x.0.hypot(x.1).total_cmp(&y.0.hypot(y.1))).unwrap();
+			let top = top_re.hypot(top_im);
+			let length = v.iter().map(|z| z.0 * z.0 + z.1 * z.1).sum::<f64>().sqrt();
+			Vector::new(v.iter().map(|z| {
+				let re = (z.0 * top_re + z.1 * top_im) / (top * length);
+				let im = (z.1 * top_re - z.0 * top_im) / (top * length);
+				Complex::new(re, im)
+			}).collect())
+		}).collect();
+		Some(Eigen {
+			values: values.into_iter().map(|(re, im)| Complex::new(re, im)).collect(),
+			vectors
+		})
+	}
+
+	/// Computes the eigenvalues of a general square matrix, as
+	/// [`eigen`](Matrix::eigen) does but without the inverse iterations for
+	/// the eigenvectors, which dominate its cost.
+	///
+	/// Returns `None` if the QR iteration fails to converge.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// # use hebrides::Complex;
+	/// let m = Matrix::new(vec![vec![2.0, 1.0], vec![1.0, 2.0]]);
+	/// let mut values = m.eigenvalues().unwrap();
+	/// values.sort_by(|a, b| a.norm().cmp(&b.norm()));
+	/// assert_eq!(values, vec![Complex::new(1.0, 0.0), Complex::new(3.0, 0.0)]);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square.
+	pub fn eigenvalues(&self) -> Option<Vec<Complex>> {
+		if !self.is_square() {
+			panic!("Matrix must be square to have eigenvalues")
+		}
+		let (balanced, _) = self.balance();
+		let values = hessenberg_eigenvalues(hessenberg(balanced.rows))?;
+		Some(values.into_iter().map(|(re, im)| Complex::new(re, im)).collect())
+	}
+
+	/// Returns the spectral radius of `self`, the largest modulus of its
+	/// eigenvalues, which is below one exactly when the discrete-time system
+	/// xₖ₊₁ = `self` xₖ is asymptotically stable.
+	///
+	/// Returns `None` if the QR iteration fails to converge.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let m = Matrix::new(vec![vec![0.5, 1.0], vec![0.0, -0.8]]);
+	/// assert!((m.spectral_radius().unwrap() - 0.8).abs() < 1e-12);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square.
+	pub fn spectral_radius(&self) -> Option<f64> {
+		let values = self.eigenvalues()?;
+		Some(values.iter().map(|z| z.norm().value()).fold(0.0, f64::max))
+	}
+
+	/// Computes the thin [`SVD`] of `self` by one-sided Jacobi rotations.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let a = Matrix::new(vec![vec![3.0, 0.0], vec![0.0, -4.0], vec![0.0, 0.0]]);
+	/// assert_eq!(a.svd().singular_values(), &[4.0, 3.0]);
+	/// ```
+	pub fn svd(&self) -> SVD {
+		let (m, n) = (self.dims.num_rows, self.dims.num_cols);
+		let wide = m < n;
+		let columns = if wide { self.rows.clone() } else { self.cols.clone() };
+		let (a, v) = jacobi_columns(columns);
+		let mut order: Vec<(f64, usize)> = a.iter().enumerate().map(|(j, col)| (col.iter().map(|e| e * e).sum::<f64>().sqrt(), j)).collect();
+		order.sort_by(|x, y| y.0.total_cmp(&x.0));
+		let singular_values: Vec<f64> = order.iter().map(|(sigma, _)| *sigma).collect();
+		let left: Vec<Vec<f64>> = order.iter().map(|(sigma, j)| {
+			a[*j].iter().map(|e| if *sigma == 0.0 { 0.0 } else { e / sigma }).collect()
+		}).collect();
+		let right: Vec<Vec<f64>> = order.iter().map(|(_, j)| v[*j].clone()).collect();
+		let from_columns = |cols: Vec<Vec<f64>>| {
+			let len = cols[0].len();
+			Matrix::new((0..len).map(|i| cols.iter().map(|c| c[i]).collect()).collect())
+		};
+		let (u, v) = if wide { (right, left) } else { (left, right) };
+		SVD { u: from_columns(u), singular_values, v: from_columns(v) }
+	}
+
+	/// Returns the determinant of `self`, computed from its [`LU`]
+	/// factorization in O(n³) time.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let a = Matrix::new(vec![vec![0.0, 2.0], vec![3.0, 1.0]]);
+	/// assert_eq!(a.determinant(), -6.0);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square.
+	pub fn determinant(&self) -> f64 {
+		self.lu().determinant()
+	}
+
+	/// Solves `self` x = `b` for x.
+	///
+	/// Returns `None` if `self` is singular.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, Matrix};
+	/// let a = Matrix::new(vec![vec![2.0, 1.0], vec![1.0, 3.0]]);
+	/// let x = a.solve(&Vector::new(vec![3.0, 5.0])).unwrap();
+	/// assert_eq!(x, Vector::new(vec![0.8, 1.4]));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square or if `b` does not have as many
+	/// components as `self` has rows.
+	pub fn solve(&self, b: &Vector<f64>) -> Option<Vector<f64>> {
+		self.lu().solve(b)
+	}
+
+	/// Returns the inverse of `self`, or `None` if `self` is singular.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let a = Matrix::new(vec![vec![2.0, 0.0], vec![4.0, 4.0]]);
+	/// assert_eq!(a.inverse().unwrap(), Matrix::new(vec![vec![0.5, 0.0], vec![-0.5, 0.25]]));
+	/// assert!(Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).inverse().is_none());
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square.
+	pub fn inverse(&self) -> Option<Matrix<f64>> {
+		let lu = self.lu();
+		let n = lu.dim();
+		let mut columns = Vec::with_capacity(n);
+		for j in 0..n {
+			let unit = Vector::new((0..n).map(|i| if i == j { 1.0 } else { 0.0 }).collect());
+			columns.push(lu.solve(&unit)?);
+		}
+		Some(Matrix::new((0..n).map(|i| columns.iter().map(|c| c[i]).collect()).collect()))
+	}
+
+	/// Returns the Moore–Penrose pseudoinverse of `self`, computed from its
+	/// [`SVD`] with singular values at most `tol` treated as zero.
+	///
+	/// For a rank-deficient `self`, `tol` decides which directions count as
+	/// part of the null space; [`SVD::default_tolerance`] is the usual choice.
+	/// A negative `tol` inverts every nonzero singular value.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let a = Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+	/// let p = a.pinv(1e-10);
+	/// let expected = Matrix::new(vec![vec![0.04, 0.08], vec![0.08, 0.16]]);
+	/// assert!(p.rows.iter().flatten().zip(expected.rows.iter().flatten()).all(|(x, y)| (x - y).abs() < 1e-12));
+	/// ```
+	pub fn pinv(&self, tol: f64) -> Matrix<f64> {
+		let svd = self.svd();
+		let tol = tol.max(0.0);
+		svd.pseudo_inverse(tol)
+	}
+
+	/// Returns a left inverse L of `self`, satisfying L `self` = I, or `None`
+	/// if `self` does not have full column rank.
+	///
+	/// The result is the pseudoinverse, which for a matrix of full column
+	/// rank equals (AᵀA)⁻¹Aᵀ; rank is judged against
+	/// [`SVD::default_tolerance`].
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let a = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 2.0], vec![0.0, 0.0]]);
+	/// let l = a.left_inverse().unwrap();
+	/// assert_eq!(l, Matrix::new(vec![vec![1.0, 0.0, 0.0], vec![0.0, 0.5, 0.0]]));
+	/// assert!(a.transpose().left_inverse().is_none());
+	/// ```
+	pub fn left_inverse(&self) -> Option<Matrix<f64>> {
+		let svd = self.svd();
+		let tol = svd.default_tolerance();
+		if svd.rank(tol) < self.dims.num_cols {
+			return None;
+		}
+		Some(svd.pseudo_inverse(tol))
+	}
+
+	/// Returns a right inverse R of `self`, satisfying `self` R = I, or
+	/// `None` if `self` does not have full row rank.
+	///
+	/// The result is the pseudoinverse, which for a matrix of full row rank
+	/// equals Aᵀ(AAᵀ)⁻¹; rank is judged against [`SVD::default_tolerance`].
+	pub fn right_inverse(&self) -> Option<Matrix<f64>> {
+		let svd = self.svd();
+		let tol = svd.default_tolerance();
+		if svd.rank(tol) < self.dims.num_rows {
+			return None;
+		}
+		Some(svd.pseudo_inverse(tol))
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+	use crate::Real;
+
+	fn approx_eq(a: &Matrix<f64>, b: &Matrix<f64>) -> bool {
+		a.rows.iter().flatten().zip(b.rows.iter().flatten()).all(|(x, y)| (x - y).abs() < 1e-12)
+	}
+
+	mod lu {
+
+		use super::*;
+
+		#[test]
+		fn reconstructs_permuted_matrix() {
+			let a = Matrix::new(vec![
+				vec![1.0, 2.0, 3.0],
+				vec![4.0, 5.0, 6.0],
+				vec![7.0, 8.0, 10.0]
+			]);
+			let lu = a.lu();
+			let permuted = Matrix::new(lu.permutation().iter().map(|i| a.rows[*i].clone()).collect());
+			assert!(approx_eq(&(lu.l() * lu.u()), &permuted));
+			assert!(!lu.is_singular())
+		}
+
+		#[test]
+		fn detects_singularity() {
+			let a = Matrix::new(vec![
+				vec![1.0, 2.0],
+				vec![2.0, 4.0]
+			]);
+			assert!(a.lu().is_singular());
+			assert!(a.solve(&Vector::new(vec![1.0, 1.0])).is_none())
+		}
+
+		#[test]
+		fn solve_requires_pivoting() {
+			let a = Matrix::new(vec![
+				vec![0.0, 1.0],
+				vec![1.0, 0.0]
+			]);
+			assert_eq!(a.solve(&Vector::new(vec![2.0, 3.0])).unwrap(), Vector::new(vec![3.0, 2.0]))
+		}
+
+		#[test]
+		fn determinant_matches_cofactors() {
+			let a = Matrix::new(vec![
+				vec![2.0, -1.0, 0.0, 3.0],
+				vec![1.0, 4.0, 2.0, -2.0],
+				vec![0.0, 5.0, -3.0, 1.0],
+				vec![7.0, 1.0, 1.0, 2.0]
+			]);
+			assert!((a.determinant() - a.cofactor_determinant()).abs() < 1e-10);
+			assert_eq!(Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).determinant(), 0.0)
+		}
+
+		#[test]
+		fn determinant_of_badly_scaled_diagonal() {
+			let a = Matrix::new(vec![vec![1e20, 0.0], vec![0.0, 1.0]]);
+			assert_eq!(a.determinant(), 1e20);
+			assert!(!a.lu().is_singular());
+			let b = Matrix::new(vec![vec![1e6, 0.0], vec![0.0, 1e-10]]);
+			assert_eq!(b.determinant(), 1e6 * 1e-10);
+			assert!(!b.lu().is_singular());
+			assert_eq!(b.solve(&Vector::new(vec![1e6, 1e-10])).unwrap(), Vector::new(vec![1.0, 1.0]))
+		}
+
+		#[test]
+		fn determinant_of_nearly_singular_matrix_is_nonzero() {
+			let a = Matrix::new(vec![vec![1.0, 1.0], vec![1.0, 1.0 + f64::EPSILON]]);
+			assert!(a.lu().is_singular());
+			assert_eq!(a.determinant(), f64::EPSILON)
+		}
+
+		#[test]
+		fn determinant_of_large_matrix() {
+			let n = 200;
+			let a = Matrix::new((0..n).map(|i| (0..n).map(|j| if i == j { 2.0 } else if i + 1 == j { 1.0 } else { 0.0 }).collect()).collect());
+			assert_eq!(a.determinant(), 2.0_f64.powi(n))
+		}
+
+		#[test]
+		#[should_panic]
+		fn non_square() {
+			let _ = Matrix::new(vec![vec![1.0, 2.0]]).lu();
+		}
+
+	}
+
+	mod cholesky {
+
+		use super::*;
+
+		fn spd() -> Matrix<f64> {
+			Matrix::new(vec![
+				vec![6.0, 2.0, 1.0],
+				vec![2.0, 5.0, 2.0],
+				vec![1.0, 2.0, 4.0]
+			])
+		}
+
+		#[test]
+		fn reconstructs_and_solves() {
+			let chol = spd().cholesky().unwrap();
+			assert!(approx_eq(&(chol.l() * chol.l().transpose()), &spd()));
+			let x = chol.solve(&Vector::new(vec![9.0, 9.0, 7.0]));
+			assert!((0..3).all(|i| (x[i] - 1.0).abs() < 1e-12));
+			assert!(Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 1.0]]).cholesky().is_none())
+		}
+
+		#[test]
+		fn update_then_downdate_round_trips() {
+			let x = Vector::new(vec![1.0, -2.0, 0.5]);
+			let mut chol = spd().cholesky().unwrap();
+			chol.update(&x);
+			let outer = Matrix::new((0..3).map(|i| (0..3).map(|j| x[i] * x[j]).collect()).collect());
+			assert!(approx_eq(&(chol.l() * chol.l().transpose()), &(spd() + outer)));
+			assert!(chol.downdate(&x));
+			assert!(approx_eq(&chol.l(), &spd().cholesky().unwrap().l()))
+		}
+
+		#[test]
+		fn failed_downdate_leaves_factor_unchanged() {
+			let mut chol = spd().cholesky().unwrap();
+			assert!(!chol.downdate(&Vector::new(vec![3.0, 0.0, 0.0])));
+			assert!(approx_eq(&chol.l(), &spd().cholesky().unwrap().l()))
+		}
+
+	}
+
+	mod qr {
+
+		use super::*;
+
+		fn example() -> Matrix<f64> {
+			Matrix::new(vec![
+				vec![2.0, -1.0, 0.0],
+				vec![1.0, 3.0, 1.0],
+				vec![0.0, 1.0, 4.0],
+				vec![1.0, 0.0, 1.0]
+			])
+		}
+
+		fn is_upper_triangular(r: &Matrix<f64>) -> bool {
+			r.rows.iter().enumerate().all(|(i, row)| row.iter().take(i).all(|e| *e == 0.0))
+		}
+
+		#[test]
+		fn reconstructs_matrix() {
+			let qr = example().qr();
+			assert!(approx_eq(&(qr.q() * qr.r()), &example()));
+			assert!(qr.q().is_orthogonal_within(1e-12) && is_upper_triangular(&qr.r()))
+		}
+
+		#[test]
+		fn rank_one_update_matches_refactorization() {
+			let (u, v) = (Vector::new(vec![1.0, 0.0, -2.0, 3.0]), Vector::new(vec![0.5, 1.0, -1.0]));
+			let mut qr = example().qr();
+			qr.update(&u, &v);
+			let outer = Matrix::new((0..4).map(|i| (0..3).map(|j| u[i] * v[j]).collect()).collect());
+			assert!(approx_eq(&(qr.q() * qr.r()), &(example() + outer)));
+			assert!(qr.q().is_orthogonal_within(1e-12) && is_upper_triangular(&qr.r()))
+		}
+
+	}
+
+	mod symmetric_eigen {
+
+		use super::*;
+
+		#[test]
+		fn eigenpairs_satisfy_definition() {
+			let a = Matrix::new(vec![
+				vec![4.0, 1.0, -2.0, 2.0],
+				vec![1.0, 2.0, 0.0, 1.0],
+				vec![-2.0, 0.0, 3.0, -2.0],
+				vec![2.0, 1.0, -2.0, -1.0]
+			]);
+			let eigen = a.symmetric_eigen();
+			assert!(eigen.values().windows(2).all(|w| w[0] <= w[1]));
+			assert!(eigen.vectors().is_orthogonal_within(1e-12));
+			for (j, lambda) in eigen.values().iter().enumerate() {
+				let v = Vector::new(eigen.vectors().cols[j].clone());
+				let av = a.clone() * v.clone();
+				assert!((0..4).all(|i| (av[i] - lambda * v[i]).abs() < 1e-12));
+			}
+			let trace: f64 = (0..4).map(|i| a.rows[i][i]).sum();
+			assert!((eigen.values().iter().sum::<f64>() - trace).abs() < 1e-12)
+		}
+
+		#[test]
+		#[should_panic]
+		fn non_symmetric() {
+			let _ = Matrix::new(vec![vec![1.0, 2.0], vec![0.0, 1.0]]).symmetric_eigen();
+		}
+
+	}
+
+	mod eigen {
+
+		use super::*;
+
+		fn residual(a: &Matrix<f64>, lambda: Complex, v: &Vector<Complex>) -> f64 {
+			(0..a.dims.num_rows).map(|i| {
+				let av = (0..a.dims.num_cols).fold(Complex::ZERO, |sum, j| sum + Complex::new(a.rows[i][j], 0.0) * v[j]);
+				(av - lambda * v[i]).norm().value()
+			}).fold(0.0, f64::max)
+		}
+
+		#[test]
+		fn balance_leaves_non_finite_matrix_alone() {
+			let a = Matrix::new(vec![vec![1.0, f64::INFINITY], vec![1e-4, 1.0]]);
+			let (b, d) = a.balance();
+			assert_eq!(b, a);
+			assert_eq!(d, Vector::new(vec![1.0, 1.0]));
+			let _ = a.eigen();
+		}
+
+		#[test]
+		fn balance_is_exact_similarity() {
+			let a = Matrix::new(vec![
+				vec![1.0, 3e5, 0.0],
+				vec![2e-5, 2.0, 4e5],
+				vec![0.0, 1e-6, 3.0]
+			]);
+			let (b, d) = a.balance();
+			for i in 0..3 {
+				assert_eq!(d[i].log2().fract(), 0.0);
+				for j in 0..3 {
+					assert_eq!(b.rows[i][j], a.rows[i][j] * d[j] / d[i]);
+				}
+			}
+			let size = |m: &Matrix<f64>| m.rows.iter().flatten().map(|e| e.abs()).sum::<f64>();
+			assert!(size(&b) < 1e-3 * size(&a))
+		}
+
+		#[test]
+		fn eigenpairs_satisfy_definition() {
+			let a = Matrix::new(vec![
+				vec![4.0, -2.0, 1.0, 3.0],
+				vec![1.0, 0.0, -5.0, 2.0],
+				vec![2.0, 3.0, 1.0, -1.0],
+				vec![0.5, -1.0, 2.0, 2.0]
+			]);
+			let eigen = a.eigen().unwrap();
+			assert!(eigen.values().iter().any(|lambda| !lambda.is_real()));
+			for (lambda, v) in eigen.values().iter().zip(eigen.vectors().iter()) {
+				assert!(residual(&a, *lambda, v) < 1e-10);
+			}
+			let trace = eigen.values().iter().fold(Complex::ZERO, |sum, lambda| sum + *lambda);
+			assert!((trace - Complex::new(7.0, 0.0)).norm().value() < 1e-12)
+		}
+
+		#[test]
+		fn badly_scaled_eigenvectors_are_back_transformed() {
+			let a = Matrix::new(vec![
+				vec![1.0, 3e5, 0.0],
+				vec![2e-5, 2.0, 4e5],
+				vec![0.0, 1e-6, 3.0]
+			]);
+			let eigen = a.eigen().unwrap();
+			for (lambda, v) in eigen.values().iter().zip(eigen.vectors().iter()) {
+				assert!(residual(&a, *lambda, v) < 1e-8);
+			}
+		}
+
+		#[test]
+		fn triangular_matrix_has_diagonal_eigenvalues() {
+			let a = Matrix::new(vec![vec![2.0, 1.0, 4.0], vec![0.0, 3.0, -1.0], vec![0.0, 0.0, -1.0]]);
+			let mut values: Vec<f64> = a.eigen().unwrap().values().iter().map(|lambda| Real::try_from(*lambda).unwrap().value()).collect();
+			values.sort_by(f64::total_cmp);
+			assert!(values.iter().zip([-1.0, 2.0, 3.0]).all(|(x, y)| (x - y).abs() < 1e-12))
+		}
+
+		#[test]
+		fn eigenvalues_match_full_decomposition() {
+			let a = Matrix::new(vec![
+				vec![4.0, -2.0, 1.0, 3.0],
+				vec![1.0, 0.0, -5.0, 2.0],
+				vec![2.0, 3.0, 1.0, -1.0],
+				vec![0.5, -1.0, 2.0, 2.0]
+			]);
+			let values = a.eigenvalues().unwrap();
+			assert_eq!(values, a.eigen().unwrap().values());
+			let radius = values.iter().map(|z| z.norm().value()).fold(0.0, f64::max);
+			assert_eq!(a.spectral_radius(), Some(radius))
+		}
+
+		#[test]
+		fn spectral_radius_of_rotation_is_one() {
+			let (sin, cos) = 0.3_f64.sin_cos();
+			let rotation = Matrix::new(vec![vec![cos, -sin], vec![sin, cos]]);
+			assert!((rotation.spectral_radius().unwrap() - 1.0).abs() < 1e-14)
+		}
+
+		#[test]
+		#[should_panic]
+		fn non_square() {
+			let _ = Matrix::new(vec![vec![1.0, 2.0]]).eigen();
+		}
+
+	}
+
+	mod svd {
+
+		use super::*;
+
+		fn reconstruct(svd: &SVD) -> Matrix<f64> {
+			let sigma = Matrix::from_diagonal(&Vector::new(svd.singular_values().to_vec()));
+			svd.u().clone() * sigma * svd.v().transpose()
+		}
+
+		#[test]
+		fn reconstructs_tall_and_wide_matrices() {
+			let a = Matrix::new(vec![
+				vec![1.0, 2.0, 0.0],
+				vec![2.0, -1.0, 3.0],
+				vec![0.5, 4.0, 1.0],
+				vec![1.0, 1.0, 1.0]
+			]);
+			for m in [a.clone(), a.transpose()] {
+				let svd = m.svd();
+				assert!(approx_eq(&reconstruct(&svd), &m));
+				let identity = Matrix::from_diagonal(&Vector::new(vec![1.0; 3]));
+				assert!(approx_eq(&(svd.u().transpose() * svd.u().clone()), &identity));
+				assert!(approx_eq(&(svd.v().transpose() * svd.v().clone()), &identity));
+				assert!(svd.singular_values().windows(2).all(|w| w[0] >= w[1]));
+			}
+		}
+
+		#[test]
+		fn rank_deficient_matrix_has_zero_singular_value() {
+			let a = Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+			let svd = a.svd();
+			assert!((svd.singular_values()[0] - 5.0).abs() < 1e-12 && svd.singular_values()[1].abs() < 1e-12);
+			assert!(approx_eq(&reconstruct(&svd), &a))
+		}
+
+		#[test]
+		fn pinv_satisfies_penrose_conditions() {
+			let a = Matrix::new(vec![
+				vec![1.0, 2.0, 3.0],
+				vec![2.0, 4.0, 6.0],
+				vec![1.0, 0.0, 1.0],
+				vec![0.0, 1.0, 1.0]
+			]);
+			let p = a.pinv(a.svd().default_tolerance());
+			assert!(approx_eq(&(a.clone() * p.clone() * a.clone()), &a));
+			assert!(approx_eq(&(p.clone() * a.clone() * p.clone()), &p));
+			assert!(approx_eq(&(a.clone() * p.clone()).transpose(), &(a.clone() * p.clone())));
+			assert!(approx_eq(&(p.clone() * a.clone()).transpose(), &(p * a)))
+		}
+
+		#[test]
+		fn one_sided_inverses() {
+			let a = Matrix::new(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 7.0]]);
+			let identity = Matrix::from_diagonal(&Vector::new(vec![1.0; 2]));
+			assert!(approx_eq(&(a.left_inverse().unwrap() * a.clone()), &identity));
+			assert!(approx_eq(&(a.transpose() * a.transpose().right_inverse().unwrap()), &identity));
+			assert!(a.right_inverse().is_none());
+			let deficient = Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]);
+			assert!(deficient.left_inverse().is_none())
+		}
+
+		#[test]
+		fn pinv_tolerance_drops_small_singular_values() {
+			let a = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1e-9]]);
+			assert_eq!(a.pinv(1e-6), Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 0.0]]));
+			assert!((a.pinv(0.0).rows[1][1] - 1e9).abs() < 1e-3)
+		}
+
+	}
+
+}
@@ -0,0 +1,137 @@
+//! Exact formatting.
+//!
+//! [`ToLatex`] renders scalars, vectors, and matrices as LaTeX, writing
+//! [`Rational`] entries as fractions and [`BigInt`] entries in full, and
+//! [`Matrix::to_aligned_string`] lays matrices out as plain text with their
+//! columns aligned, for producing exact worked solutions.
+
+use std::fmt::Display;
+
+use super::{Vector, Matrix};
+use crate::{BigInt, Rational, Real};
+
+/// Values with a LaTeX representation.
+///
+/// ```
+/// # use hebrides::{Rational, linal::{Matrix, ToLatex}};
+/// let m = Matrix::new(vec![vec![Rational::new(1, 2), Rational::from(0)], vec![Rational::from(3), Rational::new(-2, 3)]]);
+/// assert_eq!(m.to_latex(), "\\begin{bmatrix} \\frac{1}{2} & 0 \\\\ 3 & -\\frac{2}{3} \\end{bmatrix}");
+/// ```
+pub trait ToLatex {
+
+	/// Returns LaTeX source for `self`, suitable for math mode.
+	fn to_latex(&self) -> String;
+
+}
+
+macro_rules! impl_latex_display {
+	($($t:ty),*) => {
+		$(
+			impl ToLatex for $t {
+				fn to_latex(&self) -> String {
+					self.to_string()
+				}
+			}
+		)*
+	}
+}
+
+impl_latex_display![i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, Real, BigInt];
+
+impl ToLatex for Rational {
+	fn to_latex(&self) -> String {
+		if self.is_integer() {
+			return self.numer().to_string();
+		}
+		let sign = if self.numer() < 0 { "-" } else { "" };
+		format!("{}\\frac{{{}}}{{{}}}", sign, self.numer().unsigned_abs(), self.denom())
+	}
+}
+
+impl<T> ToLatex for Matrix<T> where T: ToLatex {
+	fn to_latex(&self) -> String {
+		let body = self.rows.iter().map(|row| {
+			row.iter().map(|e| e.to_latex()).collect::<Vec<String>>().join(" & ")
+		}).collect::<Vec<String>>().join(" \\\\ ");
+		format!("\\begin{{bmatrix}} {} \\end{{bmatrix}}", body)
+	}
+}
+
+/// Vectors are rendered as columns.
+impl<T> ToLatex for Vector<T> where T: ToLatex {
+	fn to_latex(&self) -> String {
+		let body = self.iter().map(|e| e.to_latex()).collect::<Vec<String>>().join(" \\\\ ");
+		format!("\\begin{{bmatrix}} {} \\end{{bmatrix}}", body)
+	}
+}
+
+impl<T> Matrix<T> where T: Display {
+
+	/// Returns `self` as plain text, one bracketed row per line, with each
+	/// column right-aligned and fractions aligned on their slashes.
+	///
+	/// ```
+	/// # use hebrides::{Rational, linal::Matrix};
+	/// let m = Matrix::new(vec![
+	///     vec![Rational::new(1, 2), Rational::from(-3)],
+	///     vec![Rational::from(10), Rational::new(5, 12)]
+	/// ]);
+	/// assert_eq!(m.to_aligned_string(), "[  1/2  -3    ]\n[ 10     5/12 ]");
+	/// ```
+	pub fn to_aligned_string(&self) -> String {
+		let cells: Vec<Vec<(String, String)>> = self.rows.iter().map(|row| row.iter().map(|e| {
+			let repr = e.to_string();
+			match repr.find('/') {
+				Some(k) => (repr[..k].to_owned(), repr[k..].to_owned()),
+				None => (repr, String::new())
+			}
+		}).collect()).collect();
+		let widths: Vec<(usize, usize)> = (0..self.dims.num_cols).map(|j| {
+			cells.iter().fold((0, 0), |(left, right), row| {
+				(left.max(row[j].0.chars().count()), right.max(row[j].1.chars().count()))
+			})
+		}).collect();
+		cells.iter().map(|row| {
+			let entries: Vec<String> = row.iter().zip(widths.iter()).map(|((left, right), (lw, rw))| {
+				format!("{:>lw$}{:<rw$}", left, right, lw = lw, rw = rw)
+			}).collect();
+			format!("[ {} ]", entries.join("  "))
+		}).collect::<Vec<String>>().join("\n")
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn exact_inverse_as_worked_solution() {
+		let m = Matrix::new(vec![vec![Rational::from(2), Rational::from(1)], vec![Rational::from(1), Rational::from(3)]]);
+		let det = m[(0, 0)] * m[(1, 1)] - m[(0, 1)] * m[(1, 0)];
+		let inverse = Matrix::new(vec![vec![m[(1, 1)] / det, -m[(0, 1)] / det], vec![-m[(1, 0)] / det, m[(0, 0)] / det]]);
+		assert_eq!(inverse.to_aligned_string(), "[  3/5  -1/5 ]\n[ -1/5   2/5 ]");
+		assert_eq!(inverse.to_latex(), "\\begin{bmatrix} \\frac{3}{5} & -\\frac{1}{5} \\\\ -\\frac{1}{5} & \\frac{2}{5} \\end{bmatrix}");
+		let parsed = Matrix::new(["3/5 -1/5", "-1/5 2/5"].iter().map(|line| {
+			line.split_whitespace().map(|s| s.parse::<Rational>().unwrap()).collect()
+		}).collect());
+		assert_eq!(parsed, inverse)
+	}
+
+	#[test]
+	fn plain_entries_and_vectors() {
+		let m = Matrix::new(vec![vec![1, 200], vec![-30, 4]]);
+		assert_eq!(m.to_aligned_string(), "[   1  200 ]\n[ -30    4 ]");
+		assert_eq!(Vector::new(vec![1.5, -2.0]).to_latex(), "\\begin{bmatrix} 1.5 \\\\ -2 \\end{bmatrix}")
+	}
+
+	#[test]
+	fn big_integer_entries() {
+		let big: BigInt = "-123456789012345678901234567890".parse().unwrap();
+		let m = Matrix::new(vec![vec![big, BigInt::from(7)], vec![BigInt::from(0), BigInt::from(-12)]]);
+		assert_eq!(m.to_latex(), "\\begin{bmatrix} -123456789012345678901234567890 & 7 \\\\ 0 & -12 \\end{bmatrix}");
+		assert_eq!(m.to_aligned_string(), "[ -123456789012345678901234567890    7 ]\n[                               0  -12 ]")
+	}
+
+}
@@ -0,0 +1,307 @@
+//! Sampled functions.
+//!
+//! Real functions represented by their values on a grid, with calculus
+//! carried out by linear operators on those values.
+
+use super::{Vector, Matrix, InnerProductSpace, NormedSpace, VectorSpace};
+
+/// Choice of discrete differentiation operator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Differentiation {
+	/// Second order finite differences, one-sided at the endpoints.
+	FiniteDifference,
+	/// Differentiation of the global interpolating polynomial, which is
+	/// spectrally accurate on Chebyshev grids.
+	Spectral
+}
+
+/// A real function sampled on a strictly increasing grid.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionSampled {
+	grid: Vec<f64>,
+	values: Vec<f64>
+}
+
+impl FunctionSampled {
+
+	/// Constructs a sampled function from its `grid` and the `values` taken
+	/// there.
+	///
+	/// # Panics
+	/// Panics if `grid` and `values` differ in length, if there are fewer
+	/// than two samples, or if `grid` is not strictly increasing.
+	pub fn new(grid: Vec<f64>, values: Vec<f64>) -> FunctionSampled {
+		if grid.len() != values.len() {
+			panic!("Grid and values must be of the same length")
+		}
+		if grid.len() < 2 {
+			panic!("Sampled functions need at least two samples")
+		}
+		if grid.windows(2).any(|w| w[0] >= w[1]) {
+			panic!("Grid must be strictly increasing")
+		}
+		FunctionSampled { grid, values }
+	}
+
+	/// Samples `f` on `grid`.
+	///
+	/// ```
+	/// # use hebrides::linal::FunctionSampled;
+	/// let f = FunctionSampled::from_fn(vec![0.0, 1.0, 2.0], |x| x * x);
+	/// assert_eq!(f.values(), &[0.0, 1.0, 4.0]);
+	/// ```
+	pub fn from_fn(grid: Vec<f64>, f: impl Fn(f64) -> f64) -> FunctionSampled {
+		let values = grid.iter().map(|x| f(*x)).collect();
+		FunctionSampled::new(grid, values)
+	}
+
+	/// Returns `n` evenly spaced grid points from `a` to `b` inclusive.
+	pub fn uniform_grid(n: usize, a: f64, b: f64) -> Vec<f64> {
+		(0..n).map(|i| a + (b - a) * i as f64 / (n - 1) as f64).collect()
+	}
+
+	/// Returns the `n` Chebyshev extreme points on [`a`, `b`] in increasing
+	/// order.
+	pub fn chebyshev_grid(n: usize, a: f64, b: f64) -> Vec<f64> {
+		(0..n).map(|i| {
+			let t = -(std::f64::consts::PI * i as f64 / (n - 1) as f64).cos();
+			(a + b) / 2.0 + (b - a) / 2.0 * t
+		}).collect()
+	}
+
+	/// Returns the grid `self` is sampled on.
+	pub fn grid(&self) -> &[f64] {
+		&self.grid
+	}
+
+	/// Returns the sampled values of `self`.
+	pub fn values(&self) -> &[f64] {
+		&self.values
+	}
+
+	/// Returns the matrix mapping sampled values on `grid` to sampled values
+	/// of their derivative.
+	///
+	/// # Panics
+	/// Panics if `grid` has fewer than two points.
+	pub fn differentiation_matrix(grid: &[f64], method: Differentiation) -> Matrix<f64> {
+		let n = grid.len();
+		if n < 2 {
+			panic!("Differentiation needs at least two grid points")
+		}
+		let mut rows = vec![vec![0.0; n]; n];
+		match method {
+			Differentiation::FiniteDifference if n == 2 => {
+				let slope = 1.0 / (grid[1] - grid[0]);
+				rows = vec![vec![-slope, slope]; 2];
+			},
+			Differentiation::FiniteDifference => {
+				let (h1, h2) = (grid[1] - grid[0], grid[2] - grid[1]);
+				rows[0][0] = -(2.0 * h1 + h2) / (h1 * (h1 + h2));
+				rows[0][1] = (h1 + h2) / (h1 * h2);
+				rows[0][2] = -h1 / (h2 * (h1 + h2));
+				for i in 1..n - 1 {
+					let (h1, h2) = (grid[i] - grid[i - 1], grid[i + 1] - grid[i]);
+					rows[i][i - 1] = -h2 / (h1 * (h1 + h2));
+					rows[i][i] = (h2 - h1) / (h1 * h2);
+					rows[i][i + 1] = h1 / (h2 * (h1 + h2));
+				}
+				let (h1, h2) = (grid[n - 2] - grid[n - 3], grid[n - 1] - grid[n - 2]);
+				rows[n - 1][n - 3] = h2 / (h1 * (h1 + h2));
+				rows[n - 1][n - 2] = -(h1 + h2) / (h1 * h2);
+				rows[n - 1][n - 1] = (2.0 * h2 + h1) / (h2 * (h1 + h2));
+			},
+			Differentiation::Spectral => {
+				let weights: Vec<f64> = (0..n).map(|j| {
+					1.0 / (0..n).filter(|k| *k != j).map(|k| grid[j] - grid[k]).product::<f64>()
+				}).collect();
+				for i in 0..n {
+					for j in (0..n).filter(|j| *j != i) {
+						rows[i][j] = weights[j] / weights[i] / (grid[i] - grid[j]);
+					}
+					rows[i][i] = -rows[i].iter().sum::<f64>();
+				}
+			}
+		}
+		Matrix::new(rows)
+	}
+
+	/// Returns the derivative of `self` on the same grid.
+	///
+	/// ```
+	/// # use hebrides::linal::{FunctionSampled, Differentiation};
+	/// let grid = FunctionSampled::chebyshev_grid(16, 0.0, 1.0);
+	/// let f = FunctionSampled::from_fn(grid, f64::exp);
+	/// let df = f.derivative(Differentiation::Spectral);
+	/// assert!(df.values().iter().zip(f.values()).all(|(a, b)| (a - b).abs() < 1e-10));
+	/// ```
+	pub fn derivative(&self, method: Differentiation) -> FunctionSampled {
+		let d = FunctionSampled::differentiation_matrix(&self.grid, method);
+		let values = d * Vector::new(self.values.clone());
+		FunctionSampled { grid: self.grid.clone(), values: (0..values.dim()).map(|i| values[i]).collect() }
+	}
+
+	/// Returns the trapezoidal quadrature weights for the grid of `self`.
+	pub fn quadrature_weights(&self) -> Vector<f64> {
+		let n = self.grid.len();
+		Vector::new((0..n).map(|i| {
+			let left = if i > 0 { self.grid[i] - self.grid[i - 1] } else { 0.0 };
+			let right = if i < n - 1 { self.grid[i + 1] - self.grid[i] } else { 0.0 };
+			(left + right) / 2.0
+		}).collect())
+	}
+
+	/// Returns the integral of `self` over its grid by the trapezoidal rule.
+	///
+	/// ```
+	/// # use hebrides::linal::FunctionSampled;
+	/// let f = FunctionSampled::from_fn(vec![0.0, 1.0, 3.0], |x| 2.0 * x);
+	/// assert_eq!(f.integral(), 9.0);
+	/// ```
+	pub fn integral(&self) -> f64 {
+		self.quadrature_weights().dot(&Vector::new(self.values.clone()))
+	}
+
+	/// Returns the running integral of `self` from the start of its grid.
+	pub fn cumulative_integral(&self) -> FunctionSampled {
+		let mut total = 0.0;
+		let mut values = vec![0.0];
+		for i in 1..self.grid.len() {
+			total += (self.grid[i] - self.grid[i - 1]) * (self.values[i] + self.values[i - 1]) / 2.0;
+			values.push(total);
+		}
+		FunctionSampled { grid: self.grid.clone(), values }
+	}
+
+	/// Returns the coefficients of the first `degree` + 1 Legendre
+	/// polynomials, shifted to the interval spanned by the grid, in the
+	/// orthogonal projection of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::FunctionSampled;
+	/// let grid = FunctionSampled::uniform_grid(2001, -1.0, 1.0);
+	/// let c = FunctionSampled::from_fn(grid, |x| x * x).project_legendre(2);
+	/// assert!((c[0] - 1.0 / 3.0).abs() < 1e-5 && c[1].abs() < 1e-12 && (c[2] - 2.0 / 3.0).abs() < 1e-5);
+	/// ```
+	pub fn project_legendre(&self, degree: usize) -> Vec<f64> {
+		let basis = self.legendre_basis(degree);
+		(0..=degree).map(|k| self.inner(&basis[k]) * (2 * k + 1) as f64 / (self.grid[self.grid.len() - 1] - self.grid[0])).collect()
+	}
+
+	/// Returns the Legendre series with the given `coefficients`, shifted to
+	/// the interval spanned by the grid of `self`, sampled on that grid.
+	pub fn legendre_series(&self, coefficients: &[f64]) -> FunctionSampled {
+		let basis = self.legendre_basis(coefficients.len().saturating_sub(1));
+		let values = (0..self.grid.len()).map(|i| coefficients.iter().zip(basis.iter()).map(|(c, p)| c * p.values[i]).sum()).collect();
+		FunctionSampled { grid: self.grid.clone(), values }
+	}
+
+	fn legendre_basis(&self, degree: usize) -> Vec<FunctionSampled> {
+		let (a, b) = (self.grid[0], self.grid[self.grid.len() - 1]);
+		let t: Vec<f64> = self.grid.iter().map(|x| (2.0 * x - a - b) / (b - a)).collect();
+		let mut basis = vec![vec![1.0; t.len()], t.clone()];
+		for k in 1..degree {
+			let next = (0..t.len()).map(|i| {
+				((2 * k + 1) as f64 * t[i] * basis[k][i] - k as f64 * basis[k - 1][i]) / (k + 1) as f64
+			}).collect();
+			basis.push(next);
+		}
+		basis.truncate(degree + 1);
+		basis.into_iter().map(|values| FunctionSampled { grid: self.grid.clone(), values }).collect()
+	}
+
+	fn check_grid(&self, other: &Self) {
+		if self.grid != other.grid {
+			panic!("Sampled functions must share a grid to be combined")
+		}
+	}
+
+}
+
+impl VectorSpace for FunctionSampled {
+	type Scalar = f64;
+	fn zero_like(&self) -> Self {
+		FunctionSampled { grid: self.grid.clone(), values: vec![0.0; self.grid.len()] }
+	}
+	fn plus(&self, other: &Self) -> Self {
+		self.check_grid(other);
+		FunctionSampled { grid: self.grid.clone(), values: self.values.iter().zip(other.values.iter()).map(|(a, b)| a + b).collect() }
+	}
+	fn minus(&self, other: &Self) -> Self {
+		self.check_grid(other);
+		FunctionSampled { grid: self.grid.clone(), values: self.values.iter().zip(other.values.iter()).map(|(a, b)| a - b).collect() }
+	}
+	fn scaled(&self, s: f64) -> Self {
+		FunctionSampled { grid: self.grid.clone(), values: self.values.iter().map(|v| v * s).collect() }
+	}
+}
+
+impl NormedSpace for FunctionSampled {
+	fn norm(&self) -> f64 {
+		self.inner(self).sqrt()
+	}
+}
+
+impl InnerProductSpace for FunctionSampled {
+	/// Returns the L2 inner product of `self` and `other` under the
+	/// trapezoidal rule.
+	fn inner(&self, other: &Self) -> f64 {
+		self.check_grid(other);
+		let weights = self.quadrature_weights();
+		(0..self.grid.len()).map(|i| weights[i] * self.values[i] * other.values[i]).sum()
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn finite_differences_are_exact_for_quadratics() {
+		let grid = vec![0.0, 0.5, 1.5, 2.0, 3.5];
+		let f = FunctionSampled::from_fn(grid.clone(), |x| 3.0 * x * x - x + 2.0);
+		let df = f.derivative(Differentiation::FiniteDifference);
+		for (x, d) in grid.iter().zip(df.values()) {
+			assert!((d - (6.0 * x - 1.0)).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn two_point_derivative_is_the_secant() {
+		let f = FunctionSampled::new(vec![1.0, 3.0], vec![2.0, 6.0]);
+		assert_eq!(f.derivative(Differentiation::FiniteDifference).values(), &[2.0, 2.0])
+	}
+
+	#[test]
+	fn cumulative_integral_ends_at_integral() {
+		let f = FunctionSampled::from_fn(FunctionSampled::uniform_grid(101, 0.0, 1.0), |x| x.sin());
+		let running = f.cumulative_integral();
+		assert!((running.values()[100] - f.integral()).abs() < 1e-15);
+		assert!((f.integral() - (1.0 - 1.0_f64.cos())).abs() < 1e-4)
+	}
+
+	#[test]
+	fn legendre_projection_round_trips_polynomials() {
+		let grid = FunctionSampled::uniform_grid(4001, 2.0, 4.0);
+		let f = FunctionSampled::from_fn(grid, |x| x * x * x - x);
+		let approx = f.legendre_series(&f.project_legendre(3));
+		assert!(NormedSpace::norm(&approx.minus(&f)) < 1e-4)
+	}
+
+	#[test]
+	fn sampled_functions_are_an_inner_product_space() {
+		let grid = FunctionSampled::uniform_grid(3, 0.0, 2.0);
+		let f = FunctionSampled::from_fn(grid.clone(), |x| x);
+		let g = FunctionSampled::from_fn(grid, |_| 1.0);
+		assert_eq!(f.inner(&g), 2.0);
+		assert_eq!(f.axpy(-1.0, &f), f.zero_like())
+	}
+
+	#[test]
+	#[should_panic]
+	fn grid_must_increase() {
+		let _ = FunctionSampled::new(vec![0.0, 0.0], vec![1.0, 1.0]);
+	}
+
+}
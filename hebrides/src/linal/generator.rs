@@ -0,0 +1,169 @@
+//! Parameterized matrix families.
+//!
+//! A [`MatrixGenerator`] wraps the construction of a matrix from a fixed
+//! number of real parameters, caching the matrices it has built and
+//! evaluating over whole parameter grids, so that parameter sweeps share one
+//! definition of the family.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use super::Matrix;
+use crate::Angle;
+
+/// A family of matrices indexed by `P` real parameters.
+///
+/// Matrices are cached by the exact bit patterns of their parameters, so
+/// repeated evaluations at the same point are only built once; `0.0` and
+/// `-0.0` are cached separately.
+///
+/// ```
+/// # use hebrides::linal::{Matrix, MatrixGenerator};
+/// let scaling = MatrixGenerator::new(|[a, b]| Matrix::new(vec![vec![a, 0.0], vec![0.0, b]]));
+/// let grid = scaling.evaluate_grid(&[vec![1.0, 2.0], vec![3.0, 4.0, 5.0]]);
+/// assert_eq!(grid.len(), 6);
+/// assert_eq!(grid[1], ([1.0, 4.0], Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 4.0]])));
+/// assert_eq!(scaling.cached(), 6);
+/// ```
+pub struct MatrixGenerator<T, const P: usize> {
+	family: Box<dyn Fn([f64; P]) -> Matrix<T>>,
+	cache: RefCell<HashMap<[u64; P], Matrix<T>>>
+}
+
+impl<T, const P: usize> MatrixGenerator<T, P> where T: Clone {
+
+	/// Constructs a generator for the family built by `family`.
+	pub fn new(family: impl Fn([f64; P]) -> Matrix<T> + 'static) -> Self {
+		MatrixGenerator { family: Box::new(family), cache: RefCell::new(HashMap::new()) }
+	}
+
+	/// Returns the member of the family at `params`, building it only if it
+	/// is not already cached.
+	pub fn evaluate(&self, params: [f64; P]) -> Matrix<T> {
+		let key = params.map(f64::to_bits);
+		if let Some(matrix) = self.cache.borrow().get(&key) {
+			return matrix.clone();
+		}
+		let matrix = (self.family)(params);
+		self.cache.borrow_mut().insert(key, matrix.clone());
+		matrix
+	}
+
+	/// Returns the members of the family at each point of `points`, in
+	/// order.
+	pub fn evaluate_all(&self, points: &[[f64; P]]) -> Vec<Matrix<T>> {
+		points.iter().map(|params| self.evaluate(*params)).collect()
+	}
+
+	/// Returns the members of the family over the Cartesian product of the
+	/// values in `axes`, the `k`th of which lists the values of the `k`th
+	/// parameter, paired with their parameters.
+	///
+	/// Points are ordered lexicographically, with the last parameter varying
+	/// fastest.
+	pub fn evaluate_grid(&self, axes: &[Vec<f64>; P]) -> Vec<([f64; P], Matrix<T>)> {
+		let total = axes.iter().map(|axis| axis.len()).product();
+		(0..total).map(|mut index| {
+			let mut params = [0.0; P];
+			for (param, axis) in params.iter_mut().zip(axes.iter()).rev() {
+				*param = axis[index % axis.len()];
+				index /= axis.len();
+			}
+			(params, self.evaluate(params))
+		}).collect()
+	}
+
+	/// Returns the number of cached matrices.
+	pub fn cached(&self) -> usize {
+		self.cache.borrow().len()
+	}
+
+	/// Empties the cache.
+	pub fn clear_cache(&self) {
+		self.cache.borrow_mut().clear();
+	}
+
+}
+
+impl MatrixGenerator<f64, 1> {
+
+	/// Returns the family of 3x3 homogeneous matrices rotating the plane
+	/// counterclockwise by θ radians; see [`Matrix::rotation_2d`].
+	pub fn rotation() -> Self {
+		MatrixGenerator::new(|[theta]| Matrix::<f64>::rotation_2d(Angle::from_radians(theta)))
+	}
+
+}
+
+impl MatrixGenerator<f64, 2> {
+
+	/// Returns the family of plane-stress stiffness matrices of an isotropic
+	/// material with Young's modulus E and Poisson's ratio ν, relating the
+	/// strains (εxx, εyy, γxy) to the stresses (σxx, σyy, τxy).
+	///
+	/// ```
+	/// # use hebrides::linal::MatrixGenerator;
+	/// let d = MatrixGenerator::stiffness().evaluate([200e9, 0.3]);
+	/// assert!((d.rows[0][1] / d.rows[0][0] - 0.3).abs() < 1e-12);
+	/// ```
+	pub fn stiffness() -> Self {
+		MatrixGenerator::new(|[e, nu]| {
+			let scale = e / (1.0 - nu * nu);
+			Matrix::new(vec![
+				vec![scale, scale * nu, 0.0],
+				vec![scale * nu, scale, 0.0],
+				vec![0.0, 0.0, scale * (1.0 - nu) / 2.0]
+			])
+		})
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+	use std::rc::Rc;
+	use std::cell::Cell;
+
+	#[test]
+	fn builds_each_point_once() {
+		let builds = Rc::new(Cell::new(0));
+		let counter = Rc::clone(&builds);
+		let generator = MatrixGenerator::new(move |[t]| {
+			counter.set(counter.get() + 1);
+			Matrix::new(vec![vec![t, 1.0]])
+		});
+		let first = generator.evaluate_all(&[[1.0], [2.0], [1.0]]);
+		assert_eq!(first[0], first[2]);
+		assert_eq!(builds.get(), 2);
+		generator.clear_cache();
+		let _ = generator.evaluate([1.0]);
+		assert_eq!(builds.get(), 3)
+	}
+
+	#[test]
+	fn grid_orders_last_parameter_fastest() {
+		let generator = MatrixGenerator::new(|[a, b, c]| Matrix::new(vec![vec![a, b, c]]));
+		let points: Vec<[f64; 3]> = generator.evaluate_grid(&[vec![0.0, 1.0], vec![2.0], vec![3.0, 4.0]])
+											 .into_iter()
+											 .map(|(params, _)| params)
+											 .collect();
+		assert_eq!(points, vec![[0.0, 2.0, 3.0], [0.0, 2.0, 4.0], [1.0, 2.0, 3.0], [1.0, 2.0, 4.0]])
+	}
+
+	#[test]
+	fn rotations_compose() {
+		let rotation = MatrixGenerator::rotation();
+		let product = rotation.evaluate([0.4]) * rotation.evaluate([0.7]);
+		let direct = rotation.evaluate([1.1]);
+		assert!(product.rows.iter().flatten().zip(direct.rows.iter().flatten()).all(|(a, b)| (a - b).abs() < 1e-12))
+	}
+
+	#[test]
+	fn empty_axis_gives_empty_grid() {
+		let generator = MatrixGenerator::new(|[a, b]| Matrix::new(vec![vec![a, b]]));
+		assert!(generator.evaluate_grid(&[vec![1.0], vec![]]).is_empty())
+	}
+
+}
@@ -0,0 +1,318 @@
+//! Runtime-dispatched numeric kernels.
+//!
+//! The innermost loops of the float routines in `hebrides` go through the
+//! [`Kernels`] table for their element type. The table is filled in once per
+//! process with the widest SIMD implementation the running CPU supports, so a
+//! single binary uses AVX-512, AVX2, or NEON where available without being
+//! compiled with `target-cpu` flags.
+
+use std::ops::{Add, Mul};
+use std::sync::OnceLock;
+use crate::Semiring;
+
+/// SIMD instruction set used by a set of [`Kernels`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SimdLevel {
+	/// Portable scalar code.
+	Scalar,
+	/// AVX2 with FMA on x86-64.
+	Avx2,
+	/// AVX-512F on x86-64.
+	Avx512,
+	/// NEON on AArch64.
+	Neon
+}
+
+impl SimdLevel {
+
+	/// Returns whether or not the running CPU supports `self`.
+	pub fn is_supported(self) -> bool {
+		match self {
+			SimdLevel::Scalar => true,
+			#[cfg(target_arch = "x86_64")]
+			SimdLevel::Avx2 => is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma"),
+			#[cfg(target_arch = "x86_64")]
+			SimdLevel::Avx512 => is_x86_feature_detected!("avx512f"),
+			#[cfg(target_arch = "aarch64")]
+			SimdLevel::Neon => std::arch::is_aarch64_feature_detected!("neon"),
+			#[allow(unreachable_patterns)]
+			_ => false
+		}
+	}
+
+	/// Returns the widest [`SimdLevel`] supported by the running CPU.
+	///
+	/// Detection only happens on the first call; later calls are free. With
+	/// the `strict` feature enabled this is always [`SimdLevel::Scalar`], as
+	/// vectorized reductions sum in a hardware-dependent order.
+	pub fn detect() -> SimdLevel {
+		if cfg!(feature = "strict") {
+			return SimdLevel::Scalar;
+		}
+		static DETECTED: OnceLock<SimdLevel> = OnceLock::new();
+		*DETECTED.get_or_init(|| {
+			[SimdLevel::Avx512, SimdLevel::Avx2, SimdLevel::Neon].into_iter()
+																 .find(|level| level.is_supported())
+																 .unwrap_or(SimdLevel::Scalar)
+		})
+	}
+
+}
+
+/// Table of kernel implementations over T for a single [`SimdLevel`].
+#[derive(Debug, Copy, Clone)]
+pub struct Kernels<T: 'static> {
+	level: SimdLevel,
+	dot: fn(&[T], &[T]) -> T,
+	axpy: fn(T, &[T], &mut [T])
+}
+
+impl<T> Kernels<T> {
+
+	/// Returns the instruction set used by `self`.
+	pub fn level(&self) -> SimdLevel {
+		self.level
+	}
+
+	/// Returns the dot product of `a` and `b`.
+	///
+	/// # Panics
+	/// Panics if `a` and `b` have different lengths.
+	pub fn dot(&self, a: &[T], b: &[T]) -> T {
+		if a.len() != b.len() {
+			panic!("Slices must be of the same length to take their dot product")
+		}
+		(self.dot)(a, b)
+	}
+
+	/// Adds `alpha` times `x` to `y` in place.
+	///
+	/// # Panics
+	/// Panics if `x` and `y` have different lengths.
+	pub fn axpy(&self, alpha: T, x: &[T], y: &mut [T]) {
+		if x.len() != y.len() {
+			panic!("Slices must be of the same length to be accumulated")
+		}
+		(self.axpy)(alpha, x, y)
+	}
+
+}
+
+/// Element types with runtime-dispatched [`Kernels`].
+pub trait Dispatch: Sized + 'static {
+
+	/// Returns the kernels for [`SimdLevel::detect`], or, with the `tune`
+	/// feature enabled, for the tuned variant once [`crate::tune`] has cached
+	/// one.
+	fn kernels() -> Kernels<Self>;
+
+	/// Returns the kernels for `level`, or `None` if the running CPU does not
+	/// support it.
+	fn kernels_for(level: SimdLevel) -> Option<Kernels<Self>>;
+
+}
+
+fn scalar_dot<T>(a: &[T], b: &[T]) -> T where T: Semiring {
+	T::sum_of(a.iter().zip(b.iter()).map(|(x, y)| *x * *y))
+}
+
+fn scalar_axpy<T>(alpha: T, x: &[T], y: &mut [T]) where T: Copy + Mul<Output=T> + Add<Output=T> {
+	for (e, x) in y.iter_mut().zip(x.iter()) {
+		*e = *e + alpha * *x;
+	}
+}
+
+/// Defines safe entry points for the `$feature` implementations of the dot
+/// and axpy kernels over `$t`, vectorized over `$lanes` lanes.
+///
+/// The entry points are only ever stored in a [`Kernels`] table after
+/// [`SimdLevel::is_supported`] has confirmed `$feature` at runtime.
+macro_rules! simd_kernels {
+	(
+		$feature:literal, $t:ty, $lanes:literal, $dot:ident, $axpy:ident,
+		splat: $splat:path, load: $load:path, store: $store:path, fma: $fma:path, sum: $sum:expr
+	) => {
+		pub(super) fn $dot(a: &[$t], b: &[$t]) -> $t {
+			#[target_feature(enable = $feature)]
+			unsafe fn kernel(a: &[$t], b: &[$t]) -> $t {
+				let chunks = a.len() / $lanes;
+				let mut acc = $splat(0.0);
+				for k in 0..chunks {
+					// SAFETY: `k * $lanes + $lanes <= a.len() == b.len()`.
+					unsafe {
+						acc = $fma(acc, $load(a.as_ptr().add(k * $lanes)), $load(b.as_ptr().add(k * $lanes)));
+					}
+				}
+				let tail: $t = a[chunks * $lanes..].iter().zip(b[chunks * $lanes..].iter()).map(|(x, y)| x * y).sum();
+				$sum(acc) + tail
+			}
+			// SAFETY: only reachable once `$feature` has been detected.
+			unsafe { kernel(a, b) }
+		}
+
+		pub(super) fn $axpy(alpha: $t, x: &[$t], y: &mut [$t]) {
+			#[target_feature(enable = $feature)]
+			unsafe fn kernel(alpha: $t, x: &[$t], y: &mut [$t]) {
+				let chunks = x.len() / $lanes;
+				let alpha_v = $splat(alpha);
+				for k in 0..chunks {
+					// SAFETY: `k * $lanes + $lanes <= x.len() == y.len()`.
+					unsafe {
+						let p = y.as_mut_ptr().add(k * $lanes);
+						$store(p, $fma($load(p), alpha_v, $load(x.as_ptr().add(k * $lanes))));
+					}
+				}
+				for (e, x) in y[chunks * $lanes..].iter_mut().zip(x[chunks * $lanes..].iter()) {
+					*e += alpha * x;
+				}
+			}
+			// SAFETY: only reachable once `$feature` has been detected.
+			unsafe { kernel(alpha, x, y) }
+		}
+	}
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+
+	use std::arch::x86_64::*;
+
+	// x86 FMA takes (a, b, c) and computes a * b + c; reorder to (acc, x, y).
+	#[target_feature(enable = "avx2,fma")]
+	fn fma_pd(acc: __m256d, x: __m256d, y: __m256d) -> __m256d { _mm256_fmadd_pd(x, y, acc) }
+	#[target_feature(enable = "avx2,fma")]
+	fn fma_ps(acc: __m256, x: __m256, y: __m256) -> __m256 { _mm256_fmadd_ps(x, y, acc) }
+	#[target_feature(enable = "avx512f")]
+	fn fma512_pd(acc: __m512d, x: __m512d, y: __m512d) -> __m512d { _mm512_fmadd_pd(x, y, acc) }
+	#[target_feature(enable = "avx512f")]
+	fn fma512_ps(acc: __m512, x: __m512, y: __m512) -> __m512 { _mm512_fmadd_ps(x, y, acc) }
+
+	#[target_feature(enable = "avx2")]
+	fn sum_pd(v: __m256d) -> f64 {
+		let mut lanes = [0.0; 4];
+		// SAFETY: `lanes` holds exactly one vector.
+		unsafe { _mm256_storeu_pd(lanes.as_mut_ptr(), v) };
+		lanes.iter().sum()
+	}
+
+	#[target_feature(enable = "avx2")]
+	fn sum_ps(v: __m256) -> f32 {
+		let mut lanes = [0.0; 8];
+		// SAFETY: `lanes` holds exactly one vector.
+		unsafe { _mm256_storeu_ps(lanes.as_mut_ptr(), v) };
+		lanes.iter().sum()
+	}
+
+	simd_kernels!("avx2,fma", f64, 4, dot_f64_avx2, axpy_f64_avx2,
+				  splat: _mm256_set1_pd, load: _mm256_loadu_pd, store: _mm256_storeu_pd, fma: fma_pd, sum: sum_pd);
+	simd_kernels!("avx2,fma", f32, 8, dot_f32_avx2, axpy_f32_avx2,
+				  splat: _mm256_set1_ps, load: _mm256_loadu_ps, store: _mm256_storeu_ps, fma: fma_ps, sum: sum_ps);
+	simd_kernels!("avx512f", f64, 8, dot_f64_avx512, axpy_f64_avx512,
+				  splat: _mm512_set1_pd, load: _mm512_loadu_pd, store: _mm512_storeu_pd, fma: fma512_pd, sum: _mm512_reduce_add_pd);
+	simd_kernels!("avx512f", f32, 16, dot_f32_avx512, axpy_f32_avx512,
+				  splat: _mm512_set1_ps, load: _mm512_loadu_ps, store: _mm512_storeu_ps, fma: fma512_ps, sum: _mm512_reduce_add_ps);
+
+}
+
+#[cfg(target_arch = "aarch64")]
+mod arm {
+
+	use std::arch::aarch64::*;
+
+	simd_kernels!("neon", f64, 2, dot_f64_neon, axpy_f64_neon,
+				  splat: vdupq_n_f64, load: vld1q_f64, store: vst1q_f64, fma: vfmaq_f64, sum: vaddvq_f64);
+	simd_kernels!("neon", f32, 4, dot_f32_neon, axpy_f32_neon,
+				  splat: vdupq_n_f32, load: vld1q_f32, store: vst1q_f32, fma: vfmaq_f32, sum: vaddvq_f32);
+
+}
+
+/// Implements [`Dispatch`] for `$t` from its per-level kernel entry points.
+macro_rules! impl_dispatch {
+	($t:ty, avx2: ($avx2_dot:ident, $avx2_axpy:ident), avx512: ($avx512_dot:ident, $avx512_axpy:ident), neon: ($neon_dot:ident, $neon_axpy:ident)) => {
+		impl Dispatch for $t {
+
+			fn kernels() -> Kernels<$t> {
+				#[cfg(feature = "tune")]
+				if let Some(parameters) = crate::tune::cached() {
+					return parameters.kernels();
+				}
+				static KERNELS: OnceLock<Kernels<$t>> = OnceLock::new();
+				*KERNELS.get_or_init(|| <$t>::kernels_for(SimdLevel::detect()).unwrap())
+			}
+
+			fn kernels_for(level: SimdLevel) -> Option<Kernels<$t>> {
+				if !level.is_supported() {
+					return None;
+				}
+				let (dot, axpy): (fn(&[$t], &[$t]) -> $t, fn($t, &[$t], &mut [$t])) = match level {
+					#[cfg(target_arch = "x86_64")]
+					SimdLevel::Avx2 => (x86::$avx2_dot, x86::$avx2_axpy),
+					#[cfg(target_arch = "x86_64")]
+					SimdLevel::Avx512 => (x86::$avx512_dot, x86::$avx512_axpy),
+					#[cfg(target_arch = "aarch64")]
+					SimdLevel::Neon => (arm::$neon_dot, arm::$neon_axpy),
+					_ => (scalar_dot, scalar_axpy)
+				};
+				Some(Kernels { level, dot, axpy })
+			}
+
+		}
+	}
+}
+
+impl_dispatch!(f64, avx2: (dot_f64_avx2, axpy_f64_avx2), avx512: (dot_f64_avx512, axpy_f64_avx512), neon: (dot_f64_neon, axpy_f64_neon));
+impl_dispatch!(f32, avx2: (dot_f32_avx2, axpy_f32_avx2), avx512: (dot_f32_avx512, axpy_f32_avx512), neon: (dot_f32_neon, axpy_f32_neon));
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	const LEVELS: [SimdLevel; 4] = [SimdLevel::Scalar, SimdLevel::Avx2, SimdLevel::Avx512, SimdLevel::Neon];
+
+	#[test]
+	fn detected_level_is_supported() {
+		assert!(SimdLevel::detect().is_supported());
+		// Tests of the tune feature install their own variants concurrently.
+		if !cfg!(feature = "tune") {
+			assert_eq!(f64::kernels().level(), SimdLevel::detect())
+		}
+	}
+
+	#[test]
+	fn every_supported_level_agrees_with_scalar() {
+		// Lengths straddling every lane width exercise the scalar tails.
+		for n in [0, 1, 3, 7, 8, 15, 16, 17, 100] {
+			let a: Vec<f64> = (0..n).map(|i| (i as f64 * 0.37).sin()).collect();
+			let b: Vec<f64> = (0..n).map(|i| (i as f64 * 0.11).cos()).collect();
+			let expected_dot = scalar_dot(&a, &b);
+			let mut expected_axpy = b.clone();
+			scalar_axpy(-1.5, &a, &mut expected_axpy);
+			for kernels in LEVELS.iter().filter_map(|level| f64::kernels_for(*level)) {
+				assert!((kernels.dot(&a, &b) - expected_dot).abs() < 1e-12);
+				let mut y = b.clone();
+				kernels.axpy(-1.5, &a, &mut y);
+				assert!(y.iter().zip(expected_axpy.iter()).all(|(p, q)| (p - q).abs() < 1e-12));
+			}
+			let a32: Vec<f32> = a.iter().map(|x| *x as f32).collect();
+			let b32: Vec<f32> = b.iter().map(|x| *x as f32).collect();
+			for kernels in LEVELS.iter().filter_map(|level| f32::kernels_for(*level)) {
+				assert!((kernels.dot(&a32, &b32) - scalar_dot(&a32, &b32)).abs() < 1e-4);
+			}
+		}
+	}
+
+	#[test]
+	fn unsupported_levels_are_refused() {
+		for level in LEVELS {
+			assert_eq!(f32::kernels_for(level).is_some(), level.is_supported());
+		}
+	}
+
+	#[test]
+	#[should_panic]
+	fn mismatched_lengths() {
+		let _ = f64::kernels().dot(&[1.0, 2.0], &[1.0]);
+	}
+
+}
@@ -0,0 +1,134 @@
+//! Low-rank corrections.
+//!
+//! Solving and inverting matrices that differ from an already factorized or
+//! inverted matrix by a low-rank term, without starting over.
+
+use super::{Vector, Matrix, Solver};
+
+/// Solves (A + UCV) x = `b` for x using the Woodbury identity, given a
+/// factorization `a` of A.
+///
+/// For an n x n matrix A and a rank k correction this costs k + 1 solves
+/// with `a` plus O(n·k²) work, rather than a fresh O(n³) factorization.
+/// Returns `None` if A, C, or the k x k capacitance matrix C⁻¹ + VA⁻¹U is
+/// singular.
+///
+/// ```
+/// # use hebrides::linal::{Vector, Matrix, woodbury_solve};
+/// let a = Matrix::new(vec![vec![2.0, 0.0], vec![0.0, 4.0]]);
+/// let u = Matrix::new(vec![vec![1.0], vec![1.0]]);
+/// let c = Matrix::new(vec![vec![2.0]]);
+/// let v = Matrix::new(vec![vec![1.0, 1.0]]);
+/// let x = woodbury_solve(&a.lu(), &u, &c, &v, &Vector::new(vec![4.0, 6.0])).unwrap();
+/// assert!((x[0] - 0.6).abs() < 1e-15 && (x[1] - 0.8).abs() < 1e-15);
+/// ```
+///
+/// # Panics
+/// Panics if `u` is not n x k, `c` is not k x k, `v` is not k x n, or `b`
+/// does not have n components.
+pub fn woodbury_solve<S: Solver>(a: &S, u: &Matrix<f64>, c: &Matrix<f64>, v: &Matrix<f64>, b: &Vector<f64>) -> Option<Vector<f64>> {
+	let (n, k) = (a.dim(), c.dims.num_rows);
+	if u.dims.num_rows != n || u.dims.num_cols != k || c.dims.num_cols != k || v.dims.num_rows != k || v.dims.num_cols != n || b.dim != n {
+		panic!("Correction factors must have compatible dimensions")
+	}
+	let y = a.solve(b)?;
+	let mut z = Vec::with_capacity(k);
+	for col in &u.cols {
+		z.push(a.solve(&Vector::new(col.clone()))?);
+	}
+	let c_inverse = c.inverse()?;
+	let capacitance = Matrix::new((0..k).map(|i| (0..k).map(|j| {
+		c_inverse.rows[i][j] + v.rows[i].iter().zip(z[j].components.iter()).map(|(p, q)| p * q).sum::<f64>()
+	}).collect()).collect());
+	let vy = Vector::new(v.rows.iter().map(|row| row.iter().zip(y.components.iter()).map(|(p, q)| p * q).sum()).collect());
+	let t = capacitance.solve(&vy)?;
+	Some(Vector::new((0..n).map(|i| y[i] - (0..k).map(|j| z[j][i] * t[j]).sum::<f64>()).collect()))
+}
+
+/// Returns (A + `u` `v`ᵀ)⁻¹ given `a_inv` = A⁻¹, using the Sherman-Morrison
+/// formula in O(n²) time.
+///
+/// Returns `None` if the updated matrix is (numerically) singular.
+///
+/// ```
+/// # use hebrides::linal::{Vector, Matrix, sherman_morrison};
+/// let a_inv = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+/// let u = Vector::new(vec![1.0, 0.0]);
+/// let updated = sherman_morrison(&a_inv, &u, &u).unwrap();
+/// assert_eq!(updated, Matrix::new(vec![vec![0.5, 0.0], vec![0.0, 1.0]]));
+/// ```
+///
+/// # Panics
+/// Panics if `a_inv` is not square or if `u` and `v` do not have as many
+/// components as `a_inv` has rows.
+pub fn sherman_morrison(a_inv: &Matrix<f64>, u: &Vector<f64>, v: &Vector<f64>) -> Option<Matrix<f64>> {
+	let n = a_inv.dims.num_rows;
+	if !a_inv.is_square() || u.dim != n || v.dim != n {
+		panic!("Correction vectors must match the dimension of the inverse")
+	}
+	let a_inv_u: Vec<f64> = a_inv.rows.iter().map(|row| row.iter().zip(u.components.iter()).map(|(p, q)| p * q).sum()).collect();
+	let v_a_inv: Vec<f64> = a_inv.cols.iter().map(|col| col.iter().zip(v.components.iter()).map(|(p, q)| p * q).sum()).collect();
+	let correction: f64 = v.components.iter().zip(a_inv_u.iter()).map(|(p, q)| p * q).sum();
+	let denominator = 1.0 + correction;
+	if denominator.abs() <= f64::EPSILON * (1.0 + correction.abs()) {
+		return None;
+	}
+	Some(Matrix::new((0..n).map(|i| (0..n).map(|j| {
+		a_inv.rows[i][j] - a_inv_u[i] * v_a_inv[j] / denominator
+	}).collect()).collect()))
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn base() -> Matrix<f64> {
+		Matrix::new(vec![
+			vec![5.0, 1.0, 0.0, 1.0],
+			vec![1.0, 4.0, 1.0, 0.0],
+			vec![0.0, 1.0, 6.0, 2.0],
+			vec![1.0, 0.0, 2.0, 7.0]
+		])
+	}
+
+	#[test]
+	fn woodbury_matches_direct_solve() {
+		let u = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 2.0], vec![1.0, 1.0], vec![-1.0, 0.5]]);
+		let c = Matrix::new(vec![vec![2.0, 1.0], vec![0.0, 3.0]]);
+		let v = Matrix::new(vec![vec![0.5, 1.0, 0.0, 2.0], vec![1.0, 0.0, -1.0, 1.0]]);
+		let b = Vector::new(vec![1.0, 2.0, 3.0, 4.0]);
+		let direct = (base() + u.clone() * c.clone() * v.clone()).solve(&b).unwrap();
+		for x in [woodbury_solve(&base().lu(), &u, &c, &v, &b).unwrap(), woodbury_solve(&base().cholesky().unwrap(), &u, &c, &v, &b).unwrap()] {
+			assert!((0..4).all(|i| (x[i] - direct[i]).abs() < 1e-12));
+		}
+	}
+
+	#[test]
+	fn sherman_morrison_matches_direct_inverse() {
+		let (u, v) = (Vector::new(vec![1.0, -1.0, 2.0, 0.0]), Vector::new(vec![0.0, 3.0, 1.0, 1.0]));
+		let outer = Matrix::new((0..4).map(|i| (0..4).map(|j| u[i] * v[j]).collect()).collect());
+		let updated = sherman_morrison(&base().inverse().unwrap(), &u, &v).unwrap();
+		let direct = (base() + outer).inverse().unwrap();
+		assert!(updated.rows.iter().flatten().zip(direct.rows.iter().flatten()).all(|(a, b)| (a - b).abs() < 1e-12))
+	}
+
+	#[test]
+	fn singular_updates_are_rejected() {
+		let identity = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+		let (u, v) = (Vector::new(vec![1.0, 0.0]), Vector::new(vec![-1.0, 0.0]));
+		assert!(sherman_morrison(&identity, &u, &v).is_none());
+		let b = Vector::new(vec![1.0, 1.0]);
+		let (column, row) = (Matrix::new(vec![vec![1.0], vec![0.0]]), Matrix::new(vec![vec![-1.0, 0.0]]));
+		assert!(woodbury_solve(&identity.lu(), &column, &Matrix::new(vec![vec![1.0]]), &row, &b).is_none())
+	}
+
+	#[test]
+	#[should_panic]
+	fn mismatched_correction() {
+		let identity = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+		let u = Matrix::new(vec![vec![1.0], vec![0.0]]);
+		let _ = woodbury_solve(&identity.lu(), &u, &Matrix::new(vec![vec![1.0]]), &u, &Vector::new(vec![1.0, 1.0]));
+	}
+
+}
@@ -0,0 +1,368 @@
+//! Least squares.
+//!
+//! Solvers for overdetermined systems Ax ≈ b, including the errors-in-variables
+//! setting where both A and b are noisy, and regression diagnostics for
+//! ordinary least squares fits.
+
+use super::{Vector, Matrix};
+
+/// A dense matrix as a list of columns or rows.
+type Lines = Vec<Vec<f64>>;
+
+/// The thin QR factors of `a`, Q as m x n columns and R as n x n rows, or
+/// `None` if `a` does not have full column rank.
+fn thin_qr(a: &Matrix<f64>) -> Option<(Lines, Lines)> {
+	let (m, n) = (a.dims.num_rows, a.dims.num_cols);
+	let qr = a.qr();
+	let (q, r) = (qr.q(), qr.r());
+	let r: Lines = r.rows[..n].iter().map(|row| row[..n].to_vec()).collect();
+	let largest = (0..n).fold(0.0_f64, |max, j| max.max(r[j][j].abs()));
+	if (0..n).any(|j| r[j][j].abs() <= largest * m.max(n) as f64 * f64::EPSILON) {
+		return None;
+	}
+	Some((q.cols[..n].to_vec(), r))
+}
+
+/// Solves the upper triangular system `r` x = `y` by back substitution.
+fn back_substitute(r: &[Vec<f64>], y: &[f64]) -> Vec<f64> {
+	let n = r.len();
+	let mut x = vec![0.0; n];
+	for i in (0..n).rev() {
+		let sum: f64 = (i + 1..n).map(|j| r[i][j] * x[j]).sum();
+		x[i] = (y[i] - sum) / r[i][i];
+	}
+	x
+}
+
+/// Solves Ax ≈ `b` in the ordinary least squares sense, minimizing
+/// ‖Ax - b‖, by Householder [`QR`](super::QR) factorization.
+///
+/// Returns `None` if `a` does not have full column rank, in which case the
+/// minimizer is not unique; [`Matrix::pinv`] picks the one of least norm.
+///
+/// ```
+/// # use hebrides::linal::{Vector, Matrix, lstsq};
+/// let a = Matrix::new(vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]]);
+/// let x = lstsq(&a, &Vector::new(vec![1.0, 2.0, 2.0])).unwrap();
+/// assert!((x[0] - 7.0 / 6.0).abs() < 1e-12 && (x[1] - 0.5).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+/// Panics if `b` does not have as many components as `a` has rows, or if
+/// `a` has fewer rows than columns.
+pub fn lstsq(a: &Matrix<f64>, b: &Vector<f64>) -> Option<Vector<f64>> {
+	let (m, n) = (a.dims.num_rows, a.dims.num_cols);
+	if b.dim != m {
+		panic!("Right-hand side must have as many components as the Matrix has rows")
+	}
+	if m < n {
+		panic!("Least squares needs at least as many equations as unknowns")
+	}
+	let (q, r) = thin_qr(a)?;
+	let qtb: Vec<f64> = q.iter().map(|col| col.iter().zip(b.iter()).map(|(x, y)| x * y).sum()).collect();
+	Some(Vector::new(back_substitute(&r, &qtb)))
+}
+
+/// An ordinary least squares fit with the diagnostics of linear regression,
+/// as computed by [`lstsq_diagnostics`].
+///
+/// The standard errors assume independent errors of equal variance,
+/// estimated from the residuals with m - n degrees of freedom.
+#[derive(Debug, Clone)]
+pub struct LeastSquaresFit {
+	coefficients: Vector<f64>,
+	residuals: Vector<f64>,
+	residual_variance: f64,
+	standard_errors: Vector<f64>,
+	leverage: Vector<f64>,
+	variance_inflation: Vector<f64>,
+	condition_number: f64
+}
+
+impl LeastSquaresFit {
+
+	/// Returns the fitted coefficients x.
+	pub fn coefficients(&self) -> &Vector<f64> {
+		&self.coefficients
+	}
+
+	/// Returns the residuals b - Ax.
+	pub fn residuals(&self) -> &Vector<f64> {
+		&self.residuals
+	}
+
+	/// Returns the unbiased estimate ‖b - Ax‖²/(m - n) of the error variance.
+	pub fn residual_variance(&self) -> f64 {
+		self.residual_variance
+	}
+
+	/// Returns the standard error of each coefficient, the square root of
+	/// the diagonal of σ²(AᵀA)⁻¹.
+	pub fn standard_errors(&self) -> &Vector<f64> {
+		&self.standard_errors
+	}
+
+	/// Returns the leverage of each observation, the diagonal of the hat
+	/// matrix A(AᵀA)⁻¹Aᵀ. Leverages lie in [0, 1] and sum to n; an
+	/// observation with leverage well above n/m pulls strongly on the fit.
+	pub fn leverage(&self) -> &Vector<f64> {
+		&self.leverage
+	}
+
+	/// Returns the variance inflation factor of each column, 1/(1 - R²)
+	/// where R² measures how well that column is predicted by the others
+	/// and a constant. A column that is itself constant, such as an
+	/// intercept, has no defined factor and reports NaN.
+	pub fn variance_inflation(&self) -> &Vector<f64> {
+		&self.variance_inflation
+	}
+
+	/// Returns the columns whose variance inflation factor exceeds
+	/// `threshold`, with their factors, from most to least inflated. A
+	/// threshold of 5 or 10 is conventional.
+	pub fn collinear(&self, threshold: f64) -> Vec<(usize, f64)> {
+		let mut report: Vec<(usize, f64)> = self.variance_inflation.iter().copied().enumerate().filter(|(_, v)| *v > threshold).collect();
+		report.sort_by(|a, b| b.1.total_cmp(&a.1));
+		report
+	}
+
+	/// Returns the 2-norm condition number of A, the ratio of its largest
+	/// to its smallest singular value.
+	pub fn condition_number(&self) -> f64 {
+		self.condition_number
+	}
+
+}
+
+/// Returns the variance inflation factor of each column of `a`.
+fn variance_inflation(a: &Matrix<f64>) -> Vector<f64> {
+	let (m, n) = (a.dims.num_rows, a.dims.num_cols);
+	let varying: Vec<bool> = a.cols.iter().map(|col| col.iter().any(|e| *e != col[0])).collect();
+	Vector::from_fn(n, |j| {
+		if !varying[j] {
+			return f64::NAN;
+		}
+		let column = &a.cols[j];
+		let mean = column.iter().sum::<f64>() / m as f64;
+		let total: f64 = column.iter().map(|e| (e - mean) * (e - mean)).sum();
+		let others: Vec<usize> = (0..n).filter(|k| *k != j && varying[*k]).collect();
+		let design = Matrix::new((0..m).map(|i| {
+			std::iter::once(1.0).chain(others.iter().map(|k| a.rows[i][*k])).collect()
+		}).collect());
+		let fit = design.pinv(design.svd().default_tolerance()) * Vector::new(column.clone());
+		let predicted = design * fit;
+		let residual: f64 = column.iter().zip(predicted.iter()).map(|(e, p)| (e - p) * (e - p)).sum();
+		total / residual
+	})
+}
+
+/// Fits Ax ≈ `b` by ordinary least squares as [`lstsq`] does, and returns
+/// the fit together with standard errors, leverages, variance inflation
+/// factors, and the condition number of `a`.
+///
+/// Returns `None` if `a` does not have full column rank.
+///
+/// ```
+/// # use hebrides::linal::{Vector, Matrix, lstsq_diagnostics};
+/// // An intercept and two nearly collinear predictors.
+/// let a = Matrix::new((0..8).map(|i| {
+///     let x = i as f64;
+///     vec![1.0, x, 2.0 * x + if i % 2 == 0 { 0.1 } else { -0.1 }]
+/// }).collect());
+/// let b = Vector::from_fn(8, |i| 1.0 + i as f64 + 0.05 * (i % 3) as f64);
+/// let fit = lstsq_diagnostics(&a, &b).unwrap();
+/// let report = fit.collinear(10.0);
+/// assert_eq!(report.len(), 2);
+/// assert!(fit.variance_inflation()[0].is_nan());
+/// assert!((fit.leverage().sum() - 3.0).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+/// Panics if `b` does not have as many components as `a` has rows, or if
+/// `a` does not have more rows than columns.
+pub fn lstsq_diagnostics(a: &Matrix<f64>, b: &Vector<f64>) -> Option<LeastSquaresFit> {
+	let (m, n) = (a.dims.num_rows, a.dims.num_cols);
+	if b.dim != m {
+		panic!("Right-hand side must have as many components as the Matrix has rows")
+	}
+	if m <= n {
+		panic!("Regression diagnostics need more observations than coefficients")
+	}
+	let (q, r) = thin_qr(a)?;
+	let qtb: Vec<f64> = q.iter().map(|col| col.iter().zip(b.iter()).map(|(x, y)| x * y).sum()).collect();
+	let coefficients = Vector::new(back_substitute(&r, &qtb));
+	let residuals = b.clone() - a.clone() * coefficients.clone();
+	let residual_variance = residuals.iter().map(|e| e * e).sum::<f64>() / (m - n) as f64;
+	// The columns of R⁻¹ solve R y = eⱼ, and (AᵀA)⁻¹ = R⁻¹R⁻ᵀ.
+	let r_inverse: Lines = (0..n).map(|j| {
+		back_substitute(&r, &(0..n).map(|i| if i == j { 1.0 } else { 0.0 }).collect::<Vec<f64>>())
+	}).collect();
+	let standard_errors = Vector::from_fn(n, |i| {
+		(residual_variance * r_inverse.iter().map(|col| col[i] * col[i]).sum::<f64>()).sqrt()
+	});
+	let leverage = Vector::from_fn(m, |i| q.iter().map(|col| col[i] * col[i]).sum());
+	let singular_values = Matrix::new(r).svd().singular_values().to_vec();
+	let condition_number = singular_values[0] / singular_values[n - 1];
+	Some(LeastSquaresFit {
+		coefficients,
+		residuals,
+		residual_variance,
+		standard_errors,
+		leverage,
+		variance_inflation: variance_inflation(a),
+		condition_number
+	})
+}
+
+/// Solves Ax ≈ `b` in the total least squares sense, minimizing the
+/// Frobenius norm of the corrections to both `a` and `b`.
+///
+/// The solution is read off the right singular vector of [A | b] belonging
+/// to its smallest singular value. Returns `None` if that vector has no
+/// component along `b`, in which case no total least squares solution
+/// exists.
+///
+/// ```
+/// # use hebrides::linal::{Vector, Matrix, tls};
+/// let a = Matrix::new(vec![vec![1.0], vec![2.0], vec![3.0]]);
+/// let x = tls(&a, &Vector::new(vec![2.0, 4.0, 6.0])).unwrap();
+/// assert!((x[0] - 2.0).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+/// Panics if `b` does not have as many components as `a` has rows, or if
+/// `a` has fewer rows than columns plus one.
+pub fn tls(a: &Matrix<f64>, b: &Vector<f64>) -> Option<Vector<f64>> {
+	let n = a.dims.num_cols;
+	tls_scaled(a, b, &Vector::new(vec![1.0; a.dims.num_rows]), &Vector::new(vec![1.0; n + 1]))
+}
+
+/// Solves Ax ≈ `b` in the weighted and scaled total least squares sense.
+///
+/// Row `i` of the system is weighted by `row_weights[i]`, and column `j` of
+/// `a` is multiplied by `column_scales[j]` before fitting, with the final
+/// entry of `column_scales` applying to `b`. Scaling each column by the
+/// reciprocal of the standard deviation of its noise recovers the maximum
+/// likelihood estimate when the noise is independent across columns; a very
+/// small scale on `b` approaches ordinary least squares.
+///
+/// Returns `None` if no total least squares solution exists.
+///
+/// # Panics
+/// Panics if `b` or `row_weights` do not have as many components as `a` has
+/// rows, if `column_scales` does not have one more component than `a` has
+/// columns, if any weight or scale is not positive, or if `a` has fewer rows
+/// than columns plus one.
+pub fn tls_scaled(a: &Matrix<f64>, b: &Vector<f64>, row_weights: &Vector<f64>, column_scales: &Vector<f64>) -> Option<Vector<f64>> {
+	let (m, n) = (a.dims.num_rows, a.dims.num_cols);
+	if b.dim != m || row_weights.dim != m || column_scales.dim != n + 1 {
+		panic!("Right-hand side, weights, and scales must match the dimensions of the Matrix")
+	}
+	if m < n + 1 {
+		panic!("Total least squares needs more equations than unknowns")
+	}
+	if row_weights.components.iter().chain(column_scales.components.iter()).any(|w| *w <= 0.0) {
+		panic!("Weights and scales must be positive")
+	}
+	let augmented = Matrix::new((0..m).map(|i| {
+		let w = row_weights[i].sqrt();
+		(0..=n).map(|j| {
+			let entry = if j < n { a.rows[i][j] } else { b[i] };
+			w * entry * column_scales[j]
+		}).collect()
+	}).collect());
+	let svd = augmented.svd();
+	let v = &svd.v().cols[n];
+	let last = v[n] * column_scales[n];
+	if last.abs() <= f64::EPSILON * v.iter().fold(0.0_f64, |max, e| max.max(e.abs())) {
+		return None;
+	}
+	Some(Vector::new((0..n).map(|j| -v[j] * column_scales[j] / last).collect()))
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn consistent_system_is_solved_exactly() {
+		let a = Matrix::new(vec![vec![1.0, 2.0], vec![3.0, 1.0], vec![0.0, 1.0], vec![2.0, 2.0]]);
+		let x = tls(&a, &Vector::new(vec![5.0, 5.0, 2.0, 6.0])).unwrap();
+		assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] - 2.0).abs() < 1e-12)
+	}
+
+	#[test]
+	fn tls_fits_orthogonal_distance_line() {
+		let a = Matrix::new(vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0]]);
+		let b = Vector::new(vec![1.1, 1.9, 3.2, 3.8]);
+		let x = tls(&a, &b).unwrap();
+		let residual = |slope: f64| (0..4).map(|i| (b[i] - slope * a.rows[i][0]).powi(2)).sum::<f64>() / (1.0 + slope * slope);
+		assert!(residual(x[0]) <= residual(x[0] + 1e-6) && residual(x[0]) <= residual(x[0] - 1e-6))
+	}
+
+	#[test]
+	fn tiny_scale_on_b_approaches_ordinary_least_squares() {
+		let a = Matrix::new(vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0]]);
+		let b = Vector::new(vec![1.1, 1.9, 3.2, 3.8]);
+		let ols = (0..4).map(|i| a.rows[i][0] * b[i]).sum::<f64>() / 30.0;
+		let x = tls_scaled(&a, &b, &Vector::new(vec![1.0; 4]), &Vector::new(vec![1.0, 1e-6])).unwrap();
+		assert!((x[0] - ols).abs() < 1e-9)
+	}
+
+	#[test]
+	fn simple_regression_matches_closed_form() {
+		let xs = [0.0, 1.0, 2.0, 4.0, 7.0];
+		let b = Vector::new(vec![1.2, 2.9, 5.1, 9.2, 14.6]);
+		let a = Matrix::new(xs.iter().map(|x| vec![1.0, *x]).collect());
+		let fit = lstsq_diagnostics(&a, &b).unwrap();
+		let mean = xs.iter().sum::<f64>() / 5.0;
+		let sxx: f64 = xs.iter().map(|x| (x - mean) * (x - mean)).sum();
+		let slope = xs.iter().zip(b.iter()).map(|(x, y)| (x - mean) * y).sum::<f64>() / sxx;
+		assert!((fit.coefficients()[1] - slope).abs() < 1e-12);
+		assert!((fit.standard_errors()[1] - (fit.residual_variance() / sxx).sqrt()).abs() < 1e-12);
+		for (i, x) in xs.iter().enumerate() {
+			assert!((fit.leverage()[i] - (0.2 + (x - mean) * (x - mean) / sxx)).abs() < 1e-12);
+		}
+		assert!(fit.residuals().iter().sum::<f64>().abs() < 1e-12);
+		assert_eq!(lstsq(&a, &b).unwrap(), fit.coefficients().clone())
+	}
+
+	#[test]
+	fn variance_inflation_of_two_predictors() {
+		let u = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+		let v = [2.0, 1.0, 4.0, 3.0, 6.0, 7.0];
+		let a = Matrix::new((0..6).map(|i| vec![1.0, u[i], v[i]]).collect());
+		let fit = lstsq_diagnostics(&a, &Vector::new(vec![1.0, 0.0, 2.0, 1.0, 3.0, 5.0])).unwrap();
+		let correlation = {
+			let (mu, mv) = (3.5, 23.0 / 6.0);
+			let cov: f64 = (0..6).map(|i| (u[i] - mu) * (v[i] - mv)).sum();
+			let (su, sv): (f64, f64) = ((0..6).map(|i| (u[i] - mu).powi(2)).sum(), (0..6).map(|i| (v[i] - mv).powi(2)).sum());
+			cov / (su * sv).sqrt()
+		};
+		let expected = 1.0 / (1.0 - correlation * correlation);
+		assert!((fit.variance_inflation()[1] - expected).abs() < 1e-9);
+		assert!((fit.variance_inflation()[2] - expected).abs() < 1e-9);
+		assert!(fit.condition_number() > 1.0)
+	}
+
+	#[test]
+	fn rank_deficient_least_squares() {
+		let a = Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]);
+		assert!(lstsq(&a, &Vector::new(vec![1.0, 2.0, 3.0])).is_none());
+		assert!(lstsq_diagnostics(&a, &Vector::new(vec![1.0, 2.0, 3.0])).is_none())
+	}
+
+	#[test]
+	fn unsolvable_system() {
+		let a = Matrix::new(vec![vec![0.1], vec![0.0], vec![0.0]]);
+		assert!(tls(&a, &Vector::new(vec![0.0, 1.0, 0.0])).is_none())
+	}
+
+	#[test]
+	#[should_panic]
+	fn underdetermined() {
+		let _ = tls(&Matrix::new(vec![vec![1.0, 2.0]]), &Vector::new(vec![1.0]));
+	}
+
+}
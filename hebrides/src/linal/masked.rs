@@ -0,0 +1,622 @@
+//! Masked containers for data with missing entries.
+//!
+//! [`MaskedVector`] and [`MaskedMatrix`] pair a [`Vector`] or [`Matrix`] with
+//! a validity mask, so that reductions and imputation can skip over the
+//! entries that are missing from a dataset. Boolean masks also select from
+//! plain vectors and matrices, either keeping the entries they mark or
+//! choosing elementwise between two alternatives.
+
+use std::ops::Add;
+
+use super::{Vector, Matrix};
+
+/// A [`Vector`] paired with a mask describing which of its components are valid.
+#[derive(Debug, Clone)]
+pub struct MaskedVector<T> {
+	data: Vector<T>,
+	mask: Vec<bool>
+}
+
+impl<T> MaskedVector<T> {
+
+	/// Constructs a [`MaskedVector`] from `data` and a `mask` in which `true`
+	/// marks a valid component.
+	///
+	/// # Panics
+	/// Panics if `mask` is not the same length as `data`.
+	pub fn new(data: Vector<T>, mask: Vec<bool>) -> MaskedVector<T> {
+		if data.dim != mask.len() {
+			panic!("Mask must have one entry per component of the MaskedVector")
+		}
+		Self { data, mask }
+	}
+
+	/// Returns the underlying data of `self`, including invalid components.
+	pub fn data(&self) -> &Vector<T> {
+		&self.data
+	}
+
+	/// Returns the validity mask of `self`.
+	pub fn mask(&self) -> &[bool] {
+		&self.mask
+	}
+
+	/// Returns whether or not the `index`th component of `self` is valid.
+	pub fn is_valid(&self, index: usize) -> bool {
+		self.mask[index]
+	}
+
+	/// Returns the number of valid components in `self`.
+	pub fn count(&self) -> usize {
+		self.mask.iter().filter(|valid| **valid).count()
+	}
+
+	/// Iterates over the valid components of `self`.
+	fn valid(&self) -> impl Iterator<Item=&T> {
+		self.data.components.iter()
+							.zip(self.mask.iter())
+							.filter(|pair| *pair.1)
+							.map(|pair| pair.0)
+	}
+
+}
+
+impl<T> MaskedVector<T> where T: Copy {
+
+	/// Returns the `index`th component of `self` if it exists and is valid.
+	pub fn get(&self, index: usize) -> Option<T> {
+		if *self.mask.get(index)? {
+			return Some(self.data[index]);
+		}
+		None
+	}
+
+	/// Returns a [`Vector`] with every invalid component replaced by `value`.
+	pub fn fill(&self, value: T) -> Vector<T> {
+		let components = self.data.components.iter()
+											 .zip(self.mask.iter())
+											 .map(|(e, valid)| if *valid { *e } else { value })
+											 .collect();
+		Vector::new(components)
+	}
+
+}
+
+impl<T> MaskedVector<T> where T: Copy + Add<Output=T> {
+
+	/// Sum of the valid components of `self`, or `None` if there are none.
+	pub fn sum(&self) -> Option<T> {
+		self.valid().copied().reduce(|a, b| a + b)
+	}
+
+}
+
+impl<T> MaskedVector<T> where T: Copy + PartialOrd {
+
+	/// Smallest valid component of `self`, or `None` if there are none.
+	pub fn min(&self) -> Option<T> {
+		self.valid().copied().reduce(|a, b| if b < a { b } else { a })
+	}
+
+	/// Largest valid component of `self`, or `None` if there are none.
+	pub fn max(&self) -> Option<T> {
+		self.valid().copied().reduce(|a, b| if b > a { b } else { a })
+	}
+
+}
+
+impl MaskedVector<f64> {
+
+	/// Constructs a [`MaskedVector`] in which every NaN component of `data` is
+	/// marked as invalid.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, MaskedVector};
+	/// let v = MaskedVector::from_nan(Vector::new(vec![1.0, f64::NAN, 3.0]));
+	/// assert_eq!(v.count(), 2);
+	/// assert_eq!(v.mean(), Some(2.0));
+	/// ```
+	pub fn from_nan(data: Vector<f64>) -> MaskedVector<f64> {
+		let mask = data.components.iter().map(|e| !e.is_nan()).collect();
+		Self { data, mask }
+	}
+
+	/// Mean of the valid components of `self`, or `None` if there are none.
+	pub fn mean(&self) -> Option<f64> {
+		self.sum().map(|sum| sum / self.count() as f64)
+	}
+
+	/// Returns a [`Vector`] with every invalid component replaced by the mean
+	/// of the valid components, or `None` if there are no valid components.
+	pub fn fill_mean(&self) -> Option<Vector<f64>> {
+		self.mean().map(|mean| self.fill(mean))
+	}
+
+	/// Returns a [`Vector`] with every invalid component replaced by linear
+	/// interpolation between its nearest valid neighbours.
+	///
+	/// Invalid components before the first (or after the last) valid component
+	/// take on the value of that component. Returns `None` if there are no
+	/// valid components.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, MaskedVector};
+	/// let v = MaskedVector::from_nan(Vector::new(vec![f64::NAN, 1.0, f64::NAN, 3.0]));
+	/// assert_eq!(v.fill_interpolate().unwrap(), Vector::new(vec![1.0, 1.0, 2.0, 3.0]));
+	/// ```
+	pub fn fill_interpolate(&self) -> Option<Vector<f64>> {
+		let known: Vec<usize> = (0..self.data.dim).filter(|i| self.mask[*i]).collect();
+		if known.is_empty() {
+			return None;
+		}
+		let mut components = self.data.components.clone();
+		let mut next: usize = 0;
+		for (i, component) in components.iter_mut().enumerate() {
+			if self.mask[i] {
+				next += 1;
+				continue;
+			}
+			*component = match (next.checked_sub(1).map(|k| known[k]), known.get(next)) {
+				(Some(left), Some(&right)) => {
+					let t = (i - left) as f64 / (right - left) as f64;
+					self.data[left] + t * (self.data[right] - self.data[left])
+				},
+				(Some(left), None) => self.data[left],
+				(None, Some(&right)) => self.data[right],
+				(None, None) => unreachable!()
+			};
+		}
+		Some(Vector::new(components))
+	}
+
+}
+
+impl Vector<f64> {
+
+	/// Sum of the components of `self`, ignoring NaNs.
+	pub fn nansum(&self) -> f64 {
+		self.components.iter().filter(|e| !e.is_nan()).sum()
+	}
+
+	/// Mean of the components of `self`, ignoring NaNs.
+	///
+	/// Returns NaN if every component of `self` is NaN.
+	pub fn nanmean(&self) -> f64 {
+		let count = self.components.iter().filter(|e| !e.is_nan()).count();
+		self.nansum() / count as f64
+	}
+
+}
+
+/// A [`Matrix`] paired with a mask describing which of its entries are valid.
+pub struct MaskedMatrix<T> {
+	data: Matrix<T>,
+	mask: Vec<Vec<bool>>
+}
+
+impl<T> MaskedMatrix<T> {
+
+	/// Returns the underlying data of `self`, including invalid entries.
+	pub fn data(&self) -> &Matrix<T> {
+		&self.data
+	}
+
+	/// Returns the validity mask of `self` in rows.
+	pub fn mask(&self) -> &Vec<Vec<bool>> {
+		&self.mask
+	}
+
+	/// Returns whether or not the entry at (`row`, `col`) is valid.
+	pub fn is_valid(&self, row: usize, col: usize) -> bool {
+		self.mask[row][col]
+	}
+
+	/// Returns the number of valid entries in `self`.
+	pub fn count(&self) -> usize {
+		self.mask.iter().flatten().filter(|valid| **valid).count()
+	}
+
+	/// Iterates over the valid entries of the `col`th column of `self`.
+	fn valid_in_col(&self, col: usize) -> impl Iterator<Item=&T> {
+		self.data.cols[col].iter()
+						   .enumerate()
+						   .filter(move |(i, _)| self.mask[*i][col])
+						   .map(|(_, e)| e)
+	}
+
+}
+
+impl<T> MaskedMatrix<T> where T: Copy {
+
+	/// Constructs a [`MaskedMatrix`] from `data` and a `mask` (given in rows) in
+	/// which `true` marks a valid entry.
+	///
+	/// # Panics
+	/// Panics if `mask` does not have the same dimensions as `data`.
+	pub fn new(data: Matrix<T>, mask: Vec<Vec<bool>>) -> MaskedMatrix<T> {
+		let rows_match = mask.len() == data.dims.num_rows;
+		if !rows_match || mask.iter().any(|row| row.len() != data.dims.num_cols) {
+			panic!("Mask must have the same dimensions as the MaskedMatrix")
+		}
+		Self { data, mask }
+	}
+
+	/// Returns the entry at (`row`, `col`) if it exists and is valid.
+	pub fn get(&self, row: usize, col: usize) -> Option<T> {
+		if *self.mask.get(row)?.get(col)? {
+			return Some(self.data.rows[row][col]);
+		}
+		None
+	}
+
+	/// Returns a [`Matrix`] with every invalid entry replaced by `value`.
+	pub fn fill(&self, value: T) -> Matrix<T> {
+		let rows = self.data.rows.iter()
+								 .zip(self.mask.iter())
+								 .map(|(row, mask)| {
+									 row.iter()
+										.zip(mask.iter())
+										.map(|(e, valid)| if *valid { *e } else { value })
+										.collect()
+								 })
+								 .collect();
+		Matrix::new(rows)
+	}
+
+}
+
+impl<T> MaskedMatrix<T> where T: Copy + Add<Output=T> {
+
+	/// Sum of the valid entries of `self`, or `None` if there are none.
+	pub fn sum(&self) -> Option<T> {
+		self.data.rows.iter()
+					  .flatten()
+					  .zip(self.mask.iter().flatten())
+					  .filter(|pair| *pair.1)
+					  .map(|pair| *pair.0)
+					  .reduce(|a, b| a + b)
+	}
+
+	/// Sums of the valid entries in each column of `self`.
+	pub fn col_sums(&self) -> Vec<Option<T>> {
+		(0..self.data.dims.num_cols).map(|j| self.valid_in_col(j).copied().reduce(|a, b| a + b))
+									.collect()
+	}
+
+}
+
+impl MaskedMatrix<f64> {
+
+	/// Constructs a [`MaskedMatrix`] in which every NaN entry of `data` is
+	/// marked as invalid.
+	pub fn from_nan(data: Matrix<f64>) -> MaskedMatrix<f64> {
+		let mask = data.rows.iter()
+							.map(|row| row.iter().map(|e| !e.is_nan()).collect())
+							.collect();
+		Self { data, mask }
+	}
+
+	/// Mean of the valid entries of `self`, or `None` if there are none.
+	pub fn mean(&self) -> Option<f64> {
+		self.sum().map(|sum| sum / self.count() as f64)
+	}
+
+	/// Means of the valid entries in each column of `self`.
+	pub fn col_means(&self) -> Vec<Option<f64>> {
+		(0..self.data.dims.num_cols).map(|j| {
+										let count = self.valid_in_col(j).count();
+										self.valid_in_col(j).copied()
+															.reduce(|a, b| a + b)
+															.map(|sum| sum / count as f64)
+									})
+									.collect()
+	}
+
+	/// Returns a [`Matrix`] with every invalid entry replaced by the mean of
+	/// the valid entries in its column.
+	///
+	/// Returns `None` if any column of `self` has no valid entries.
+	///
+	/// ```
+	/// # use hebrides::linal::{Matrix, MaskedMatrix};
+	/// let m = MaskedMatrix::from_nan(Matrix::new(vec![
+	///     vec![1.0, f64::NAN],
+	///     vec![f64::NAN, 4.0],
+	///     vec![3.0, 6.0]
+	/// ]));
+	/// let filled = Matrix::new(vec![
+	///     vec![1.0, 5.0],
+	///     vec![2.0, 4.0],
+	///     vec![3.0, 6.0]
+	/// ]);
+	/// assert_eq!(m.fill_col_mean().unwrap(), filled);
+	/// ```
+	pub fn fill_col_mean(&self) -> Option<Matrix<f64>> {
+		let means = self.col_means().into_iter().collect::<Option<Vec<f64>>>()?;
+		let rows = self.data.rows.iter()
+								 .zip(self.mask.iter())
+								 .map(|(row, mask)| {
+									 row.iter()
+										.zip(mask.iter())
+										.zip(means.iter())
+										.map(|((e, valid), mean)| if *valid { *e } else { *mean })
+										.collect()
+								 })
+								 .collect();
+		Some(Matrix::new(rows))
+	}
+
+}
+
+impl Matrix<f64> {
+
+	/// Sum of the entries of `self`, ignoring NaNs.
+	pub fn nansum(&self) -> f64 {
+		self.rows.iter().flatten().filter(|e| !e.is_nan()).sum()
+	}
+
+	/// Mean of the entries of `self`, ignoring NaNs.
+	///
+	/// Returns NaN if every entry of `self` is NaN.
+	pub fn nanmean(&self) -> f64 {
+		let count = self.rows.iter().flatten().filter(|e| !e.is_nan()).count();
+		self.nansum() / count as f64
+	}
+
+}
+
+impl<T> Vector<T> where T: Copy {
+
+	/// Returns the components of `self` at which `mask` is `true`, or `None`
+	/// if it is `true` nowhere.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let v = Vector::new(vec![3, -1, 4, -1, 5]);
+	/// let positive: Vec<bool> = v.iter().map(|x| *x > 0).collect();
+	/// assert_eq!(v.select(&positive), Some(Vector::new(vec![3, 4, 5])));
+	/// assert_eq!(v.filter(|x| *x > 0), v.select(&positive));
+	/// assert_eq!(v.filter(|x| *x > 9), None);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `mask` is not the same length as `self`.
+	pub fn select(&self, mask: &[bool]) -> Option<Vector<T>> {
+		if mask.len() != self.dim {
+			panic!("Mask must have one entry per component of the Vector")
+		}
+		let selected = self.components.iter().zip(mask.iter()).filter(|pair| *pair.1).map(|pair| *pair.0).collect();
+		Vector::try_new(selected).ok()
+	}
+
+	/// Returns the components of `self` satisfying `predicate`, or `None` if
+	/// none do.
+	pub fn filter(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Vector<T>> {
+		Vector::try_new(self.components.iter().copied().filter(|x| predicate(x)).collect()).ok()
+	}
+
+	/// Returns the [`Vector`] taking each component from `a` where `mask` is
+	/// `true` and from `b` where it is `false`.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let x = Vector::new(vec![-2.0, 0.5, -0.1, 3.0]);
+	/// let negative: Vec<bool> = x.iter().map(|e| *e < 0.0).collect();
+	/// let relu = Vector::choose(&negative, &Vector::zeros(4), &x);
+	/// assert_eq!(relu, Vector::new(vec![0.0, 0.5, 0.0, 3.0]));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `mask`, `a`, and `b` are not all the same length.
+	pub fn choose(mask: &[bool], a: &Vector<T>, b: &Vector<T>) -> Vector<T> {
+		if a.dim != b.dim || mask.len() != a.dim {
+			panic!("Mask and alternatives must all be of the same length")
+		}
+		Vector::from_fn(a.dim, |i| if mask[i] { a[i] } else { b[i] })
+	}
+
+}
+
+impl<T> Matrix<T> where T: Copy {
+
+	/// Returns the rows of `self` at which `mask` is `true`, or `None` if it
+	/// is `true` nowhere.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let samples = Matrix::new(vec![vec![1.0, 9.0], vec![2.0, -1.0], vec![3.0, 4.0]]);
+	/// let kept = samples.filter_rows(|row| row.iter().all(|x| *x >= 0.0)).unwrap();
+	/// assert_eq!(kept, samples.select_rows(&[true, false, true]).unwrap());
+	/// assert_eq!(kept.select_columns(&[false, true]).unwrap(), Matrix::new(vec![vec![9.0], vec![4.0]]));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `mask` does not have one entry per row of `self`.
+	pub fn select_rows(&self, mask: &[bool]) -> Option<Matrix<T>> {
+		if mask.len() != self.dims.num_rows {
+			panic!("Mask must have one entry per row of the Matrix")
+		}
+		let rows = self.rows.iter().zip(mask.iter()).filter(|pair| *pair.1).map(|pair| pair.0.clone()).collect();
+		Matrix::try_new(rows).ok()
+	}
+
+	/// Returns the columns of `self` at which `mask` is `true`, or `None` if
+	/// it is `true` nowhere.
+	///
+	/// # Panics
+	/// Panics if `mask` does not have one entry per column of `self`.
+	pub fn select_columns(&self, mask: &[bool]) -> Option<Matrix<T>> {
+		if mask.len() != self.dims.num_cols {
+			panic!("Mask must have one entry per column of the Matrix")
+		}
+		let rows = self.rows.iter().map(|row| {
+			row.iter().zip(mask.iter()).filter(|pair| *pair.1).map(|pair| *pair.0).collect()
+		}).collect();
+		Matrix::try_new(rows).ok()
+	}
+
+	/// Returns the rows of `self` satisfying `predicate`, or `None` if none
+	/// do.
+	pub fn filter_rows(&self, mut predicate: impl FnMut(&[T]) -> bool) -> Option<Matrix<T>> {
+		Matrix::try_new(self.rows.iter().filter(|row| predicate(row)).cloned().collect()).ok()
+	}
+
+	/// Returns the [`Matrix`] taking each entry from `a` where `mask` is
+	/// `true` and from `b` where it is `false`.
+	///
+	/// # Panics
+	/// Panics if `mask`, `a`, and `b` are not all of the same dimensions.
+	pub fn choose(mask: &[Vec<bool>], a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+		if a.dims != b.dims || mask.len() != a.dims.num_rows || mask.iter().any(|row| row.len() != a.dims.num_cols) {
+			panic!("Mask and alternatives must all be of the same dimensions")
+		}
+		Matrix::new(mask.iter().enumerate().map(|(i, row)| {
+			row.iter().enumerate().map(|(j, m)| if *m { a.rows[i][j] } else { b.rows[i][j] }).collect()
+		}).collect())
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	mod masked_vector {
+
+		use super::*;
+
+		#[test]
+		fn reductions_skip_invalid_components() {
+			let v = MaskedVector::new(Vector::new(vec![1, 100, 3, 4]), vec![true, false, true, true]);
+			assert_eq!(v.count(), 3);
+			assert_eq!(v.sum(), Some(8));
+			assert_eq!(v.min(), Some(1));
+			assert_eq!(v.max(), Some(4));
+			assert_eq!(v.get(1), None);
+		}
+
+		#[test]
+		fn fully_masked_reductions() {
+			let v = MaskedVector::new(Vector::new(vec![1.0, 2.0]), vec![false, false]);
+			assert_eq!(v.sum(), None);
+			assert_eq!(v.mean(), None);
+			assert!(v.fill_interpolate().is_none());
+		}
+
+		#[test]
+		fn fill() {
+			let v = MaskedVector::new(Vector::new(vec![1, 2, 3]), vec![true, false, true]);
+			assert_eq!(v.fill(0), Vector::new(vec![1, 0, 3]))
+		}
+
+		#[test]
+		fn fill_mean() {
+			let v = MaskedVector::from_nan(Vector::new(vec![1.0, f64::NAN, 5.0]));
+			assert_eq!(v.fill_mean().unwrap(), Vector::new(vec![1.0, 3.0, 5.0]))
+		}
+
+		#[test]
+		fn fill_interpolate() {
+			let v = MaskedVector::from_nan(Vector::new(vec![0.0, f64::NAN, f64::NAN, 3.0, f64::NAN]));
+			assert_eq!(v.fill_interpolate().unwrap(), Vector::new(vec![0.0, 1.0, 2.0, 3.0, 3.0]))
+		}
+
+		#[test]
+		#[should_panic]
+		fn mismatched_mask() {
+			let _ = MaskedVector::new(Vector::new(vec![1, 2]), vec![true]);
+		}
+
+		#[test]
+		fn nan_statistics() {
+			let v = Vector::new(vec![1.0, f64::NAN, 2.0, 3.0]);
+			assert_eq!(v.nansum(), 6.0);
+			assert_eq!(v.nanmean(), 2.0);
+		}
+
+	}
+
+	mod masked_matrix {
+
+		use super::*;
+
+		#[test]
+		fn reductions_skip_invalid_entries() {
+			let m = MaskedMatrix::from_nan(Matrix::new(vec![
+				vec![1.0, f64::NAN],
+				vec![3.0, 4.0]
+			]));
+			assert_eq!(m.count(), 3);
+			assert_eq!(m.sum(), Some(8.0));
+			assert_eq!(m.col_sums(), vec![Some(4.0), Some(4.0)]);
+			assert_eq!(m.col_means(), vec![Some(2.0), Some(4.0)]);
+		}
+
+		#[test]
+		fn fill() {
+			let m = MaskedMatrix::new(Matrix::new(vec![
+				vec![1, 2],
+				vec![3, 4]
+			]), vec![vec![true, false], vec![false, true]]);
+			assert_eq!(m.fill(0), Matrix::new(vec![vec![1, 0], vec![0, 4]]))
+		}
+
+		#[test]
+		fn fill_col_mean_requires_valid_columns() {
+			let m = MaskedMatrix::from_nan(Matrix::new(vec![
+				vec![1.0, f64::NAN],
+				vec![3.0, f64::NAN]
+			]));
+			assert!(m.fill_col_mean().is_none())
+		}
+
+		#[test]
+		fn nan_statistics() {
+			let m = Matrix::new(vec![
+				vec![1.0, f64::NAN],
+				vec![f64::NAN, 5.0]
+			]);
+			assert_eq!(m.nansum(), 6.0);
+			assert_eq!(m.nanmean(), 3.0);
+		}
+
+	}
+
+	mod selection {
+
+		use super::*;
+
+		#[test]
+		fn select_matches_filter() {
+			let v = Vector::new(vec![1.5, -2.0, 0.0, 7.25]);
+			let mask: Vec<bool> = v.iter().map(|x| *x != 0.0).collect();
+			assert_eq!(v.select(&mask), v.filter(|x| *x != 0.0));
+			assert_eq!(v.select(&[false; 4]), None)
+		}
+
+		#[test]
+		fn choose_matrix_entries() {
+			let a = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+			let b = Matrix::new(vec![vec![-1, -2], vec![-3, -4]]);
+			let mask = vec![vec![true, false], vec![false, true]];
+			assert_eq!(Matrix::choose(&mask, &a, &b), Matrix::new(vec![vec![1, -2], vec![-3, 4]]))
+		}
+
+		#[test]
+		fn empty_selections() {
+			let m = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+			assert!(m.select_rows(&[false, false]).is_none());
+			assert!(m.select_columns(&[false, false]).is_none());
+			assert!(m.filter_rows(|row| row[0] > 5).is_none())
+		}
+
+		#[test]
+		#[should_panic]
+		fn mask_length_mismatch() {
+			let _ = Vector::new(vec![1, 2, 3]).select(&[true]);
+		}
+
+	}
+
+}
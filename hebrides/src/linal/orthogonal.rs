@@ -0,0 +1,192 @@
+//! Orthogonalization.
+//!
+//! Routines for building orthonormal bases out of arbitrary spanning sets.
+
+use super::{Vector, Matrix};
+
+/// Returns an orthonormal basis for the span of `vectors`, computed by
+/// modified Gram-Schmidt.
+///
+/// Each vector is orthogonalized against the basis built so far twice, which
+/// keeps the result orthonormal to working precision even for nearly
+/// dependent inputs. Vectors that are (numerically) dependent on those before
+/// them are skipped, so the basis may have fewer vectors than `vectors`.
+///
+/// ```
+/// # use hebrides::linal::{Vector, orthonormalize};
+/// let basis = orthonormalize(&[
+///     Vector::new(vec![3.0, 4.0]),
+///     Vector::new(vec![6.0, 8.0]),
+///     Vector::new(vec![1.0, 0.0])
+/// ]);
+/// assert_eq!(basis.len(), 2);
+/// assert_eq!(basis[0], Vector::new(vec![0.6, 0.8]));
+/// ```
+///
+/// # Panics
+/// Panics if `vectors` are not all of the same dimension.
+pub fn orthonormalize(vectors: &[Vector<f64>]) -> Vec<Vector<f64>> {
+	if vectors.iter().any(|v| v.dim != vectors[0].dim) {
+		panic!("Vectors must be of the same dimension to be orthonormalized")
+	}
+	let mut basis: Vec<Vector<f64>> = Vec::new();
+	for v in vectors {
+		let original = v.norm();
+		let mut w = v.components.clone();
+		for _ in 0..2 {
+			for q in &basis {
+				let projection: f64 = w.iter().zip(q.components.iter()).map(|(a, b)| a * b).sum();
+				for (e, b) in w.iter_mut().zip(q.components.iter()) {
+					*e -= projection * b;
+				}
+			}
+		}
+		let residual = w.iter().map(|e| e * e).sum::<f64>().sqrt();
+		if residual <= original * v.dim as f64 * f64::EPSILON || residual == 0.0 {
+			continue;
+		}
+		basis.push(Vector::new(w.into_iter().map(|e| e / residual).collect()));
+	}
+	basis
+}
+
+impl Matrix<f64> {
+
+	/// Returns a [`Matrix`] whose columns form an orthonormal basis for the
+	/// column space of `self`, or `None` if `self` is zero.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let m = Matrix::new(vec![vec![1.0, 2.0], vec![1.0, 2.0], vec![0.0, 0.0]]);
+	/// let q = m.orthonormal_columns().unwrap();
+	/// assert_eq!(q.dims.num_cols, 1);
+	/// ```
+	pub fn orthonormal_columns(&self) -> Option<Matrix<f64>> {
+		let columns: Vec<Vector<f64>> = self.cols.iter().map(|col| Vector::new(col.clone())).collect();
+		let basis = orthonormalize(&columns);
+		if basis.is_empty() {
+			return None;
+		}
+		Some(Matrix::new((0..self.dims.num_rows).map(|i| basis.iter().map(|q| q[i]).collect()).collect()))
+	}
+
+	/// Returns the orthogonal matrix nearest to `self` in the Frobenius norm,
+	/// the factor U Vᵀ of its [`SVD`](super::SVD) U Σ Vᵀ.
+	///
+	/// Unlike Gram-Schmidt, which leaves the first column fixed and pushes
+	/// all of the correction onto later ones, this spreads the correction
+	/// symmetrically over every column. It is meant for restoring rotation
+	/// matrices that have accumulated numerical shear, which it maps back to
+	/// the nearest rotation.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let drifted = Matrix::new(vec![vec![1.0, 1e-6], vec![0.0, 1.0]]);
+	/// assert!(drifted.orthogonality_drift() > 1e-7);
+	/// assert!(drifted.orthonormalize().orthogonality_drift() < 1e-15);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square.
+	pub fn orthonormalize(&self) -> Matrix<f64> {
+		if !self.is_square() {
+			panic!("Only square matrices can be orthonormalized")
+		}
+		let svd = self.svd();
+		svd.u().clone() * svd.v().transpose()
+	}
+
+	/// Returns the Frobenius norm of `self`ᵀ `self` - I, which is zero
+	/// exactly when the columns of `self` are orthonormal and grows with
+	/// the shear and scaling `self` has accumulated.
+	pub fn orthogonality_drift(&self) -> f64 {
+		let gram = self.transpose() * self.clone();
+		gram.iter().map(|((i, j), e)| {
+			let deviation = if i == j { e - 1.0 } else { *e };
+			deviation * deviation
+		}).sum::<f64>().sqrt()
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn basis_is_orthonormal() {
+		let vectors = vec![
+			Vector::new(vec![1.0, 1.0, 0.0, 2.0]),
+			Vector::new(vec![1.0, 0.0, 1.0, -1.0]),
+			Vector::new(vec![0.0, 3.0, 1.0, 1.0])
+		];
+		let basis = orthonormalize(&vectors);
+		assert_eq!(basis.len(), 3);
+		for (i, p) in basis.iter().enumerate() {
+			for (j, q) in basis.iter().enumerate() {
+				let expected = if i == j { 1.0 } else { 0.0 };
+				assert!((p.dot(q) - expected).abs() < 1e-14);
+			}
+		}
+	}
+
+	#[test]
+	fn nearly_dependent_vectors_stay_orthogonal() {
+		let vectors = vec![
+			Vector::new(vec![1.0, 1e-9, 0.0]),
+			Vector::new(vec![1.0, 0.0, 1e-9]),
+			Vector::new(vec![1.0, 0.0, 0.0])
+		];
+		let basis = orthonormalize(&vectors);
+		assert_eq!(basis.len(), 3);
+		assert!(basis[0].dot(&basis[2]).abs() < 1e-12 && basis[1].dot(&basis[2]).abs() < 1e-12)
+	}
+
+	#[test]
+	fn zero_vectors_are_skipped() {
+		let basis = orthonormalize(&[Vector::new(vec![0.0, 0.0]), Vector::new(vec![0.0, 2.0])]);
+		assert_eq!(basis, vec![Vector::new(vec![0.0, 1.0])])
+	}
+
+	#[test]
+	fn orthonormal_columns_span_column_space() {
+		let m = Matrix::new(vec![
+			vec![1.0, 2.0, 1.0],
+			vec![0.0, 1.0, 1.0],
+			vec![1.0, 3.0, 2.0]
+		]);
+		let q = m.orthonormal_columns().unwrap();
+		assert_eq!(q.dims.num_cols, 2);
+		let gram = q.transpose() * q.clone();
+		assert!(gram.is_diagonal_within(1e-12) && (0..2).all(|i| (gram[i][i] - 1.0).abs() < 1e-12));
+		assert!(Matrix::new(vec![vec![0.0], vec![0.0]]).orthonormal_columns().is_none())
+	}
+
+	#[test]
+	#[should_panic]
+	fn mismatched_dimensions() {
+		let _ = orthonormalize(&[Vector::new(vec![1.0]), Vector::new(vec![1.0, 0.0])]);
+	}
+
+	#[test]
+	fn orthonormalize_restores_drifted_rotation() {
+		let rotation = Matrix::<f64>::rotation_2d(crate::Angle::from_radians(0.3));
+		let mut drifted = rotation.clone();
+		for _ in 0..1000 {
+			drifted = drifted * Matrix::new(vec![vec![1.0 + 1e-9, 2e-9, 0.0], vec![-1e-9, 1.0, 0.0], vec![0.0, 0.0, 1.0]]);
+		}
+		assert!(drifted.orthogonality_drift() > 1e-7);
+		let restored = drifted.orthonormalize();
+		assert!(restored.orthogonality_drift() < 1e-14);
+		assert!((restored.determinant() - 1.0).abs() < 1e-14);
+		assert!(restored.iter().all(|((i, j), e)| (e - rotation.rows[i][j]).abs() < 1e-5))
+	}
+
+	#[test]
+	#[should_panic]
+	fn orthonormalize_requires_square() {
+		let _ = Matrix::new(vec![vec![1.0, 0.0]]).orthonormalize();
+	}
+
+}
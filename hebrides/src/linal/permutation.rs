@@ -0,0 +1,328 @@
+//! Permutations, sorting, and selection.
+//!
+//! A [`Permutation`] records a reordering so that it can be applied to other
+//! data afterwards, such as reordering the eigenvectors of a decomposition to
+//! match its sorted eigenvalues. When only the few largest or smallest
+//! components are wanted, [`Vector::top_k`] and [`Vector::bottom_k`] select
+//! them in linear time rather than sorting everything.
+
+use std::cmp::Ordering;
+
+use super::{Vector, Matrix};
+
+/// A reordering of `n` items.
+///
+/// Applying a permutation gathers: the `i`th item of the result is the
+/// `indices()[i]`th item of the input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Permutation {
+	indices: Vec<usize>
+}
+
+impl Permutation {
+
+	/// Returns the identity permutation on `n` items.
+	pub fn identity(n: usize) -> Permutation {
+		Permutation { indices: (0..n).collect() }
+	}
+
+	/// Constructs a [`Permutation`] gathering from `indices`, or `None` if
+	/// `indices` is not a rearrangement of `0..indices.len()`.
+	///
+	/// ```
+	/// # use hebrides::linal::Permutation;
+	/// assert!(Permutation::from_indices(vec![2, 0, 1]).is_some());
+	/// assert!(Permutation::from_indices(vec![2, 0, 2]).is_none());
+	/// ```
+	pub fn from_indices(indices: Vec<usize>) -> Option<Permutation> {
+		let mut seen = vec![false; indices.len()];
+		for i in indices.iter() {
+			if *i >= seen.len() || seen[*i] {
+				return None;
+			}
+			seen[*i] = true;
+		}
+		Some(Permutation { indices })
+	}
+
+	/// Returns the index gathered into each position.
+	pub fn indices(&self) -> &[usize] {
+		&self.indices
+	}
+
+	/// Returns the number of items `self` reorders.
+	pub fn len(&self) -> usize {
+		self.indices.len()
+	}
+
+	/// Returns `true` if `self` reorders no items.
+	pub fn is_empty(&self) -> bool {
+		self.indices.is_empty()
+	}
+
+	/// Returns the permutation undoing `self`.
+	pub fn inverse(&self) -> Permutation {
+		let mut indices = vec![0; self.len()];
+		for (i, j) in self.indices.iter().enumerate() {
+			indices[*j] = i;
+		}
+		Permutation { indices }
+	}
+
+	/// Returns the permutation equivalent to applying `self` and then
+	/// `other`.
+	///
+	/// # Panics
+	/// Panics if `self` and `other` reorder different numbers of items.
+	pub fn then(&self, other: &Permutation) -> Permutation {
+		if self.len() != other.len() {
+			panic!("Permutations must be of the same length to be composed")
+		}
+		Permutation { indices: other.indices.iter().map(|i| self.indices[*i]).collect() }
+	}
+
+	/// Returns the items of `items` reordered by `self`.
+	///
+	/// # Panics
+	/// Panics if `items` does not have as many items as `self` reorders.
+	pub fn apply_slice<T>(&self, items: &[T]) -> Vec<T> where T: Clone {
+		if items.len() != self.len() {
+			panic!("Permutation must be of the same length as what it reorders")
+		}
+		self.indices.iter().map(|i| items[*i].clone()).collect()
+	}
+
+	/// Returns the components of `v` reordered by `self`.
+	///
+	/// # Panics
+	/// Panics if `v` does not have as many components as `self` reorders.
+	pub fn apply<T>(&self, v: &Vector<T>) -> Vector<T> where T: Clone {
+		Vector::new(self.apply_slice(&v.components))
+	}
+
+	/// Returns the rows of `m` reordered by `self`.
+	///
+	/// # Panics
+	/// Panics if `m` does not have as many rows as `self` reorders.
+	pub fn apply_rows<T>(&self, m: &Matrix<T>) -> Matrix<T> where T: Clone {
+		Matrix::new(self.apply_slice(&m.rows))
+	}
+
+	/// Returns the columns of `m` reordered by `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, Matrix};
+	/// let m = Matrix::new(vec![vec![3.0, 1.0], vec![1.0, 3.0]]);
+	/// let eigen = m.symmetric_eigen();
+	/// // Largest eigenvalue first, with the eigenvectors following along.
+	/// let values = Vector::new(eigen.values().to_vec());
+	/// let order = values.argsort().reversed();
+	/// let values = order.apply(&values);
+	/// let vectors = order.apply_columns(eigen.vectors());
+	/// assert!((values[0] - 4.0).abs() < 1e-12);
+	/// assert!((vectors.rows[0][0] - vectors.rows[1][0]).abs() < 1e-12);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `m` does not have as many columns as `self` reorders.
+	pub fn apply_columns<T>(&self, m: &Matrix<T>) -> Matrix<T> where T: Clone {
+		Matrix::new(m.rows.iter().map(|row| self.apply_slice(row)).collect())
+	}
+
+	/// Returns `self` with the order of its positions reversed.
+	pub fn reversed(&self) -> Permutation {
+		Permutation { indices: self.indices.iter().rev().copied().collect() }
+	}
+
+}
+
+fn ordering<K>(a: &K, b: &K) -> Ordering where K: PartialOrd {
+	a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+impl<T> Vector<T> where T: PartialOrd {
+
+	/// Returns the [`Permutation`] sorting the components of `self` in
+	/// increasing order.
+	///
+	/// The sort is stable, so equal components keep their relative order;
+	/// incomparable components, such as NaNs, are treated as equal to
+	/// everything.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let v = Vector::new(vec![3.0, -1.0, 2.0, -1.0]);
+	/// let order = v.argsort();
+	/// assert_eq!(order.indices(), &[1, 3, 2, 0]);
+	/// assert_eq!(order.apply(&v), Vector::new(vec![-1.0, -1.0, 2.0, 3.0]));
+	/// ```
+	pub fn argsort(&self) -> Permutation {
+		let mut indices: Vec<usize> = (0..self.dim).collect();
+		indices.sort_by(|i, j| ordering(&self.components[*i], &self.components[*j]));
+		Permutation { indices }
+	}
+
+}
+
+impl<T> Vector<T> {
+
+	/// Sorts the components of `self` in increasing order of `key` and
+	/// returns the [`Permutation`] that did so, to reorder paired data the
+	/// same way.
+	///
+	/// As with [`argsort`](Vector::argsort), the sort is stable and
+	/// incomparable keys are treated as equal.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let mut v = Vector::new(vec![-3, 1, -2]);
+	/// let labels = Vector::new(vec!['a', 'b', 'c']);
+	/// let order = v.sort_by_key_with_permutation(|x: &i32| x.abs());
+	/// assert_eq!(v, Vector::new(vec![1, -2, -3]));
+	/// assert_eq!(order.apply(&labels), Vector::new(vec!['b', 'c', 'a']));
+	/// ```
+	pub fn sort_by_key_with_permutation<K>(&mut self, mut key: impl FnMut(&T) -> K) -> Permutation where K: PartialOrd {
+		let keys: Vec<K> = self.components.iter().map(&mut key).collect();
+		let mut indices: Vec<usize> = (0..self.dim).collect();
+		indices.sort_by(|i, j| ordering(&keys[*i], &keys[*j]));
+		let mut slots: Vec<Option<T>> = std::mem::take(&mut self.components).into_iter().map(Some).collect();
+		self.components = indices.iter().map(|i| slots[*i].take().unwrap()).collect();
+		Permutation { indices }
+	}
+
+}
+
+/// Returns the indices of the `k` largest of `values` (or smallest, if
+/// `largest` is false) from most to least extreme, with ties going to the
+/// lower index.
+fn select<T>(values: &[T], k: usize, largest: bool) -> Vec<usize> where T: PartialOrd {
+	let k = k.min(values.len());
+	let compare = |i: &usize, j: &usize| {
+		let by_value = ordering(&values[*i], &values[*j]);
+		let by_value = if largest { by_value.reverse() } else { by_value };
+		by_value.then(i.cmp(j))
+	};
+	let mut indices: Vec<usize> = (0..values.len()).collect();
+	if k == 0 {
+		return vec![];
+	}
+	if k < indices.len() {
+		indices.select_nth_unstable_by(k - 1, compare);
+		indices.truncate(k);
+	}
+	indices.sort_unstable_by(compare);
+	indices
+}
+
+impl<T> Vector<T> where T: Copy + PartialOrd {
+
+	/// Returns the `k` largest components of `self` as (index, value) pairs,
+	/// largest first.
+	///
+	/// Selection takes O(n + k log k) time, as only the chosen components
+	/// are sorted. Ties go to the lower index, incomparable components such
+	/// as NaNs are treated as equal to everything, and `k` is capped at the
+	/// dimension of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let scores = Vector::new(vec![0.2, 0.9, 0.4, 0.9, 0.1]);
+	/// assert_eq!(scores.top_k(3), vec![(1, 0.9), (3, 0.9), (2, 0.4)]);
+	/// assert_eq!(scores.bottom_k(2), vec![(4, 0.1), (0, 0.2)]);
+	/// ```
+	pub fn top_k(&self, k: usize) -> Vec<(usize, T)> {
+		select(&self.components, k, true).into_iter().map(|i| (i, self.components[i])).collect()
+	}
+
+	/// Returns the `k` smallest components of `self` as (index, value)
+	/// pairs, smallest first, as [`top_k`](Vector::top_k) does for the
+	/// largest.
+	pub fn bottom_k(&self, k: usize) -> Vec<(usize, T)> {
+		select(&self.components, k, false).into_iter().map(|i| (i, self.components[i])).collect()
+	}
+
+}
+
+impl<T> Matrix<T> where T: Copy + PartialOrd {
+
+	/// Returns the `k` largest entries of each row of `self` as (column,
+	/// value) pairs, largest first, as [`Vector::top_k`] does for vectors.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// // Each point's two most similar neighbours, excluding itself.
+	/// let similarity = Matrix::new(vec![
+	///     vec![f64::NEG_INFINITY, 0.8, 0.1, 0.5],
+	///     vec![0.8, f64::NEG_INFINITY, 0.3, 0.6],
+	/// ]);
+	/// let neighbours = similarity.top_k_per_row(2);
+	/// assert_eq!(neighbours[0], vec![(1, 0.8), (3, 0.5)]);
+	/// assert_eq!(neighbours[1], vec![(0, 0.8), (3, 0.6)]);
+	/// ```
+	pub fn top_k_per_row(&self, k: usize) -> Vec<Vec<(usize, T)>> {
+		self.rows.iter().map(|row| select(row, k, true).into_iter().map(|j| (j, row[j])).collect()).collect()
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn inverse_undoes_permutation() {
+		let v = Vector::new(vec![5, 9, 1, 7]);
+		let order = v.argsort();
+		assert_eq!(order.inverse().apply(&order.apply(&v)), v);
+		assert_eq!(order.then(&order.inverse()), Permutation::identity(4))
+	}
+
+	#[test]
+	fn then_applies_in_sequence() {
+		let v = Vector::new(vec!['a', 'b', 'c']);
+		let p = Permutation::from_indices(vec![1, 2, 0]).unwrap();
+		let q = Permutation::from_indices(vec![0, 2, 1]).unwrap();
+		assert_eq!(p.then(&q).apply(&v), q.apply(&p.apply(&v)))
+	}
+
+	#[test]
+	fn rows_and_columns_follow_permutation() {
+		let m = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+		let p = Permutation::from_indices(vec![2, 0, 1]).unwrap();
+		assert_eq!(p.apply_rows(&m), Matrix::new(vec![vec![7, 8, 9], vec![1, 2, 3], vec![4, 5, 6]]));
+		assert_eq!(p.apply_columns(&m), p.apply_rows(&m.transpose()).transpose())
+	}
+
+	#[test]
+	fn argsort_is_stable_with_nan() {
+		let v = Vector::new(vec![2.0, f64::NAN, 1.0, 2.0]);
+		let order = v.argsort();
+		let first = order.indices().iter().position(|i| *i == 0).unwrap();
+		let last = order.indices().iter().position(|i| *i == 3).unwrap();
+		assert!(first < last)
+	}
+
+	#[test]
+	fn selection_matches_full_sort() {
+		let v = Vector::from_fn(50, |i| ((i * 37) % 50) as i64 - 25);
+		let order = v.argsort();
+		let sorted: Vec<(usize, i64)> = order.indices().iter().map(|i| (*i, v[*i])).collect();
+		assert_eq!(v.bottom_k(7), sorted[..7].to_vec());
+		assert_eq!(v.top_k(7), sorted.iter().rev().take(7).copied().collect::<Vec<_>>())
+	}
+
+	#[test]
+	fn k_is_capped_at_dimension() {
+		let v = Vector::new(vec![3, 1, 2]);
+		assert_eq!(v.top_k(10), vec![(0, 3), (2, 2), (1, 1)]);
+		assert!(v.bottom_k(0).is_empty())
+	}
+
+	#[test]
+	#[should_panic]
+	fn length_mismatch() {
+		let _ = Permutation::identity(3).apply(&Vector::new(vec![1, 2]));
+	}
+
+}
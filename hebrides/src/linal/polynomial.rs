@@ -0,0 +1,47 @@
+//! Dense univariate polynomials.
+
+use super::Scalar;
+
+/// A polynomial in one variable, stored as its coefficients in ascending
+/// order of degree.
+///
+/// As a [`VectorSpace`](super::VectorSpace), polynomials of different degrees
+/// combine as though padded with zero coefficients, and the inner product is
+/// that of L²[-1, 1], ⟨p, q⟩ = ∫ p(x) conj(q(x)) dx over [-1, 1].
+///
+/// ```
+/// # use hebrides::linal::Polynomial;
+/// let p = Polynomial::new(vec![1.0, 0.0, 2.0]);
+/// assert_eq!(p.degree(), Some(2));
+/// assert_eq!(p.eval(3.0), 19.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polynomial<T = f64> {
+	coefficients: Vec<T>
+}
+
+impl<T: Scalar> Polynomial<T> {
+
+	/// Constructs the polynomial Σ `coefficients[k]` xᵏ.
+	///
+	/// An empty `coefficients` gives the zero polynomial.
+	pub fn new(coefficients: Vec<T>) -> Polynomial<T> {
+		Self { coefficients }
+	}
+
+	/// Returns the coefficients of `self` in ascending order of degree.
+	pub fn coefficients(&self) -> &[T] {
+		&self.coefficients
+	}
+
+	/// Returns the degree of `self`, or `None` if `self` is zero.
+	pub fn degree(&self) -> Option<usize> {
+		self.coefficients.iter().rposition(|c| *c != T::from_real(0.0))
+	}
+
+	/// Evaluates `self` at `x` by Horner's method.
+	pub fn eval(&self, x: T) -> T {
+		self.coefficients.iter().rev().fold(T::from_real(0.0), |sum, c| sum * x + *c)
+	}
+
+}
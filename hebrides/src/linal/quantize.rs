@@ -0,0 +1,258 @@
+//! Quantization and normalization.
+//!
+//! Scalar quantizers mapping the entries of a [`Matrix`] onto a small set of
+//! levels, fitted either uniformly over the range of the data or by k-means,
+//! and per-channel min-max and percentile normalization, for preparing
+//! matrices for low-precision storage or inference.
+
+use super::{Axis, Matrix, Vector};
+use crate::random::Rng;
+use crate::stats;
+
+/// Returns the `p`-th percentile of `sorted`, interpolating linearly between
+/// the closest ranks.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+	let rank = p / 100.0 * (sorted.len() - 1) as f64;
+	let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+	sorted[below] + (sorted[above] - sorted[below]) * (rank - below as f64)
+}
+
+/// A scalar quantizer, rounding each value to the nearest of a fixed set of
+/// levels and encoding it as that level's index.
+///
+/// ```
+/// # use hebrides::linal::Quantizer;
+/// let q = Quantizer::uniform(0.0, 1.0, 5);
+/// assert_eq!(q.levels(), &[0.0, 0.25, 0.5, 0.75, 1.0]);
+/// assert_eq!(q.encode(0.3), 1);
+/// assert_eq!(q.decode(q.encode(0.9)), 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantizer {
+	levels: Vec<f64>
+}
+
+impl Quantizer {
+
+	/// Constructs a quantizer from its levels, which are sorted into
+	/// ascending order.
+	///
+	/// # Panics
+	/// Panics if `levels` is empty or contains NaN.
+	pub fn new(mut levels: Vec<f64>) -> Self {
+		if levels.is_empty() || levels.iter().any(|l| l.is_nan()) {
+			panic!("Quantizer levels must be nonempty and not NaN")
+		}
+		levels.sort_by(f64::total_cmp);
+		Quantizer { levels }
+	}
+
+	/// Constructs a quantizer with `levels` evenly spaced levels from `min`
+	/// to `max` inclusive.
+	///
+	/// # Panics
+	/// Panics if `levels` is zero.
+	pub fn uniform(min: f64, max: f64, levels: usize) -> Self {
+		if levels == 0 {
+			panic!("Quantizer must have at least one level")
+		}
+		if levels == 1 {
+			return Quantizer::new(vec![(min + max) / 2.0]);
+		}
+		let step = (max - min) / (levels - 1) as f64;
+		Quantizer::new((0..levels).map(|i| min + step * i as f64).collect())
+	}
+
+	/// Fits a uniform quantizer spanning the smallest to the largest entry of
+	/// `m`.
+	pub fn fit_uniform(m: &Matrix<f64>, levels: usize) -> Self {
+		Quantizer::uniform(m.min(), m.max(), levels)
+	}
+
+	/// Fits a quantizer whose levels are the centroids of a k-means
+	/// clustering of the entries of `m`, which places more levels where the
+	/// entries are dense and so loses less than a uniform quantizer on
+	/// skewed data.
+	///
+	/// ```
+	/// # use hebrides::{linal::{Matrix, Quantizer}, random::Rng};
+	/// let m = Matrix::new(vec![vec![0.0, 0.1, 0.2], vec![9.8, 9.9, 10.0]]);
+	/// let q = Quantizer::fit_kmeans(&m, 2, &mut Rng::new(0));
+	/// assert!((q.levels()[0] - 0.1).abs() < 1e-12 && (q.levels()[1] - 9.9).abs() < 1e-12);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `levels` is zero or greater than the number of entries of
+	/// `m`.
+	pub fn fit_kmeans(m: &Matrix<f64>, levels: usize, rng: &mut Rng) -> Self {
+		let points: Vec<Vector<f64>> = m.rows.iter().flatten().map(|e| Vector::new(vec![*e])).collect();
+		let (_, centroids) = stats::kmeans(&points, levels, rng);
+		Quantizer::new(centroids.iter().map(|c| c[0]).collect())
+	}
+
+	/// Returns the levels of `self` in ascending order.
+	pub fn levels(&self) -> &[f64] {
+		&self.levels
+	}
+
+	/// Returns the index of the level nearest to `x`, taking the lower level
+	/// on ties.
+	pub fn encode(&self, x: f64) -> usize {
+		let above = self.levels.partition_point(|l| *l < x);
+		if above == 0 {
+			return 0;
+		}
+		if above == self.levels.len() || x - self.levels[above - 1] <= self.levels[above] - x {
+			return above - 1;
+		}
+		above
+	}
+
+	/// Returns the level with index `code`.
+	///
+	/// # Panics
+	/// Panics if `code` is not the index of a level.
+	pub fn decode(&self, code: usize) -> f64 {
+		self.levels[code]
+	}
+
+}
+
+/// A [`Matrix`] stored as level indices into a [`Quantizer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizedMatrix {
+	codes: Matrix<usize>,
+	quantizer: Quantizer
+}
+
+impl QuantizedMatrix {
+
+	/// Returns the level index of each entry.
+	pub fn codes(&self) -> &Matrix<usize> {
+		&self.codes
+	}
+
+	/// Returns the quantizer the entries were encoded with.
+	pub fn quantizer(&self) -> &Quantizer {
+		&self.quantizer
+	}
+
+	/// Returns the matrix of levels the entries were rounded to.
+	pub fn dequantize(&self) -> Matrix<f64> {
+		Matrix::new(self.codes.rows.iter().map(|row| row.iter().map(|c| self.quantizer.decode(*c)).collect()).collect())
+	}
+
+}
+
+impl Matrix<f64> {
+
+	/// Quantizes the entries of `self` onto `levels` evenly spaced levels
+	/// spanning their range.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let m = Matrix::new(vec![vec![0.0, 0.26], vec![0.74, 1.0]]);
+	/// let q = m.quantize(5);
+	/// assert_eq!(q.codes(), &Matrix::new(vec![vec![0, 1], vec![3, 4]]));
+	/// assert_eq!(q.dequantize(), Matrix::new(vec![vec![0.0, 0.25], vec![0.75, 1.0]]));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `levels` is zero.
+	pub fn quantize(&self, levels: usize) -> QuantizedMatrix {
+		self.quantize_with(&Quantizer::fit_uniform(self, levels))
+	}
+
+	/// Quantizes the entries of `self` with `quantizer`.
+	pub fn quantize_with(&self, quantizer: &Quantizer) -> QuantizedMatrix {
+		let codes = Matrix::new(self.rows.iter().map(|row| row.iter().map(|e| quantizer.encode(*e)).collect()).collect());
+		QuantizedMatrix { codes, quantizer: quantizer.clone() }
+	}
+
+	/// Applies `f` to each line of `self` in the direction of `axis`.
+	fn map_lines(&self, axis: Axis, f: impl Fn(&[f64]) -> Vec<f64>) -> Matrix<f64> {
+		match axis {
+			Axis::Rows => Matrix::new(self.rows.iter().map(|row| f(row)).collect()),
+			Axis::Columns => Matrix::new(self.cols.iter().map(|col| f(col)).collect()).transpose()
+		}
+	}
+
+	/// Rescales each line of `self` in the direction of `axis`, treating
+	/// each as a channel, so that its smallest entry becomes zero and its
+	/// largest one. A constant line becomes all zeros.
+	///
+	/// ```
+	/// # use hebrides::linal::{Axis, Matrix};
+	/// let m = Matrix::new(vec![vec![1.0, 10.0], vec![3.0, 30.0], vec![5.0, 20.0]]);
+	/// let expected = Matrix::new(vec![vec![0.0, 0.0], vec![0.5, 1.0], vec![1.0, 0.5]]);
+	/// assert_eq!(m.normalize_min_max(Axis::Columns), expected);
+	/// ```
+	pub fn normalize_min_max(&self, axis: Axis) -> Matrix<f64> {
+		self.normalize_percentile(axis, 0.0, 100.0)
+	}
+
+	/// Rescales each line of `self` in the direction of `axis` so that its
+	/// `lower`-th percentile becomes zero and its `upper`-th percentile one,
+	/// clamping entries outside that range. This is min-max normalization
+	/// that is robust to a few outliers.
+	///
+	/// A line whose percentiles coincide becomes all zeros.
+	///
+	/// # Panics
+	/// Panics unless 0 <= `lower` <= `upper` <= 100.
+	pub fn normalize_percentile(&self, axis: Axis, lower: f64, upper: f64) -> Matrix<f64> {
+		if !(0.0 <= lower && lower <= upper && upper <= 100.0) {
+			panic!("Percentiles must satisfy 0 <= lower <= upper <= 100")
+		}
+		self.map_lines(axis, |line| {
+			let mut sorted = line.to_vec();
+			sorted.sort_by(f64::total_cmp);
+			let (low, high) = (percentile(&sorted, lower), percentile(&sorted, upper));
+			if high == low {
+				return vec![0.0; line.len()];
+			}
+			line.iter().map(|e| ((e - low) / (high - low)).clamp(0.0, 1.0)).collect()
+		})
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn encoding_rounds_to_nearest_level() {
+		let q = Quantizer::new(vec![2.0, -1.0, 0.0]);
+		assert_eq!(q.levels(), &[-1.0, 0.0, 2.0]);
+		let codes: Vec<usize> = [-5.0, -0.6, -0.5, 0.9, 1.1, 7.0].iter().map(|x| q.encode(*x)).collect();
+		assert_eq!(codes, vec![0, 0, 0, 1, 2, 2])
+	}
+
+	#[test]
+	fn quantization_error_is_bounded_by_half_a_step() {
+		let m = Matrix::new((0..4).map(|i| (0..5).map(|j| ((i * 5 + j) as f64 * 0.77).sin()).collect()).collect());
+		let q = m.quantize(16);
+		let step = (m.max() - m.min()) / 15.0;
+		let error = (q.dequantize() - m).rows.iter().flatten().fold(0.0_f64, |a, e| a.max(e.abs()));
+		assert!(error <= step / 2.0 + 1e-12)
+	}
+
+	#[test]
+	fn kmeans_beats_uniform_on_skewed_data() {
+		let m = Matrix::new(vec![(0..20).map(|i| if i < 18 { i as f64 * 0.01 } else { 100.0 }).collect()]);
+		let squared_error = |q: &Quantizer| (m.quantize_with(q).dequantize() - m.clone()).rows.iter().flatten().map(|e| e * e).sum::<f64>();
+		let kmeans = Quantizer::fit_kmeans(&m, 4, &mut Rng::new(3));
+		assert!(squared_error(&kmeans) < squared_error(&Quantizer::fit_uniform(&m, 4)))
+	}
+
+	#[test]
+	fn percentile_normalization_clamps_outliers() {
+		let m = Matrix::new(vec![vec![0.0, 1.0, 2.0, 3.0, 4.0, 1000.0], vec![5.0; 6]]);
+		let normalized = m.normalize_percentile(Axis::Rows, 0.0, 80.0);
+		assert_eq!(normalized.rows[0], vec![0.0, 0.25, 0.5, 0.75, 1.0, 1.0]);
+		assert_eq!(normalized.rows[1], vec![0.0; 6])
+	}
+
+}
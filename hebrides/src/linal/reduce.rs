@@ -0,0 +1,427 @@
+//! Reductions and scans.
+//!
+//! Sums, products, extrema, and means of the components of a [`Vector`] or
+//! the entries of a [`Matrix`], the latter either in whole or along an
+//! [`Axis`]. Their cumulative counterparts keep every partial result, and
+//! discrete differences undo cumulative sums.
+
+use std::ops::{Add, Sub, Mul};
+
+use crate::elem::Semiring;
+
+use super::{Vector, Matrix};
+
+/// The lines of a [`Matrix`] that an axis-wise operation runs along.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Axis {
+	/// Along each row, giving one result per row.
+	Rows,
+	/// Along each column, giving one result per column.
+	Columns
+}
+
+fn min_index<T>(values: &[T]) -> usize where T: PartialOrd {
+	(1..values.len()).fold(0, |best, i| if values[i] < values[best] { i } else { best })
+}
+
+fn max_index<T>(values: &[T]) -> usize where T: PartialOrd {
+	(1..values.len()).fold(0, |best, i| if values[i] > values[best] { i } else { best })
+}
+
+impl<T> Vector<T> where T: Semiring {
+
+	/// Returns the sum of the components of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let v = Vector::new(vec![3, -1, 4, 1, -5]);
+	/// assert_eq!(v.sum(), 2);
+	/// assert_eq!(v.product(), 60);
+	/// assert_eq!((v.min(), v.max()), (-5, 4));
+	/// assert_eq!((v.argmin(), v.argmax()), (4, 2));
+	/// ```
+	pub fn sum(&self) -> T {
+		T::sum_of(self.components.iter().copied())
+	}
+
+}
+
+impl<T> Vector<T> where T: Copy + Mul<Output=T> {
+
+	/// Returns the product of the components of `self`.
+	pub fn product(&self) -> T {
+		self.components.iter().copied().reduce(|a, b| a * b).unwrap()
+	}
+
+}
+
+impl<T> Vector<T> where T: Copy + PartialOrd {
+
+	/// Returns the smallest component of `self`.
+	///
+	/// Components incomparable with the running minimum, such as NaNs after
+	/// the first component, are skipped.
+	pub fn min(&self) -> T {
+		self.components[self.argmin()]
+	}
+
+	/// Returns the largest component of `self`.
+	///
+	/// Components incomparable with the running maximum, such as NaNs after
+	/// the first component, are skipped.
+	pub fn max(&self) -> T {
+		self.components[self.argmax()]
+	}
+
+	/// Returns the index of the first smallest component of `self`.
+	pub fn argmin(&self) -> usize {
+		min_index(&self.components)
+	}
+
+	/// Returns the index of the first largest component of `self`.
+	pub fn argmax(&self) -> usize {
+		max_index(&self.components)
+	}
+
+}
+
+impl<T> Matrix<T> {
+
+	fn lines(&self, axis: Axis) -> &[Vec<T>] {
+		match axis {
+			Axis::Rows => &self.rows,
+			Axis::Columns => &self.cols
+		}
+	}
+
+}
+
+impl<T> Matrix<T> where T: Semiring {
+
+	/// Returns the sum of the entries of `self`.
+	pub fn sum(&self) -> T {
+		T::sum_of(self.rows.iter().flatten().copied())
+	}
+
+	/// Returns the sums along each line of `self` in the direction of `axis`.
+	///
+	/// ```
+	/// # use hebrides::linal::{Matrix, Vector, Axis};
+	/// let m = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+	/// assert_eq!(m.sum_axis(Axis::Rows), Vector::new(vec![6, 15]));
+	/// assert_eq!(m.sum_axis(Axis::Columns), Vector::new(vec![5, 7, 9]));
+	/// assert_eq!(m.argmax_axis(Axis::Columns), vec![1, 1, 1]);
+	/// ```
+	pub fn sum_axis(&self, axis: Axis) -> Vector<T> {
+		self.lines(axis).iter().map(|line| T::sum_of(line.iter().copied())).collect()
+	}
+
+}
+
+impl<T> Matrix<T> where T: Copy + Mul<Output=T> {
+
+	/// Returns the product of the entries of `self`.
+	pub fn product(&self) -> T {
+		self.rows.iter().flatten().copied().reduce(|a, b| a * b).unwrap()
+	}
+
+	/// Returns the products along each line of `self` in the direction of
+	/// `axis`.
+	pub fn product_axis(&self, axis: Axis) -> Vector<T> {
+		self.lines(axis).iter().map(|line| line.iter().copied().reduce(|a, b| a * b).unwrap()).collect()
+	}
+
+}
+
+impl<T> Matrix<T> where T: Copy + PartialOrd {
+
+	/// Returns the smallest entry of `self`.
+	pub fn min(&self) -> T {
+		self.min_axis(Axis::Rows).min()
+	}
+
+	/// Returns the largest entry of `self`.
+	pub fn max(&self) -> T {
+		self.max_axis(Axis::Rows).max()
+	}
+
+	/// Returns the smallest entry along each line of `self` in the direction
+	/// of `axis`.
+	pub fn min_axis(&self, axis: Axis) -> Vector<T> {
+		self.lines(axis).iter().map(|line| line[min_index(line)]).collect()
+	}
+
+	/// Returns the largest entry along each line of `self` in the direction
+	/// of `axis`.
+	pub fn max_axis(&self, axis: Axis) -> Vector<T> {
+		self.lines(axis).iter().map(|line| line[max_index(line)]).collect()
+	}
+
+	/// Returns the position within each line of `self`, in the direction of
+	/// `axis`, of its first smallest entry.
+	pub fn argmin_axis(&self, axis: Axis) -> Vec<usize> {
+		self.lines(axis).iter().map(|line| min_index(line)).collect()
+	}
+
+	/// Returns the position within each line of `self`, in the direction of
+	/// `axis`, of its first largest entry.
+	pub fn argmax_axis(&self, axis: Axis) -> Vec<usize> {
+		self.lines(axis).iter().map(|line| max_index(line)).collect()
+	}
+
+}
+
+/// Returns the running results of folding `line` with `f`, starting from its
+/// first element.
+fn scan<T>(line: &[T], f: impl Fn(T, T) -> T) -> Vec<T> where T: Copy {
+	let mut scanned = Vec::with_capacity(line.len());
+	scanned.push(line[0]);
+	for e in line.iter().skip(1) {
+		scanned.push(f(scanned[scanned.len() - 1], *e));
+	}
+	scanned
+}
+
+/// Returns the `order`th discrete difference of `line`, which is empty if
+/// `order` is not less than its length.
+fn difference<T>(line: &[T], order: usize) -> Vec<T> where T: Copy + Sub<Output=T> {
+	let mut line = line.to_vec();
+	for _ in 0..order {
+		line = line.windows(2).map(|w| w[1] - w[0]).collect();
+	}
+	line
+}
+
+fn running_min<T>(a: T, b: T) -> T where T: PartialOrd {
+	if b < a { b } else { a }
+}
+
+fn running_max<T>(a: T, b: T) -> T where T: PartialOrd {
+	if b > a { b } else { a }
+}
+
+impl<T> Vector<T> where T: Copy + Add<Output=T> {
+
+	/// Returns the running sums of the components of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let v = Vector::new(vec![3, 1, 4, 1, 5]);
+	/// assert_eq!(v.cumsum(), Vector::new(vec![3, 4, 8, 9, 14]));
+	/// assert_eq!(v.cumprod(), Vector::new(vec![3, 3, 12, 12, 60]));
+	/// assert_eq!(v.cummax(), Vector::new(vec![3, 3, 4, 4, 5]));
+	/// assert_eq!(v.cumsum().diff(1), Some(Vector::new(vec![1, 4, 1, 5])));
+	/// ```
+	pub fn cumsum(&self) -> Vector<T> {
+		Vector::new(scan(&self.components, |a, b| a + b))
+	}
+
+}
+
+impl<T> Vector<T> where T: Copy + Mul<Output=T> {
+
+	/// Returns the running products of the components of `self`.
+	pub fn cumprod(&self) -> Vector<T> {
+		Vector::new(scan(&self.components, |a, b| a * b))
+	}
+
+}
+
+impl<T> Vector<T> where T: Copy + PartialOrd {
+
+	/// Returns the running minima of the components of `self`, skipping
+	/// incomparable components as [`min`](Vector::min) does.
+	pub fn cummin(&self) -> Vector<T> {
+		Vector::new(scan(&self.components, running_min))
+	}
+
+	/// Returns the running maxima of the components of `self`, skipping
+	/// incomparable components as [`max`](Vector::max) does.
+	pub fn cummax(&self) -> Vector<T> {
+		Vector::new(scan(&self.components, running_max))
+	}
+
+}
+
+impl<T> Vector<T> where T: Copy + Sub<Output=T> {
+
+	/// Returns the `order`th discrete difference of `self`, whose `i`th
+	/// component for the first order is `self[i + 1] - self[i]`, or `None`
+	/// if `order` is not less than the dimension of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let squares = Vector::new(vec![0, 1, 4, 9, 16]);
+	/// assert_eq!(squares.diff(2), Some(Vector::new(vec![2, 2, 2])));
+	/// assert_eq!(squares.diff(5), None);
+	/// ```
+	pub fn diff(&self, order: usize) -> Option<Vector<T>> {
+		Vector::try_new(difference(&self.components, order)).ok()
+	}
+
+}
+
+impl<T> Matrix<T> where T: Copy {
+
+	/// Constructs a [`Matrix`] from its lines in the direction of `axis`.
+	fn from_lines(lines: Vec<Vec<T>>, axis: Axis) -> Matrix<T> {
+		match axis {
+			Axis::Rows => Matrix::new(lines),
+			Axis::Columns => Matrix::new(lines).transpose()
+		}
+	}
+
+}
+
+impl<T> Matrix<T> where T: Copy + Add<Output=T> {
+
+	/// Returns the running sums along each line of `self` in the direction
+	/// of `axis`.
+	///
+	/// ```
+	/// # use hebrides::linal::{Matrix, Axis};
+	/// let m = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+	/// assert_eq!(m.cumsum_axis(Axis::Rows), Matrix::new(vec![vec![1, 3, 6], vec![4, 9, 15]]));
+	/// assert_eq!(m.cumsum_axis(Axis::Columns), Matrix::new(vec![vec![1, 2, 3], vec![5, 7, 9]]));
+	/// assert_eq!(m.diff_axis(Axis::Columns, 1), Some(Matrix::new(vec![vec![3, 3, 3]])));
+	/// ```
+	pub fn cumsum_axis(&self, axis: Axis) -> Matrix<T> {
+		Matrix::from_lines(self.lines(axis).iter().map(|line| scan(line, |a, b| a + b)).collect(), axis)
+	}
+
+}
+
+impl<T> Matrix<T> where T: Copy + Mul<Output=T> {
+
+	/// Returns the running products along each line of `self` in the
+	/// direction of `axis`.
+	pub fn cumprod_axis(&self, axis: Axis) -> Matrix<T> {
+		Matrix::from_lines(self.lines(axis).iter().map(|line| scan(line, |a, b| a * b)).collect(), axis)
+	}
+
+}
+
+impl<T> Matrix<T> where T: Copy + PartialOrd {
+
+	/// Returns the running minima along each line of `self` in the
+	/// direction of `axis`.
+	pub fn cummin_axis(&self, axis: Axis) -> Matrix<T> {
+		Matrix::from_lines(self.lines(axis).iter().map(|line| scan(line, running_min)).collect(), axis)
+	}
+
+	/// Returns the running maxima along each line of `self` in the
+	/// direction of `axis`.
+	pub fn cummax_axis(&self, axis: Axis) -> Matrix<T> {
+		Matrix::from_lines(self.lines(axis).iter().map(|line| scan(line, running_max)).collect(), axis)
+	}
+
+}
+
+impl<T> Matrix<T> where T: Copy + Sub<Output=T> {
+
+	/// Returns the `order`th discrete differences along each line of `self`
+	/// in the direction of `axis`, or `None` if `order` is not less than the
+	/// length of those lines.
+	pub fn diff_axis(&self, axis: Axis, order: usize) -> Option<Matrix<T>> {
+		if order >= self.lines(axis)[0].len() {
+			return None;
+		}
+		Some(Matrix::from_lines(self.lines(axis).iter().map(|line| difference(line, order)).collect(), axis))
+	}
+
+}
+
+macro_rules! impl_float_reductions {
+	($t:ty) => {
+		impl Vector<$t> {
+
+			/// Returns the arithmetic mean of the components of `self`.
+			///
+			/// ```
+			/// # use hebrides::linal::Vector;
+			#[doc = concat!("assert_eq!(Vector::new(vec![1.0_", stringify!($t), ", 2.0, 6.0]).mean(), 3.0);")]
+			/// ```
+			pub fn mean(&self) -> $t {
+				self.sum() / self.dim as $t
+			}
+
+		}
+
+		impl Matrix<$t> {
+
+			/// Returns the arithmetic mean of the entries of `self`.
+			pub fn mean(&self) -> $t {
+				self.sum() / (self.dims.num_rows * self.dims.num_cols) as $t
+			}
+
+			/// Returns the means along each line of `self` in the direction of
+			/// `axis`.
+			pub fn mean_axis(&self, axis: Axis) -> Vector<$t> {
+				self.lines(axis).iter().map(|line| line.iter().sum::<$t>() / line.len() as $t).collect()
+			}
+
+		}
+	}
+}
+
+impl_float_reductions![f32];
+impl_float_reductions![f64];
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn first_extremum_wins_ties() {
+		let v = Vector::new(vec![2, 7, 1, 7, 1]);
+		assert_eq!((v.argmin(), v.argmax()), (2, 1))
+	}
+
+	#[test]
+	fn nan_after_first_is_skipped() {
+		let v = Vector::new(vec![1.0, f64::NAN, -2.0]);
+		assert_eq!((v.min(), v.max()), (-2.0, 1.0))
+	}
+
+	#[test]
+	fn cumulative_extrema_skip_nan() {
+		let v = Vector::new(vec![2.0, f64::NAN, 1.0, 3.0]);
+		assert_eq!(v.cummin(), Vector::new(vec![2.0, 2.0, 1.0, 1.0]));
+		assert_eq!(v.cummax(), Vector::new(vec![2.0, 2.0, 2.0, 3.0]))
+	}
+
+	#[test]
+	fn diff_inverts_cumsum() {
+		let v = Vector::new(vec![5, -2, 7, 0, 3]);
+		let sums = v.cumsum();
+		assert_eq!(sums.diff(1).unwrap(), Vector::new(v.iter().skip(1).copied().collect()));
+		assert_eq!(v.diff(0), Some(v.clone()));
+		assert_eq!(v.diff(4), Some(Vector::new(vec![58])))
+	}
+
+	#[test]
+	fn axis_scans_agree_with_transpose() {
+		let m = Matrix::new(vec![vec![1.0_f64, -3.0, 2.0], vec![0.5, 4.0, -1.0]]);
+		let t = m.transpose();
+		assert_eq!(m.cumprod_axis(Axis::Columns), t.cumprod_axis(Axis::Rows).transpose());
+		assert_eq!(m.cummax_axis(Axis::Rows), t.cummax_axis(Axis::Columns).transpose());
+		assert_eq!(m.cummin_axis(Axis::Rows), Matrix::new(vec![vec![1.0, -3.0, -3.0], vec![0.5, 0.5, -1.0]]));
+		assert_eq!(m.diff_axis(Axis::Rows, 2), Some(Matrix::new(vec![vec![9.0], vec![-8.5]])));
+		assert_eq!(m.diff_axis(Axis::Columns, 2), None)
+	}
+
+	#[test]
+	fn axis_reductions_agree_with_transpose() {
+		let m = Matrix::new(vec![vec![1.0_f64, -3.0, 2.0], vec![0.5, 4.0, -1.0]]);
+		let t = m.transpose();
+		assert_eq!(m.sum_axis(Axis::Columns), t.sum_axis(Axis::Rows));
+		assert_eq!(m.product_axis(Axis::Rows), Vector::new(vec![-6.0, -2.0]));
+		assert_eq!(m.min_axis(Axis::Columns), Vector::new(vec![0.5, -3.0, -1.0]));
+		assert_eq!(m.argmin_axis(Axis::Rows), vec![1, 2]);
+		assert_eq!(m.mean_axis(Axis::Columns), Vector::new(vec![0.75, 0.5, 0.5]));
+		assert_eq!((m.min(), m.max(), m.sum()), (-3.0, 4.0, 3.5));
+		assert_eq!(m.mean(), 3.5 / 6.0)
+	}
+
+}
@@ -0,0 +1,324 @@
+//! Searching sorted vectors.
+//!
+//! Binary search into a sorted [`Vector`], and the histogramming and
+//! deduplication built on it, for lookups over sorted grids such as the knots
+//! of an interpolant or the edges of histogram bins. Sorted vectors of
+//! indices also act as sets, for composing selections of rows and columns or
+//! sparsity patterns. Set results may be empty and so are returned as
+//! [`Vec`]s.
+
+use std::cmp::Ordering;
+
+use super::Vector;
+
+/// Which end of a run of equal components a search lands on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Side {
+	/// Before every component equal to the value searched for.
+	Left,
+	/// After every component equal to the value searched for.
+	Right
+}
+
+impl<T> Vector<T> where T: PartialOrd {
+
+	/// Returns the index at which `value` would be inserted into `self`,
+	/// which must be sorted in increasing order, to keep it sorted; `side`
+	/// decides which end of any run of components equal to `value` that is.
+	///
+	/// Takes O(log n) time. The result is unspecified if `self` is not
+	/// sorted.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, Side};
+	/// let grid = Vector::new(vec![0.0, 1.0, 1.0, 2.5]);
+	/// assert_eq!(grid.searchsorted(1.0, Side::Left), 1);
+	/// assert_eq!(grid.searchsorted(1.0, Side::Right), 3);
+	/// assert_eq!(grid.searchsorted(-1.0, Side::Left), 0);
+	/// assert_eq!(grid.searchsorted(9.0, Side::Right), 4);
+	/// ```
+	pub fn searchsorted(&self, value: T, side: Side) -> usize {
+		match side {
+			Side::Left => self.components.partition_point(|e| *e < value),
+			Side::Right => self.components.partition_point(|e| *e <= value)
+		}
+	}
+
+	/// Returns whether or not the components of `self` are all
+	/// nondecreasing or all nonincreasing.
+	///
+	/// Vectors containing incomparable components, such as NaNs, are not
+	/// monotonic.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// assert!(Vector::new(vec![1, 2, 2, 5]).is_monotonic());
+	/// assert!(Vector::new(vec![5, 2, 2, 1]).is_monotonic());
+	/// assert!(!Vector::new(vec![1, 3, 2]).is_monotonic());
+	/// ```
+	pub fn is_monotonic(&self) -> bool {
+		let pairs = || self.components.windows(2);
+		pairs().all(|w| w[0] <= w[1]) || pairs().all(|w| w[0] >= w[1])
+	}
+
+	/// Returns the bin of each component of `self` among the sorted edges
+	/// `bins`: `i` such that `bins[i - 1] <= x < bins[i]`, with 0 below the
+	/// first edge and `bins.dim()` at or above the last.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let edges = Vector::new(vec![0.0, 10.0, 20.0]);
+	/// let ages = Vector::new(vec![-1.0, 0.0, 9.5, 10.0, 35.0]);
+	/// assert_eq!(ages.digitize(&edges), vec![0, 1, 1, 2, 3]);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `bins` is not sorted in increasing order.
+	pub fn digitize(&self, bins: &Vector<T>) -> Vec<usize> where T: Copy {
+		if !bins.components.windows(2).all(|w| w[0] <= w[1]) {
+			panic!("Bin edges must be sorted in increasing order")
+		}
+		self.components.iter().map(|x| bins.searchsorted(*x, Side::Right)).collect()
+	}
+
+}
+
+impl<T> Vector<T> where T: Copy + PartialOrd {
+
+	/// Returns the distinct components of `self` in increasing order, along
+	/// with how many times each occurs.
+	///
+	/// Incomparable components, such as NaNs, are never equal to anything
+	/// and so each counted once, after every comparable component.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let rolls = Vector::new(vec![3, 1, 3, 6, 1, 3]);
+	/// let (values, counts) = rolls.unique();
+	/// assert_eq!(values, Vector::new(vec![1, 3, 6]));
+	/// assert_eq!(counts, vec![2, 3, 1]);
+	/// ```
+	pub fn unique(&self) -> (Vector<T>, Vec<usize>) {
+		let (mut sorted, incomparable): (Vec<T>, Vec<T>) = self.components.iter().partition(|e| e.partial_cmp(e).is_some());
+		sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+		let mut values: Vec<T> = Vec::with_capacity(sorted.len());
+		let mut counts: Vec<usize> = Vec::with_capacity(sorted.len());
+		for e in sorted {
+			match values.last() {
+				Some(last) if *last == e => *counts.last_mut().unwrap() += 1,
+				_ => {
+					values.push(e);
+					counts.push(1);
+				}
+			}
+		}
+		for e in incomparable {
+			values.push(e);
+			counts.push(1);
+		}
+		(Vector::new(values), counts)
+	}
+
+}
+
+/// Merges the strictly increasing `a` and `b`, keeping the indices for which
+/// `keep(in_a, in_b)` holds.
+fn merge(a: &[usize], b: &[usize], keep: impl Fn(bool, bool) -> bool) -> Vec<usize> {
+	let (mut i, mut j) = (0, 0);
+	let mut merged = vec![];
+	while i < a.len() || j < b.len() {
+		let order = match (a.get(i), b.get(j)) {
+			(Some(x), Some(y)) => x.cmp(y),
+			(Some(_), None) => Ordering::Less,
+			_ => Ordering::Greater
+		};
+		let (index, in_a, in_b) = match order {
+			Ordering::Less => (a[i], true, false),
+			Ordering::Greater => (b[j], false, true),
+			Ordering::Equal => (a[i], true, true)
+		};
+		if keep(in_a, in_b) {
+			merged.push(index);
+		}
+		i += in_a as usize;
+		j += in_b as usize;
+	}
+	merged
+}
+
+impl Vector<usize> {
+
+	/// Panics unless `self` is strictly increasing, as the set operations
+	/// require.
+	fn assert_index_set(&self) {
+		if !self.components.windows(2).all(|w| w[0] < w[1]) {
+			panic!("Index sets must be sorted in strictly increasing order")
+		}
+	}
+
+	/// Returns the indices in either `self` or `other`, both of which must
+	/// be strictly increasing, in increasing order.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let (a, b) = (Vector::new(vec![0, 2, 5]), Vector::new(vec![2, 3, 9]));
+	/// assert_eq!(a.union(&b), vec![0, 2, 3, 5, 9]);
+	/// assert_eq!(a.intersection(&b), vec![2]);
+	/// assert_eq!(a.difference(&b), vec![0, 5]);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` or `other` is not strictly increasing.
+	pub fn union(&self, other: &Vector<usize>) -> Vec<usize> {
+		self.assert_index_set();
+		other.assert_index_set();
+		merge(&self.components, &other.components, |_, _| true)
+	}
+
+	/// Returns the indices in both `self` and `other`, both of which must be
+	/// strictly increasing, in increasing order.
+	///
+	/// # Panics
+	/// Panics if `self` or `other` is not strictly increasing.
+	pub fn intersection(&self, other: &Vector<usize>) -> Vec<usize> {
+		self.assert_index_set();
+		other.assert_index_set();
+		merge(&self.components, &other.components, |in_a, in_b| in_a && in_b)
+	}
+
+	/// Returns the indices in `self` but not in `other`, both of which must
+	/// be strictly increasing, in increasing order.
+	///
+	/// # Panics
+	/// Panics if `self` or `other` is not strictly increasing.
+	pub fn difference(&self, other: &Vector<usize>) -> Vec<usize> {
+		self.assert_index_set();
+		other.assert_index_set();
+		merge(&self.components, &other.components, |in_a, in_b| in_a && !in_b)
+	}
+
+	/// Returns the indices in 0..`n` that are not in `self`, which need not
+	/// be sorted, in increasing order.
+	///
+	/// # Panics
+	/// Panics if any index of `self` is not less than `n`.
+	pub fn complement(&self, n: usize) -> Vec<usize> {
+		let mask = self.mask(n);
+		(0..n).filter(|i| !mask[*i]).collect()
+	}
+
+	/// Returns the membership mask of `self` over 0..`n`, which is `true`
+	/// exactly at the indices in `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let selected = Vector::new(vec![3, 1]);
+	/// assert_eq!(selected.mask(5), vec![false, true, false, true, false]);
+	/// assert_eq!(selected.complement(5), vec![0, 2, 4]);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if any index of `self` is not less than `n`.
+	pub fn mask(&self, n: usize) -> Vec<bool> {
+		let mut mask = vec![false; n];
+		for i in self.components.iter() {
+			if *i >= n {
+				panic!("Index out of bounds for mask")
+			}
+			mask[*i] = true;
+		}
+		mask
+	}
+
+	/// Returns the position in `self` of each index in 0..`n`, or `None`
+	/// for indices `self` does not contain, inverting the map from positions
+	/// to indices; where an index repeats, its last position is kept.
+	///
+	/// This translates indices of a whole into indices of the selection
+	/// `self` makes from it, as when composing selections of rows.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// let kept_rows = Vector::new(vec![4, 0, 2]);
+	/// assert_eq!(kept_rows.invert(5), vec![Some(1), None, Some(2), None, Some(0)]);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if any index of `self` is not less than `n`.
+	pub fn invert(&self, n: usize) -> Vec<Option<usize>> {
+		let mut positions = vec![None; n];
+		for (k, i) in self.components.iter().enumerate() {
+			if *i >= n {
+				panic!("Index out of bounds for inversion")
+			}
+			positions[*i] = Some(k);
+		}
+		positions
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn searchsorted_brackets_every_value() {
+		let grid = Vector::new(vec![1, 3, 3, 3, 7, 9]);
+		for value in 0..11 {
+			let (left, right) = (grid.searchsorted(value, Side::Left), grid.searchsorted(value, Side::Right));
+			assert!(grid.iter().take(left).all(|e| *e < value));
+			assert!(grid.iter().skip(right).all(|e| *e > value));
+			assert!(grid.iter().skip(left).take(right - left).all(|e| *e == value))
+		}
+	}
+
+	#[test]
+	fn nan_breaks_monotonicity() {
+		assert!(!Vector::new(vec![1.0, f64::NAN, 2.0]).is_monotonic());
+		assert!(Vector::new(vec![4.0]).is_monotonic())
+	}
+
+	#[test]
+	fn unique_counts_sum_to_dimension() {
+		let v = Vector::new(vec![2.0, f64::NAN, -1.0, 2.0, f64::NAN]);
+		let (values, counts) = v.unique();
+		assert_eq!(&values.iter().take(2).copied().collect::<Vec<_>>(), &[-1.0, 2.0]);
+		assert!(values[2].is_nan() && values[3].is_nan());
+		assert_eq!(counts, vec![1, 2, 1, 1])
+	}
+
+	#[test]
+	fn set_operations_agree_with_masks() {
+		let a = Vector::new(vec![1, 4, 6, 7, 11]);
+		let b = Vector::new(vec![0, 4, 7, 8]);
+		let (ma, mb) = (a.mask(12), b.mask(12));
+		let by_mask = |keep: fn(bool, bool) -> bool| (0..12).filter(|i| keep(ma[*i], mb[*i])).collect::<Vec<usize>>();
+		assert_eq!(a.union(&b), by_mask(|x, y| x || y));
+		assert_eq!(a.intersection(&b), by_mask(|x, y| x && y));
+		assert_eq!(a.difference(&b), by_mask(|x, y| x && !y));
+		assert_eq!(b.difference(&b), Vec::<usize>::new())
+	}
+
+	#[test]
+	fn complement_and_inversion_partition_the_range() {
+		let selection = Vector::new(vec![5, 2, 3]);
+		let positions = selection.invert(7);
+		assert_eq!(selection.complement(7), (0..7).filter(|i| positions[*i].is_none()).collect::<Vec<_>>());
+		assert!(selection.iter().enumerate().all(|(k, i)| positions[*i] == Some(k)))
+	}
+
+	#[test]
+	#[should_panic]
+	fn unsorted_index_set() {
+		let _ = Vector::new(vec![3, 1]).union(&Vector::new(vec![2]));
+	}
+
+	#[test]
+	#[should_panic]
+	fn unsorted_bins() {
+		let _ = Vector::new(vec![1.0]).digitize(&Vector::new(vec![2.0, 1.0]));
+	}
+
+}
@@ -0,0 +1,368 @@
+//! Abstract vector spaces.
+//!
+//! Traits describing vector spaces, normed spaces, and inner product spaces,
+//! so that algorithms such as Gram-Schmidt can be written once over any
+//! conforming type, including user-defined ones. [`Vector`]s, arrays,
+//! scalars, and [`Polynomial`]s all conform.
+
+use crate::{Complex, Field};
+
+use super::{Polynomial, Vector};
+
+/// The scalar field a [`VectorSpace`] is defined over.
+pub trait Scalar: Field + PartialEq {
+
+	/// Embeds a real number in the field.
+	fn from_real(x: f64) -> Self;
+
+	/// Returns the complex conjugate of `self`; the identity on real fields.
+	fn conj(self) -> Self;
+
+	/// Returns the absolute value of `self`.
+	fn modulus(self) -> f64;
+
+}
+
+macro_rules! impl_real_scalar {
+	($t:ty) => {
+		impl Scalar for $t {
+			fn from_real(x: f64) -> Self {
+				x as $t
+			}
+			fn conj(self) -> Self {
+				self
+			}
+			fn modulus(self) -> f64 {
+				self.abs() as f64
+			}
+		}
+	}
+}
+
+impl_real_scalar![f32];
+impl_real_scalar![f64];
+
+impl Scalar for Complex {
+	fn from_real(x: f64) -> Self {
+		Complex::new(x, 0.0)
+	}
+	fn conj(self) -> Self {
+		self.conjugate()
+	}
+	fn modulus(self) -> f64 {
+		self.norm().value()
+	}
+}
+
+/// A vector space over the field [`VectorSpace::Scalar`].
+///
+/// Elements need not share a dimension up front, so the additive identity is
+/// produced relative to an existing element.
+pub trait VectorSpace: Clone {
+
+	/// The field the space is defined over.
+	type Scalar: Scalar;
+
+	/// Returns the zero vector of the space `self` lives in.
+	fn zero_like(&self) -> Self;
+
+	/// Returns `self` + `other`.
+	fn plus(&self, other: &Self) -> Self;
+
+	/// Returns `self` - `other`.
+	fn minus(&self, other: &Self) -> Self;
+
+	/// Returns `self` scaled by `s`.
+	fn scaled(&self, s: Self::Scalar) -> Self;
+
+	/// Returns `self` + `a` * `x`.
+	fn axpy(&self, a: Self::Scalar, x: &Self) -> Self {
+		self.plus(&x.scaled(a))
+	}
+
+}
+
+/// A [`VectorSpace`] equipped with a norm.
+pub trait NormedSpace: VectorSpace {
+
+	/// Returns the norm of `self`.
+	fn norm(&self) -> f64;
+
+	/// Returns `self` scaled to unit norm, or `None` if `self` is zero.
+	fn normalized(&self) -> Option<Self> {
+		let n = NormedSpace::norm(self);
+		if n == 0.0 {
+			return None;
+		}
+		Some(self.scaled(Self::Scalar::from_real(1.0 / n)))
+	}
+
+}
+
+/// A [`NormedSpace`] whose norm is induced by an inner product.
+pub trait InnerProductSpace: NormedSpace {
+
+	/// Returns the inner product of `self` and `other`, linear in `self` and
+	/// conjugate-linear in `other`.
+	fn inner(&self, other: &Self) -> Self::Scalar;
+
+}
+
+fn combine<T: Scalar>(a: &[T], b: &[T], f: impl Fn(T, T) -> T) -> Vec<T> {
+	if a.len() != b.len() {
+		panic!("Vectors must be of the same dimension to be combined")
+	}
+	a.iter().zip(b.iter()).map(|(x, y)| f(*x, *y)).collect()
+}
+
+fn inner_of<T: Scalar>(a: &[T], b: &[T]) -> T {
+	if a.len() != b.len() {
+		panic!("Vectors must be of the same dimension to have an inner product")
+	}
+	a.iter().zip(b.iter()).fold(T::from_real(0.0), |acc, (x, y)| acc + *x * y.conj())
+}
+
+fn norm_of<T: Scalar>(a: &[T]) -> f64 {
+	a.iter().map(|x| x.modulus() * x.modulus()).sum::<f64>().sqrt()
+}
+
+impl<T: Scalar> VectorSpace for Vector<T> {
+	type Scalar = T;
+	fn zero_like(&self) -> Self {
+		Vector::new(vec![T::from_real(0.0); self.dim])
+	}
+	fn plus(&self, other: &Self) -> Self {
+		Vector::new(combine(&self.components, &other.components, |x, y| x + y))
+	}
+	fn minus(&self, other: &Self) -> Self {
+		Vector::new(combine(&self.components, &other.components, |x, y| x - y))
+	}
+	fn scaled(&self, s: T) -> Self {
+		Vector::new(self.components.iter().map(|x| *x * s).collect())
+	}
+}
+
+impl<T: Scalar> NormedSpace for Vector<T> {
+	fn norm(&self) -> f64 {
+		norm_of(&self.components)
+	}
+}
+
+impl<T: Scalar> InnerProductSpace for Vector<T> {
+	fn inner(&self, other: &Self) -> T {
+		inner_of(&self.components, &other.components)
+	}
+}
+
+impl<T: Scalar, const N: usize> VectorSpace for [T; N] {
+	type Scalar = T;
+	fn zero_like(&self) -> Self {
+		[T::from_real(0.0); N]
+	}
+	fn plus(&self, other: &Self) -> Self {
+		std::array::from_fn(|i| self[i] + other[i])
+	}
+	fn minus(&self, other: &Self) -> Self {
+		std::array::from_fn(|i| self[i] - other[i])
+	}
+	fn scaled(&self, s: T) -> Self {
+		self.map(|x| x * s)
+	}
+}
+
+impl<T: Scalar, const N: usize> NormedSpace for [T; N] {
+	fn norm(&self) -> f64 {
+		norm_of(self)
+	}
+}
+
+impl<T: Scalar, const N: usize> InnerProductSpace for [T; N] {
+	fn inner(&self, other: &Self) -> T {
+		inner_of(self, other)
+	}
+}
+
+macro_rules! impl_scalar_space {
+	($t:ty) => {
+		impl VectorSpace for $t {
+			type Scalar = $t;
+			fn zero_like(&self) -> Self {
+				<$t as Scalar>::from_real(0.0)
+			}
+			fn plus(&self, other: &Self) -> Self {
+				*self + *other
+			}
+			fn minus(&self, other: &Self) -> Self {
+				*self - *other
+			}
+			fn scaled(&self, s: $t) -> Self {
+				*self * s
+			}
+		}
+
+		impl NormedSpace for $t {
+			fn norm(&self) -> f64 {
+				self.modulus()
+			}
+		}
+
+		impl InnerProductSpace for $t {
+			fn inner(&self, other: &Self) -> $t {
+				*self * other.conj()
+			}
+		}
+	}
+}
+
+impl_scalar_space![f32];
+impl_scalar_space![f64];
+impl_scalar_space![Complex];
+
+impl<T: Scalar> VectorSpace for Polynomial<T> {
+	type Scalar = T;
+	fn zero_like(&self) -> Self {
+		Polynomial::new(Vec::new())
+	}
+	fn plus(&self, other: &Self) -> Self {
+		Polynomial::new(padded(self.coefficients(), other.coefficients(), |x, y| x + y))
+	}
+	fn minus(&self, other: &Self) -> Self {
+		Polynomial::new(padded(self.coefficients(), other.coefficients(), |x, y| x - y))
+	}
+	fn scaled(&self, s: T) -> Self {
+		Polynomial::new(self.coefficients().iter().map(|c| *c * s).collect())
+	}
+}
+
+impl<T: Scalar> NormedSpace for Polynomial<T> {
+	fn norm(&self) -> f64 {
+		self.inner(self).modulus().sqrt()
+	}
+}
+
+impl<T: Scalar> InnerProductSpace for Polynomial<T> {
+	/// Returns ∫ `self`(x) conj(`other`(x)) dx over [-1, 1], using
+	/// ∫ xᵏ dx = 2 / (k + 1) for even k and 0 for odd k.
+	fn inner(&self, other: &Self) -> T {
+		let mut sum = T::from_real(0.0);
+		for (i, a) in self.coefficients().iter().enumerate() {
+			for (j, b) in other.coefficients().iter().enumerate().skip(i % 2).step_by(2) {
+				sum = sum + *a * b.conj() * T::from_real(2.0 / (i + j + 1) as f64);
+			}
+		}
+		sum
+	}
+}
+
+fn padded<T: Scalar>(a: &[T], b: &[T], f: impl Fn(T, T) -> T) -> Vec<T> {
+	let zero = T::from_real(0.0);
+	(0..a.len().max(b.len())).map(|k| {
+		f(a.get(k).copied().unwrap_or(zero), b.get(k).copied().unwrap_or(zero))
+	}).collect()
+}
+
+/// Returns an orthonormal basis for the span of `vectors` in any
+/// [`InnerProductSpace`], computed by modified Gram-Schmidt.
+///
+/// As with [`orthonormalize`](super::orthonormalize), each vector is
+/// orthogonalized twice and vectors that are (numerically) dependent on those
+/// before them are skipped.
+///
+/// ```
+/// # use hebrides::linal::gram_schmidt;
+/// let basis = gram_schmidt(&[[2.0, 0.0], [4.0, 4.0], [1.0, 1.0]]);
+/// assert_eq!(basis, vec![[1.0, 0.0], [0.0, 1.0]]);
+/// ```
+pub fn gram_schmidt<V: InnerProductSpace>(vectors: &[V]) -> Vec<V> {
+	let mut basis: Vec<V> = Vec::new();
+	for v in vectors {
+		let original = NormedSpace::norm(v);
+		let mut w = v.clone();
+		for _ in 0..2 {
+			for q in &basis {
+				w = w.axpy(-w.inner(q), q);
+			}
+		}
+		let residual = NormedSpace::norm(&w);
+		if residual <= original * 16.0 * f64::EPSILON || residual == 0.0 {
+			continue;
+		}
+		basis.push(w.scaled(V::Scalar::from_real(1.0 / residual)));
+	}
+	basis
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn vector_and_array_agree() {
+		let v = Vector::new(vec![1.0, 2.0, 2.0]);
+		let a = [1.0, 2.0, 2.0];
+		assert_eq!(NormedSpace::norm(&v), 3.0);
+		assert_eq!(NormedSpace::norm(&a), 3.0);
+		assert_eq!(v.inner(&v.scaled(2.0)), a.inner(&a.scaled(2.0)));
+		assert_eq!(v.axpy(-1.0, &v), v.zero_like())
+	}
+
+	#[test]
+	fn complex_inner_product_is_conjugate_linear() {
+		let u = Vector::new(vec![Complex::I, Complex::ONE]);
+		assert_eq!(u.inner(&u), Complex::new(2.0, 0.0));
+		assert_eq!(u.inner(&u.scaled(Complex::I)), Complex::new(0.0, -2.0));
+		assert_eq!(Complex::I.inner(&Complex::I), Complex::ONE)
+	}
+
+	#[test]
+	fn gram_schmidt_over_complex_vectors() {
+		let basis = gram_schmidt(&[
+			Vector::new(vec![Complex::ONE, Complex::I]),
+			Vector::new(vec![Complex::I, Complex::ONE]),
+			Vector::new(vec![Complex::new(2.0, 0.0), Complex::new(0.0, 2.0)])
+		]);
+		assert_eq!(basis.len(), 2);
+		assert!(basis[0].inner(&basis[1]).modulus() < 1e-15);
+		assert!(basis.iter().all(|q| (NormedSpace::norm(q) - 1.0).abs() < 1e-15))
+	}
+
+	#[test]
+	fn gram_schmidt_over_monomials_gives_legendre_polynomials() {
+		let monomials: Vec<Polynomial> = (0..3).map(|k| {
+			let mut c = vec![0.0; k + 1];
+			c[k] = 1.0;
+			Polynomial::new(c)
+		}).collect();
+		let basis = gram_schmidt(&monomials);
+		// The normalized P₂ is √(5/2) (3x² - 1) / 2.
+		let scale = (2.5_f64).sqrt() / 2.0;
+		let expected = [-scale, 0.0, 3.0 * scale];
+		assert_eq!(basis.len(), 3);
+		assert!(basis[2].coefficients().iter().zip(expected).all(|(c, e)| (c - e).abs() < 1e-14));
+		assert!(basis[0].inner(&basis[2]).abs() < 1e-15);
+		assert!(basis.iter().all(|q| (NormedSpace::norm(q) - 1.0).abs() < 1e-14))
+	}
+
+	#[test]
+	fn polynomials_of_different_degree_combine() {
+		let p = Polynomial::new(vec![1.0, 2.0]);
+		let q = Polynomial::new(vec![0.0, 0.0, 3.0]);
+		assert_eq!(p.plus(&q), Polynomial::new(vec![1.0, 2.0, 3.0]));
+		assert_eq!(p.minus(&p).degree(), None);
+		assert_eq!(p.inner(&q), 2.0)
+	}
+
+	#[test]
+	fn normalizing_zero_fails() {
+		assert!([0.0_f64; 3].normalized().is_none());
+		assert_eq!(4.0_f64.normalized(), Some(1.0))
+	}
+
+	#[test]
+	#[should_panic]
+	fn mismatched_dimensions() {
+		let _ = Vector::new(vec![1.0]).plus(&Vector::new(vec![1.0, 2.0]));
+	}
+
+}
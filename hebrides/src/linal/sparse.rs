@@ -0,0 +1,677 @@
+//! Sparse matrices in compressed sparse row (CSR) format, and sparse vectors.
+//!
+//! A [`SparseMatrix`] only stores its explicitly provided entries, so matrices
+//! that are almost entirely zero take memory and time proportional to their
+//! number of nonzeros rather than to their full dimensions. A [`SparseVector`]
+//! does the same for vectors, such as text features with millions of
+//! dimensions of which a handful are present.
+
+use std::ops::{Add, Mul, Sub};
+
+use super::{Vector, Matrix, MatrixDimensions};
+use crate::Semiring;
+
+/// Implementation for a sparse matrix over T in compressed sparse row format.
+///
+/// The entries of row `i` are stored in `values[row_offsets[i]..row_offsets[i + 1]]`
+/// with their column indices in the same range of `col_indices`, sorted in
+/// ascending order. Entries that are not stored are `T::default()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMatrix<T> {
+	row_offsets: Vec<usize>,
+	col_indices: Vec<usize>,
+	values: Vec<T>,
+	dims: MatrixDimensions
+}
+
+impl<T> SparseMatrix<T> {
+
+	/// Returns the dimensions of `self`.
+	pub fn dims(&self) -> MatrixDimensions {
+		self.dims
+	}
+
+	/// Returns the number of explicitly stored entries in `self`.
+	pub fn nnz(&self) -> usize {
+		self.values.len()
+	}
+
+	/// Returns the offsets into `col_indices` and `values` at which each row
+	/// of `self` begins, followed by the total number of stored entries.
+	pub fn row_offsets(&self) -> &[usize] {
+		&self.row_offsets
+	}
+
+	/// Returns the column index of each stored entry of `self`.
+	pub fn col_indices(&self) -> &[usize] {
+		&self.col_indices
+	}
+
+	/// Returns the value of each stored entry of `self`.
+	pub fn values(&self) -> &[T] {
+		&self.values
+	}
+
+	/// Returns the stored entry at (`row`, `col`), or `None` if there is none
+	/// or (`row`, `col`) is out of bounds. [`SparseMatrix::entry`] tells the
+	/// two apart.
+	pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+		if row >= self.dims.num_rows || col >= self.dims.num_cols {
+			return None;
+		}
+		let range = self.row_offsets[row]..self.row_offsets[row + 1];
+		self.col_indices[range.clone()].binary_search(&col)
+									   .ok()
+									   .map(|k| &self.values[range.start + k])
+	}
+
+	/// Iterates over the stored entries of the `row`th row of `self` as
+	/// (column, value) pairs.
+	pub fn row(&self, row: usize) -> impl Iterator<Item=(usize, &T)> {
+		let range = self.row_offsets[row]..self.row_offsets[row + 1];
+		self.col_indices[range.clone()].iter().copied().zip(self.values[range].iter())
+	}
+
+}
+
+impl<T> SparseMatrix<T> where T: Copy + Add<Output=T> {
+
+	/// Constructs a [`SparseMatrix`] from (row, column, value) triplets.
+	///
+	/// Triplets may be given in any order; duplicate positions are summed.
+	///
+	/// ```
+	/// # use hebrides::linal::SparseMatrix;
+	/// let m = SparseMatrix::from_triplets(2, 3, &[(1, 2, 4.0), (0, 0, 1.0), (1, 2, 1.0)]);
+	/// assert_eq!(m.nnz(), 2);
+	/// assert_eq!(m.get(1, 2), Some(&5.0));
+	/// assert_eq!(m.get(0, 1), None);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if either dimension is zero or if any triplet is out of bounds.
+	pub fn from_triplets(num_rows: usize, num_cols: usize, triplets: &[(usize, usize, T)]) -> SparseMatrix<T> {
+		if num_rows == 0 || num_cols == 0 {
+			panic!("SparseMatrix must have nonzero dimensions")
+		}
+		if triplets.iter().any(|(i, j, _)| *i >= num_rows || *j >= num_cols) {
+			panic!("Index out of bounds for SparseMatrix")
+		}
+		let mut sorted = triplets.to_vec();
+		sorted.sort_by_key(|(i, j, _)| (*i, *j));
+		let mut row_offsets = vec![0; num_rows + 1];
+		let mut col_indices: Vec<usize> = Vec::with_capacity(sorted.len());
+		let mut values: Vec<T> = Vec::with_capacity(sorted.len());
+		let mut last: Option<(usize, usize)> = None;
+		for (i, j, value) in sorted {
+			if last == Some((i, j)) {
+				let end = values.len() - 1;
+				values[end] = values[end] + value;
+				continue;
+			}
+			col_indices.push(j);
+			values.push(value);
+			row_offsets[i + 1] += 1;
+			last = Some((i, j));
+		}
+		for i in 0..num_rows {
+			row_offsets[i + 1] += row_offsets[i];
+		}
+		Self { row_offsets, col_indices, values, dims: MatrixDimensions::new(num_rows, num_cols) }
+	}
+
+	/// Returns the transpose of `self`.
+	pub fn transpose(&self) -> SparseMatrix<T> {
+		let triplets: Vec<(usize, usize, T)> = (0..self.dims.num_rows).flat_map(|i| self.row(i).map(move |(j, v)| (j, i, *v)))
+																	  .collect();
+		SparseMatrix::from_triplets(self.dims.num_cols, self.dims.num_rows, &triplets)
+	}
+
+}
+
+impl<T> SparseMatrix<T> where T: Copy + Default + PartialEq + Add<Output=T> {
+
+	/// Constructs a [`SparseMatrix`] storing every entry of `dense` that is not
+	/// `T::default()`.
+	pub fn from_dense(dense: &Matrix<T>) -> SparseMatrix<T> {
+		let zero = T::default();
+		let triplets: Vec<(usize, usize, T)> = dense.rows.iter()
+														 .enumerate()
+														 .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, e)| (i, j, *e)))
+														 .filter(|(_, _, e)| *e != zero)
+														 .collect();
+		SparseMatrix::from_triplets(dense.dims.num_rows, dense.dims.num_cols, &triplets)
+	}
+
+}
+
+impl<T> SparseMatrix<T> where T: Copy + Default {
+
+	/// Returns the entry at (`row`, `col`), which is `T::default()` if it is
+	/// not stored, or `None` if (`row`, `col`) is out of bounds.
+	///
+	/// ```
+	/// # use hebrides::linal::SparseMatrix;
+	/// let m = SparseMatrix::from_triplets(2, 2, &[(0, 1, 5)]);
+	/// assert_eq!((m.get(1, 1), m.get(2, 0)), (None, None));
+	/// assert_eq!((m.entry(0, 1), m.entry(1, 1), m.entry(2, 0)), (Some(5), Some(0), None));
+	/// ```
+	pub fn entry(&self, row: usize, col: usize) -> Option<T> {
+		if row >= self.dims.num_rows || col >= self.dims.num_cols {
+			return None;
+		}
+		Some(self.get(row, col).copied().unwrap_or_default())
+	}
+
+	/// Returns the dense [`Matrix`] equivalent to `self`.
+	pub fn to_dense(&self) -> Matrix<T> {
+		let mut rows = vec![vec![T::default(); self.dims.num_cols]; self.dims.num_rows];
+		for (i, row) in rows.iter_mut().enumerate() {
+			for (j, value) in self.row(i) {
+				row[j] = *value;
+			}
+		}
+		Matrix::new(rows)
+	}
+
+}
+
+impl<T> SparseMatrix<T> where T: Semiring {
+
+	/// Multiplies `self` by the dense vector `v`.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, SparseMatrix};
+	/// let m = SparseMatrix::from_triplets(2, 2, &[(0, 1, 2), (1, 0, 3)]);
+	/// assert_eq!(m.matvec(&Vector::new(vec![1, 1])), Vector::new(vec![2, 3]));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `v` does not have as many components as `self` has columns.
+	pub fn matvec(&self, v: &Vector<T>) -> Vector<T> {
+		if v.dim != self.dims.num_cols {
+			panic!("Vector must be compatible with matrix to be transformed by it")
+		}
+		let components = (0..self.dims.num_rows).map(|i| self.row(i).fold(T::default(), |sum, (j, value)| sum + *value * v[j]))
+												.collect();
+		Vector::new(components)
+	}
+
+}
+
+impl<T> Mul<&Vector<T>> for &SparseMatrix<T> where T: Semiring {
+	type Output = Vector<T>;
+	fn mul(self, other: &Vector<T>) -> Vector<T> {
+		self.matvec(other)
+	}
+}
+
+impl<T> Add<Self> for SparseMatrix<T> where T: Copy + Add<Output=T> {
+	type Output = Self;
+	fn add(self, other: Self) -> Self {
+		if self.dims != other.dims {
+			panic!("Sums can only be taken between matrices of the same dimensions")
+		}
+		let mut row_offsets = Vec::with_capacity(self.dims.num_rows + 1);
+		let mut col_indices = Vec::with_capacity(self.nnz() + other.nnz());
+		let mut values = Vec::with_capacity(self.nnz() + other.nnz());
+		row_offsets.push(0);
+		for i in 0..self.dims.num_rows {
+			let mut a = self.row(i).peekable();
+			let mut b = other.row(i).peekable();
+			loop {
+				let (j, value) = match (a.peek(), b.peek()) {
+					(Some(&(ja, va)), Some(&(jb, vb))) if ja == jb => {
+						a.next();
+						b.next();
+						(ja, *va + *vb)
+					},
+					(Some(&(ja, va)), Some(&(jb, _))) if ja < jb => {
+						a.next();
+						(ja, *va)
+					},
+					(_, Some(&(jb, vb))) => {
+						b.next();
+						(jb, *vb)
+					},
+					(Some(&(ja, va)), None) => {
+						a.next();
+						(ja, *va)
+					},
+					(None, None) => break
+				};
+				col_indices.push(j);
+				values.push(value);
+			}
+			row_offsets.push(values.len());
+		}
+		Self { row_offsets, col_indices, values, dims: self.dims }
+	}
+}
+
+impl<T> SparseMatrix<T> where T: Semiring {
+
+	/// Multiplies `self` by the sparse vector `v`, touching only the stored
+	/// entries of both.
+	///
+	/// # Panics
+	/// Panics if `v` does not have as many components as `self` has columns.
+	pub fn matvec_sparse(&self, v: &SparseVector<T>) -> Vector<T> {
+		if v.dim != self.dims.num_cols {
+			panic!("Vector must be compatible with matrix to be transformed by it")
+		}
+		let components = (0..self.dims.num_rows).map(|i| {
+			let range = self.row_offsets[i]..self.row_offsets[i + 1];
+			merged_dot(&self.col_indices[range.clone()], &self.values[range], &v.indices, &v.values)
+		}).collect();
+		Vector::new(components)
+	}
+
+}
+
+impl SparseMatrix<f64> {
+
+	/// Solves `self` x = `b` for symmetric positive definite `self` by the
+	/// conjugate gradient method, which needs only products with `self` and
+	/// so never fills in its zeros.
+	///
+	/// Iterates until the residual norm falls below `tolerance` times the
+	/// norm of `b`, returning `None` if that takes more than
+	/// `max_iterations` steps. In exact arithmetic the method terminates in
+	/// at most n steps.
+	///
+	/// ```
+	/// # use hebrides::linal::{SparseMatrix, SparseVector};
+	/// // The 1D Laplacian, with a point source as the right-hand side.
+	/// let n = 100;
+	/// let triplets: Vec<(usize, usize, f64)> = (0..n).flat_map(|i| {
+	///     let mut row = vec![(i, i, 2.0)];
+	///     if i > 0 { row.push((i, i - 1, -1.0)) }
+	///     if i + 1 < n { row.push((i, i + 1, -1.0)) }
+	///     row
+	/// }).collect();
+	/// let laplacian = SparseMatrix::from_triplets(n, n, &triplets);
+	/// let source = SparseVector::from_pairs(n, &[(49, 1.0)]);
+	/// let x = laplacian.conjugate_gradient(&source.to_dense(), 1e-12, n).unwrap();
+	/// assert!((laplacian.matvec(&x)[49] - 1.0).abs() < 1e-10);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square or if `b` does not have as many
+	/// components as `self` has rows.
+	pub fn conjugate_gradient(&self, b: &Vector<f64>, tolerance: f64, max_iterations: usize) -> Option<Vector<f64>> {
+		if self.dims.num_rows != self.dims.num_cols {
+			panic!("Conjugate gradients need a square matrix")
+		}
+		if b.dim != self.dims.num_rows {
+			panic!("Right-hand side must have as many components as the Matrix has rows")
+		}
+		let target = tolerance * b.dot(b).sqrt();
+		let mut x = Vector::zeros(b.dim);
+		let mut residual = b.clone();
+		let mut direction = residual.clone();
+		let mut residual_norm = residual.dot(&residual);
+		for _ in 0..=max_iterations {
+			if residual_norm.sqrt() <= target {
+				return Some(x);
+			}
+			let image = self.matvec(&direction);
+			let step = residual_norm / direction.dot(&image);
+			x += direction.clone() * step;
+			residual -= image * step;
+			let next_norm = residual.dot(&residual);
+			direction = residual.clone() + direction * (next_norm / residual_norm);
+			residual_norm = next_norm;
+		}
+		None
+	}
+
+}
+
+/// Returns the dot product of two sparse vectors given by sorted indices and
+/// their values.
+fn merged_dot<T>(a_indices: &[usize], a_values: &[T], b_indices: &[usize], b_values: &[T]) -> T where T: Semiring {
+	let (mut i, mut j) = (0, 0);
+	let mut sum = T::default();
+	while i < a_indices.len() && j < b_indices.len() {
+		match a_indices[i].cmp(&b_indices[j]) {
+			std::cmp::Ordering::Less => i += 1,
+			std::cmp::Ordering::Greater => j += 1,
+			std::cmp::Ordering::Equal => {
+				sum = sum + a_values[i] * b_values[j];
+				i += 1;
+				j += 1;
+			}
+		}
+	}
+	sum
+}
+
+/// Implementation for a sparse vector over T as sorted (index, value) pairs.
+///
+/// Components that are not stored are `T::default()`.
+///
+/// ```
+/// # use hebrides::linal::{Vector, SparseVector};
+/// let words = SparseVector::from_pairs(1_000_000, &[(42, 2.0), (7, 1.0), (999_999, 3.0)]);
+/// let other = SparseVector::from_pairs(1_000_000, &[(42, 0.5), (8, 4.0)]);
+/// assert_eq!(words.nnz(), 3);
+/// assert_eq!(words.dot_sparse(&other), 1.0);
+/// assert_eq!(words.get(7), Some(&1.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseVector<T> {
+	dim: usize,
+	indices: Vec<usize>,
+	values: Vec<T>
+}
+
+impl<T> SparseVector<T> {
+
+	/// Returns the dimension of `self`, including the components that are
+	/// not stored.
+	pub fn dim(&self) -> usize {
+		self.dim
+	}
+
+	/// Returns the number of explicitly stored components of `self`.
+	pub fn nnz(&self) -> usize {
+		self.values.len()
+	}
+
+	/// Returns the indices of the stored components of `self`, in
+	/// ascending order.
+	pub fn indices(&self) -> &[usize] {
+		&self.indices
+	}
+
+	/// Returns the values of the stored components of `self`.
+	pub fn values(&self) -> &[T] {
+		&self.values
+	}
+
+	/// Returns the stored component at `index`, or `None` if there is none
+	/// or `index` is out of bounds. [`SparseVector::entry`] tells the two
+	/// apart.
+	pub fn get(&self, index: usize) -> Option<&T> {
+		if index >= self.dim {
+			return None;
+		}
+		self.indices.binary_search(&index).ok().map(|k| &self.values[k])
+	}
+
+	/// Iterates over the stored components of `self` as (index, value)
+	/// pairs.
+	pub fn iter(&self) -> impl Iterator<Item=(usize, &T)> {
+		self.indices.iter().copied().zip(self.values.iter())
+	}
+
+}
+
+impl<T> SparseVector<T> where T: Copy + Add<Output=T> {
+
+	/// Constructs a `dim`-dimensional [`SparseVector`] from (index, value)
+	/// pairs.
+	///
+	/// Pairs may be given in any order; duplicate indices are summed.
+	///
+	/// # Panics
+	/// Panics if any index is out of bounds.
+	pub fn from_pairs(dim: usize, pairs: &[(usize, T)]) -> SparseVector<T> {
+		if pairs.iter().any(|(i, _)| *i >= dim) {
+			panic!("Index out of bounds for SparseVector")
+		}
+		let mut sorted = pairs.to_vec();
+		sorted.sort_by_key(|(i, _)| *i);
+		let mut indices: Vec<usize> = Vec::with_capacity(sorted.len());
+		let mut values: Vec<T> = Vec::with_capacity(sorted.len());
+		for (i, value) in sorted {
+			if indices.last() == Some(&i) {
+				let end = values.len() - 1;
+				values[end] = values[end] + value;
+				continue;
+			}
+			indices.push(i);
+			values.push(value);
+		}
+		SparseVector { dim, indices, values }
+	}
+
+}
+
+impl<T> SparseVector<T> where T: Copy + Default + PartialEq + Add<Output=T> {
+
+	/// Constructs a [`SparseVector`] storing every component of `dense` that
+	/// is not `T::default()`.
+	pub fn from_dense(dense: &Vector<T>) -> SparseVector<T> {
+		let zero = T::default();
+		let (indices, values) = dense.iter().copied().enumerate().filter(|(_, e)| *e != zero).unzip();
+		SparseVector { dim: dense.dim, indices, values }
+	}
+
+}
+
+impl<T> SparseVector<T> where T: Copy + Default {
+
+	/// Returns the component at `index`, which is `T::default()` if it is not
+	/// stored, or `None` if `index` is out of bounds.
+	pub fn entry(&self, index: usize) -> Option<T> {
+		if index >= self.dim {
+			return None;
+		}
+		Some(self.get(index).copied().unwrap_or_default())
+	}
+
+	/// Gathers the components of `dense` at `indices` into a
+	/// [`SparseVector`], whatever their values.
+	///
+	/// # Panics
+	/// Panics if any index is out of bounds for `dense`.
+	pub fn gather(dense: &Vector<T>, indices: &[usize]) -> SparseVector<T> {
+		if indices.iter().any(|i| *i >= dense.dim) {
+			panic!("Index out of bounds for Vector")
+		}
+		let mut indices = indices.to_vec();
+		indices.sort_unstable();
+		indices.dedup();
+		let values = indices.iter().map(|i| dense[*i]).collect();
+		SparseVector { dim: dense.dim, indices, values }
+	}
+
+	/// Returns the dense [`Vector`] equivalent to `self`.
+	pub fn to_dense(&self) -> Vector<T> {
+		let mut dense = Vector::new(vec![T::default(); self.dim]);
+		self.scatter(&mut dense);
+		dense
+	}
+
+	/// Scatters the stored components of `self` into `dense`, overwriting
+	/// the components at their indices and leaving the rest unchanged.
+	///
+	/// # Panics
+	/// Panics if `dense` is not of the same dimension as `self`.
+	pub fn scatter(&self, dense: &mut Vector<T>) {
+		if dense.dim != self.dim {
+			panic!("Vectors must be of the same dimension to scatter into")
+		}
+		for (i, value) in self.iter() {
+			dense[i] = *value;
+		}
+	}
+
+}
+
+impl<T> SparseVector<T> where T: Semiring {
+
+	/// Returns the dot product of `self` with the dense vector `other`, in
+	/// time proportional to the stored components of `self`.
+	///
+	/// # Panics
+	/// Panics if `other` is not of the same dimension as `self`.
+	pub fn dot(&self, other: &Vector<T>) -> T {
+		if other.dim != self.dim {
+			panic!("Vectors must be of the same dimension for their dot product to be taken")
+		}
+		self.iter().fold(T::default(), |sum, (i, value)| sum + *value * other[i])
+	}
+
+	/// Returns the dot product of `self` with the sparse vector `other`, in
+	/// time proportional to their stored components.
+	///
+	/// # Panics
+	/// Panics if `other` is not of the same dimension as `self`.
+	pub fn dot_sparse(&self, other: &SparseVector<T>) -> T {
+		if other.dim != self.dim {
+			panic!("Vectors must be of the same dimension for their dot product to be taken")
+		}
+		merged_dot(&self.indices, &self.values, &other.indices, &other.values)
+	}
+
+	/// Returns `self` with every stored component multiplied by `scalar`.
+	pub fn scale(&self, scalar: T) -> SparseVector<T> {
+		SparseVector { dim: self.dim, indices: self.indices.clone(), values: self.values.iter().map(|v| *v * scalar).collect() }
+	}
+
+}
+
+impl<T> Add<Self> for SparseVector<T> where T: Copy + Add<Output=T> {
+	type Output = Self;
+	fn add(self, other: Self) -> Self {
+		if self.dim != other.dim {
+			panic!("Sums can only be taken between vectors of the same dimension")
+		}
+		let pairs: Vec<(usize, T)> = self.iter().chain(other.iter()).map(|(i, v)| (i, *v)).collect();
+		SparseVector::from_pairs(self.dim, &pairs)
+	}
+}
+
+impl<T> Sub<Self> for SparseVector<T> where T: Copy + Add<Output=T> + std::ops::Neg<Output=T> {
+	type Output = Self;
+	fn sub(self, other: Self) -> Self {
+		if self.dim != other.dim {
+			panic!("Differences can only be taken between vectors of the same dimension")
+		}
+		let pairs: Vec<(usize, T)> = self.iter().map(|(i, v)| (i, *v)).chain(other.iter().map(|(i, v)| (i, -*v))).collect();
+		SparseVector::from_pairs(self.dim, &pairs)
+	}
+}
+
+impl<T> Mul<&SparseVector<T>> for &SparseMatrix<T> where T: Semiring {
+	type Output = Vector<T>;
+	fn mul(self, other: &SparseVector<T>) -> Vector<T> {
+		self.matvec_sparse(other)
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+	use crate::elem::ApproxEq;
+
+	fn example() -> Matrix<i32> {
+		Matrix::new(vec![
+			vec![0, 2, 0, 0],
+			vec![1, 0, 0, 3],
+			vec![0, 0, 0, 0]
+		])
+	}
+
+	#[test]
+	fn dense_round_trip() {
+		let sparse = SparseMatrix::from_dense(&example());
+		assert_eq!(sparse.nnz(), 3);
+		assert_eq!(sparse.row_offsets(), &[0, 1, 3, 3]);
+		assert_eq!(sparse.col_indices(), &[1, 0, 3]);
+		assert_eq!(sparse.to_dense(), example())
+	}
+
+	#[test]
+	fn matvec_matches_dense() {
+		let sparse = SparseMatrix::from_dense(&example());
+		let v = Vector::new(vec![1, 2, 3, 4]);
+		assert_eq!(&sparse * &v, example() * v)
+	}
+
+	#[test]
+	fn addition() {
+		let a = SparseMatrix::from_triplets(2, 3, &[(0, 0, 1), (0, 2, 2), (1, 1, 3)]);
+		let b = SparseMatrix::from_triplets(2, 3, &[(0, 1, 4), (0, 2, 5), (1, 0, 6)]);
+		let expected = Matrix::new(vec![
+			vec![1, 4, 7],
+			vec![6, 3, 0]
+		]);
+		assert_eq!((a + b).to_dense(), expected)
+	}
+
+	#[test]
+	fn transpose() {
+		let sparse = SparseMatrix::from_dense(&example());
+		assert_eq!(sparse.transpose().to_dense(), example().transpose())
+	}
+
+	#[test]
+	fn sparse_vector_round_trip_and_products() {
+		let dense = Vector::new(vec![0, 3, 0, 0, -2, 0]);
+		let sparse = SparseVector::from_dense(&dense);
+		assert_eq!(sparse.indices(), &[1, 4]);
+		assert_eq!(sparse.to_dense(), dense);
+		let other = Vector::new(vec![5, 1, 2, 7, 4, 9]);
+		assert_eq!(sparse.dot(&other), dense.dot(&other));
+		assert_eq!(sparse.dot_sparse(&SparseVector::from_dense(&other)), dense.dot(&other));
+		let gathered = SparseVector::gather(&other, &[5, 0, 5]);
+		assert_eq!((gathered.indices(), gathered.values()), (&[0, 5][..], &[5, 9][..]));
+		let mut target = Vector::new(vec![1; 6]);
+		sparse.scatter(&mut target);
+		assert_eq!(target, Vector::new(vec![1, 3, 1, 1, -2, 1]))
+	}
+
+	#[test]
+	fn sparse_vector_arithmetic() {
+		let a = SparseVector::from_pairs(5, &[(3, 1.0), (0, 2.0), (3, 0.5)]);
+		let b = SparseVector::from_pairs(5, &[(1, 4.0), (3, 1.5)]);
+		assert_eq!((a.clone() + b.clone()).to_dense(), Vector::new(vec![2.0, 4.0, 0.0, 3.0, 0.0]));
+		assert_eq!((a.clone() - b).to_dense(), Vector::new(vec![2.0, -4.0, 0.0, 0.0, 0.0]));
+		assert_eq!(a.scale(2.0).get(3), Some(&3.0));
+		assert_eq!(a.get(4), None);
+		assert_eq!(a.get(5), None);
+		assert_eq!((a.entry(4), a.entry(5)), (Some(0.0), None))
+	}
+
+	#[test]
+	fn sparse_matvec_matches_dense() {
+		let sparse = SparseMatrix::from_dense(&example());
+		let v = SparseVector::from_pairs(4, &[(3, 2), (1, -1)]);
+		assert_eq!(&sparse * &v, example() * v.to_dense())
+	}
+
+	#[test]
+	fn conjugate_gradient_solves_spd_system() {
+		let a = SparseMatrix::from_triplets(3, 3, &[(0, 0, 4.0), (0, 1, 1.0), (1, 0, 1.0), (1, 1, 3.0), (2, 2, 2.0)]);
+		let b = SparseVector::from_pairs(3, &[(0, 1.0), (2, 4.0)]).to_dense();
+		let x = a.conjugate_gradient(&b, 1e-14, 10).unwrap();
+		assert!(x.abs_diff_eq(&Vector::new(vec![3.0 / 11.0, -1.0 / 11.0, 2.0]), 1e-14));
+		assert!(a.conjugate_gradient(&b, 1e-14, 0).is_none())
+	}
+
+	#[test]
+	#[should_panic]
+	fn out_of_bounds_pair() {
+		let _ = SparseVector::from_pairs(3, &[(3, 1.0)]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn out_of_bounds_triplet() {
+		let _ = SparseMatrix::from_triplets(2, 2, &[(2, 0, 1.0)]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn mismatched_sum() {
+		let a = SparseMatrix::from_triplets(2, 2, &[(0, 0, 1)]);
+		let b = SparseMatrix::from_triplets(2, 3, &[(0, 0, 1)]);
+		let _ = a + b;
+	}
+
+}
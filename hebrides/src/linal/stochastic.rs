@@ -0,0 +1,155 @@
+//! Probability vectors and stochastic matrices.
+//!
+//! Projection onto the probability simplex, row and column normalization,
+//! and predicates recognizing the transition matrices of Markov chains, for
+//! [`f32`] and [`f64`].
+
+use super::{Vector, Matrix};
+
+macro_rules! impl_stochastic {
+	($t:ty) => {
+		impl Vector<$t> {
+
+			/// Returns the Euclidean projection of `self` onto the probability
+			/// simplex, the closest vector with nonnegative components summing to
+			/// one.
+			///
+			/// ```
+			/// # use hebrides::linal::Vector;
+			#[doc = concat!("let v = Vector::new(vec![0.5_", stringify!($t), ", 1.5, -1.0]);")]
+			/// assert_eq!(v.project_to_simplex(), Vector::new(vec![0.0, 1.0, 0.0]));
+			/// ```
+			pub fn project_to_simplex(&self) -> Self {
+				let mut sorted = self.components.clone();
+				sorted.sort_by(|a, b| b.total_cmp(a));
+				let mut sum = 0.0;
+				let mut threshold = 0.0;
+				for (j, u) in sorted.iter().enumerate() {
+					sum += u;
+					let candidate = (sum - 1.0) / (j + 1) as $t;
+					if *u > candidate {
+						threshold = candidate;
+					}
+				}
+				Vector::new(self.components.iter().map(|e| (e - threshold).max(0.0)).collect())
+			}
+
+		}
+
+		impl Matrix<$t> {
+
+			/// Divides each row of `self` by its sum, so that every row sums to
+			/// one.
+			///
+			/// ```
+			/// # use hebrides::linal::Matrix;
+			#[doc = concat!("let mut counts = Matrix::new(vec![vec![1.0_", stringify!($t), ", 3.0], vec![2.0, 2.0]]);")]
+			/// counts.normalize_rows();
+			/// assert_eq!(counts, Matrix::new(vec![vec![0.25, 0.75], vec![0.5, 0.5]]));
+			/// assert!(counts.is_row_stochastic());
+			/// ```
+			///
+			/// # Panics
+			/// Panics if some row sums to zero.
+			pub fn normalize_rows(&mut self) {
+				let rows = self.rows.iter().map(|row| {
+					let sum: $t = row.iter().sum();
+					if sum == 0.0 {
+						panic!("Rows must have nonzero sums to be normalized")
+					}
+					row.iter().map(|e| e / sum).collect()
+				}).collect();
+				*self = Matrix::new(rows);
+			}
+
+			/// Divides each column of `self` by its sum, so that every column
+			/// sums to one.
+			///
+			/// # Panics
+			/// Panics if some column sums to zero.
+			pub fn normalize_columns(&mut self) {
+				let sums: Vec<$t> = self.cols.iter().map(|col| col.iter().sum()).collect();
+				if sums.contains(&0.0) {
+					panic!("Columns must have nonzero sums to be normalized")
+				}
+				let rows = self.rows.iter().map(|row| row.iter().zip(sums.iter()).map(|(e, s)| e / s).collect()).collect();
+				*self = Matrix::new(rows);
+			}
+
+			/// Returns whether or not `self` has nonnegative entries and rows
+			/// summing to within `epsilon` of one.
+			pub fn is_row_stochastic_within(&self, epsilon: $t) -> bool {
+				self.rows.iter().all(|row| row.iter().all(|e| *e >= 0.0) && (row.iter().sum::<$t>() - 1.0).abs() <= epsilon)
+			}
+
+			/// Returns whether or not `self` has nonnegative entries and columns
+			/// summing to within `epsilon` of one.
+			pub fn is_column_stochastic_within(&self, epsilon: $t) -> bool {
+				self.cols.iter().all(|col| col.iter().all(|e| *e >= 0.0) && (col.iter().sum::<$t>() - 1.0).abs() <= epsilon)
+			}
+
+			/// Returns whether or not `self` is row stochastic up to the rounding
+			/// error of summing its rows.
+			pub fn is_row_stochastic(&self) -> bool {
+				self.is_row_stochastic_within(self.dims.num_cols as $t * <$t>::EPSILON)
+			}
+
+			/// Returns whether or not `self` is column stochastic up to the
+			/// rounding error of summing its columns.
+			pub fn is_column_stochastic(&self) -> bool {
+				self.is_column_stochastic_within(self.dims.num_rows as $t * <$t>::EPSILON)
+			}
+
+		}
+	}
+}
+
+impl_stochastic![f32];
+impl_stochastic![f64];
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn simplex_projection_is_idempotent_and_feasible() {
+		let v = Vector::new(vec![0.3_f64, -0.2, 0.9, 0.4]);
+		let p = v.project_to_simplex();
+		assert!(p.iter().all(|e| *e >= 0.0));
+		assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-15);
+		assert!(p.project_to_simplex().iter().zip(p.iter()).all(|(a, b)| (a - b).abs() < 1e-15))
+	}
+
+	#[test]
+	fn simplex_projection_is_closest_point() {
+		let v = Vector::new(vec![0.3_f64, -0.2, 0.9, 0.4]);
+		let p = v.project_to_simplex();
+		let distance = |q: &Vector<f64>| q.iter().zip(v.iter()).map(|(a, b)| (a - b).powi(2)).sum::<f64>();
+		for q in [vec![0.25, 0.25, 0.25, 0.25], vec![0.0, 0.0, 1.0, 0.0], vec![0.1, 0.0, 0.6, 0.3]] {
+			assert!(distance(&p) <= distance(&Vector::new(q)));
+		}
+	}
+
+	#[test]
+	fn normalized_columns_are_column_stochastic() {
+		let mut m = Matrix::new(vec![vec![1.0_f64, 0.0, 7.0], vec![2.0, 5.0, 0.1], vec![3.0, 1.0, 3.0]]);
+		m.normalize_columns();
+		assert!(m.is_column_stochastic());
+		assert!(!m.is_row_stochastic());
+		assert_eq!(m.cols[0], vec![1.0 / 6.0, 2.0 / 6.0, 3.0 / 6.0])
+	}
+
+	#[test]
+	fn negative_entries_are_not_stochastic() {
+		let m = Matrix::new(vec![vec![1.5_f32, -0.5], vec![0.5, 0.5]]);
+		assert!(!m.is_row_stochastic())
+	}
+
+	#[test]
+	#[should_panic]
+	fn zero_row() {
+		Matrix::new(vec![vec![1.0_f64, -1.0], vec![1.0, 1.0]]).normalize_rows();
+	}
+
+}
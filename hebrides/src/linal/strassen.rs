@@ -0,0 +1,387 @@
+//! Fast matrix multiplication.
+//!
+//! [`MulContext`] selects between the standard algorithm and the Strassen and
+//! Strassen-Winograd recursions, falling back to the standard algorithm once
+//! subproblems shrink below a crossover size. The context owns the scratch
+//! memory the recursions need, so repeated multiplications through the same
+//! context do not reallocate.
+
+
+use super::{Matrix, MatrixDimensions};
+use crate::{Semiring, Ring};
+use crate::random::Rng;
+
+/// Algorithm used by a [`MulContext`] to multiply matrices.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MulAlgorithm {
+	/// The standard O(n³) algorithm.
+	Naive,
+	/// Strassen's algorithm: seven recursive products and eighteen additions.
+	Strassen,
+	/// The Winograd variant of Strassen's algorithm: seven recursive products
+	/// and fifteen additions.
+	Winograd
+}
+
+/// Options and reusable scratch memory for matrix multiplication.
+#[derive(Debug, Clone)]
+pub struct MulContext<T> {
+	algorithm: MulAlgorithm,
+	crossover: usize,
+	scratch: Vec<T>
+}
+
+impl<T> MulContext<T> {
+
+	/// Default dimension at or below which subproblems use the standard algorithm.
+	pub const DEFAULT_CROSSOVER: usize = 64;
+
+	/// Constructs a [`MulContext`] using `algorithm` with the default crossover.
+	///
+	/// With the `tune` feature enabled, the default is the tuned crossover
+	/// once [`crate::tune`] has cached one, and [`Self::DEFAULT_CROSSOVER`]
+	/// until then.
+	pub fn new(algorithm: MulAlgorithm) -> MulContext<T> {
+		#[cfg(feature = "tune")]
+		if let Some(parameters) = crate::tune::cached() {
+			return Self { algorithm, crossover: parameters.crossover, scratch: Vec::new() };
+		}
+		Self { algorithm, crossover: Self::DEFAULT_CROSSOVER, scratch: Vec::new() }
+	}
+
+	/// Sets the dimension at or below which subproblems use the standard algorithm.
+	///
+	/// # Panics
+	/// Panics if `crossover` is zero.
+	pub fn with_crossover(mut self, crossover: usize) -> MulContext<T> {
+		if crossover == 0 {
+			panic!("Crossover must be positive")
+		}
+		self.crossover = crossover;
+		self
+	}
+
+	/// Returns the algorithm used by `self`.
+	pub fn algorithm(&self) -> MulAlgorithm {
+		self.algorithm
+	}
+
+	/// Returns the dimension at or below which subproblems use the standard algorithm.
+	pub fn crossover(&self) -> usize {
+		self.crossover
+	}
+
+}
+
+/// Writes the product of the `n`x`n` views `a` and `b` (with row strides
+/// `sa` and `sb`) into the contiguous `n`x`n` buffer `c`.
+fn naive<T>(a: &[T], sa: usize, b: &[T], sb: usize, c: &mut [T], n: usize) where T: Semiring {
+	for i in 0..n {
+		let row = &mut c[i * n..(i + 1) * n];
+		row.fill(T::default());
+		for k in 0..n {
+			let aik = a[i * sa + k];
+			for (j, e) in row.iter_mut().enumerate() {
+				*e = *e + aik * b[k * sb + j];
+			}
+		}
+	}
+}
+
+/// Writes `f` applied to corresponding entries of the `n`x`n` views `a` and
+/// `b` into the contiguous buffer `out`.
+fn combine<T>(a: &[T], sa: usize, b: &[T], sb: usize, out: &mut [T], n: usize, f: impl Fn(T, T) -> T) where T: Copy {
+	for i in 0..n {
+		for j in 0..n {
+			out[i * n + j] = f(a[i * sa + j], b[i * sb + j]);
+		}
+	}
+}
+
+/// Returns the `n`x`n` quadrants of a view with row stride `s` in the order
+/// top-left, top-right, bottom-left, bottom-right.
+fn quadrants<T>(m: &[T], s: usize, n: usize) -> [&[T]; 4] {
+	[m, &m[n..], &m[n * s..], &m[n * s + n..]]
+}
+
+/// Returns the number of scratch entries needed to multiply `n`x`n` matrices
+/// with `algorithm`.
+fn scratch_len(algorithm: MulAlgorithm, n: usize, crossover: usize) -> usize {
+	let per_level = match algorithm {
+		MulAlgorithm::Naive => return 0,
+		MulAlgorithm::Strassen => 9,
+		MulAlgorithm::Winograd => 15
+	};
+	let mut total = 0;
+	let mut m = n;
+	while m > crossover {
+		m /= 2;
+		total += per_level * m * m;
+	}
+	total
+}
+
+/// Writes the product of the `n`x`n` views `a` and `b` into `c` using
+/// Strassen's algorithm.
+fn strassen<T>((a, sa): (&[T], usize), (b, sb): (&[T], usize), c: &mut [T], n: usize, crossover: usize, scratch: &mut [T])
+where T: Ring {
+	if n <= crossover || !n.is_multiple_of(2) {
+		return naive(a, sa, b, sb, c, n);
+	}
+	let h = n / 2;
+	let (level, rest) = scratch.split_at_mut(9 * h * h);
+	let mut parts = level.chunks_mut(h * h);
+	let mut next = || parts.next().unwrap();
+	let (t1, t2) = (next(), next());
+	let (m1, m2, m3, m4, m5, m6, m7) = (next(), next(), next(), next(), next(), next(), next());
+	let [a11, a12, a21, a22] = quadrants(a, sa, h);
+	let [b11, b12, b21, b22] = quadrants(b, sb, h);
+	let add = |x: T, y: T| x + y;
+	let sub = |x: T, y: T| x - y;
+
+	combine(a11, sa, a22, sa, t1, h, add);
+	combine(b11, sb, b22, sb, t2, h, add);
+	strassen((t1, h), (t2, h), m1, h, crossover, rest);
+	combine(a21, sa, a22, sa, t1, h, add);
+	strassen((t1, h), (b11, sb), m2, h, crossover, rest);
+	combine(b12, sb, b22, sb, t2, h, sub);
+	strassen((a11, sa), (t2, h), m3, h, crossover, rest);
+	combine(b21, sb, b11, sb, t2, h, sub);
+	strassen((a22, sa), (t2, h), m4, h, crossover, rest);
+	combine(a11, sa, a12, sa, t1, h, add);
+	strassen((t1, h), (b22, sb), m5, h, crossover, rest);
+	combine(a21, sa, a11, sa, t1, h, sub);
+	combine(b11, sb, b12, sb, t2, h, add);
+	strassen((t1, h), (t2, h), m6, h, crossover, rest);
+	combine(a12, sa, a22, sa, t1, h, sub);
+	combine(b21, sb, b22, sb, t2, h, add);
+	strassen((t1, h), (t2, h), m7, h, crossover, rest);
+
+	for i in 0..h {
+		for j in 0..h {
+			let k = i * h + j;
+			c[i * n + j] = m1[k] + m4[k] - m5[k] + m7[k];
+			c[i * n + j + h] = m3[k] + m5[k];
+			c[(i + h) * n + j] = m2[k] + m4[k];
+			c[(i + h) * n + j + h] = m1[k] - m2[k] + m3[k] + m6[k];
+		}
+	}
+}
+
+/// Writes the product of the `n`x`n` views `a` and `b` into `c` using the
+/// Winograd variant of Strassen's algorithm.
+fn winograd<T>((a, sa): (&[T], usize), (b, sb): (&[T], usize), c: &mut [T], n: usize, crossover: usize, scratch: &mut [T])
+where T: Ring {
+	if n <= crossover || !n.is_multiple_of(2) {
+		return naive(a, sa, b, sb, c, n);
+	}
+	let h = n / 2;
+	let (level, rest) = scratch.split_at_mut(15 * h * h);
+	let mut parts = level.chunks_mut(h * h);
+	let mut next = || parts.next().unwrap();
+	let (s1, s2, s3, s4) = (next(), next(), next(), next());
+	let (t1, t2, t3, t4) = (next(), next(), next(), next());
+	let (p1, p2, p3, p4, p5, p6, p7) = (next(), next(), next(), next(), next(), next(), next());
+	let [a11, a12, a21, a22] = quadrants(a, sa, h);
+	let [b11, b12, b21, b22] = quadrants(b, sb, h);
+	let add = |x: T, y: T| x + y;
+	let sub = |x: T, y: T| x - y;
+
+	combine(a21, sa, a22, sa, s1, h, add);
+	combine(s1, h, a11, sa, s2, h, sub);
+	combine(a11, sa, a21, sa, s3, h, sub);
+	combine(a12, sa, s2, h, s4, h, sub);
+	combine(b12, sb, b11, sb, t1, h, sub);
+	combine(b22, sb, t1, h, t2, h, sub);
+	combine(b22, sb, b12, sb, t3, h, sub);
+	combine(t2, h, b21, sb, t4, h, sub);
+
+	winograd((a11, sa), (b11, sb), p1, h, crossover, rest);
+	winograd((a12, sa), (b21, sb), p2, h, crossover, rest);
+	winograd((s4, h), (b22, sb), p3, h, crossover, rest);
+	winograd((a22, sa), (t4, h), p4, h, crossover, rest);
+	winograd((s1, h), (t1, h), p5, h, crossover, rest);
+	winograd((s2, h), (t2, h), p6, h, crossover, rest);
+	winograd((s3, h), (t3, h), p7, h, crossover, rest);
+
+	for i in 0..h {
+		for j in 0..h {
+			let k = i * h + j;
+			let u2 = p1[k] + p6[k];
+			let u3 = u2 + p7[k];
+			let u4 = u2 + p5[k];
+			c[i * n + j] = p1[k] + p2[k];
+			c[i * n + j + h] = u4 + p3[k];
+			c[(i + h) * n + j] = u3 - p4[k];
+			c[(i + h) * n + j + h] = u3 + p5[k];
+		}
+	}
+}
+
+impl<T> MulContext<T> where T: Ring {
+
+	/// Returns the smallest padded dimension at least `n` that halves evenly
+	/// down to a size no greater than the crossover.
+	fn padded_dim(&self, n: usize) -> usize {
+		let mut levels = 0;
+		while n.div_ceil(1 << levels) > self.crossover {
+			levels += 1;
+		}
+		n.div_ceil(1 << levels) << levels
+	}
+
+	/// Multiplies `a` by `b` using the algorithm and crossover of `self`.
+	///
+	/// ```
+	/// # use hebrides::linal::{Matrix, MulAlgorithm, MulContext};
+	/// let mut context = MulContext::new(MulAlgorithm::Strassen).with_crossover(1);
+	/// let a = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+	/// let b = Matrix::new(vec![vec![7, 8], vec![9, 10], vec![11, 12]]);
+	/// assert_eq!(context.multiply(&a, &b), a * b);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `a` and `b` have incompatible dimensions.
+	pub fn multiply(&mut self, a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+		if !MatrixDimensions::are_compatible(a.dims, b.dims) {
+			panic!("Matrices must have compatible dimensions to be multiplied")
+		}
+		let largest = a.dims.num_rows.max(a.dims.num_cols).max(b.dims.num_cols);
+		if self.algorithm == MulAlgorithm::Naive || largest <= self.crossover {
+			return a.clone() * b.clone();
+		}
+		let n = self.padded_dim(largest);
+		let pad = |m: &Matrix<T>| {
+			let mut flat = vec![T::default(); n * n];
+			for (i, row) in m.rows.iter().enumerate() {
+				flat[i * n..i * n + row.len()].copy_from_slice(row);
+			}
+			flat
+		};
+		let (pa, pb) = (pad(a), pad(b));
+		let mut pc = vec![T::default(); n * n];
+		let needed = scratch_len(self.algorithm, n, self.crossover);
+		if self.scratch.len() < needed {
+			self.scratch.resize(needed, T::default());
+		}
+		let scratch = &mut self.scratch[..needed];
+		match self.algorithm {
+			MulAlgorithm::Strassen => strassen((&pa, n), (&pb, n), &mut pc, n, self.crossover, scratch),
+			MulAlgorithm::Winograd => winograd((&pa, n), (&pb, n), &mut pc, n, self.crossover, scratch),
+			MulAlgorithm::Naive => unreachable!()
+		}
+		let rows = (0..a.dims.num_rows).map(|i| pc[i * n..i * n + b.dims.num_cols].to_vec()).collect();
+		Matrix::new(rows)
+	}
+
+}
+
+impl MulContext<f64> {
+
+	/// Benchmarks candidate crossovers on random `size`x`size` matrices and
+	/// keeps the fastest, returning it.
+	///
+	/// Candidates are the powers of two from 16 up to `size`. Tuning is only
+	/// meaningful on the machine that will perform the multiplications.
+	pub fn tune(&mut self, size: usize) -> usize {
+		let mut rng = Rng::new(size as u64);
+		let mut random = || Matrix::new((0..size).map(|_| (0..size).map(|_| rng.next_f64()).collect()).collect());
+		let (a, b) = (random(), random());
+		let mut best = (self.crossover, std::time::Duration::MAX);
+		let mut candidate = 16;
+		while candidate <= size.max(16) {
+			self.crossover = candidate;
+			let start = std::time::Instant::now();
+			let _ = self.multiply(&a, &b);
+			let elapsed = start.elapsed();
+			if elapsed < best.1 {
+				best = (candidate, elapsed);
+			}
+			candidate *= 2;
+		}
+		self.crossover = best.0;
+		self.crossover
+	}
+
+}
+
+impl<T> Matrix<T> where T: Ring {
+
+	/// Multiplies `self` by `other` using the options held by `context`.
+	pub fn mul_with(&self, other: &Matrix<T>, context: &mut MulContext<T>) -> Matrix<T> {
+		context.multiply(self, other)
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn sequential(rows: usize, cols: usize, offset: i64) -> Matrix<i64> {
+		Matrix::new((0..rows).map(|i| (0..cols).map(|j| ((i * cols + j) as i64 + offset) % 7 - 3).collect()).collect())
+	}
+
+	#[test]
+	fn strassen_matches_naive() {
+		let a = sequential(37, 23, 1);
+		let b = sequential(23, 41, 2);
+		let mut context = MulContext::new(MulAlgorithm::Strassen).with_crossover(4);
+		assert_eq!(context.multiply(&a, &b), a * b)
+	}
+
+	#[test]
+	fn winograd_matches_naive() {
+		let a = sequential(33, 33, 4);
+		let b = sequential(33, 17, 5);
+		let mut context = MulContext::new(MulAlgorithm::Winograd).with_crossover(3);
+		assert_eq!(a.mul_with(&b, &mut context), a * b)
+	}
+
+	#[test]
+	fn scratch_is_reused() {
+		let mut context = MulContext::new(MulAlgorithm::Winograd).with_crossover(8);
+		let a = sequential(40, 40, 0);
+		let _ = context.multiply(&a, &a);
+		let capacity = context.scratch.len();
+		let b = sequential(20, 20, 0);
+		let _ = context.multiply(&b, &b);
+		assert_eq!(context.scratch.len(), capacity)
+	}
+
+	#[test]
+	fn padded_dimension_halves_to_crossover() {
+		let context: MulContext<f64> = MulContext::new(MulAlgorithm::Strassen).with_crossover(10);
+		assert_eq!(context.padded_dim(10), 10);
+		assert_eq!(context.padded_dim(21), 24);
+		assert_eq!(context.padded_dim(100), 112)
+	}
+
+	#[test]
+	fn float_products_agree() {
+		let mut rng = Rng::new(1);
+		let mut random = |n: usize| Matrix::new((0..n).map(|_| (0..n).map(|_| rng.next_f64()).collect()).collect());
+		let (a, b) = (random(50), random(50));
+		let mut context = MulContext::new(MulAlgorithm::Strassen).with_crossover(8);
+		let fast = context.multiply(&a, &b);
+		let slow = a * b;
+		assert!(fast.rows.iter().flatten().zip(slow.rows.iter().flatten()).all(|(x, y)| (x - y).abs() < 1e-10))
+	}
+
+	#[test]
+	fn tune_picks_a_candidate() {
+		let mut context = MulContext::new(MulAlgorithm::Strassen);
+		let crossover = context.tune(64);
+		assert!([16, 32, 64].contains(&crossover));
+		assert_eq!(context.crossover(), crossover)
+	}
+
+	#[test]
+	#[should_panic]
+	fn incompatible_dimensions() {
+		let mut context = MulContext::new(MulAlgorithm::Strassen);
+		let _ = context.multiply(&sequential(2, 3, 0), &sequential(2, 3, 0));
+	}
+
+}
@@ -0,0 +1,603 @@
+//! Triangular and banded matrices.
+//!
+//! [`TriangularMatrix`] and [`BandedMatrix`] store only the entries their
+//! structure allows to be nonzero, so products and solves cost time in
+//! proportion to that structure rather than to the full square matrix.
+
+use std::ops::Mul;
+
+use super::{Vector, Matrix};
+use crate::Semiring;
+
+/// Which half of a square matrix a [`TriangularMatrix`] occupies.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Triangle {
+	/// Entries on and below the diagonal.
+	Lower,
+	/// Entries on and above the diagonal.
+	Upper
+}
+
+/// Implementation for a square triangular matrix over T with packed storage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriangularMatrix<T> {
+	entries: Vec<T>,
+	dim: usize,
+	triangle: Triangle
+}
+
+impl<T> TriangularMatrix<T> {
+
+	/// Returns the number of rows (and columns) of `self`.
+	pub fn dim(&self) -> usize {
+		self.dim
+	}
+
+	/// Returns which half of the matrix `self` occupies.
+	pub fn triangle(&self) -> Triangle {
+		self.triangle
+	}
+
+	/// Returns whether or not (`row`, `col`) lies in the stored triangle.
+	fn stores(&self, row: usize, col: usize) -> bool {
+		match self.triangle {
+			Triangle::Lower => col <= row,
+			Triangle::Upper => col >= row
+		}
+	}
+
+	/// Returns the range of columns stored in the `row`th row of `self`.
+	fn row_range(&self, row: usize) -> std::ops::Range<usize> {
+		match self.triangle {
+			Triangle::Lower => 0..row + 1,
+			Triangle::Upper => row..self.dim
+		}
+	}
+
+	/// Returns the position in `entries` of the stored entry (`row`, `col`).
+	fn offset(&self, row: usize, col: usize) -> usize {
+		match self.triangle {
+			Triangle::Lower => row * (row + 1) / 2 + col,
+			Triangle::Upper => row * self.dim - row * row.saturating_sub(1) / 2 + col - row
+		}
+	}
+
+	/// Returns the entry at (`row`, `col`), or `None` if it lies outside the
+	/// stored triangle or out of bounds. [`TriangularMatrix::entry`] tells
+	/// the two apart.
+	pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+		if row >= self.dim || col >= self.dim {
+			return None;
+		}
+		if !self.stores(row, col) {
+			return None;
+		}
+		Some(&self.entries[self.offset(row, col)])
+	}
+
+}
+
+impl<T> TriangularMatrix<T> where T: Copy {
+
+	/// Constructs a [`TriangularMatrix`] from the given triangle of `dense`,
+	/// ignoring the entries outside it.
+	///
+	/// # Panics
+	/// Panics if `dense` is not square.
+	pub fn from_dense(dense: &Matrix<T>, triangle: Triangle) -> TriangularMatrix<T> {
+		if !dense.is_square() {
+			panic!("Matrix must be square to be triangular")
+		}
+		let dim = dense.dims.num_rows;
+		let mut m = Self { entries: Vec::with_capacity(dim * (dim + 1) / 2), dim, triangle };
+		for (i, row) in dense.rows.iter().enumerate() {
+			let range = m.row_range(i);
+			m.entries.extend_from_slice(&row[range]);
+		}
+		m
+	}
+
+	/// Constructs a lower [`TriangularMatrix`] from `dense`.
+	pub fn lower(dense: &Matrix<T>) -> TriangularMatrix<T> {
+		TriangularMatrix::from_dense(dense, Triangle::Lower)
+	}
+
+	/// Constructs an upper [`TriangularMatrix`] from `dense`.
+	pub fn upper(dense: &Matrix<T>) -> TriangularMatrix<T> {
+		TriangularMatrix::from_dense(dense, Triangle::Upper)
+	}
+
+}
+
+impl<T> TriangularMatrix<T> where T: Copy + Default {
+
+	/// Returns the entry at (`row`, `col`), which is `T::default()` outside
+	/// the stored triangle, or `None` if (`row`, `col`) is out of bounds.
+	pub fn entry(&self, row: usize, col: usize) -> Option<T> {
+		if row >= self.dim || col >= self.dim {
+			return None;
+		}
+		Some(self.get(row, col).copied().unwrap_or_default())
+	}
+
+	/// Returns the dense [`Matrix`] equivalent to `self`.
+	pub fn to_dense(&self) -> Matrix<T> {
+		Matrix::new((0..self.dim).map(|i| (0..self.dim).map(|j| self.get(i, j).copied().unwrap_or_default()).collect())
+								 .collect())
+	}
+
+}
+
+impl<T> TriangularMatrix<T> where T: Semiring {
+
+	/// Multiplies `self` by the vector `v`.
+	///
+	/// # Panics
+	/// Panics if `v` does not have as many components as `self` has columns.
+	pub fn matvec(&self, v: &Vector<T>) -> Vector<T> {
+		if v.dim != self.dim {
+			panic!("Vector must be compatible with matrix to be transformed by it")
+		}
+		let components = (0..self.dim).map(|i| self.row_range(i).fold(T::default(), |sum, j| sum + self.entries[self.offset(i, j)] * v[j]))
+									  .collect();
+		Vector::new(components)
+	}
+
+}
+
+impl<T> Mul<&Vector<T>> for &TriangularMatrix<T> where T: Semiring {
+	type Output = Vector<T>;
+	fn mul(self, other: &Vector<T>) -> Vector<T> {
+		self.matvec(other)
+	}
+}
+
+impl<T> Mul<Self> for &TriangularMatrix<T> where T: Semiring {
+	type Output = TriangularMatrix<T>;
+	fn mul(self, other: Self) -> TriangularMatrix<T> {
+		if self.dim != other.dim {
+			panic!("Matrices must have compatible dimensions to be multiplied")
+		}
+		if self.triangle != other.triangle {
+			panic!("TriangularMatrix products must be between matrices of the same Triangle")
+		}
+		let mut product = TriangularMatrix { entries: Vec::with_capacity(self.entries.len()), dim: self.dim, triangle: self.triangle };
+		for i in 0..self.dim {
+			for j in self.row_range(i) {
+				let (start, end) = match self.triangle {
+					Triangle::Lower => (j, i),
+					Triangle::Upper => (i, j)
+				};
+				let sum = (start..=end).fold(T::default(), |sum, k| sum + self.entries[self.offset(i, k)] * other.entries[other.offset(k, j)]);
+				product.entries.push(sum);
+			}
+		}
+		product
+	}
+}
+
+impl TriangularMatrix<f64> {
+
+	/// Solves `self` x = `b` for x by forward or back substitution.
+	///
+	/// Returns `None` if `self` has a zero on its diagonal.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, Matrix, TriangularMatrix};
+	/// let l = TriangularMatrix::lower(&Matrix::new(vec![vec![2.0, 0.0], vec![1.0, 4.0]]));
+	/// assert_eq!(l.solve(&Vector::new(vec![2.0, 9.0])), Some(Vector::new(vec![1.0, 2.0])));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `b` does not have as many components as `self` has rows.
+	pub fn solve(&self, b: &Vector<f64>) -> Option<Vector<f64>> {
+		if b.dim != self.dim {
+			panic!("Right-hand side must have as many components as the Matrix has rows")
+		}
+		if (0..self.dim).any(|i| self.entries[self.offset(i, i)] == 0.0) {
+			return None;
+		}
+		let mut x = b.components.clone();
+		let order: Box<dyn Iterator<Item=usize>> = match self.triangle {
+			Triangle::Lower => Box::new(0..self.dim),
+			Triangle::Upper => Box::new((0..self.dim).rev())
+		};
+		for i in order {
+			for j in self.row_range(i).filter(|j| *j != i) {
+				x[i] -= self.entries[self.offset(i, j)] * x[j];
+			}
+			x[i] /= self.entries[self.offset(i, i)];
+		}
+		Some(Vector::new(x))
+	}
+
+}
+
+/// Implementation for a square banded matrix over T.
+///
+/// Only the entries at most `lower` places below and `upper` places above
+/// the diagonal are stored; all others are `T::default()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandedMatrix<T> {
+	entries: Vec<T>,
+	dim: usize,
+	lower: usize,
+	upper: usize
+}
+
+impl<T> BandedMatrix<T> {
+
+	/// Returns the number of rows (and columns) of `self`.
+	pub fn dim(&self) -> usize {
+		self.dim
+	}
+
+	/// Returns the lower and upper bandwidths of `self`.
+	pub fn bandwidths(&self) -> (usize, usize) {
+		(self.lower, self.upper)
+	}
+
+	/// Returns the number of stored entries per row of `self`.
+	fn width(&self) -> usize {
+		self.lower + self.upper + 1
+	}
+
+	/// Returns whether or not (`row`, `col`) lies within the band of `self`.
+	fn stores(&self, row: usize, col: usize) -> bool {
+		col + self.lower >= row && col <= row + self.upper
+	}
+
+	/// Returns the range of in-bounds columns in the band of the `row`th row.
+	fn row_range(&self, row: usize) -> std::ops::Range<usize> {
+		row.saturating_sub(self.lower)..(row + self.upper + 1).min(self.dim)
+	}
+
+	/// Returns the position in `entries` of the banded entry (`row`, `col`).
+	fn offset(&self, row: usize, col: usize) -> usize {
+		row * self.width() + col + self.lower - row
+	}
+
+	/// Returns the entry at (`row`, `col`), or `None` if it lies outside the
+	/// band or out of bounds. [`BandedMatrix::entry`] tells the two apart.
+	pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+		if row >= self.dim || col >= self.dim {
+			return None;
+		}
+		if !self.stores(row, col) {
+			return None;
+		}
+		Some(&self.entries[self.offset(row, col)])
+	}
+
+	/// Sets the entry at (`row`, `col`) to `value`.
+	///
+	/// # Panics
+	/// Panics if (`row`, `col`) is out of bounds or outside the band.
+	pub fn set(&mut self, row: usize, col: usize, value: T) {
+		if row >= self.dim || col >= self.dim {
+			panic!("Index out of bounds for BandedMatrix")
+		}
+		if !self.stores(row, col) {
+			panic!("Entry lies outside the band of the BandedMatrix")
+		}
+		let offset = self.offset(row, col);
+		self.entries[offset] = value;
+	}
+
+}
+
+impl<T> BandedMatrix<T> where T: Copy + Default {
+
+	/// Constructs a `dim`x`dim` [`BandedMatrix`] of `T::default()` with the
+	/// given bandwidths.
+	///
+	/// # Panics
+	/// Panics if `dim` is zero.
+	pub fn zeros(dim: usize, lower: usize, upper: usize) -> BandedMatrix<T> {
+		if dim == 0 {
+			panic!("BandedMatrix must have nonzero dimension")
+		}
+		let (lower, upper) = (lower.min(dim - 1), upper.min(dim - 1));
+		Self { entries: vec![T::default(); dim * (lower + upper + 1)], dim, lower, upper }
+	}
+
+	/// Constructs a [`BandedMatrix`] from the band of `dense`, ignoring the
+	/// entries outside it.
+	///
+	/// # Panics
+	/// Panics if `dense` is not square.
+	pub fn from_dense(dense: &Matrix<T>, lower: usize, upper: usize) -> BandedMatrix<T> {
+		if !dense.is_square() {
+			panic!("Matrix must be square to be banded")
+		}
+		let mut m = BandedMatrix::zeros(dense.dims.num_rows, lower, upper);
+		for i in 0..m.dim {
+			for j in m.row_range(i) {
+				m.set(i, j, dense.rows[i][j]);
+			}
+		}
+		m
+	}
+
+	/// Constructs a tridiagonal [`BandedMatrix`] from its subdiagonal,
+	/// diagonal, and superdiagonal.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, Matrix, BandedMatrix};
+	/// let m = BandedMatrix::tridiagonal(&Vector::new(vec![1, 1]), &Vector::new(vec![2, 2, 2]), &Vector::new(vec![3, 3]));
+	/// assert_eq!(m.to_dense(), Matrix::new(vec![vec![2, 3, 0], vec![1, 2, 3], vec![0, 1, 2]]));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `sub` and `sup` do not have one fewer component than `diag`.
+	pub fn tridiagonal(sub: &Vector<T>, diag: &Vector<T>, sup: &Vector<T>) -> BandedMatrix<T> {
+		if sub.dim + 1 != diag.dim || sup.dim + 1 != diag.dim {
+			panic!("Off-diagonals must have one fewer component than the diagonal")
+		}
+		let mut m = BandedMatrix::zeros(diag.dim, 1, 1);
+		for i in 0..diag.dim {
+			m.set(i, i, diag[i]);
+			if i + 1 < diag.dim {
+				m.set(i + 1, i, sub[i]);
+				m.set(i, i + 1, sup[i]);
+			}
+		}
+		m
+	}
+
+	/// Returns the entry at (`row`, `col`), which is `T::default()` outside
+	/// the band, or `None` if (`row`, `col`) is out of bounds.
+	pub fn entry(&self, row: usize, col: usize) -> Option<T> {
+		if row >= self.dim || col >= self.dim {
+			return None;
+		}
+		Some(self.get(row, col).copied().unwrap_or_default())
+	}
+
+	/// Returns the dense [`Matrix`] equivalent to `self`.
+	pub fn to_dense(&self) -> Matrix<T> {
+		Matrix::new((0..self.dim).map(|i| (0..self.dim).map(|j| self.get(i, j).copied().unwrap_or_default()).collect())
+								 .collect())
+	}
+
+}
+
+impl<T> BandedMatrix<T> where T: Semiring {
+
+	/// Multiplies `self` by the vector `v`.
+	///
+	/// # Panics
+	/// Panics if `v` does not have as many components as `self` has columns.
+	pub fn matvec(&self, v: &Vector<T>) -> Vector<T> {
+		if v.dim != self.dim {
+			panic!("Vector must be compatible with matrix to be transformed by it")
+		}
+		let components = (0..self.dim).map(|i| self.row_range(i).fold(T::default(), |sum, j| sum + self.entries[self.offset(i, j)] * v[j]))
+									  .collect();
+		Vector::new(components)
+	}
+
+}
+
+impl<T> Mul<&Vector<T>> for &BandedMatrix<T> where T: Semiring {
+	type Output = Vector<T>;
+	fn mul(self, other: &Vector<T>) -> Vector<T> {
+		self.matvec(other)
+	}
+}
+
+impl<T> Mul<Self> for &BandedMatrix<T> where T: Semiring {
+	type Output = BandedMatrix<T>;
+	fn mul(self, other: Self) -> BandedMatrix<T> {
+		if self.dim != other.dim {
+			panic!("Matrices must have compatible dimensions to be multiplied")
+		}
+		let mut product = BandedMatrix::zeros(self.dim, self.lower + other.lower, self.upper + other.upper);
+		for i in 0..self.dim {
+			for k in self.row_range(i) {
+				let a = self.entries[self.offset(i, k)];
+				for j in other.row_range(k) {
+					let offset = product.offset(i, j);
+					product.entries[offset] = product.entries[offset] + a * other.entries[other.offset(k, j)];
+				}
+			}
+		}
+		product
+	}
+}
+
+impl BandedMatrix<f64> {
+
+	/// Solves `self` x = `b` for x by banded Gaussian elimination with
+	/// partial pivoting.
+	///
+	/// Runs in O(n·`lower`·(`lower` + `upper`)) time. Returns `None` if `self`
+	/// is (numerically) singular.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, BandedMatrix};
+	/// let m = BandedMatrix::tridiagonal(&Vector::new(vec![-1.0]), &Vector::new(vec![2.0, 2.0]), &Vector::new(vec![-1.0]));
+	/// assert_eq!(m.solve(&Vector::new(vec![1.0, 1.0])), Some(Vector::new(vec![1.0, 1.0])));
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `b` does not have as many components as `self` has rows.
+	pub fn solve(&self, b: &Vector<f64>) -> Option<Vector<f64>> {
+		let n = self.dim;
+		if b.dim != n {
+			panic!("Right-hand side must have as many components as the Matrix has rows")
+		}
+		// Pivoting can push fill-in up to `lower` places past the upper band.
+		let mut work = BandedMatrix::zeros(n, self.lower, self.lower + self.upper);
+		for i in 0..n {
+			for j in self.row_range(i) {
+				work.set(i, j, self.entries[self.offset(i, j)]);
+			}
+		}
+		let mut x = b.components.clone();
+		let scale = self.entries.iter().fold(0.0_f64, |max, e| max.max(e.abs()));
+		let tolerance = scale * n as f64 * f64::EPSILON;
+		for k in 0..n {
+			let last = (k + work.lower + 1).min(n);
+			let pivot = (k..last).max_by(|p, q| work.entries[work.offset(*p, k)].abs().total_cmp(&work.entries[work.offset(*q, k)].abs()))
+								 .unwrap();
+			let columns = k..(k + work.upper + 1).min(n);
+			if pivot != k {
+				for j in columns.clone() {
+					let (a, b) = (work.offset(k, j), work.offset(pivot, j));
+					work.entries.swap(a, b);
+				}
+				x.swap(k, pivot);
+			}
+			let diagonal = work.entries[work.offset(k, k)];
+			if diagonal.abs() <= tolerance {
+				return None;
+			}
+			for r in k + 1..last {
+				let factor = work.entries[work.offset(r, k)] / diagonal;
+				for j in columns.clone() {
+					let offset = work.offset(r, j);
+					work.entries[offset] -= factor * work.entries[work.offset(k, j)];
+				}
+				x[r] -= factor * x[k];
+			}
+		}
+		for i in (0..n).rev() {
+			for j in i + 1..(i + work.upper + 1).min(n) {
+				x[i] -= work.entries[work.offset(i, j)] * x[j];
+			}
+			x[i] /= work.entries[work.offset(i, i)];
+		}
+		Some(Vector::new(x))
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn dense() -> Matrix<f64> {
+		Matrix::new(vec![
+			vec![4.0, 1.0, 2.0, 0.0],
+			vec![3.0, 5.0, 1.0, 7.0],
+			vec![1.0, 2.0, 6.0, 1.0],
+			vec![8.0, 1.0, 3.0, 9.0]
+		])
+	}
+
+	fn approx_eq(a: &Vector<f64>, b: &Vector<f64>) -> bool {
+		(0..a.dim).all(|i| (a[i] - b[i]).abs() < 1e-12)
+	}
+
+	mod triangular {
+
+		use super::*;
+
+		#[test]
+		fn dense_round_trip() {
+			let upper = TriangularMatrix::upper(&dense());
+			assert_eq!(upper.get(0, 3), Some(&0.0));
+			assert_eq!(upper.get(3, 0), None);
+			assert_eq!(upper.get(4, 0), None);
+			assert_eq!((upper.entry(3, 0), upper.entry(0, 4)), (Some(0.0), None));
+			assert_eq!(upper.to_dense().rows[1], vec![0.0, 5.0, 1.0, 7.0]);
+			let lower = TriangularMatrix::lower(&dense());
+			assert_eq!(lower.to_dense().rows[2], vec![1.0, 2.0, 6.0, 0.0])
+		}
+
+		#[test]
+		fn product_matches_dense() {
+			for triangle in [Triangle::Lower, Triangle::Upper] {
+				let t = TriangularMatrix::from_dense(&dense(), triangle);
+				let product = &t * &t;
+				assert_eq!(product.triangle(), triangle);
+				assert_eq!(product.to_dense(), t.to_dense() * t.to_dense());
+			}
+		}
+
+		#[test]
+		fn solve_inverts_matvec() {
+			let x = Vector::new(vec![1.0, -2.0, 0.5, 3.0]);
+			for triangle in [Triangle::Lower, Triangle::Upper] {
+				let t = TriangularMatrix::from_dense(&dense(), triangle);
+				assert!(approx_eq(&t.solve(&(&t * &x)).unwrap(), &x));
+			}
+		}
+
+		#[test]
+		fn zero_diagonal_is_singular() {
+			let t = TriangularMatrix::lower(&Matrix::new(vec![vec![1.0, 0.0], vec![1.0, 0.0]]));
+			assert!(t.solve(&Vector::new(vec![1.0, 1.0])).is_none())
+		}
+
+		#[test]
+		#[should_panic]
+		fn mixed_triangles() {
+			let _ = &TriangularMatrix::lower(&dense()) * &TriangularMatrix::upper(&dense());
+		}
+
+	}
+
+	mod banded {
+
+		use super::*;
+
+		#[test]
+		fn from_dense_keeps_band() {
+			let m = BandedMatrix::from_dense(&dense(), 1, 2);
+			assert_eq!(m.get(0, 2), Some(&2.0));
+			assert_eq!(m.get(3, 0), None);
+			assert_eq!(m.get(0, 4), None);
+			assert_eq!((m.entry(3, 0), m.entry(4, 0)), (Some(0.0), None));
+			assert_eq!(m.to_dense().rows[3], vec![0.0, 0.0, 3.0, 9.0])
+		}
+
+		#[test]
+		fn matvec_and_product_match_dense() {
+			let a = BandedMatrix::from_dense(&dense(), 1, 0);
+			let b = BandedMatrix::from_dense(&dense(), 1, 2);
+			let v = Vector::new(vec![1.0, 2.0, 3.0, 4.0]);
+			assert_eq!(&b * &v, b.to_dense() * v);
+			let product = &a * &b;
+			assert_eq!(product.bandwidths(), (2, 2));
+			assert_eq!(product.to_dense(), a.to_dense() * b.to_dense())
+		}
+
+		#[test]
+		fn solve_requires_pivoting() {
+			let m = BandedMatrix::from_dense(&Matrix::new(vec![
+				vec![0.0, 1.0, 0.0],
+				vec![2.0, 1.0, 1.0],
+				vec![0.0, 3.0, 1.0]
+			]), 1, 1);
+			let x = Vector::new(vec![1.0, 2.0, 3.0]);
+			assert!(approx_eq(&m.solve(&(&m * &x)).unwrap(), &x))
+		}
+
+		#[test]
+		fn large_tridiagonal_solve() {
+			let n = 1000;
+			let m = BandedMatrix::tridiagonal(&Vector::new(vec![-1.0; n - 1]), &Vector::new(vec![2.0; n]), &Vector::new(vec![-1.0; n - 1]));
+			let x = Vector::new((0..n).map(|i| (i as f64).sin()).collect());
+			let solution = m.solve(&(&m * &x)).unwrap();
+			assert!((0..n).all(|i| (solution[i] - x[i]).abs() < 1e-6))
+		}
+
+		#[test]
+		fn singular() {
+			let m = BandedMatrix::tridiagonal(&Vector::new(vec![1.0]), &Vector::new(vec![1.0, 1.0]), &Vector::new(vec![1.0]));
+			assert!(m.solve(&Vector::new(vec![1.0, 2.0])).is_none())
+		}
+
+		#[test]
+		#[should_panic]
+		fn set_outside_band() {
+			let mut m: BandedMatrix<f64> = BandedMatrix::zeros(3, 1, 0);
+			m.set(0, 1, 1.0);
+		}
+
+	}
+
+}
@@ -0,0 +1,205 @@
+//! Fundamental subspaces.
+//!
+//! Bases for the column and null spaces of float matrices, computed from
+//! their reduced row echelon forms, and the angles between subspaces.
+
+use super::{Vector, Matrix, orthonormalize};
+use super::trace::eliminate;
+
+/// Returns the principal angles, in radians and increasing order, between
+/// the subspaces spanned by `basis_a` and `basis_b`.
+///
+/// There are as many angles as the smaller of the two subspace dimensions.
+/// They are computed from the singular values of the product of orthonormal
+/// bases, switching to the singular values of the residual for angles below
+/// pi/4, where cosines lose precision.
+///
+/// ```
+/// # use hebrides::linal::{Vector, principal_angles};
+/// let plane = [Vector::new(vec![1.0, 0.0, 0.0]), Vector::new(vec![0.0, 1.0, 0.0])];
+/// let line = [Vector::new(vec![0.0, 1.0, 1.0])];
+/// let angles = principal_angles(&plane, &line);
+/// assert!((angles[0] - std::f64::consts::FRAC_PI_4).abs() < 1e-15);
+/// ```
+///
+/// # Panics
+/// Panics if the vectors are not all of the same dimension.
+pub fn principal_angles(basis_a: &[Vector<f64>], basis_b: &[Vector<f64>]) -> Vec<f64> {
+	let dim = basis_a.iter().chain(basis_b).map(|v| v.dim).next();
+	if basis_a.iter().chain(basis_b).any(|v| Some(v.dim) != dim) {
+		panic!("Vectors must be of the same dimension to compare their spans")
+	}
+	let (mut qa, mut qb) = (orthonormalize(basis_a), orthonormalize(basis_b));
+	if qa.len() < qb.len() {
+		std::mem::swap(&mut qa, &mut qb);
+	}
+	if qb.is_empty() {
+		return Vec::new();
+	}
+	let cross = Matrix::new(qa.iter().map(|a| qb.iter().map(|b| a.dot(b)).collect()).collect());
+	let cosines = cross.svd().singular_values().to_vec();
+	let residual = Matrix::new((0..qa[0].dim).map(|i| (0..qb.len()).map(|j| {
+		qb[j][i] - (0..qa.len()).map(|k| qa[k][i] * cross.rows[k][j]).sum::<f64>()
+	}).collect()).collect());
+	let mut sines = residual.svd().singular_values().to_vec();
+	sines.reverse();
+	cosines.iter().zip(sines.iter()).map(|(c, s)| {
+		if s * s < 0.5 { s.min(1.0).asin() } else { c.min(1.0).acos() }
+	}).collect()
+}
+
+/// Returns the distance between the subspaces spanned by `basis_a` and
+/// `basis_b`: the 2-norm of the difference of their orthogonal projectors.
+///
+/// This is the sine of the largest principal angle for subspaces of equal
+/// dimension, and 1 for subspaces of different dimensions.
+///
+/// # Panics
+/// Panics if the vectors are not all of the same dimension.
+pub fn subspace_distance(basis_a: &[Vector<f64>], basis_b: &[Vector<f64>]) -> f64 {
+	let angles = principal_angles(basis_a, basis_b);
+	if orthonormalize(basis_a).len() != orthonormalize(basis_b).len() {
+		return 1.0;
+	}
+	angles.last().map_or(0.0, |theta| theta.sin())
+}
+
+impl Matrix<f64> {
+
+	/// Returns the reduced row echelon form of `self` along with the indices
+	/// of its pivot columns.
+	///
+	/// Elimination uses partial pivoting; entries smaller than a tolerance
+	/// relative to the largest entry of `self` are treated as zero. See
+	/// [`Matrix::rref_traced`] for a record of the row operations.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// let m = Matrix::new(vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 7.0]]);
+	/// let (r, pivots) = m.rref();
+	/// assert_eq!(r, Matrix::new(vec![vec![1.0, 2.0, 0.0], vec![0.0, 0.0, 1.0]]));
+	/// assert_eq!(pivots, vec![0, 2]);
+	/// ```
+	pub fn rref(&self) -> (Matrix<f64>, Vec<usize>) {
+		let mut rows = self.rows.clone();
+		let pivots = eliminate(&mut rows, true, None);
+		(Matrix::new(rows), pivots)
+	}
+
+	/// Returns the rank of `self`.
+	pub fn rank(&self) -> usize {
+		self.rref().1.len()
+	}
+
+	/// Returns a basis for the column space of `self`: its pivot columns.
+	///
+	/// The basis is empty if `self` is zero.
+	pub fn column_space(&self) -> Vec<Vector<f64>> {
+		self.rref().1.into_iter().map(|j| Vector::new(self.cols[j].clone())).collect()
+	}
+
+	/// Returns a basis for the null space of `self`, the solutions to
+	/// `self` x = 0.
+	///
+	/// There is one basis vector per free column of the reduced row echelon
+	/// form of `self`; the basis is empty if `self` has full column rank.
+	///
+	/// ```
+	/// # use hebrides::linal::{Vector, Matrix};
+	/// let m = Matrix::new(vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 7.0]]);
+	/// assert_eq!(m.null_space(), vec![Vector::new(vec![-2.0, 1.0, 0.0])]);
+	/// ```
+	pub fn null_space(&self) -> Vec<Vector<f64>> {
+		let n = self.dims.num_cols;
+		let (r, pivots) = self.rref();
+		(0..n).filter(|j| !pivots.contains(j)).map(|free| {
+			let mut x = vec![0.0; n];
+			x[free] = 1.0;
+			for (i, p) in pivots.iter().enumerate() {
+				x[*p] = -r.rows[i][free];
+			}
+			Vector::new(x)
+		}).collect()
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn example() -> Matrix<f64> {
+		Matrix::new(vec![
+			vec![1.0, 2.0, 0.0, 1.0],
+			vec![2.0, 4.0, 1.0, 4.0],
+			vec![3.0, 6.0, 1.0, 5.0]
+		])
+	}
+
+	#[test]
+	fn rank_nullity() {
+		let m = example();
+		assert_eq!(m.rank(), 2);
+		assert_eq!(m.column_space().len() + m.null_space().len(), m.dims.num_cols)
+	}
+
+	#[test]
+	fn null_space_solves_homogeneous_system() {
+		let m = example();
+		for x in m.null_space() {
+			let image = m.clone() * x;
+			assert!((0..3).all(|i| image[i].abs() < 1e-12));
+		}
+	}
+
+	#[test]
+	fn column_space_uses_pivot_columns() {
+		let m = example();
+		assert_eq!(m.column_space(), vec![Vector::new(vec![1.0, 2.0, 3.0]), Vector::new(vec![0.0, 1.0, 1.0])])
+	}
+
+	#[test]
+	fn full_rank_and_zero_matrices() {
+		let identity = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+		assert!(identity.null_space().is_empty());
+		let zero = Matrix::new(vec![vec![0.0, 0.0]]);
+		assert!(zero.column_space().is_empty());
+		assert_eq!(zero.null_space().len(), 2)
+	}
+
+	#[test]
+	fn principal_angles_of_identical_and_orthogonal_subspaces() {
+		let a = [Vector::new(vec![1.0, 1.0, 0.0, 0.0]), Vector::new(vec![0.0, 1.0, 1.0, 0.0])];
+		let b = [Vector::new(vec![1.0, 2.0, 1.0, 0.0]), Vector::new(vec![1.0, 0.0, -1.0, 0.0])];
+		assert!(principal_angles(&a, &b).iter().all(|theta| theta.abs() < 1e-7));
+		assert!(subspace_distance(&a, &b) < 1e-7);
+		let c = [Vector::new(vec![0.0, 0.0, 0.0, 1.0])];
+		assert!((principal_angles(&a, &c)[0] - std::f64::consts::FRAC_PI_2).abs() < 1e-15);
+		assert_eq!(subspace_distance(&a, &c), 1.0)
+	}
+
+	#[test]
+	fn small_angles_are_resolved() {
+		let theta = 1e-10_f64;
+		let a = [Vector::new(vec![1.0, 0.0])];
+		let b = [Vector::new(vec![theta.cos(), theta.sin()])];
+		let angles = principal_angles(&a, &b);
+		assert!((angles[0] - theta).abs() < 1e-20);
+		assert!((subspace_distance(&a, &b) - theta.sin()).abs() < 1e-20)
+	}
+
+	#[test]
+	fn nearly_dependent_rows_are_rank_deficient() {
+		// The largest entry puts the tolerance at 2e3 ε ≈ 4.4e-13.
+		let m = Matrix::new(vec![vec![1e3, 1.0], vec![1e3, 1.0 + 1e-13]]);
+		assert_eq!(m.rank(), 1)
+	}
+
+	#[test]
+	fn barely_independent_rows_are_full_rank() {
+		let m = Matrix::new(vec![vec![1e3, 1.0], vec![1e3, 1.0 + 1e-12]]);
+		assert_eq!(m.rank(), 2)
+	}
+
+}
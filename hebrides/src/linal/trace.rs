@@ -0,0 +1,313 @@
+//! Elimination traces.
+//!
+//! Step-by-step records of the elementary row operations performed by
+//! Gaussian elimination, reduction to reduced row echelon form, and LU
+//! factorization, for building linear algebra teaching tools. Tracing is
+//! opt-in through the `_traced` variants of those methods, so the untraced
+//! versions pay nothing for it.
+
+use std::fmt;
+
+use super::{Matrix, LU};
+
+/// An elementary row operation. Rows are numbered from zero here and from
+/// one when displayed, as in most textbooks.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RowOperation {
+	/// Exchanges two rows.
+	Swap(usize, usize),
+	/// Multiplies a row by a nonzero factor.
+	Scale {
+		/// The row being scaled.
+		row: usize,
+		/// The factor it is scaled by.
+		factor: f64
+	},
+	/// Adds a multiple of the `source` row to the `target` row.
+	AddScaled {
+		/// The row being changed.
+		target: usize,
+		/// The row whose multiple is added.
+		source: usize,
+		/// The multiple of `source` that is added.
+		factor: f64
+	}
+}
+
+impl RowOperation {
+
+	/// Returns a LaTeX description of `self`, such as `R_2 \to R_2 - 3 R_1`.
+	pub fn to_latex(&self) -> String {
+		match *self {
+			RowOperation::Swap(a, b) => format!("R_{{{}}} \\leftrightarrow R_{{{}}}", a + 1, b + 1),
+			RowOperation::Scale { row, factor } => format!("R_{{{}}} \\to {} R_{{{}}}", row + 1, factor, row + 1),
+			RowOperation::AddScaled { target, source, factor } => {
+				let (sign, size) = if factor < 0.0 { ("-", -factor) } else { ("+", factor) };
+				format!("R_{{{}}} \\to R_{{{}}} {} {} R_{{{}}}", target + 1, target + 1, sign, size, source + 1)
+			}
+		}
+	}
+
+}
+
+impl fmt::Display for RowOperation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			RowOperation::Swap(a, b) => write!(f, "R{} <-> R{}", a + 1, b + 1),
+			RowOperation::Scale { row, factor } => write!(f, "R{} -> {} R{}", row + 1, factor, row + 1),
+			RowOperation::AddScaled { target, source, factor } => {
+				let (sign, size) = if factor < 0.0 { ('-', -factor) } else { ('+', factor) };
+				write!(f, "R{} -> R{} {} {} R{}", target + 1, target + 1, sign, size, source + 1)
+			}
+		}
+	}
+}
+
+/// A single step of an [`EliminationTrace`]: a row operation and the matrix
+/// it produced.
+#[derive(Debug, Clone)]
+pub struct EliminationStep {
+	operation: RowOperation,
+	matrix: Matrix<f64>
+}
+
+impl EliminationStep {
+
+	/// Returns the row operation performed in this step.
+	pub fn operation(&self) -> RowOperation {
+		self.operation
+	}
+
+	/// Returns the matrix after the operation.
+	pub fn matrix(&self) -> &Matrix<f64> {
+		&self.matrix
+	}
+
+}
+
+/// The sequence of row operations an elimination performed, along with the
+/// matrix after each of them.
+///
+/// ```
+/// # use hebrides::linal::{Matrix, RowOperation};
+/// let m = Matrix::new(vec![vec![2.0, 4.0], vec![1.0, 3.0]]);
+/// let trace = m.rref_traced();
+/// assert_eq!(trace.steps()[0].operation(), RowOperation::Scale { row: 0, factor: 0.5 });
+/// assert_eq!(trace.steps()[1].to_string().lines().next(), Some("R2 -> R2 - 1 R1"));
+/// assert_eq!(trace.result(), &Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct EliminationTrace {
+	initial: Matrix<f64>,
+	steps: Vec<EliminationStep>
+}
+
+fn matrix_lines(matrix: &Matrix<f64>) -> String {
+	matrix.rows.iter().map(|row| {
+		format!("[{}]", row.iter().map(|e| e.to_string()).collect::<Vec<String>>().join(", "))
+	}).collect::<Vec<String>>().join("\n")
+}
+
+fn bmatrix(matrix: &Matrix<f64>) -> String {
+	let body = matrix.rows.iter().map(|row| {
+		row.iter().map(|e| e.to_string()).collect::<Vec<String>>().join(" & ")
+	}).collect::<Vec<String>>().join(" \\\\ ");
+	format!("\\begin{{bmatrix}} {} \\end{{bmatrix}}", body)
+}
+
+impl EliminationTrace {
+
+	/// Returns the matrix before any operation.
+	pub fn initial(&self) -> &Matrix<f64> {
+		&self.initial
+	}
+
+	/// Returns the steps of the elimination in order.
+	pub fn steps(&self) -> &[EliminationStep] {
+		&self.steps
+	}
+
+	/// Returns the matrix after the last step.
+	pub fn result(&self) -> &Matrix<f64> {
+		self.steps.last().map_or(&self.initial, |step| &step.matrix)
+	}
+
+	/// Returns a LaTeX `align*` environment showing each matrix of the
+	/// elimination, with the operation producing it written over the arrow.
+	pub fn to_latex(&self) -> String {
+		let mut lines = vec![format!("& {}", bmatrix(&self.initial))];
+		for step in &self.steps {
+			lines.push(format!("\\xrightarrow{{{}}} & {}", step.operation.to_latex(), bmatrix(&step.matrix)));
+		}
+		format!("\\begin{{align*}}\n{}\n\\end{{align*}}", lines.join(" \\\\\n"))
+	}
+
+}
+
+impl fmt::Display for EliminationStep {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}\n{}", self.operation, matrix_lines(&self.matrix))
+	}
+}
+
+impl fmt::Display for EliminationTrace {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", matrix_lines(&self.initial))?;
+		for step in &self.steps {
+			write!(f, "\n\n{}", step)?;
+		}
+		Ok(())
+	}
+}
+
+/// Gaussian elimination with partial pivoting on `rows`, to reduced row
+/// echelon form if `reduced` and to row echelon form otherwise, returning
+/// the pivot columns. Each row operation is recorded in `steps` if given.
+///
+/// Entries smaller than a tolerance relative to the largest entry are
+/// treated as zero.
+pub(super) fn eliminate(rows: &mut [Vec<f64>], reduced: bool, mut steps: Option<&mut Vec<EliminationStep>>) -> Vec<usize> {
+	let m = rows.len();
+	let n = rows.first().map_or(0, |row| row.len());
+	let scale = rows.iter().flatten().fold(0.0_f64, |max, e| max.max(e.abs()));
+	let tolerance = scale * m.max(n) as f64 * f64::EPSILON;
+	let mut record = |rows: &[Vec<f64>], operation: RowOperation| {
+		if let Some(steps) = steps.as_deref_mut() {
+			steps.push(EliminationStep { operation, matrix: Matrix::new(rows.to_vec()) });
+		}
+	};
+	let mut pivots = Vec::new();
+	for col in 0..n {
+		let row = pivots.len();
+		if row == m {
+			break;
+		}
+		let pivot = (row..m).max_by(|a, b| rows[*a][col].abs().total_cmp(&rows[*b][col].abs())).unwrap();
+		if rows[pivot][col].abs() <= tolerance {
+			for r in rows.iter_mut().skip(row) {
+				r[col] = 0.0;
+			}
+			continue;
+		}
+		if pivot != row {
+			rows.swap(row, pivot);
+			record(rows, RowOperation::Swap(row, pivot));
+		}
+		if reduced && rows[row][col] != 1.0 {
+			let lead = rows[row][col];
+			for e in rows[row].iter_mut() {
+				*e /= lead;
+			}
+			record(rows, RowOperation::Scale { row, factor: 1.0 / lead });
+		}
+		let pivot_row = rows[row].clone();
+		for i in 0..m {
+			if i == row || (!reduced && i < row) || rows[i][col] == 0.0 {
+				continue;
+			}
+			let factor = rows[i][col] / pivot_row[col];
+			for (e, p) in rows[i].iter_mut().zip(pivot_row.iter()) {
+				*e -= factor * p;
+			}
+			rows[i][col] = 0.0;
+			record(rows, RowOperation::AddScaled { target: i, source: row, factor: -factor });
+		}
+		pivots.push(col);
+	}
+	pivots
+}
+
+impl Matrix<f64> {
+
+	fn traced(&self, reduced: bool) -> EliminationTrace {
+		let mut rows = self.rows.clone();
+		let mut steps = Vec::new();
+		eliminate(&mut rows, reduced, Some(&mut steps));
+		EliminationTrace { initial: self.clone(), steps }
+	}
+
+	/// Reduces `self` to row echelon form by Gaussian elimination with
+	/// partial pivoting, recording each row operation.
+	///
+	/// Only rows below each pivot are eliminated and pivots are not scaled,
+	/// so the result is upper triangular when `self` is square and
+	/// nonsingular.
+	pub fn row_echelon_traced(&self) -> EliminationTrace {
+		self.traced(false)
+	}
+
+	/// Reduces `self` to reduced row echelon form, recording each row
+	/// operation. The result matches [`Matrix::rref`].
+	pub fn rref_traced(&self) -> EliminationTrace {
+		self.traced(true)
+	}
+
+	/// Computes the [`LU`] factorization of `self` along with a trace of the
+	/// forward elimination behind it: the row swaps of the pivoting, and the
+	/// subtractions whose multipliers make up `L`, ending at `U`.
+	///
+	/// # Panics
+	/// Panics if `self` is not square.
+	pub fn lu_traced(&self) -> (LU, EliminationTrace) {
+		(self.lu(), self.row_echelon_traced())
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn replay(trace: &EliminationTrace) -> Matrix<f64> {
+		let mut rows = trace.initial().rows.clone();
+		for step in trace.steps() {
+			match step.operation() {
+				RowOperation::Swap(a, b) => rows.swap(a, b),
+				RowOperation::Scale { row, factor } => rows[row].iter_mut().for_each(|e| *e *= factor),
+				RowOperation::AddScaled { target, source, factor } => {
+					let source = rows[source].clone();
+					rows[target].iter_mut().zip(source.iter()).for_each(|(e, s)| *e += factor * s);
+				}
+			}
+		}
+		Matrix::new(rows)
+	}
+
+	fn example() -> Matrix<f64> {
+		Matrix::new(vec![vec![1.0, 2.0, -1.0], vec![4.0, 2.0, 3.0], vec![2.0, -2.0, 5.0]])
+	}
+
+	#[test]
+	fn replaying_operations_reproduces_result() {
+		for trace in [example().rref_traced(), example().row_echelon_traced()] {
+			let replayed = replay(&trace);
+			assert!(replayed.rows.iter().flatten().zip(trace.result().rows.iter().flatten()).all(|(a, b)| (a - b).abs() < 1e-12));
+		}
+	}
+
+	#[test]
+	fn traced_rref_matches_rref() {
+		let m = Matrix::new(vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 7.0]]);
+		assert_eq!(m.rref_traced().result(), &m.rref().0)
+	}
+
+	#[test]
+	fn lu_trace_ends_at_u() {
+		let (lu, trace) = example().lu_traced();
+		let u = lu.u();
+		assert!(trace.result().rows.iter().flatten().zip(u.rows.iter().flatten()).all(|(a, b)| (a - b).abs() < 1e-12));
+		assert_eq!(trace.steps()[0].operation(), RowOperation::Swap(0, 1))
+	}
+
+	#[test]
+	fn formatting() {
+		let operation = RowOperation::AddScaled { target: 2, source: 0, factor: -0.5 };
+		assert_eq!(operation.to_string(), "R3 -> R3 - 0.5 R1");
+		assert_eq!(operation.to_latex(), "R_{3} \\to R_{3} - 0.5 R_{1}");
+		let trace = Matrix::new(vec![vec![0.0, 1.0], vec![1.0, 0.0]]).row_echelon_traced();
+		assert_eq!(trace.to_string(), "[0, 1]\n[1, 0]\n\nR1 <-> R2\n[1, 0]\n[0, 1]");
+		assert!(trace.to_latex().contains("\\xrightarrow{R_{1} \\leftrightarrow R_{2}} & \\begin{bmatrix} 1 & 0 \\\\ 0 & 1 \\end{bmatrix}"))
+	}
+
+}
@@ -0,0 +1,289 @@
+//! Camera and affine transforms.
+//!
+//! View, projection, rotation, scaling, and translation matrices for
+//! [`f32`] and [`f64`]. All of them act on column vectors, and rotations,
+//! scalings, and translations are homogeneous, so `a * b` applies `b` first
+//! and then `a`.
+
+use crate::Angle;
+
+use super::{Vector, Matrix};
+
+fn homogeneous_identity<T>(dim: usize) -> Vec<Vec<T>> where T: From<u8> {
+	(0..dim).map(|i| (0..dim).map(|j| T::from((i == j) as u8)).collect()).collect()
+}
+
+macro_rules! impl_transforms {
+	($t:ty, $alias:ident) => {
+		impl Matrix<$t> {
+
+			/// Creates a homogenous transformation matrix that will cause a vector to point at 
+			/// `dir`, using `up` for orientation.
+			///
+			/// Like the projections, the view matrix acts on column vectors, so it
+			/// composes as `projection * view * model`.
+			pub fn look_at_rh(eye: &Vector<$t>, dir: &Vector<$t>, up: &Vector<$t>) -> Matrix<$t> {
+				let f = dir.normalized();
+				let s = f.cross(up).normalized();
+				let u = s.cross(&f);
+
+				Matrix::new(vec![
+					vec![s[0], s[1], s[2], -eye.dot(&s)],
+					vec![u[0], u[1], u[2], -eye.dot(&u)],
+					vec![-f[0], -f[1], -f[2], eye.dot(&f)],
+					vec![0.0, 0.0, 0.0, 1.0]
+				])
+			}
+
+			/// Creates a homogenous transformation matrix that will cause a vector to point at 
+			/// `dir`, using `up` for orientation, in a left-handed coordinate system.
+			pub fn look_at_lh(eye: &Vector<$t>, dir: &Vector<$t>, up: &Vector<$t>) -> Matrix<$t> {
+				let f = dir.normalized();
+				let s = up.cross(&f).normalized();
+				let u = f.cross(&s);
+
+				Matrix::new(vec![
+					vec![s[0], s[1], s[2], -eye.dot(&s)],
+					vec![u[0], u[1], u[2], -eye.dot(&u)],
+					vec![f[0], f[1], f[2], -eye.dot(&f)],
+					vec![0.0, 0.0, 0.0, 1.0]
+				])
+			}
+
+			/// Creates a perspective matrix from the given parameters.
+			///
+			/// The view space is right-handed and depth is mapped to -1..1, as in
+			/// OpenGL.
+			pub fn perspective(fovy: &$t, aspect: &$t, znear: &$t, zfar: &$t) -> Matrix<$t> {
+				let f = 1.0 / (fovy / 2.0).tan();
+				Matrix::new(vec![
+					vec![f / aspect, 0.0, 0.0, 0.0],
+					vec![0.0, f, 0.0, 0.0],
+					vec![0.0, 0.0, (zfar + znear) / (znear - zfar), 2.0*zfar*znear / (znear - zfar)],
+					vec![0.0, 0.0, -1.0, 0.0]
+				])
+			}
+
+			/// Creates a perspective matrix from the given parameters for a
+			/// left-handed view space, mapping depth to -1..1.
+			pub fn perspective_lh(fovy: &$t, aspect: &$t, znear: &$t, zfar: &$t) -> Matrix<$t> {
+				let f = 1.0 / (fovy / 2.0).tan();
+				Matrix::new(vec![
+					vec![f / aspect, 0.0, 0.0, 0.0],
+					vec![0.0, f, 0.0, 0.0],
+					vec![0.0, 0.0, (zfar + znear) / (zfar - znear), 2.0*zfar*znear / (znear - zfar)],
+					vec![0.0, 0.0, 1.0, 0.0]
+				])
+			}
+
+			/// Creates a perspective matrix from the given parameters for a
+			/// right-handed view space, mapping depth to 0..1 as in Vulkan, wgpu, and
+			/// DirectX.
+			///
+			/// ```
+			#[doc = concat!("# use hebrides::linal::{Vector, ", stringify!($alias), "};")]
+			#[doc = concat!("let p = ", stringify!($alias), "::perspective_zo(&1.0, &1.0, &1.0, &10.0);")]
+			/// let near = p.clone() * Vector::new(vec![0.0, 0.0, -1.0, 1.0]);
+			/// let far = p * Vector::new(vec![0.0, 0.0, -10.0, 1.0]);
+			/// assert_eq!((near[2] / near[3], far[2] / far[3]), (0.0, 1.0));
+			/// ```
+			pub fn perspective_zo(fovy: &$t, aspect: &$t, znear: &$t, zfar: &$t) -> Matrix<$t> {
+				let f = 1.0 / (fovy / 2.0).tan();
+				Matrix::new(vec![
+					vec![f / aspect, 0.0, 0.0, 0.0],
+					vec![0.0, f, 0.0, 0.0],
+					vec![0.0, 0.0, zfar / (znear - zfar), zfar*znear / (znear - zfar)],
+					vec![0.0, 0.0, -1.0, 0.0]
+				])
+			}
+
+			/// Creates a perspective matrix from the given parameters for a
+			/// left-handed view space, mapping depth to 0..1 as in Vulkan, wgpu, and
+			/// DirectX.
+			pub fn perspective_lh_zo(fovy: &$t, aspect: &$t, znear: &$t, zfar: &$t) -> Matrix<$t> {
+				let f = 1.0 / (fovy / 2.0).tan();
+				Matrix::new(vec![
+					vec![f / aspect, 0.0, 0.0, 0.0],
+					vec![0.0, f, 0.0, 0.0],
+					vec![0.0, 0.0, zfar / (zfar - znear), zfar*znear / (znear - zfar)],
+					vec![0.0, 0.0, 1.0, 0.0]
+				])
+			}
+
+			/// Creates a 3x3 homogeneous matrix rotating the plane counterclockwise
+			/// by `angle`.
+			///
+			/// ```
+			#[doc = concat!("# use hebrides::{Angle, linal::{Vector, ", stringify!($alias), "}};")]
+			#[doc = concat!("let r = ", stringify!($alias), "::rotation_2d(Angle::from_degrees(90.0));")]
+			/// let p = r * Vector::new(vec![1.0, 0.0, 1.0]);
+			/// assert!(p[0].abs() < 1e-6 && (p[1] - 1.0).abs() < 1e-6);
+			/// ```
+			pub fn rotation_2d(angle: Angle) -> Matrix<$t> {
+				let (sin, cos) = (angle.to_radians() as $t).sin_cos();
+				Matrix::new(vec![
+					vec![cos, -sin, 0.0],
+					vec![sin, cos, 0.0],
+					vec![0.0, 0.0, 1.0]
+				])
+			}
+
+			/// Creates a 4x4 homogeneous matrix rotating space by `angle` about the
+			/// x-axis, counterclockwise when looking down the axis towards the origin.
+			pub fn rotation_x(angle: Angle) -> Matrix<$t> {
+				let (sin, cos) = (angle.to_radians() as $t).sin_cos();
+				Matrix::new(vec![
+					vec![1.0, 0.0, 0.0, 0.0],
+					vec![0.0, cos, -sin, 0.0],
+					vec![0.0, sin, cos, 0.0],
+					vec![0.0, 0.0, 0.0, 1.0]
+				])
+			}
+
+			/// Creates a 4x4 homogeneous matrix rotating space by `angle` about the
+			/// y-axis, counterclockwise when looking down the axis towards the origin.
+			pub fn rotation_y(angle: Angle) -> Matrix<$t> {
+				let (sin, cos) = (angle.to_radians() as $t).sin_cos();
+				Matrix::new(vec![
+					vec![cos, 0.0, sin, 0.0],
+					vec![0.0, 1.0, 0.0, 0.0],
+					vec![-sin, 0.0, cos, 0.0],
+					vec![0.0, 0.0, 0.0, 1.0]
+				])
+			}
+
+			/// Creates a 4x4 homogeneous matrix rotating space by `angle` about the
+			/// z-axis, counterclockwise when looking down the axis towards the origin.
+			pub fn rotation_z(angle: Angle) -> Matrix<$t> {
+				let (sin, cos) = (angle.to_radians() as $t).sin_cos();
+				Matrix::new(vec![
+					vec![cos, -sin, 0.0, 0.0],
+					vec![sin, cos, 0.0, 0.0],
+					vec![0.0, 0.0, 1.0, 0.0],
+					vec![0.0, 0.0, 0.0, 1.0]
+				])
+			}
+
+			/// Creates a 4x4 homogeneous matrix rotating space by `angle` about
+			/// `axis`, counterclockwise when looking down the axis towards the origin.
+			///
+			/// # Panics
+			/// Panics if `axis` is not three dimensional.
+			pub fn rotation_axis(axis: &Vector<$t>, angle: Angle) -> Matrix<$t> {
+				if axis.dim() != 3 {
+					panic!("Rotation axis must be three dimensional")
+				}
+				let a = axis.normalized();
+				let (x, y, z) = (a[0], a[1], a[2]);
+				let (sin, cos) = (angle.to_radians() as $t).sin_cos();
+				let t = 1.0 - cos;
+				Matrix::new(vec![
+					vec![t*x*x + cos, t*x*y - sin*z, t*x*z + sin*y, 0.0],
+					vec![t*x*y + sin*z, t*y*y + cos, t*y*z - sin*x, 0.0],
+					vec![t*x*z - sin*y, t*y*z + sin*x, t*z*z + cos, 0.0],
+					vec![0.0, 0.0, 0.0, 1.0]
+				])
+			}
+
+			/// Creates a homogeneous matrix scaling each axis by the corresponding
+			/// component of `factors`.
+			///
+			/// A two dimensional `factors` gives a 3x3 matrix and a three dimensional
+			/// one a 4x4 matrix.
+			///
+			/// ```
+			#[doc = concat!("# use hebrides::linal::{Vector, ", stringify!($alias), "};")]
+			#[doc = concat!("let s = ", stringify!($alias), "::scaling(&Vector::new(vec![2.0, 3.0]));")]
+			/// assert_eq!(s * Vector::new(vec![1.0, 1.0, 1.0]), Vector::new(vec![2.0, 3.0, 1.0]));
+			/// ```
+			pub fn scaling(factors: &Vector<$t>) -> Matrix<$t> {
+				let n = factors.dim();
+				let mut rows = homogeneous_identity(n + 1);
+				for (i, row) in rows.iter_mut().take(n).enumerate() {
+					row[i] = factors[i];
+				}
+				Matrix::new(rows)
+			}
+
+			/// Creates a homogeneous matrix translating points by `offset`.
+			///
+			/// A two dimensional `offset` gives a 3x3 matrix and a three dimensional
+			/// one a 4x4 matrix.
+			///
+			/// ```
+			#[doc = concat!("# use hebrides::linal::{Vector, ", stringify!($alias), "};")]
+			#[doc = concat!("let t = ", stringify!($alias), "::translation(&Vector::new(vec![1.0, -2.0, 5.0]));")]
+			/// let p = t * Vector::new(vec![1.0, 1.0, 1.0, 1.0]);
+			/// assert_eq!(p, Vector::new(vec![2.0, -1.0, 6.0, 1.0]));
+			/// ```
+			pub fn translation(offset: &Vector<$t>) -> Matrix<$t> {
+				let n = offset.dim();
+				let mut rows = homogeneous_identity(n + 1);
+				for (i, row) in rows.iter_mut().take(n).enumerate() {
+					row[n] = offset[i];
+				}
+				Matrix::new(rows)
+			}
+
+		}
+	}
+}
+
+impl_transforms![f32, Matrix32];
+impl_transforms![f64, Matrix64];
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+	use crate::linal::{Matrix32, Matrix64};
+
+	fn close(a: &Vector<f32>, b: &Vector<f32>) -> bool {
+		(0..a.dim()).all(|i| (a[i] - b[i]).abs() < 1e-6)
+	}
+
+	#[test]
+	fn principal_rotations_follow_right_hand_rule() {
+		let quarter = Angle::from_degrees(90.0);
+		let x = Vector::new(vec![1.0, 0.0, 0.0, 1.0]);
+		let y = Vector::new(vec![0.0, 1.0, 0.0, 1.0]);
+		let z = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+		assert!(close(&(Matrix32::rotation_x(quarter) * y.clone()), &z));
+		assert!(close(&(Matrix32::rotation_y(quarter) * z.clone()), &x));
+		assert!(close(&(Matrix32::rotation_z(quarter) * x), &y))
+	}
+
+	#[test]
+	fn axis_rotation_matches_principal_rotations() {
+		let angle = Angle::from_degrees(37.0);
+		let axis = Vector::new(vec![0.0, 0.0, 2.0]);
+		let (a, b) = (Matrix32::rotation_axis(&axis, angle), Matrix32::rotation_z(angle));
+		assert!((0..4).all(|i| (0..4).all(|j| (a[i][j] - b[i][j]).abs() < 1e-6)));
+		assert!(Matrix32::rotation_axis(&Vector::new(vec![1.0, 2.0, 3.0]), angle).is_orthogonal_within(1e-6))
+	}
+
+	#[test]
+	fn transforms_compose_right_to_left() {
+		let scale = Matrix32::scaling(&Vector::new(vec![2.0, 2.0]));
+		let shift = Matrix32::translation(&Vector::new(vec![1.0, 0.0]));
+		let p = Vector::new(vec![1.0, 1.0, 1.0]);
+		assert_eq!(shift.clone() * scale.clone() * p.clone(), Vector::new(vec![3.0, 2.0, 1.0]));
+		assert_eq!(scale * shift * p, Vector::new(vec![4.0, 2.0, 1.0]))
+	}
+
+	#[test]
+	fn double_precision_matches_single() {
+		let angle = Angle::from_degrees(30.0);
+		let (single, double) = (Matrix32::rotation_y(angle), Matrix64::rotation_y(angle));
+		assert!((0..4).all(|i| (0..4).all(|j| (single[i][j] as f64 - double[i][j]).abs() < 1e-6)));
+		let p = Matrix64::perspective_zo(&1.0, &1.0, &1.0, &10.0) * Vector::new(vec![0.0, 0.0, -10.0, 1.0]);
+		assert!((p[2] / p[3] - 1.0).abs() < 1e-15)
+	}
+
+	#[test]
+	#[should_panic]
+	fn axis_must_be_three_dimensional() {
+		let _ = Matrix32::rotation_axis(&Vector::new(vec![1.0, 0.0]), Angle::from_degrees(10.0));
+	}
+
+}
@@ -0,0 +1,209 @@
+//! Matrices and vectors with typed axes.
+//!
+//! [`TypedMatrix`] and [`TypedVector`] tag the axes of a [`Matrix`] or
+//! [`Vector`] with marker types naming what they index, such as samples or
+//! features. Transposition swaps the tags, and products only compile when
+//! the inner axes agree, so mixing up semantically different axes of the
+//! same length is caught at compile time rather than producing a silently
+//! wrong result. Any type can serve as a marker; zero-sized structs are
+//! typical.
+//!
+//! ```compile_fail
+//! # use hebrides::linal::{Matrix, TypedMatrix};
+//! struct Samples;
+//! struct Features;
+//! let data: TypedMatrix<f64, Samples, Features> = TypedMatrix::new(Matrix::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]));
+//! // Features do not index the rows of `data`.
+//! let _ = data.clone() * data;
+//! ```
+
+use std::marker::PhantomData;
+use std::ops::{Add, Sub, Mul};
+
+use super::{Vector, Matrix};
+use crate::Semiring;
+
+/// A [`Matrix`] whose rows are indexed by the axis `R` and whose columns
+/// are indexed by the axis `C`.
+///
+/// ```
+/// # use hebrides::linal::{Matrix, TypedMatrix};
+/// struct Samples;
+/// struct Features;
+/// let data: TypedMatrix<f64, Samples, Features> = TypedMatrix::new(Matrix::new(vec![
+///     vec![1.0, 2.0, 0.0],
+///     vec![0.0, 1.0, 3.0]
+/// ]));
+/// let gram: TypedMatrix<f64, Features, Features> = data.transpose() * data;
+/// assert_eq!(gram.as_matrix()[(1, 2)], 3.0);
+/// ```
+pub struct TypedMatrix<T, R, C> {
+	matrix: Matrix<T>,
+	axes: PhantomData<fn() -> (R, C)>
+}
+
+impl<T, R, C> TypedMatrix<T, R, C> {
+
+	/// Tags the axes of `matrix` with `R` and `C`.
+	pub fn new(matrix: Matrix<T>) -> Self {
+		TypedMatrix { matrix, axes: PhantomData }
+	}
+
+	/// Returns the untyped matrix.
+	pub fn as_matrix(&self) -> &Matrix<T> {
+		&self.matrix
+	}
+
+	/// Discards the axis tags, returning the untyped matrix.
+	pub fn into_matrix(self) -> Matrix<T> {
+		self.matrix
+	}
+
+	/// Replaces the axis tags of `self`, for when a matrix legitimately
+	/// changes meaning, such as a covariance reinterpreted as a kernel.
+	pub fn retag<R2, C2>(self) -> TypedMatrix<T, R2, C2> {
+		TypedMatrix::new(self.matrix)
+	}
+
+}
+
+impl<T, R, C> TypedMatrix<T, R, C> where T: Copy {
+
+	/// Returns the transpose of `self`, whose axes are swapped.
+	pub fn transpose(&self) -> TypedMatrix<T, C, R> {
+		TypedMatrix::new(self.matrix.transpose())
+	}
+
+}
+
+impl<T, R, C> Clone for TypedMatrix<T, R, C> where T: Clone {
+	fn clone(&self) -> Self {
+		TypedMatrix::new(self.matrix.clone())
+	}
+}
+
+impl<T, R, C> std::fmt::Debug for TypedMatrix<T, R, C> where T: std::fmt::Display {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.matrix.fmt(f)
+	}
+}
+
+impl<T, R, C> PartialEq for TypedMatrix<T, R, C> where T: PartialEq {
+	fn eq(&self, other: &Self) -> bool {
+		self.matrix == other.matrix
+	}
+}
+
+impl<T, R, C> Add<Self> for TypedMatrix<T, R, C> where T: Copy + Add<Output=T> {
+	type Output = Self;
+	fn add(self, other: Self) -> Self {
+		TypedMatrix::new(self.matrix + other.matrix)
+	}
+}
+
+impl<T, R, C> Sub<Self> for TypedMatrix<T, R, C> where T: Copy + Sub<Output=T> {
+	type Output = Self;
+	fn sub(self, other: Self) -> Self {
+		TypedMatrix::new(self.matrix - other.matrix)
+	}
+}
+
+impl<T, R, K, C> Mul<TypedMatrix<T, K, C>> for TypedMatrix<T, R, K> where T: Semiring {
+	type Output = TypedMatrix<T, R, C>;
+	fn mul(self, other: TypedMatrix<T, K, C>) -> TypedMatrix<T, R, C> {
+		TypedMatrix::new(self.matrix * other.matrix)
+	}
+}
+
+impl<T, R, C> Mul<TypedVector<T, C>> for TypedMatrix<T, R, C> where T: Semiring {
+	type Output = TypedVector<T, R>;
+	fn mul(self, other: TypedVector<T, C>) -> TypedVector<T, R> {
+		TypedVector::new(self.matrix * other.vector)
+	}
+}
+
+/// A [`Vector`] whose components are indexed by the axis `A`.
+pub struct TypedVector<T, A> {
+	vector: Vector<T>,
+	axis: PhantomData<fn() -> A>
+}
+
+impl<T, A> TypedVector<T, A> {
+
+	/// Tags the axis of `vector` with `A`.
+	pub fn new(vector: Vector<T>) -> Self {
+		TypedVector { vector, axis: PhantomData }
+	}
+
+	/// Returns the untyped vector.
+	pub fn as_vector(&self) -> &Vector<T> {
+		&self.vector
+	}
+
+	/// Discards the axis tag, returning the untyped vector.
+	pub fn into_vector(self) -> Vector<T> {
+		self.vector
+	}
+
+	/// Replaces the axis tag of `self`.
+	pub fn retag<B>(self) -> TypedVector<T, B> {
+		TypedVector::new(self.vector)
+	}
+
+}
+
+impl<T, A> Clone for TypedVector<T, A> where T: Clone {
+	fn clone(&self) -> Self {
+		TypedVector::new(self.vector.clone())
+	}
+}
+
+impl<T, A> std::fmt::Debug for TypedVector<T, A> where T: std::fmt::Debug {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.vector.fmt(f)
+	}
+}
+
+impl<T, A> PartialEq for TypedVector<T, A> where T: PartialEq {
+	fn eq(&self, other: &Self) -> bool {
+		self.vector == other.vector
+	}
+}
+
+impl<T, A> Add<Self> for TypedVector<T, A> where T: Copy + Add<Output=T> {
+	type Output = Self;
+	fn add(self, other: Self) -> Self {
+		TypedVector::new(self.vector + other.vector)
+	}
+}
+
+impl<T, A> Sub<Self> for TypedVector<T, A> where T: Copy + Sub<Output=T> {
+	type Output = Self;
+	fn sub(self, other: Self) -> Self {
+		TypedVector::new(self.vector - other.vector)
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	struct Samples;
+	struct Features;
+	struct Components;
+
+	#[test]
+	fn products_follow_axes() {
+		let data: TypedMatrix<f64, Samples, Features> = TypedMatrix::new(Matrix::new(vec![vec![1.0, 0.0], vec![2.0, 1.0], vec![0.0, 3.0]]));
+		let projection: TypedMatrix<f64, Features, Components> = TypedMatrix::new(Matrix::new(vec![vec![1.0], vec![-1.0]]));
+		let scores = data.clone() * projection.clone();
+		assert_eq!(scores.as_matrix(), &Matrix::new(vec![vec![1.0], vec![1.0], vec![-3.0]]));
+		let weights: TypedVector<f64, Features> = TypedVector::new(Vector::new(vec![2.0, 1.0]));
+		let predictions: TypedVector<f64, Samples> = data.clone() * weights;
+		assert_eq!(predictions.into_vector(), Vector::new(vec![2.0, 5.0, 3.0]));
+		let back: TypedMatrix<f64, Components, Samples> = scores.transpose();
+		assert_eq!(back.into_matrix(), Matrix::new(vec![vec![1.0, 1.0, -3.0]]))
+	}
+
+}
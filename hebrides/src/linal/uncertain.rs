@@ -0,0 +1,151 @@
+//! Vectors of correlated uncertain quantities.
+//!
+//! [`UncertainVector`] pairs a mean with a full covariance matrix and
+//! propagates both through linear maps exactly and through nonlinear maps
+//! to first order, so quantities that share error sources keep their
+//! correlation where independent [`Uncertain`] values would lose it.
+
+use std::ops::Add;
+
+use super::{Vector, Matrix};
+use crate::Uncertain;
+
+/// A vector-valued measurement with mean μ and covariance Σ.
+///
+/// ```
+/// # use hebrides::{Uncertain, linal::{Matrix, UncertainVector}};
+/// let x = UncertainVector::independent(&[Uncertain::new(1.0, 0.1), Uncertain::new(2.0, 0.2)]);
+/// // The sum and the difference of the components are correlated.
+/// let y = x.transform(&Matrix::new(vec![vec![1.0, 1.0], vec![1.0, -1.0]]));
+/// assert!((y.get(0).std_dev() - 0.05_f64.sqrt()).abs() < 1e-12);
+/// assert!((y.covariance()[(0, 1)] + 0.03).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct UncertainVector {
+	mean: Vector<f64>,
+	covariance: Matrix<f64>
+}
+
+impl UncertainVector {
+
+	/// Constructs the uncertain vector with mean `mean` and covariance
+	/// `covariance`.
+	///
+	/// # Panics
+	/// Panics if `covariance` is not square with the dimension of `mean`.
+	pub fn new(mean: Vector<f64>, covariance: Matrix<f64>) -> Self {
+		if covariance.dims.num_rows != mean.dim() || covariance.dims.num_cols != mean.dim() {
+			panic!("Covariance must be square with the dimension of the mean")
+		}
+		UncertainVector { mean, covariance }
+	}
+
+	/// Constructs the uncertain vector of independent `components`, whose
+	/// covariance is diagonal.
+	pub fn independent(components: &[Uncertain]) -> Self {
+		let mean = components.iter().map(|c| c.value()).collect();
+		let variances = components.iter().map(|c| c.variance()).collect();
+		UncertainVector { mean, covariance: Matrix::from_diagonal(&variances) }
+	}
+
+	/// Returns the mean μ.
+	pub fn mean(&self) -> &Vector<f64> {
+		&self.mean
+	}
+
+	/// Returns the covariance Σ.
+	pub fn covariance(&self) -> &Matrix<f64> {
+		&self.covariance
+	}
+
+	/// Returns the dimension of `self`.
+	pub fn dim(&self) -> usize {
+		self.mean.dim()
+	}
+
+	/// Returns component `index` with its marginal standard deviation,
+	/// forgetting its correlation with the other components.
+	pub fn get(&self, index: usize) -> Uncertain {
+		Uncertain::new(self.mean[index], self.covariance[(index, index)].max(0.0).sqrt())
+	}
+
+	/// Returns the correlation coefficient between components `i` and `j`.
+	pub fn correlation(&self, i: usize, j: usize) -> f64 {
+		self.covariance[(i, j)] / (self.covariance[(i, i)] * self.covariance[(j, j)]).sqrt()
+	}
+
+	/// Returns A`self`, with mean Aμ and covariance AΣAᵀ.
+	///
+	/// # Panics
+	/// Panics if the number of columns of `a` is not the dimension of
+	/// `self`.
+	pub fn transform(&self, a: &Matrix<f64>) -> Self {
+		let mean = a.clone() * self.mean.clone();
+		let covariance = a.clone() * self.covariance.clone() * a.transpose();
+		UncertainVector { mean, covariance }
+	}
+
+	/// Returns f(`self`) to first order, with mean f(μ) and covariance JΣJᵀ
+	/// for the Jacobian J of `f` at μ, estimated by central differences.
+	///
+	/// ```
+	/// # use hebrides::{Uncertain, linal::{Vector, UncertainVector}};
+	/// // Polar to Cartesian coordinates.
+	/// let polar = UncertainVector::independent(&[Uncertain::new(2.0, 0.01), Uncertain::new(0.0, 0.05)]);
+	/// let cartesian = polar.map(|p| Vector::new(vec![p[0] * p[1].cos(), p[0] * p[1].sin()]));
+	/// assert!((cartesian.get(0).std_dev() - 0.01).abs() < 1e-6);
+	/// assert!((cartesian.get(1).std_dev() - 0.1).abs() < 1e-6);
+	/// ```
+	pub fn map(&self, f: impl Fn(&Vector<f64>) -> Vector<f64>) -> Self {
+		let centre = f(&self.mean);
+		let columns: Vec<Vec<f64>> = (0..self.dim()).map(|j| {
+			let h = f64::EPSILON.cbrt() * self.mean[j].abs().max(1.0);
+			let (mut forward, mut backward) = (self.mean.clone(), self.mean.clone());
+			forward[j] += h;
+			backward[j] -= h;
+			(f(&forward) - f(&backward)).iter().map(|d| d / (2.0 * h)).collect()
+		}).collect();
+		let jacobian = Matrix::new(columns).transpose();
+		UncertainVector { covariance: self.transform(&jacobian).covariance, mean: centre }
+	}
+
+}
+
+/// Sums two independent uncertain vectors, whose covariances add.
+impl Add<Self> for UncertainVector {
+	type Output = Self;
+	fn add(self, other: Self) -> Self {
+		UncertainVector::new(self.mean + other.mean, self.covariance + other.covariance)
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn correlation_survives_differences() {
+		// Two readings sharing a calibration offset with standard deviation 0.3.
+		let readings = UncertainVector::new(
+			Vector::new(vec![5.0, 7.0]),
+			Matrix::new(vec![vec![0.09 + 0.01, 0.09], vec![0.09, 0.09 + 0.04]])
+		);
+		let difference = readings.transform(&Matrix::new(vec![vec![-1.0, 1.0]]));
+		assert!((difference.get(0).std_dev() - 0.05_f64.sqrt()).abs() < 1e-12);
+		let naive = readings.get(1) - readings.get(0);
+		assert!(naive.std_dev() > 0.4);
+		assert!((readings.correlation(0, 1) - 0.09 / (0.1_f64 * 0.13).sqrt()).abs() < 1e-12)
+	}
+
+	#[test]
+	fn linear_map_matches_transform() {
+		let x = UncertainVector::independent(&[Uncertain::new(1.0, 0.5), Uncertain::new(-2.0, 0.25), Uncertain::new(0.5, 1.0)]);
+		let a = Matrix::new(vec![vec![1.0, 2.0, 0.0], vec![-1.0, 0.5, 3.0]]);
+		let mapped = x.map(|v| a.clone() * v.clone());
+		let transformed = x.transform(&a);
+		assert_eq!(mapped.mean(), transformed.mean());
+		assert!((mapped.covariance().clone() - transformed.covariance().clone()).rows.iter().flatten().all(|e| e.abs() < 1e-8))
+	}
+
+}
@@ -0,0 +1,403 @@
+//! Feedforward neural network layer math.
+//!
+//! `nn` provides the pieces needed to train small multilayer perceptrons on
+//! top of [`Matrix<f32>`]: dense layers with forward and backward passes,
+//! elementwise activations, a softmax cross-entropy loss, and optimizers that
+//! apply the resulting gradients. Batches are laid out with one sample per row.
+
+use crate::linal::Matrix;
+use crate::random::Rng;
+
+/// Elementwise activation functions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Activation {
+	/// Leaves its input unchanged.
+	Identity,
+	/// Rectified linear unit, max(0, x).
+	Relu,
+	/// Logistic sigmoid, 1 / (1 + e^-x).
+	Sigmoid,
+	/// Hyperbolic tangent.
+	Tanh
+}
+
+impl Activation {
+
+	/// Evaluates the activation at `x`.
+	pub fn apply(&self, x: f32) -> f32 {
+		match self {
+			Activation::Identity => x,
+			Activation::Relu => x.max(0.0),
+			Activation::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+			Activation::Tanh => x.tanh()
+		}
+	}
+
+	/// Evaluates the derivative of the activation at `x`.
+	pub fn derivative(&self, x: f32) -> f32 {
+		match self {
+			Activation::Identity => 1.0,
+			Activation::Relu => if x > 0.0 { 1.0 } else { 0.0 },
+			Activation::Sigmoid => {
+				let s = self.apply(x);
+				s * (1.0 - s)
+			},
+			Activation::Tanh => 1.0 - x.tanh() * x.tanh()
+		}
+	}
+
+	/// Applies the activation to every entry of `m`.
+	pub fn apply_to(&self, m: &Matrix<f32>) -> Matrix<f32> {
+		m.map(|x| self.apply(x))
+	}
+
+}
+
+/// Row-wise softmax of `logits`.
+///
+/// Each row of the result is a probability distribution. The maximum of each
+/// row is subtracted before exponentiating to avoid overflow.
+///
+/// ```
+/// # use hebrides::{nn, linal::Matrix};
+/// let p = nn::softmax(&Matrix::new(vec![vec![1.0, 1.0], vec![1000.0, 1000.0]]));
+/// assert_eq!(p, Matrix::new(vec![vec![0.5, 0.5], vec![0.5, 0.5]]));
+/// ```
+pub fn softmax(logits: &Matrix<f32>) -> Matrix<f32> {
+	let rows = logits.rows.iter()
+						  .map(|row| {
+							  let max = row.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+							  let exps: Vec<f32> = row.iter().map(|e| (e - max).exp()).collect();
+							  let total: f32 = exps.iter().sum();
+							  exps.iter().map(|e| e / total).collect()
+						  })
+						  .collect();
+	Matrix::new(rows)
+}
+
+/// Mean softmax cross-entropy of `logits` against the class `labels` of each
+/// row, along with its gradient with respect to `logits`.
+///
+/// # Panics
+/// Panics if there is not one label per row of `logits`, or if any label is
+/// not a valid column index.
+pub fn softmax_cross_entropy(logits: &Matrix<f32>, labels: &[usize]) -> (f32, Matrix<f32>) {
+	if labels.len() != logits.dims.num_rows {
+		panic!("Must provide one label per row of logits")
+	}
+	if labels.iter().any(|label| *label >= logits.dims.num_cols) {
+		panic!("Labels must index into the columns of logits")
+	}
+	let batch = labels.len() as f32;
+	let probabilities = softmax(logits);
+	let loss = labels.iter()
+					 .enumerate()
+					 .map(|(i, label)| -probabilities.rows[i][*label].max(f32::MIN_POSITIVE).ln())
+					 .sum::<f32>() / batch;
+	let gradient = probabilities.rows.iter()
+									 .zip(labels.iter())
+									 .map(|(row, label)| {
+										 row.iter()
+											.enumerate()
+											.map(|(j, p)| (p - if j == *label { 1.0 } else { 0.0 }) / batch)
+											.collect()
+									 })
+									 .collect();
+	(loss, Matrix::new(gradient))
+}
+
+/// Fully connected layer computing `activation(input * weights + bias)`.
+pub struct Dense {
+	/// Weights, with one row per input and one column per output
+	pub weights: Matrix<f32>,
+	/// Bias, as a single row with one column per output
+	pub bias: Matrix<f32>,
+	/// Activation applied to the affine output
+	pub activation: Activation,
+	grad_weights: Matrix<f32>,
+	grad_bias: Matrix<f32>,
+	input: Option<Matrix<f32>>,
+	pre_activation: Option<Matrix<f32>>
+}
+
+impl Dense {
+
+	/// Constructs a [`Dense`] layer mapping `inputs` features to `outputs`
+	/// features.
+	///
+	/// Weights are drawn uniformly from the Glorot range
+	/// ±sqrt(6 / (inputs + outputs)) and biases start at zero.
+	pub fn new(inputs: usize, outputs: usize, activation: Activation, rng: &mut Rng) -> Dense {
+		let limit = (6.0 / (inputs + outputs) as f64).sqrt();
+		let weights = (0..inputs).map(|_| (0..outputs).map(|_| ((2.0 * rng.next_f64() - 1.0) * limit) as f32).collect())
+								 .collect();
+		Self {
+			weights: Matrix::new(weights),
+			bias: Matrix::new(vec![vec![0.0; outputs]]),
+			activation,
+			grad_weights: Matrix::new(vec![vec![0.0; outputs]; inputs]),
+			grad_bias: Matrix::new(vec![vec![0.0; outputs]]),
+			input: None,
+			pre_activation: None
+		}
+	}
+
+	/// Computes the output of the layer for a batch of `input` rows, caching
+	/// what `backward` needs.
+	pub fn forward(&mut self, input: &Matrix<f32>) -> Matrix<f32> {
+		let affine = input.clone() * self.weights.clone();
+		let pre_activation = Matrix::new(affine.rows.iter()
+												  .map(|row| row.iter().zip(self.bias.rows[0].iter()).map(|(a, b)| a + b).collect())
+												  .collect());
+		let output = self.activation.apply_to(&pre_activation);
+		self.input = Some(input.clone());
+		self.pre_activation = Some(pre_activation);
+		output
+	}
+
+	/// Backpropagates `grad_output`, the gradient of the loss with respect to
+	/// the last output of `forward`.
+	///
+	/// Stores the gradients of the weights and bias in `self` and returns the
+	/// gradient of the loss with respect to the layer's input.
+	///
+	/// # Panics
+	/// Panics if `forward` has not been called.
+	pub fn backward(&mut self, grad_output: &Matrix<f32>) -> Matrix<f32> {
+		let (input, pre_activation) = match (&self.input, &self.pre_activation) {
+			(Some(input), Some(pre_activation)) => (input, pre_activation),
+			_ => panic!("Dense layer must be run forward before it can be run backward")
+		};
+		let activation = self.activation;
+		let grad_pre = grad_output.zip_with(pre_activation, |g, z| g * activation.derivative(z));
+		self.grad_weights = input.transpose() * grad_pre.clone();
+		self.grad_bias = Matrix::new(vec![grad_pre.cols.iter().map(|col| col.iter().sum()).collect()]);
+		grad_pre * self.weights.transpose()
+	}
+
+	/// Returns the weights and bias of `self`, each paired with the gradient
+	/// computed by the last call to `backward`.
+	pub fn parameters(&mut self) -> Vec<(&mut Matrix<f32>, &Matrix<f32>)> {
+		vec![(&mut self.weights, &self.grad_weights), (&mut self.bias, &self.grad_bias)]
+	}
+
+}
+
+/// Update rule applying gradients to parameters.
+pub trait Optimizer {
+
+	/// Updates each parameter using its paired gradient.
+	///
+	/// Stateful optimizers identify parameters by position, so parameters
+	/// should be passed in the same order on every step.
+	fn step(&mut self, parameters: Vec<(&mut Matrix<f32>, &Matrix<f32>)>);
+
+}
+
+/// Stochastic gradient descent.
+#[derive(Debug, Clone)]
+pub struct Sgd {
+	/// Step size applied to each gradient
+	pub learning_rate: f32
+}
+
+impl Sgd {
+
+	/// Constructs an [`Sgd`] optimizer with the given `learning_rate`.
+	pub fn new(learning_rate: f32) -> Sgd {
+		Self { learning_rate }
+	}
+
+}
+
+impl Optimizer for Sgd {
+	fn step(&mut self, parameters: Vec<(&mut Matrix<f32>, &Matrix<f32>)>) {
+		for (parameter, gradient) in parameters {
+			*parameter = parameter.zip_with(gradient, |p, g| p - self.learning_rate * g);
+		}
+	}
+}
+
+/// The Adam optimizer of Kingma and Ba.
+#[derive(Debug, Clone)]
+pub struct Adam {
+	/// Step size applied to each normalized gradient
+	pub learning_rate: f32,
+	/// Decay rate of the first moment estimates
+	pub beta1: f32,
+	/// Decay rate of the second moment estimates
+	pub beta2: f32,
+	/// Term added to the denominator for numerical stability
+	pub epsilon: f32,
+	t: i32,
+	first_moments: Vec<Vec<Vec<f32>>>,
+	second_moments: Vec<Vec<Vec<f32>>>
+}
+
+impl Adam {
+
+	/// Constructs an [`Adam`] optimizer with the given `learning_rate` and the
+	/// customary defaults β₁ = 0.9, β₂ = 0.999, and ε = 1e-8.
+	pub fn new(learning_rate: f32) -> Adam {
+		Self {
+			learning_rate,
+			beta1: 0.9,
+			beta2: 0.999,
+			epsilon: 1e-8,
+			t: 0,
+			first_moments: Vec::new(),
+			second_moments: Vec::new()
+		}
+	}
+
+}
+
+impl Optimizer for Adam {
+	fn step(&mut self, parameters: Vec<(&mut Matrix<f32>, &Matrix<f32>)>) {
+		self.t += 1;
+		let correction1 = 1.0 - self.beta1.powi(self.t);
+		let correction2 = 1.0 - self.beta2.powi(self.t);
+		for (k, (parameter, gradient)) in parameters.into_iter().enumerate() {
+			if k == self.first_moments.len() {
+				let zeros = vec![vec![0.0; parameter.dims.num_cols]; parameter.dims.num_rows];
+				self.first_moments.push(zeros.clone());
+				self.second_moments.push(zeros);
+			}
+			let m = &mut self.first_moments[k];
+			let v = &mut self.second_moments[k];
+			let mut rows = parameter.rows.clone();
+			for (i, row) in rows.iter_mut().enumerate() {
+				for (j, p) in row.iter_mut().enumerate() {
+					let g = gradient.rows[i][j];
+					m[i][j] = self.beta1 * m[i][j] + (1.0 - self.beta1) * g;
+					v[i][j] = self.beta2 * v[i][j] + (1.0 - self.beta2) * g * g;
+					let m_hat = m[i][j] / correction1;
+					let v_hat = v[i][j] / correction2;
+					*p -= self.learning_rate * m_hat / (v_hat.sqrt() + self.epsilon);
+				}
+			}
+			*parameter = Matrix::new(rows);
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	mod activations {
+
+		use super::*;
+
+		#[test]
+		fn values() {
+			assert_eq!(Activation::Relu.apply(-2.0), 0.0);
+			assert_eq!(Activation::Relu.apply(3.0), 3.0);
+			assert_eq!(Activation::Sigmoid.apply(0.0), 0.5);
+			assert_eq!(Activation::Tanh.apply(0.0), 0.0);
+			assert_eq!(Activation::Identity.apply(1.5), 1.5);
+		}
+
+		#[test]
+		fn derivatives_match_finite_differences() {
+			let h = 1e-3;
+			for activation in [Activation::Identity, Activation::Sigmoid, Activation::Tanh, Activation::Relu] {
+				for x in [-1.3_f32, 0.4, 2.1] {
+					let numeric = (activation.apply(x + h) - activation.apply(x - h)) / (2.0 * h);
+					assert!((numeric - activation.derivative(x)).abs() < 1e-2);
+				}
+			}
+		}
+
+	}
+
+	mod loss {
+
+		use super::*;
+
+		#[test]
+		fn softmax_rows_sum_to_one() {
+			let p = softmax(&Matrix::new(vec![vec![1.0, 2.0, 3.0], vec![-5.0, 0.0, 5.0]]));
+			assert!(p.rows.iter().all(|row| (row.iter().sum::<f32>() - 1.0).abs() < 1e-6))
+		}
+
+		#[test]
+		fn cross_entropy_of_uniform_logits() {
+			let (loss, gradient) = softmax_cross_entropy(&Matrix::new(vec![vec![0.0, 0.0]]), &[1]);
+			assert!((loss - 2.0_f32.ln()).abs() < 1e-6);
+			assert_eq!(gradient, Matrix::new(vec![vec![0.5, -0.5]]))
+		}
+
+		#[test]
+		#[should_panic]
+		fn mismatched_labels() {
+			let _ = softmax_cross_entropy(&Matrix::new(vec![vec![0.0, 0.0]]), &[0, 1]);
+		}
+
+	}
+
+	mod dense {
+
+		use super::*;
+
+		#[test]
+		fn gradients_match_finite_differences() {
+			let mut rng = Rng::new(11);
+			let mut layer = Dense::new(3, 2, Activation::Tanh, &mut rng);
+			let input = Matrix::new(vec![vec![0.5, -0.2, 0.1], vec![-0.3, 0.8, 0.4]]);
+			let labels = [1, 0];
+			let output = layer.forward(&input);
+			let (_, grad_output) = softmax_cross_entropy(&output, &labels);
+			let _ = layer.backward(&grad_output);
+			let analytic = layer.grad_weights.rows[2][1];
+			let h = 1e-2;
+			let mut loss_at = |delta: f32| {
+				let mut rows = layer.weights.rows.clone();
+				rows[2][1] += delta;
+				let mut shifted = Dense { weights: Matrix::new(rows), ..Dense::new(3, 2, Activation::Tanh, &mut rng) };
+				shifted.bias = layer.bias.clone();
+				softmax_cross_entropy(&shifted.forward(&input), &labels).0
+			};
+			let numeric = (loss_at(h) - loss_at(-h)) / (2.0 * h);
+			assert!((numeric - analytic).abs() < 1e-3);
+		}
+
+		#[test]
+		#[should_panic]
+		fn backward_before_forward() {
+			let mut layer = Dense::new(2, 2, Activation::Relu, &mut Rng::new(0));
+			let _ = layer.backward(&Matrix::new(vec![vec![1.0, 1.0]]));
+		}
+
+		fn train_xor(optimizer: &mut impl Optimizer, epochs: usize) -> Vec<usize> {
+			let mut rng = Rng::new(2024);
+			let mut hidden = Dense::new(2, 8, Activation::Tanh, &mut rng);
+			let mut output = Dense::new(8, 2, Activation::Identity, &mut rng);
+			let input = Matrix::new(vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]]);
+			let labels = [0, 1, 1, 0];
+			for _ in 0..epochs {
+				let logits = output.forward(&hidden.forward(&input));
+				let (_, grad) = softmax_cross_entropy(&logits, &labels);
+				let _ = hidden.backward(&output.backward(&grad));
+				let mut parameters = hidden.parameters();
+				parameters.extend(output.parameters());
+				optimizer.step(parameters);
+			}
+			let logits = output.forward(&hidden.forward(&input));
+			logits.rows.iter().map(|row| if row[0] > row[1] { 0 } else { 1 }).collect()
+		}
+
+		#[test]
+		fn adam_learns_xor() {
+			assert_eq!(train_xor(&mut Adam::new(0.05), 300), vec![0, 1, 1, 0])
+		}
+
+		#[test]
+		fn sgd_learns_xor() {
+			assert_eq!(train_xor(&mut Sgd::new(0.5), 2000), vec![0, 1, 1, 0])
+		}
+
+	}
+
+}
@@ -0,0 +1,319 @@
+//! Procedural noise.
+//!
+//! Gradient noise over one to three dimensional points, with gradient tables
+//! shuffled by a seeded [`Rng`] so that the same seed always produces the same
+//! field. All generators return values roughly in [-1, 1].
+
+use crate::linal::Vector;
+use crate::random::Rng;
+
+/// A scalar noise field over one to three dimensional points.
+pub trait Noise {
+
+	/// Returns the value of the field at `point`.
+	///
+	/// # Panics
+	/// Panics if `point` is not one, two, or three dimensional.
+	fn sample(&self, point: &Vector<f64>) -> f64;
+
+}
+
+const GRADIENTS: [(f64, f64, f64); 12] = [
+	(1.0, 1.0, 0.0), (-1.0, 1.0, 0.0), (1.0, -1.0, 0.0), (-1.0, -1.0, 0.0),
+	(1.0, 0.0, 1.0), (-1.0, 0.0, 1.0), (1.0, 0.0, -1.0), (-1.0, 0.0, -1.0),
+	(0.0, 1.0, 1.0), (0.0, -1.0, 1.0), (0.0, 1.0, -1.0), (0.0, -1.0, -1.0)
+];
+
+/// Lattice hashing shared by the gradient noise generators.
+#[derive(Debug, Clone)]
+struct Permutation {
+	table: Vec<usize>
+}
+
+impl Permutation {
+
+	fn new(seed: u64) -> Permutation {
+		let mut table: Vec<usize> = (0..256).collect();
+		Rng::new(seed).shuffle(&mut table);
+		table.extend_from_within(..);
+		Permutation { table }
+	}
+
+	fn hash(&self, lattice: &[i64]) -> usize {
+		lattice.iter().fold(0, |h, i| self.table[(h + i.rem_euclid(256) as usize) & 255])
+	}
+
+	fn gradient(&self, lattice: &[i64], offset: (f64, f64, f64)) -> f64 {
+		let (gx, gy, gz) = GRADIENTS[self.hash(lattice) % 12];
+		gx * offset.0 + gy * offset.1 + gz * offset.2
+	}
+
+}
+
+fn coordinates(point: &Vector<f64>) -> Vec<f64> {
+	if !(1..=3).contains(&point.dim()) {
+		panic!("Noise is only defined for one to three dimensional points")
+	}
+	(0..point.dim()).map(|i| point[i]).collect()
+}
+
+fn fade(t: f64) -> f64 {
+	t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+	a + t * (b - a)
+}
+
+/// Ken Perlin's improved gradient noise.
+///
+/// The field is zero at every integer lattice point.
+///
+/// ```
+/// # use hebrides::{linal::Vector, noise::{Noise, Perlin}};
+/// let perlin = Perlin::new(7);
+/// let value = perlin.sample(&Vector::new(vec![0.3, 1.7]));
+/// assert!(value.abs() <= 1.0);
+/// assert_eq!(value, Perlin::new(7).sample(&Vector::new(vec![0.3, 1.7])));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Perlin {
+	permutation: Permutation
+}
+
+impl Perlin {
+
+	/// Constructs a [`Perlin`] noise field from `seed`.
+	pub fn new(seed: u64) -> Perlin {
+		Perlin { permutation: Permutation::new(seed) }
+	}
+
+}
+
+impl Noise for Perlin {
+	fn sample(&self, point: &Vector<f64>) -> f64 {
+		let x = coordinates(point);
+		let cell: Vec<i64> = x.iter().map(|c| c.floor() as i64).collect();
+		let local: Vec<f64> = x.iter().zip(cell.iter()).map(|(c, i)| c - *i as f64).collect();
+		let corners = 1 << x.len();
+		let mut values: Vec<f64> = (0..corners).map(|corner: usize| {
+			let lattice: Vec<i64> = cell.iter().enumerate().map(|(d, i)| i + ((corner >> d) & 1) as i64).collect();
+			let offset: Vec<f64> = local.iter().enumerate().map(|(d, f)| f - ((corner >> d) & 1) as f64).collect();
+			let o = (offset[0], *offset.get(1).unwrap_or(&0.0), *offset.get(2).unwrap_or(&0.0));
+			if x.len() == 1 {
+				if self.permutation.hash(&lattice) & 1 == 0 { 2.0 * o.0 } else { -2.0 * o.0 }
+			} else {
+				self.permutation.gradient(&lattice, o)
+			}
+		}).collect();
+		for f in local.iter() {
+			let t = fade(*f);
+			values = values.chunks(2).map(|pair| lerp(t, pair[0], pair[1])).collect();
+		}
+		values[0]
+	}
+}
+
+/// Ken Perlin's simplex noise, using Stefan Gustavson's formulation.
+///
+/// Simplex noise has fewer directional artifacts than [`Perlin`] noise and is
+/// cheaper in higher dimensions.
+#[derive(Debug, Clone)]
+pub struct Simplex {
+	permutation: Permutation
+}
+
+impl Simplex {
+
+	/// Constructs a [`Simplex`] noise field from `seed`.
+	pub fn new(seed: u64) -> Simplex {
+		Simplex { permutation: Permutation::new(seed) }
+	}
+
+	fn corner(&self, lattice: &[i64], offset: (f64, f64, f64), radius: f64) -> f64 {
+		let t = radius - offset.0 * offset.0 - offset.1 * offset.1 - offset.2 * offset.2;
+		if t < 0.0 {
+			return 0.0;
+		}
+		t * t * t * t * self.permutation.gradient(lattice, offset)
+	}
+
+	fn sample_1d(&self, x: f64) -> f64 {
+		let i = x.floor() as i64;
+		let x0 = x - i as f64;
+		let contribution = |lattice: i64, offset: f64| {
+			let t = 1.0 - offset * offset;
+			let h = self.permutation.hash(&[lattice]);
+			let gradient = (1 + (h & 7)) as f64 * if h & 8 == 0 { 1.0 } else { -1.0 };
+			t * t * t * t * gradient * offset
+		};
+		0.395 * (contribution(i, x0) + contribution(i + 1, x0 - 1.0))
+	}
+
+	fn sample_2d(&self, x: f64, y: f64) -> f64 {
+		let f2 = 0.5 * (3.0_f64.sqrt() - 1.0);
+		let g2 = (3.0 - 3.0_f64.sqrt()) / 6.0;
+		let s = (x + y) * f2;
+		let (i, j) = ((x + s).floor() as i64, (y + s).floor() as i64);
+		let t = (i + j) as f64 * g2;
+		let (x0, y0) = (x - (i as f64 - t), y - (j as f64 - t));
+		let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+		let (x1, y1) = (x0 - i1 as f64 + g2, y0 - j1 as f64 + g2);
+		let (x2, y2) = (x0 - 1.0 + 2.0 * g2, y0 - 1.0 + 2.0 * g2);
+		70.0 * (
+			self.corner(&[i, j], (x0, y0, 0.0), 0.5)
+			+ self.corner(&[i + i1, j + j1], (x1, y1, 0.0), 0.5)
+			+ self.corner(&[i + 1, j + 1], (x2, y2, 0.0), 0.5)
+		)
+	}
+
+	fn sample_3d(&self, x: f64, y: f64, z: f64) -> f64 {
+		let (f3, g3) = (1.0 / 3.0, 1.0 / 6.0);
+		let s = (x + y + z) * f3;
+		let (i, j, k) = ((x + s).floor() as i64, (y + s).floor() as i64, (z + s).floor() as i64);
+		let t = (i + j + k) as f64 * g3;
+		let (x0, y0, z0) = (x - (i as f64 - t), y - (j as f64 - t), z - (k as f64 - t));
+		let ((i1, j1, k1), (i2, j2, k2)) = if x0 >= y0 {
+			if y0 >= z0 {
+				((1, 0, 0), (1, 1, 0))
+			} else if x0 >= z0 {
+				((1, 0, 0), (1, 0, 1))
+			} else {
+				((0, 0, 1), (1, 0, 1))
+			}
+		} else if y0 < z0 {
+			((0, 0, 1), (0, 1, 1))
+		} else if x0 < z0 {
+			((0, 1, 0), (0, 1, 1))
+		} else {
+			((0, 1, 0), (1, 1, 0))
+		};
+		let offset = |(a, b, c): (i64, i64, i64), scale: f64| {
+			(x0 - a as f64 + scale * g3, y0 - b as f64 + scale * g3, z0 - c as f64 + scale * g3)
+		};
+		32.0 * (
+			self.corner(&[i, j, k], (x0, y0, z0), 0.6)
+			+ self.corner(&[i + i1, j + j1, k + k1], offset((i1, j1, k1), 1.0), 0.6)
+			+ self.corner(&[i + i2, j + j2, k + k2], offset((i2, j2, k2), 2.0), 0.6)
+			+ self.corner(&[i + 1, j + 1, k + 1], offset((1, 1, 1), 3.0), 0.6)
+		)
+	}
+
+}
+
+impl Noise for Simplex {
+	fn sample(&self, point: &Vector<f64>) -> f64 {
+		match coordinates(point)[..] {
+			[x] => self.sample_1d(x),
+			[x, y] => self.sample_2d(x, y),
+			[x, y, z] => self.sample_3d(x, y, z),
+			_ => unreachable!()
+		}
+	}
+}
+
+/// Fractal Brownian motion: a sum of octaves of another [`Noise`] at
+/// increasing frequencies and decreasing amplitudes.
+///
+/// ```
+/// # use hebrides::{linal::Vector, noise::{Noise, Fbm, Simplex}};
+/// let terrain = Fbm::new(Simplex::new(3), 5, 2.0, 0.5);
+/// assert!(terrain.sample(&Vector::new(vec![0.25, 0.5, 0.75])).abs() <= 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fbm<N> {
+	noise: N,
+	octaves: usize,
+	lacunarity: f64,
+	gain: f64
+}
+
+impl<N> Fbm<N> where N: Noise {
+
+	/// Constructs an [`Fbm`] summing `octaves` octaves of `noise`, each
+	/// `lacunarity` times the frequency and `gain` times the amplitude of the
+	/// one before.
+	///
+	/// # Panics
+	/// Panics if `octaves` is zero.
+	pub fn new(noise: N, octaves: usize, lacunarity: f64, gain: f64) -> Fbm<N> {
+		if octaves == 0 {
+			panic!("Fractal noise needs at least one octave")
+		}
+		Fbm { noise, octaves, lacunarity, gain }
+	}
+
+}
+
+impl<N> Noise for Fbm<N> where N: Noise {
+	/// Returns the sum of the octaves at `point`, normalized by the total
+	/// amplitude so that the result stays in the range of the base noise.
+	fn sample(&self, point: &Vector<f64>) -> f64 {
+		let (mut frequency, mut amplitude) = (1.0, 1.0);
+		let (mut total, mut norm) = (0.0, 0.0);
+		for _ in 0..self.octaves {
+			total += amplitude * self.noise.sample(&point.map(|c| c * frequency));
+			norm += amplitude;
+			frequency *= self.lacunarity;
+			amplitude *= self.gain;
+		}
+		total / norm
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn grid(dim: usize) -> Vec<Vector<f64>> {
+		(0..400).map(|i| Vector::new((0..dim).map(|d| (i as f64 * 0.173 + d as f64 * 3.7).sin() * 20.0).collect())).collect()
+	}
+
+	#[test]
+	fn perlin_vanishes_on_lattice() {
+		let perlin = Perlin::new(1);
+		for point in [vec![3.0], vec![-2.0, 5.0], vec![1.0, -7.0, 4.0]] {
+			assert_eq!(perlin.sample(&Vector::new(point)), 0.0);
+		}
+	}
+
+	#[test]
+	fn noise_is_bounded_and_varies() {
+		let generators: Vec<Box<dyn Noise>> = vec![Box::new(Perlin::new(9)), Box::new(Simplex::new(9))];
+		for noise in generators.iter() {
+			for dim in 1..=3 {
+				let values: Vec<f64> = grid(dim).iter().map(|p| noise.sample(p)).collect();
+				assert!(values.iter().all(|v| v.abs() <= 1.1));
+				assert!(values.iter().any(|v| v.abs() > 0.1));
+			}
+		}
+	}
+
+	#[test]
+	fn seeds_change_the_field() {
+		let point = Vector::new(vec![0.4, 0.7, 0.1]);
+		assert_ne!(Simplex::new(1).sample(&point), Simplex::new(2).sample(&point));
+		assert_eq!(Simplex::new(1).sample(&point), Simplex::new(1).sample(&point))
+	}
+
+	#[test]
+	fn noise_is_continuous() {
+		let noise = Simplex::new(4);
+		let (a, b) = (Vector::new(vec![1.3, 2.2]), Vector::new(vec![1.3 + 1e-7, 2.2]));
+		assert!((noise.sample(&a) - noise.sample(&b)).abs() < 1e-5)
+	}
+
+	#[test]
+	fn single_octave_fbm_is_base_noise() {
+		let point = Vector::new(vec![0.3, 0.9]);
+		assert_eq!(Fbm::new(Perlin::new(5), 1, 2.0, 0.5).sample(&point), Perlin::new(5).sample(&point))
+	}
+
+	#[test]
+	#[should_panic]
+	fn four_dimensional_points() {
+		let _ = Perlin::new(0).sample(&Vector::new(vec![0.0; 4]));
+	}
+
+}
@@ -0,0 +1,201 @@
+//! Optimization.
+//!
+//! Entropy-regularized optimal transport between discrete distributions,
+//! optionally bounded by a [`Budget`].
+
+use crate::linal::{Vector, Matrix};
+use crate::guard::{Budget, ResourceExceeded};
+
+/// An entropy-regularized optimal transport plan, computed by [`sinkhorn`].
+#[derive(Debug, Clone)]
+pub struct Transport {
+	plan: Matrix<f64>,
+	cost: f64,
+	converged: bool
+}
+
+impl Transport {
+
+	/// Returns the transport plan, whose (i, j) entry is the mass moved from
+	/// source i to target j.
+	pub fn plan(&self) -> &Matrix<f64> {
+		&self.plan
+	}
+
+	/// Returns the transport cost ⟨P, C⟩ of the plan, which approximates the
+	/// optimal transport (Wasserstein) distance as the regularization
+	/// vanishes.
+	pub fn cost(&self) -> f64 {
+		self.cost
+	}
+
+	/// Returns whether the marginals of the plan matched `a` and `b` to
+	/// within tolerance before the iteration limit.
+	pub fn converged(&self) -> bool {
+		self.converged
+	}
+
+}
+
+fn log_sum_exp(terms: impl Iterator<Item=f64>) -> f64 {
+	let terms: Vec<f64> = terms.collect();
+	let max = terms.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+	if max == f64::NEG_INFINITY {
+		return max;
+	}
+	max + terms.iter().map(|t| (t - max).exp()).sum::<f64>().ln()
+}
+
+/// Computes the optimal transport plan between the distributions `a` and
+/// `b` under `cost_matrix`, regularized by `epsilon` times the negative
+/// entropy of the plan.
+///
+/// Runs Sinkhorn's matrix scaling in the log domain, iterating on the dual
+/// potentials f and g of the plan Pᵢⱼ = exp((fᵢ + gⱼ - Cᵢⱼ) / ε), which
+/// stays stable for small `epsilon` where the classical scaling under- or
+/// overflows. Smaller `epsilon` gives plans closer to the unregularized
+/// optimum at the price of slower convergence.
+///
+/// ```
+/// # use hebrides::linal::{Vector, Matrix};
+/// # use hebrides::optimize::sinkhorn;
+/// let cost = Matrix::new(vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+/// let uniform = Vector::new(vec![0.5, 0.5]);
+/// let transport = sinkhorn(&cost, &uniform, &uniform, 0.01);
+/// assert!(transport.cost() < 1e-9);
+/// ```
+///
+/// # Panics
+/// Panics if `a` and `b` do not match the rows and columns of `cost_matrix`,
+/// if they have negative entries or different total masses, or if `epsilon`
+/// is not positive.
+pub fn sinkhorn(cost_matrix: &Matrix<f64>, a: &Vector<f64>, b: &Vector<f64>, epsilon: f64) -> Transport {
+	sinkhorn_guarded(cost_matrix, a, b, epsilon, &mut Budget::unlimited()).expect("Unlimited Budget was exceeded")
+}
+
+/// Computes the same transport plan as [`sinkhorn`], counting each Sinkhorn
+/// iteration and the plan's allocation against `budget`.
+///
+/// ```
+/// # use hebrides::linal::{Vector, Matrix};
+/// # use hebrides::optimize::sinkhorn_guarded;
+/// # use hebrides::guard::{Budget, ResourceExceeded};
+/// let cost = Matrix::new(vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+/// let a = Vector::new(vec![0.9, 0.1]);
+/// let b = Vector::new(vec![0.1, 0.9]);
+/// let mut budget = Budget::unlimited().with_max_iterations(1);
+/// let result = sinkhorn_guarded(&cost, &a, &b, 0.001, &mut budget);
+/// assert_eq!(result.unwrap_err(), ResourceExceeded::Iterations { limit: 1 });
+/// ```
+///
+/// # Errors
+/// Returns [`ResourceExceeded`] if `budget` runs out before the iteration
+/// finishes.
+///
+/// # Panics
+/// Panics under the same conditions as [`sinkhorn`].
+pub fn sinkhorn_guarded(cost_matrix: &Matrix<f64>, a: &Vector<f64>, b: &Vector<f64>, epsilon: f64, budget: &mut Budget) -> Result<Transport, ResourceExceeded> {
+	let (m, n) = (cost_matrix.dims.num_rows, cost_matrix.dims.num_cols);
+	if a.dim() != m || b.dim() != n {
+		panic!("Marginals must match the dimensions of the cost Matrix")
+	}
+	if a.iter().chain(b.iter()).any(|e| *e < 0.0) {
+		panic!("Marginals must be nonnegative")
+	}
+	let (mass_a, mass_b) = (a.iter().sum::<f64>(), b.iter().sum::<f64>());
+	if (mass_a - mass_b).abs() > 1e-9 * mass_a.max(mass_b) {
+		panic!("Marginals must have the same total mass")
+	}
+	if epsilon <= 0.0 {
+		panic!("Regularization must be positive")
+	}
+	budget.reserve(m * n)?;
+	let c = &cost_matrix.rows;
+	let (log_a, log_b): (Vec<f64>, Vec<f64>) = (a.iter().map(|e| e.ln()).collect(), b.iter().map(|e| e.ln()).collect());
+	let mut f = vec![0.0; m];
+	let mut g = vec![0.0; n];
+	let mut converged = false;
+	for _ in 0..10_000 {
+		budget.step()?;
+		for (i, fi) in f.iter_mut().enumerate() {
+			*fi = epsilon * (log_a[i] - log_sum_exp((0..n).map(|j| (g[j] - c[i][j]) / epsilon)));
+		}
+		for (j, gj) in g.iter_mut().enumerate() {
+			*gj = epsilon * (log_b[j] - log_sum_exp((0..m).map(|i| (f[i] - c[i][j]) / epsilon)));
+		}
+		let error: f64 = (0..m).map(|i| {
+			let row: f64 = (0..n).map(|j| ((f[i] + g[j] - c[i][j]) / epsilon).exp()).sum();
+			(row - a[i]).abs()
+		}).sum();
+		if error <= 1e-9 * mass_a {
+			converged = true;
+			break;
+		}
+	}
+	let plan = Matrix::new((0..m).map(|i| (0..n).map(|j| ((f[i] + g[j] - c[i][j]) / epsilon).exp()).collect()).collect());
+	let cost = plan.rows.iter().flatten().zip(c.iter().flatten()).map(|(p, q)| p * q).sum();
+	Ok(Transport { plan, cost, converged })
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn line_cost(m: usize, n: usize) -> Matrix<f64> {
+		Matrix::new((0..m).map(|i| (0..n).map(|j| (i as f64 / (m - 1) as f64 - j as f64 / (n - 1) as f64).powi(2)).collect()).collect())
+	}
+
+	#[test]
+	fn plan_has_requested_marginals() {
+		let a = Vector::new(vec![0.1, 0.4, 0.3, 0.2]);
+		let b = Vector::new(vec![0.5, 0.2, 0.3]);
+		let transport = sinkhorn(&line_cost(4, 3), &a, &b, 0.05);
+		assert!(transport.converged());
+		let plan = transport.plan();
+		assert!((0..4).all(|i| (plan.rows[i].iter().sum::<f64>() - a[i]).abs() < 1e-8));
+		assert!((0..3).all(|j| (plan.cols[j].iter().sum::<f64>() - b[j]).abs() < 1e-8))
+	}
+
+	/// The north-west corner rule, which is optimal on the line for convex
+	/// costs when sources and targets are sorted.
+	fn exact_line_cost(a: &[f64], b: &[f64], cost: &Matrix<f64>) -> f64 {
+		let (mut a, mut b) = (a.to_vec(), b.to_vec());
+		let (mut i, mut j, mut total) = (0, 0, 0.0);
+		while i < a.len() && j < b.len() {
+			let moved = a[i].min(b[j]);
+			total += moved * cost.rows[i][j];
+			a[i] -= moved;
+			b[j] -= moved;
+			if a[i] <= b[j] { i += 1 } else { j += 1 }
+		}
+		total
+	}
+
+	#[test]
+	fn small_regularization_approaches_exact_transport() {
+		let (a, b) = (vec![0.4, 0.1, 0.1, 0.4], vec![0.1, 0.4, 0.4, 0.1]);
+		let cost = line_cost(4, 4);
+		let exact = exact_line_cost(&a, &b, &cost);
+		let loose = sinkhorn(&cost, &Vector::new(a.clone()), &Vector::new(b.clone()), 0.1);
+		let tight = sinkhorn(&cost, &Vector::new(a), &Vector::new(b), 0.005);
+		assert!(loose.converged() && tight.converged());
+		assert!((tight.cost() - exact).abs() < (loose.cost() - exact).abs());
+		assert!((tight.cost() - exact).abs() < 1e-2)
+	}
+
+	#[test]
+	fn log_domain_survives_tiny_regularization() {
+		let a = Vector::new(vec![0.25; 4]);
+		let transport = sinkhorn(&line_cost(4, 4).map(|e| e * 1000.0), &a, &a, 1e-2);
+		assert!(transport.plan().rows.iter().flatten().all(|p| p.is_finite()));
+		assert!(transport.cost() < 1e-6)
+	}
+
+	#[test]
+	#[should_panic]
+	fn unbalanced_marginals() {
+		let _ = sinkhorn(&line_cost(2, 2), &Vector::new(vec![0.5, 0.5]), &Vector::new(vec![1.0, 0.5]), 0.1);
+	}
+
+}
@@ -0,0 +1,308 @@
+//! Plot data export.
+//!
+//! A [`Plot`] collects named (x, y) [`Series`], built from pairs of vectors
+//! or by sampling functions, and writes them out as CSV, JSON, a gnuplot
+//! script, or a Vega-Lite specification. Nothing is rendered here; the
+//! output is meant to be handed to whichever plotting tool is at hand.
+
+use std::fmt::Write;
+
+use crate::linal::Vector;
+
+/// A named sequence of (x, y) points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+	name: String,
+	points: Vec<(f64, f64)>
+}
+
+impl Series {
+
+	/// Constructs a [`Series`] pairing the components of `x` with those of
+	/// `y`.
+	///
+	/// # Panics
+	/// Panics if `x` and `y` have different dimensions.
+	pub fn new(name: &str, x: &Vector<f64>, y: &Vector<f64>) -> Self {
+		if x.dim() != y.dim() {
+			panic!("Cannot pair {} x values with {} y values", x.dim(), y.dim());
+		}
+		Series { name: name.to_string(), points: x.iter().copied().zip(y.iter().copied()).collect() }
+	}
+
+	/// Constructs a [`Series`] by sampling `f` at `samples` evenly spaced
+	/// points from `start` to `end` inclusive.
+	///
+	/// ```
+	/// # use hebrides::plot::Series;
+	/// let squares = Series::sample("x^2", |x| x * x, 0.0, 2.0, 3);
+	/// assert_eq!(squares.points(), &[(0.0, 0.0), (1.0, 1.0), (2.0, 4.0)]);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `samples` is less than 2.
+	pub fn sample(name: &str, f: impl Fn(f64) -> f64, start: f64, end: f64, samples: usize) -> Self {
+		let points = grid(start, end, samples).map(|x| (x, f(x))).collect();
+		Series { name: name.to_string(), points }
+	}
+
+	/// Constructs a [`Series`] tracing the planar curve `f`, sampled at
+	/// `samples` evenly spaced parameters from `start` to `end` inclusive.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// # use hebrides::plot::Series;
+	/// let circle = Series::parametric("circle", |t| Vector::new(vec![t.cos(), t.sin()]), 0.0, std::f64::consts::TAU, 65);
+	/// assert_eq!(circle.len(), 65);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `samples` is less than 2 or if `f` returns a vector that is
+	/// not two dimensional.
+	pub fn parametric(name: &str, f: impl Fn(f64) -> Vector<f64>, start: f64, end: f64, samples: usize) -> Self {
+		let points = grid(start, end, samples).map(|t| {
+			let point = f(t);
+			if point.dim() != 2 {
+				panic!("Cannot plot a point of dimension {}", point.dim());
+			}
+			(point[0], point[1])
+		}).collect();
+		Series { name: name.to_string(), points }
+	}
+
+	/// Returns the name of `self`.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Returns the points of `self`, in order.
+	pub fn points(&self) -> &[(f64, f64)] {
+		&self.points
+	}
+
+	/// Returns the number of points in `self`.
+	pub fn len(&self) -> usize {
+		self.points.len()
+	}
+
+	/// Returns `true` if `self` has no points.
+	pub fn is_empty(&self) -> bool {
+		self.points.is_empty()
+	}
+
+}
+
+fn grid(start: f64, end: f64, samples: usize) -> impl Iterator<Item=f64> {
+	if samples < 2 {
+		panic!("Cannot sample at fewer than 2 points");
+	}
+	let step = (end - start) / (samples - 1) as f64;
+	(0..samples).map(move |i| if i == samples - 1 { end } else { start + step * i as f64 })
+}
+
+/// A collection of [`Series`] to be exported together.
+///
+/// ```
+/// # use hebrides::plot::{Plot, Series};
+/// let plot = Plot::new()
+///     .with_title("Powers")
+///     .with_series(Series::sample("x", |x| x, 0.0, 1.0, 2))
+///     .with_series(Series::sample("x^2", |x| x * x, 0.0, 1.0, 2));
+/// assert_eq!(plot.to_csv(), "series,x,y\nx,0,0\nx,1,1\nx^2,0,0\nx^2,1,1\n");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Plot {
+	title: Option<String>,
+	series: Vec<Series>
+}
+
+impl Plot {
+
+	/// Constructs an empty, untitled [`Plot`].
+	pub fn new() -> Self {
+		Plot::default()
+	}
+
+	/// Sets the title of the plot.
+	pub fn with_title(mut self, title: &str) -> Self {
+		self.title = Some(title.to_string());
+		self
+	}
+
+	/// Adds `series` to the plot.
+	pub fn with_series(mut self, series: Series) -> Self {
+		self.series.push(series);
+		self
+	}
+
+	/// Returns the series of `self`, in the order they were added.
+	pub fn series(&self) -> &[Series] {
+		&self.series
+	}
+
+	/// Returns the points of `self` as CSV in long format, with a
+	/// `series,x,y` header and one row per point.
+	///
+	/// Non-finite values are written as `NaN`, `inf`, and `-inf`.
+	pub fn to_csv(&self) -> String {
+		let mut out = String::from("series,x,y\n");
+		for series in &self.series {
+			let name = csv_field(&series.name);
+			for (x, y) in &series.points {
+				let _ = writeln!(out, "{},{},{}", name, x, y);
+			}
+		}
+		out
+	}
+
+	/// Returns `self` as a JSON object with a `title` and an array of
+	/// `series`, each holding its `name` and parallel `x` and `y` arrays.
+	///
+	/// Non-finite values, which JSON cannot represent, are written as
+	/// `null`.
+	///
+	/// ```
+	/// # use hebrides::plot::{Plot, Series};
+	/// let plot = Plot::new().with_series(Series::sample("f", |x| 1.0 / x, 0.0, 1.0, 2));
+	/// assert_eq!(plot.to_json(), r#"{"title":null,"series":[{"name":"f","x":[0,1],"y":[null,1]}]}"#);
+	/// ```
+	pub fn to_json(&self) -> String {
+		let series: Vec<String> = self.series.iter().map(|series| {
+			let (x, y): (Vec<String>, Vec<String>) = series.points.iter().map(|(x, y)| (json_number(*x), json_number(*y))).unzip();
+			format!(r#"{{"name":{},"x":[{}],"y":[{}]}}"#, json_string(&series.name), x.join(","), y.join(","))
+		}).collect();
+		format!(r#"{{"title":{},"series":[{}]}}"#, self.json_title(), series.join(","))
+	}
+
+	/// Returns a gnuplot script drawing each series of `self` as a line,
+	/// with the data inlined as named data blocks.
+	pub fn to_gnuplot(&self) -> String {
+		let mut out = String::new();
+		for (i, series) in self.series.iter().enumerate() {
+			let _ = writeln!(out, "$series{} << EOD", i);
+			for (x, y) in &series.points {
+				let _ = writeln!(out, "{} {}", x, y);
+			}
+			out.push_str("EOD\n");
+		}
+		if let Some(title) = &self.title {
+			let _ = writeln!(out, "set title {}", gnuplot_string(title));
+		}
+		let commands: Vec<String> = self.series.iter().enumerate().map(|(i, series)| {
+			format!("$series{} using 1:2 with lines title {}", i, gnuplot_string(&series.name))
+		}).collect();
+		if !commands.is_empty() {
+			let _ = writeln!(out, "plot {}", commands.join(", \\\n     "));
+		}
+		out
+	}
+
+	/// Returns a Vega-Lite specification drawing each series of `self` as a
+	/// line, coloured by series name, with the data inlined.
+	///
+	/// Points with non-finite coordinates are written with `null` values,
+	/// which Vega-Lite leaves out of the line.
+	pub fn to_vega_lite(&self) -> String {
+		let values: Vec<String> = self.series.iter().flat_map(|series| {
+			let name = json_string(&series.name);
+			series.points.iter().map(move |(x, y)| format!(r#"{{"series":{},"x":{},"y":{}}}"#, name, json_number(*x), json_number(*y)))
+		}).collect();
+		let title = self.title.as_deref().map_or(String::new(), |title| format!(r#""title":{},"#, json_string(title)));
+		format!(
+			concat!(
+				r#"{{"$schema":"https://vega.github.io/schema/vega-lite/v5.json",{}"data":{{"values":[{}]}},"#,
+				r#""mark":"line","encoding":{{"x":{{"field":"x","type":"quantitative"}},"y":{{"field":"y","type":"quantitative"}},"#,
+				r#""color":{{"field":"series","type":"nominal"}}}}}}"#
+			),
+			title, values.join(",")
+		)
+	}
+
+	fn json_title(&self) -> String {
+		self.title.as_deref().map_or(String::from("null"), json_string)
+	}
+
+}
+
+fn json_number(x: f64) -> String {
+	if x.is_finite() { x.to_string() } else { String::from("null") }
+}
+
+fn json_string(s: &str) -> String {
+	let mut out = String::from("\"");
+	for c in s.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			'\t' => out.push_str("\\t"),
+			c if (c as u32) < 0x20 => { let _ = write!(out, "\\u{:04x}", c as u32); },
+			c => out.push(c)
+		}
+	}
+	out.push('"');
+	out
+}
+
+fn csv_field(s: &str) -> String {
+	if s.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", s.replace('"', "\"\""))
+	} else {
+		s.to_string()
+	}
+}
+
+fn gnuplot_string(s: &str) -> String {
+	format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn sampling_hits_both_endpoints() {
+		let series = Series::sample("sin", f64::sin, 0.1, 0.7, 7);
+		assert_eq!(series.points().first().unwrap().0, 0.1);
+		assert_eq!(series.points().last().unwrap().0, 0.7);
+		assert_eq!(series.len(), 7)
+	}
+
+	#[test]
+	fn names_are_escaped() {
+		let x = Vector::new(vec![1.0]);
+		let plot = Plot::new().with_title("a \"b\"").with_series(Series::new("p, q", &x, &x));
+		assert_eq!(plot.to_csv(), "series,x,y\n\"p, q\",1,1\n");
+		assert_eq!(plot.to_json(), r#"{"title":"a \"b\"","series":[{"name":"p, q","x":[1],"y":[1]}]}"#);
+		assert!(plot.to_gnuplot().contains("set title \"a \\\"b\\\"\""))
+	}
+
+	#[test]
+	fn gnuplot_plots_every_block() {
+		let plot = Plot::new()
+			.with_series(Series::sample("a", |x| x, 0.0, 1.0, 2))
+			.with_series(Series::sample("b", |x| -x, 0.0, 1.0, 2));
+		let script = plot.to_gnuplot();
+		assert!(script.starts_with("$series0 << EOD\n0 0\n1 1\nEOD\n$series1 << EOD\n"));
+		assert!(script.contains("plot $series0 using 1:2 with lines title \"a\""));
+		assert!(script.contains("$series1 using 1:2 with lines title \"b\""))
+	}
+
+	#[test]
+	fn vega_lite_inlines_long_data() {
+		let x = Vector::new(vec![0.0, 1.0]);
+		let spec = Plot::new().with_series(Series::new("s", &x, &Vector::new(vec![2.0, f64::NAN]))).to_vega_lite();
+		assert!(spec.contains(r#""values":[{"series":"s","x":0,"y":2},{"series":"s","x":1,"y":null}]"#));
+		assert!(spec.contains(r#""color":{"field":"series","type":"nominal"}"#));
+		assert!(!spec.contains("title") && spec.ends_with("}}}"))
+	}
+
+	#[test]
+	#[should_panic]
+	fn mismatched_vectors() {
+		let _ = Series::new("bad", &Vector::new(vec![1.0, 2.0]), &Vector::new(vec![1.0]));
+	}
+
+}
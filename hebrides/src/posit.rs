@@ -0,0 +1,277 @@
+//! Posit arithmetic.
+//!
+//! A 32-bit posit type for comparing the behavior of hebrides algorithms
+//! under an alternative to IEEE floats. Posits trade the fixed exponent field
+//! of a float for a variable-length regime, giving more precision near one
+//! and less near the extremes, and they have a single zero and a single
+//! exceptional value, NaR (not a real), in place of infinities and NaNs.
+//!
+//! Enabled by the `posit` feature.
+
+use std::cmp::Ordering;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+use crate::{Semiring, AdditiveGroup, Ring, Field};
+use crate::linal::Scalar;
+
+/// A 32-bit posit with two exponent bits, as in the 2022 posit standard.
+///
+/// Values range in magnitude from 2⁻¹²⁰ to 2¹²⁰ with up to 27 fraction
+/// bits. Results too large or too small in magnitude saturate at the
+/// extremes rather than overflowing to NaR or underflowing to zero.
+///
+/// Arithmetic is carried out in `f64` and the result rounded to the nearest
+/// posit, ties to even. This agrees with correctly rounded posit arithmetic
+/// except in rare double-rounding cases, where it may differ in the last
+/// place.
+///
+/// ```
+/// # use hebrides::posit::Posit32;
+/// let third = Posit32::ONE / Posit32::from(3.0);
+/// assert!((third.to_f64() - 1.0 / 3.0).abs() < 1e-8);
+/// assert_eq!(Posit32::from(2.0).to_bits(), 0x4800_0000);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Posit32(u32);
+
+impl Posit32 {
+
+	/// Zero.
+	pub const ZERO: Posit32 = Posit32(0);
+
+	/// One.
+	pub const ONE: Posit32 = Posit32(0x4000_0000);
+
+	/// NaR, the result of undefined operations such as division by zero.
+	pub const NAR: Posit32 = Posit32(0x8000_0000);
+
+	/// The largest positive posit, 2¹²⁰.
+	pub const MAX: Posit32 = Posit32(0x7FFF_FFFF);
+
+	/// The smallest positive posit, 2⁻¹²⁰.
+	pub const MIN_POSITIVE: Posit32 = Posit32(1);
+
+	/// Constructs a posit from its bit pattern.
+	pub fn from_bits(bits: u32) -> Self {
+		Posit32(bits)
+	}
+
+	/// Returns the bit pattern of `self`.
+	pub fn to_bits(&self) -> u32 {
+		self.0
+	}
+
+	/// Returns whether `self` is NaR.
+	pub fn is_nar(&self) -> bool {
+		*self == Self::NAR
+	}
+
+	/// Returns the value of `self` as an `f64`, which is exact; NaR becomes
+	/// NaN.
+	pub fn to_f64(&self) -> f64 {
+		if self.0 == 0 {
+			return 0.0;
+		}
+		if self.is_nar() {
+			return f64::NAN;
+		}
+		let negative = self.0 & 0x8000_0000 != 0;
+		let magnitude = if negative { self.0.wrapping_neg() } else { self.0 };
+		let body = magnitude << 1;
+		let (run, regime) = if body & 0x8000_0000 != 0 {
+			let run = body.leading_ones();
+			(run, run as i32 - 1)
+		} else {
+			let run = body.leading_zeros();
+			(run, -(run as i32))
+		};
+		let rest = body.checked_shl(run + 1).unwrap_or(0);
+		let exponent = 4 * regime + (rest >> 30) as i32;
+		let fraction = 1.0 + f64::from(rest << 2) / 4294967296.0;
+		let value = fraction * 2.0_f64.powi(exponent);
+		if negative { -value } else { value }
+	}
+
+	/// Returns the absolute value of `self`.
+	pub fn abs(self) -> Self {
+		if (self.0 as i32) < 0 { -self } else { self }
+	}
+
+	/// Returns the square root of `self`, or NaR if `self` is negative.
+	pub fn sqrt(self) -> Self {
+		Posit32::from(self.to_f64().sqrt())
+	}
+
+}
+
+/// Rounds `x` to the nearest posit, ties to even. NaN and the infinities
+/// become NaR.
+impl From<f64> for Posit32 {
+	fn from(x: f64) -> Posit32 {
+		if x == 0.0 {
+			return Posit32::ZERO;
+		}
+		if !x.is_finite() {
+			return Posit32::NAR;
+		}
+		let magnitude = x.abs();
+		let bits = if magnitude >= 2.0_f64.powi(120) {
+			Posit32::MAX.0
+		} else if magnitude <= 2.0_f64.powi(-120) {
+			Posit32::MIN_POSITIVE.0
+		} else {
+			let raw = magnitude.to_bits();
+			let exponent = ((raw >> 52) & 0x7FF) as i32 - 1023;
+			let fraction = u128::from(raw & ((1 << 52) - 1));
+			let regime = exponent.div_euclid(4);
+			let (regime_bits, regime_len) = if regime >= 0 {
+				(((1_u128 << (regime + 1)) - 1) << 1, regime as u32 + 2)
+			} else {
+				(1, (-regime) as u32 + 1)
+			};
+			let len = regime_len + 2 + 52;
+			let pattern = (regime_bits << 54) | (u128::from(exponent.rem_euclid(4) as u32) << 52) | fraction;
+			let shift = len - 31;
+			let mut kept = (pattern >> shift) as u32;
+			let remainder = pattern & ((1 << shift) - 1);
+			let half = 1 << (shift - 1);
+			if remainder > half || (remainder == half && kept & 1 == 1) {
+				kept += 1;
+			}
+			kept.clamp(Posit32::MIN_POSITIVE.0, Posit32::MAX.0)
+		};
+		if x < 0.0 { Posit32(bits.wrapping_neg()) } else { Posit32(bits) }
+	}
+}
+
+impl From<u8> for Posit32 {
+	fn from(x: u8) -> Posit32 {
+		Posit32::from(f64::from(x))
+	}
+}
+
+/// Posits are ordered as their bit patterns are as signed integers, which
+/// places NaR below every real value.
+impl Ord for Posit32 {
+	fn cmp(&self, other: &Self) -> Ordering {
+		(self.0 as i32).cmp(&(other.0 as i32))
+	}
+}
+
+impl PartialOrd for Posit32 {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+macro_rules! impl_posit_op {
+	($trait:ident, $method:ident, $op:tt) => {
+		impl $trait<Self> for Posit32 {
+			type Output = Self;
+			fn $method(self, other: Self) -> Self {
+				if self.is_nar() || other.is_nar() {
+					return Posit32::NAR;
+				}
+				Posit32::from(self.to_f64() $op other.to_f64())
+			}
+		}
+	}
+}
+
+impl_posit_op!(Add, add, +);
+impl_posit_op!(Sub, sub, -);
+impl_posit_op!(Mul, mul, *);
+impl_posit_op!(Div, div, /);
+
+impl Neg for Posit32 {
+	type Output = Self;
+	fn neg(self) -> Self {
+		Posit32(self.0.wrapping_neg())
+	}
+}
+
+impl Semiring for Posit32 {}
+impl AdditiveGroup for Posit32 {}
+impl Ring for Posit32 {}
+impl Field for Posit32 {}
+
+impl Scalar for Posit32 {
+	fn from_real(x: f64) -> Self {
+		Posit32::from(x)
+	}
+	fn conj(self) -> Self {
+		self
+	}
+	fn modulus(self) -> f64 {
+		self.abs().to_f64()
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+	use crate::linal::{Vector, InnerProductSpace, gram_schmidt};
+
+	#[test]
+	fn known_bit_patterns() {
+		for (x, bits) in [(1.0, 0x4000_0000), (-1.0, 0xC000_0000), (2.0, 0x4800_0000), (16.0, 0x6000_0000), (0.5, 0x3800_0000)] {
+			assert_eq!(Posit32::from(x).to_bits(), bits);
+			assert_eq!(Posit32::from_bits(bits).to_f64(), x);
+		}
+		assert_eq!(Posit32::MAX.to_f64(), 2.0_f64.powi(120));
+		assert_eq!(Posit32::MIN_POSITIVE.to_f64(), 2.0_f64.powi(-120))
+	}
+
+	#[test]
+	fn every_sampled_pattern_round_trips() {
+		let mut bits = 0_u32;
+		for _ in 0..100_000 {
+			bits = bits.wrapping_add(42_949);
+			let p = Posit32::from_bits(bits);
+			if !p.is_nar() {
+				assert_eq!(Posit32::from(p.to_f64()), p);
+			}
+		}
+	}
+
+	#[test]
+	fn rounds_to_nearest() {
+		let x = 0.1;
+		let p = Posit32::from(x);
+		let (below, above) = (Posit32::from_bits(p.to_bits() - 1), Posit32::from_bits(p.to_bits() + 1));
+		assert!((p.to_f64() - x).abs() <= (below.to_f64() - x).abs());
+		assert!((p.to_f64() - x).abs() <= (above.to_f64() - x).abs())
+	}
+
+	#[test]
+	fn saturates_and_propagates_nar() {
+		let huge = Posit32::from(1e30);
+		assert_eq!(huge * huge, Posit32::MAX);
+		assert_eq!(Posit32::ONE / (huge * huge * huge), Posit32::MIN_POSITIVE);
+		assert!((Posit32::ONE / Posit32::ZERO).is_nar());
+		assert!((Posit32::NAR + Posit32::ONE).is_nar());
+		assert!(Posit32::from(f64::INFINITY).is_nar())
+	}
+
+	#[test]
+	fn ordering_matches_values() {
+		let values = [-3.5, -1.0, -1e-20, 0.0, 1e-20, 0.75, 1.0, 1e10];
+		for pair in values.windows(2) {
+			assert!(Posit32::from(pair[0]) < Posit32::from(pair[1]));
+		}
+		assert!(Posit32::NAR < Posit32::from(-1e30))
+	}
+
+	#[test]
+	fn gram_schmidt_over_posits() {
+		let basis = gram_schmidt(&[
+			Vector::new(vec![Posit32::from(1.0), Posit32::from(1.0), Posit32::ZERO]),
+			Vector::new(vec![Posit32::from(1.0), Posit32::ZERO, Posit32::from(1.0)])
+		]);
+		assert_eq!(basis.len(), 2);
+		assert!(basis[0].inner(&basis[1]).abs().to_f64() < 1e-7);
+		assert!((basis[1].inner(&basis[1]).to_f64() - 1.0).abs() < 1e-7)
+	}
+
+}
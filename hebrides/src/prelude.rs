@@ -0,0 +1,18 @@
+//! The hebrides prelude.
+//!
+//! Re-exports the core scalar and linear algebra types and the traits
+//! needed to use them generically, so that most code can start with
+//!
+//! ```
+//! use hebrides::prelude::*;
+//!
+//! let m = Matrix::new(vec![vec![2.0, 0.0], vec![0.0, 3.0]]);
+//! let v = Vector::new(vec![Real::new(3.0), Real::new(4.0)]);
+//! assert_eq!(m.determinant(), 6.0);
+//! assert_eq!(v.norm(), Real::new(5.0));
+//! ```
+
+pub use crate::elem::{Angle, ApproxEq, Complex, Conjugate, Float, Real};
+pub use crate::interval::Interval;
+pub use crate::linal::{Axis, InnerProductSpace, Matrix, Metric, NormedSpace, Scalar, Solver, Vector, VectorSpace};
+pub use crate::symbolic::Expr;
@@ -0,0 +1,282 @@
+//! Seedable pseudorandom number generation.
+//!
+//! [`Rng`] is a small, fast, and fully deterministic generator: two instances
+//! constructed from the same seed always produce the same stream. It is not
+//! suitable for cryptographic use. Scalars implementing [`Random`], and
+//! vectors and matrices of them, can be drawn from a [`Distribution`] with an
+//! explicit generator, so Monte Carlo experiments and randomized tests are
+//! reproducible from their seed.
+
+use crate::{Real, Complex};
+use crate::linal::{Vector, Matrix};
+
+/// Pseudorandom number generator implementing xoshiro256**.
+#[derive(Debug, Clone)]
+pub struct Rng {
+	state: [u64; 4]
+}
+
+impl Rng {
+
+	/// Constructs an [`Rng`] from `seed`.
+	///
+	/// The seed is expanded into the generator's internal state with
+	/// SplitMix64, so nearby seeds still produce unrelated streams.
+	pub fn new(seed: u64) -> Rng {
+		let mut x = seed;
+		let mut state = [0; 4];
+		for word in state.iter_mut() {
+			x = x.wrapping_add(0x9e3779b97f4a7c15);
+			let mut z = x;
+			z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+			z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+			*word = z ^ (z >> 31);
+		}
+		Self { state }
+	}
+
+	/// Returns the next 64 pseudorandom bits.
+	pub fn next_u64(&mut self) -> u64 {
+		let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+		let t = self.state[1] << 17;
+		self.state[2] ^= self.state[0];
+		self.state[3] ^= self.state[1];
+		self.state[1] ^= self.state[2];
+		self.state[0] ^= self.state[3];
+		self.state[2] ^= t;
+		self.state[3] = self.state[3].rotate_left(45);
+		result
+	}
+
+	/// Returns a uniformly distributed f64 in [0, 1).
+	///
+	/// ```
+	/// # use hebrides::random::Rng;
+	/// let mut rng = Rng::new(7);
+	/// let x = rng.next_f64();
+	/// assert!((0.0..1.0).contains(&x));
+	/// ```
+	pub fn next_f64(&mut self) -> f64 {
+		(self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+	}
+
+	/// Returns a uniformly distributed integer in [0, `n`).
+	///
+	/// # Panics
+	/// Panics if `n` is zero.
+	pub fn below(&mut self, n: usize) -> usize {
+		if n == 0 {
+			panic!("Cannot draw from an empty range")
+		}
+		let n = n as u64;
+		let zone = u64::MAX - u64::MAX % n;
+		loop {
+			let x = self.next_u64();
+			if x < zone {
+				return (x % n) as usize;
+			}
+		}
+	}
+
+	/// Returns a standard normally distributed f64, by the Box-Muller
+	/// transform.
+	pub fn next_normal(&mut self) -> f64 {
+		let radius = (-2.0 * (1.0 - self.next_f64()).ln()).sqrt();
+		radius * (std::f64::consts::TAU * self.next_f64()).cos()
+	}
+
+	/// Shuffles `items` in place with a Fisher-Yates shuffle.
+	pub fn shuffle<T>(&mut self, items: &mut [T]) {
+		for i in (1..items.len()).rev() {
+			let j = self.below(i + 1);
+			items.swap(i, j);
+		}
+	}
+
+}
+
+/// A distribution over the reals to draw random values from.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Distribution {
+	/// Uniform over [`low`, `high`).
+	Uniform {
+		/// The inclusive lower end of the range.
+		low: f64,
+		/// The exclusive upper end of the range.
+		high: f64
+	},
+	/// Normal with the given mean and standard deviation.
+	Normal {
+		/// The mean.
+		mean: f64,
+		/// The standard deviation.
+		std_dev: f64
+	}
+}
+
+impl Distribution {
+
+	/// The uniform distribution over [0, 1).
+	pub const UNIT: Distribution = Distribution::Uniform { low: 0.0, high: 1.0 };
+
+	/// The normal distribution with mean 0 and standard deviation 1.
+	pub const STANDARD_NORMAL: Distribution = Distribution::Normal { mean: 0.0, std_dev: 1.0 };
+
+	/// Draws a value from `self` using `rng`.
+	///
+	/// # Panics
+	/// Panics if a uniform distribution does not have `low` < `high` or a
+	/// normal distribution has a negative standard deviation.
+	pub fn sample(&self, rng: &mut Rng) -> f64 {
+		match *self {
+			Distribution::Uniform { low, high } => {
+				if low.partial_cmp(&high) != Some(std::cmp::Ordering::Less) {
+					panic!("Uniform distribution must have low < high")
+				}
+				low + (high - low) * rng.next_f64()
+			},
+			Distribution::Normal { mean, std_dev } => {
+				if std_dev.is_nan() || std_dev < 0.0 {
+					panic!("Normal distribution must have a nonnegative standard deviation")
+				}
+				mean + std_dev * rng.next_normal()
+			}
+		}
+	}
+
+}
+
+/// Scalars that can be drawn at random from a [`Distribution`].
+///
+/// ```
+/// # use hebrides::{Complex, random::{Rng, Random, Distribution}};
+/// let mut rng = Rng::new(0);
+/// let z = Complex::random(&mut rng, Distribution::STANDARD_NORMAL);
+/// let x = f64::random(&mut rng, Distribution::Uniform { low: -1.0, high: 1.0 });
+/// assert!((-1.0..1.0).contains(&x));
+/// assert!(z.im() != 0.0);
+/// ```
+pub trait Random: Sized {
+
+	/// Draws a random value from `distribution` using `rng`.
+	fn random(rng: &mut Rng, distribution: Distribution) -> Self;
+
+}
+
+impl Random for f64 {
+	fn random(rng: &mut Rng, distribution: Distribution) -> Self {
+		distribution.sample(rng)
+	}
+}
+
+impl Random for f32 {
+	fn random(rng: &mut Rng, distribution: Distribution) -> Self {
+		distribution.sample(rng) as f32
+	}
+}
+
+impl Random for Real {
+	fn random(rng: &mut Rng, distribution: Distribution) -> Self {
+		Real::new(distribution.sample(rng))
+	}
+}
+
+/// The real and imaginary parts are drawn independently.
+impl Random for Complex {
+	fn random(rng: &mut Rng, distribution: Distribution) -> Self {
+		let re = distribution.sample(rng);
+		Complex::new(re, distribution.sample(rng))
+	}
+}
+
+impl<T> Vector<T> where T: Random {
+
+	/// Returns an `n`-dimensional [`Vector`] whose components are drawn
+	/// independently from `distribution` using `rng`.
+	///
+	/// ```
+	/// # use hebrides::{linal::Vector, random::{Rng, Distribution}};
+	/// let v: Vector<f64> = Vector::random(3, &mut Rng::new(1), Distribution::UNIT);
+	/// assert_eq!(v, Vector::random(3, &mut Rng::new(1), Distribution::UNIT));
+	/// ```
+	pub fn random(n: usize, rng: &mut Rng, distribution: Distribution) -> Vector<T> {
+		Vector::from_fn(n, |_| T::random(rng, distribution))
+	}
+
+}
+
+impl<T> Matrix<T> where T: Random + Copy {
+
+	/// Returns a `num_rows` x `num_cols` [`Matrix`] whose entries are drawn
+	/// independently from `distribution` using `rng`, row by row.
+	///
+	/// ```
+	/// # use hebrides::{linal::Matrix, random::{Rng, Distribution}};
+	/// let gaussian: Matrix<f64> = Matrix::random(50, 50, &mut Rng::new(7), Distribution::STANDARD_NORMAL);
+	/// let mean = gaussian.rows.iter().flatten().sum::<f64>() / 2500.0;
+	/// assert!(mean.abs() < 0.1);
+	/// ```
+	pub fn random(num_rows: usize, num_cols: usize, rng: &mut Rng, distribution: Distribution) -> Matrix<T> {
+		Matrix::new((0..num_rows).map(|_| (0..num_cols).map(|_| T::random(rng, distribution)).collect()).collect())
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn deterministic() {
+		let mut a = Rng::new(42);
+		let mut b = Rng::new(42);
+		assert!((0..100).all(|_| a.next_u64() == b.next_u64()))
+	}
+
+	#[test]
+	fn seeds_differ() {
+		let mut a = Rng::new(1);
+		let mut b = Rng::new(2);
+		assert_ne!(a.next_u64(), b.next_u64())
+	}
+
+	#[test]
+	fn below_stays_in_range() {
+		let mut rng = Rng::new(3);
+		assert!((0..1000).all(|_| rng.below(7) < 7))
+	}
+
+	#[test]
+	fn normal_moments() {
+		let mut rng = Rng::new(12);
+		let samples: Vec<f64> = (0..20000).map(|_| Distribution::Normal { mean: 3.0, std_dev: 2.0 }.sample(&mut rng)).collect();
+		let mean = samples.iter().sum::<f64>() / 20000.0;
+		let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 19999.0;
+		assert!((mean - 3.0).abs() < 0.05);
+		assert!((variance - 4.0).abs() < 0.15)
+	}
+
+	#[test]
+	fn uniform_stays_in_range() {
+		let v: Vector<f64> = Vector::random(1000, &mut Rng::new(4), Distribution::Uniform { low: -2.0, high: 5.0 });
+		assert!(v.iter().all(|x| (-2.0..5.0).contains(x)))
+	}
+
+	#[test]
+	#[should_panic]
+	fn empty_uniform() {
+		let _ = Distribution::Uniform { low: 1.0, high: 1.0 }.sample(&mut Rng::new(0));
+	}
+
+	#[test]
+	fn shuffle_is_a_permutation() {
+		let mut rng = Rng::new(5);
+		let mut items: Vec<usize> = (0..50).collect();
+		rng.shuffle(&mut items);
+		let mut sorted = items.clone();
+		sorted.sort();
+		assert_eq!(sorted, (0..50).collect::<Vec<usize>>())
+	}
+
+}
@@ -0,0 +1,337 @@
+//! Signal processing.
+//!
+//! Discrete Fourier transforms of complex [`Vector`]s of any length, the
+//! Goertzel algorithm for evaluating a single frequency bin, and
+//! cross-correlation of real signals for estimating the delay between two
+//! channels.
+
+use std::f64::consts::TAU;
+
+use crate::linal::Vector;
+use crate::Complex;
+
+/// Transforms `data` in place with the iterative radix-2 Cooley-Tukey
+/// algorithm; `data.len()` must be a power of two.
+fn radix2(data: &mut [Complex], inverse: bool) {
+	let n = data.len();
+	let mut j = 0;
+	for i in 1..n {
+		let mut bit = n >> 1;
+		while j & bit != 0 {
+			j ^= bit;
+			bit >>= 1;
+		}
+		j |= bit;
+		if i < j {
+			data.swap(i, j);
+		}
+	}
+	let sign = if inverse { 1.0 } else { -1.0 };
+	let mut len = 2;
+	while len <= n {
+		let twiddles: Vec<Complex> = (0..len / 2).map(|k| {
+			let theta = sign * TAU * k as f64 / len as f64;
+			Complex::new(theta.cos(), theta.sin())
+		}).collect();
+		for start in (0..n).step_by(len) {
+			for (k, w) in twiddles.iter().enumerate() {
+				let even = data[start + k];
+				let odd = data[start + k + len / 2] * *w;
+				data[start + k] = even + odd;
+				data[start + k + len / 2] = even - odd;
+			}
+		}
+		len <<= 1;
+	}
+}
+
+/// Transforms `data` of arbitrary length in place with Bluestein's
+/// algorithm, which rewrites the transform as a convolution with a chirp
+/// and evaluates that convolution with power-of-two FFTs.
+fn bluestein(data: &mut [Complex], inverse: bool) {
+	let n = data.len();
+	let m = (2 * n - 1).next_power_of_two();
+	let sign = if inverse { 1.0 } else { -1.0 };
+	// n² is reduced modulo 2n before scaling so that the chirp phase stays
+	// accurate for long signals.
+	let chirp: Vec<Complex> = (0..n).map(|k| {
+		let theta = sign * std::f64::consts::PI * ((k * k) % (2 * n)) as f64 / n as f64;
+		Complex::new(theta.cos(), theta.sin())
+	}).collect();
+	let mut a = vec![Complex::ZERO; m];
+	for (k, (x, w)) in data.iter().zip(chirp.iter()).enumerate() {
+		a[k] = *x * *w;
+	}
+	let mut b = vec![Complex::ZERO; m];
+	b[0] = chirp[0].conjugate();
+	for k in 1..n {
+		b[k] = chirp[k].conjugate();
+		b[m - k] = chirp[k].conjugate();
+	}
+	radix2(&mut a, false);
+	radix2(&mut b, false);
+	for (x, y) in a.iter_mut().zip(b.iter()) {
+		*x = *x * *y;
+	}
+	radix2(&mut a, true);
+	let scale = Complex::new(1.0 / m as f64, 0.0);
+	for (k, x) in data.iter_mut().enumerate() {
+		*x = a[k] * scale * chirp[k];
+	}
+}
+
+/// Transforms `data` in place, using radix-2 when its length is a power of
+/// two and Bluestein's algorithm otherwise.
+fn transform(data: &mut [Complex], inverse: bool) {
+	if data.len() <= 1 {
+		return;
+	}
+	if data.len().is_power_of_two() {
+		radix2(data, inverse)
+	} else {
+		bluestein(data, inverse)
+	}
+}
+
+/// Returns the discrete Fourier transform of `x`,
+/// X[k] = Σ x[n] e^(-2πikn/N).
+///
+/// Lengths that are powers of two use the radix-2 algorithm directly;
+/// every other length falls back to Bluestein's algorithm, which is still
+/// O(n log n) but roughly a constant factor slower.
+///
+/// ```
+/// # use hebrides::{Complex, linal::Vector};
+/// # use hebrides::signal::fft;
+/// let x = Vector::new(vec![Complex::ONE, Complex::ZERO, Complex::ZERO, Complex::ZERO]);
+/// assert_eq!(fft(&x), Vector::new(vec![Complex::ONE; 4]));
+/// ```
+pub fn fft(x: &Vector<Complex>) -> Vector<Complex> {
+	let mut data: Vec<Complex> = x.iter().copied().collect();
+	transform(&mut data, false);
+	Vector::new(data)
+}
+
+/// Returns the inverse discrete Fourier transform of `x`,
+/// x[n] = (1/N) Σ X[k] e^(2πikn/N), so that `ifft(&fft(&x))` recovers `x`.
+pub fn ifft(x: &Vector<Complex>) -> Vector<Complex> {
+	let mut data: Vec<Complex> = x.iter().copied().collect();
+	transform(&mut data, true);
+	let scale = Complex::new(1.0 / data.len() as f64, 0.0);
+	Vector::new(data.into_iter().map(|z| z * scale).collect())
+}
+
+/// Returns the single DFT bin of the real signal `x` at (possibly
+/// fractional) index `k`, Σ x[n] e^(-2πikn/N), using the Goertzel
+/// recurrence.
+///
+/// This costs O(N) per bin with one real multiplication per sample, which
+/// beats a full FFT when only a handful of frequencies are of interest, as
+/// in tone detection. A tone at `frequency` Hz sampled at `sample_rate` Hz
+/// falls in bin `frequency * N / sample_rate`.
+///
+/// ```
+/// # use hebrides::linal::Vector;
+/// # use hebrides::signal::goertzel;
+/// # use std::f64::consts::TAU;
+/// let (rate, n) = (8000.0, 205);
+/// let tone = Vector::from_fn(n, |t| (TAU * 697.0 * t as f64 / rate).sin());
+/// let power = |f: f64| goertzel(&tone, f * n as f64 / rate).modulus();
+/// assert!(power(697.0) > 10.0 * power(770.0));
+/// ```
+pub fn goertzel(x: &Vector<f64>, k: f64) -> Complex {
+	let n = x.dim();
+	if n == 0 {
+		return Complex::ZERO;
+	}
+	let omega = TAU * k / n as f64;
+	let coefficient = 2.0 * omega.cos();
+	let (mut s1, mut s2) = (0.0, 0.0);
+	for sample in x.iter() {
+		let s = sample + coefficient * s1 - s2;
+		s2 = s1;
+		s1 = s;
+	}
+	// s1 - e^(-iω) s2 is the sum phase-shifted by N - 1 samples.
+	let y = Complex::new(s1 - omega.cos() * s2, omega.sin() * s2);
+	let phase = -omega * (n - 1) as f64;
+	y * Complex::new(phase.cos(), phase.sin())
+}
+
+/// The cross-correlation of two real signals over every lag at which they
+/// overlap.
+///
+/// The value at lag k is Σ a[n + k] b[n], so a peak at a positive lag means
+/// that `a` trails `b` by that many samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossCorrelation {
+	values: Vector<f64>,
+	min_lag: isize
+}
+
+impl CrossCorrelation {
+
+	/// Returns the correlation values, in order of increasing lag from
+	/// [`min_lag`](CrossCorrelation::min_lag).
+	pub fn values(&self) -> &Vector<f64> {
+		&self.values
+	}
+
+	/// Returns the smallest lag, 1 - `b.dim()`.
+	pub fn min_lag(&self) -> isize {
+		self.min_lag
+	}
+
+	/// Returns the largest lag, `a.dim()` - 1.
+	pub fn max_lag(&self) -> isize {
+		self.min_lag + self.values.dim() as isize - 1
+	}
+
+	/// Returns the correlation at `lag`, or `None` if the signals do not
+	/// overlap at that lag.
+	pub fn at(&self, lag: isize) -> Option<f64> {
+		if lag < self.min_lag || lag > self.max_lag() {
+			return None;
+		}
+		Some(self.values[(lag - self.min_lag) as usize])
+	}
+
+	/// Returns the lag of the largest correlation value, taking the
+	/// smallest such lag on ties.
+	pub fn peak_lag(&self) -> isize {
+		self.min_lag + self.values.argmax() as isize
+	}
+
+	/// Returns the lag of the correlation peak refined to a fraction of a
+	/// sample by fitting a parabola through the peak and its two
+	/// neighbours.
+	///
+	/// A peak at either end of the lag range is not refined.
+	///
+	/// ```
+	/// # use hebrides::linal::Vector;
+	/// # use hebrides::signal::xcorr;
+	/// let pulse = |t: f64| (-(t * t) / 8.0).exp();
+	/// let a = Vector::from_fn(64, |n| pulse(n as f64 - 30.3));
+	/// let b = Vector::from_fn(64, |n| pulse(n as f64 - 20.0));
+	/// let correlation = xcorr(&a, &b);
+	/// assert_eq!(correlation.peak_lag(), 10);
+	/// assert!((correlation.delay() - 10.3).abs() < 0.05);
+	/// ```
+	pub fn delay(&self) -> f64 {
+		let peak = self.values.argmax();
+		if peak == 0 || peak == self.values.dim() - 1 {
+			return (self.min_lag + peak as isize) as f64;
+		}
+		let (left, centre, right) = (self.values[peak - 1], self.values[peak], self.values[peak + 1]);
+		let curvature = left - 2.0 * centre + right;
+		let offset = if curvature == 0.0 { 0.0 } else { 0.5 * (left - right) / curvature };
+		(self.min_lag + peak as isize) as f64 + offset
+	}
+
+}
+
+/// Returns the full linear cross-correlation of `a` and `b`, computed with
+/// zero-padded FFTs in O(n log n) time.
+///
+/// ```
+/// # use hebrides::linal::Vector;
+/// # use hebrides::signal::xcorr;
+/// let a = Vector::new(vec![0.0, 0.0, 1.0, 2.0, 0.0]);
+/// let b = Vector::new(vec![1.0, 2.0, 0.0]);
+/// let correlation = xcorr(&a, &b);
+/// assert_eq!((correlation.min_lag(), correlation.max_lag()), (-2, 4));
+/// assert_eq!(correlation.peak_lag(), 2);
+/// assert!((correlation.at(2).unwrap() - 5.0).abs() < 1e-12);
+/// ```
+pub fn xcorr(a: &Vector<f64>, b: &Vector<f64>) -> CrossCorrelation {
+	let len = a.dim() + b.dim() - 1;
+	let size = len.next_power_of_two();
+	let padded = |x: &Vector<f64>| {
+		let mut data: Vec<Complex> = x.iter().map(|e| Complex::new(*e, 0.0)).collect();
+		data.resize(size, Complex::ZERO);
+		radix2(&mut data, false);
+		data
+	};
+	let (fa, fb) = (padded(a), padded(b));
+	let mut product: Vec<Complex> = fa.iter().zip(fb.iter()).map(|(x, y)| *x * y.conjugate()).collect();
+	radix2(&mut product, true);
+	let scale = size as f64;
+	let min_lag = 1 - b.dim() as isize;
+	let values = Vector::from_fn(len, |i| {
+		let lag = min_lag + i as isize;
+		product[lag.rem_euclid(size as isize) as usize].re() / scale
+	});
+	CrossCorrelation { values, min_lag }
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn close(a: &Vector<Complex>, b: &Vector<Complex>) -> bool {
+		a.iter().zip(b.iter()).all(|(x, y)| (*x - *y).modulus() < 1e-12)
+	}
+
+	fn naive_dft(x: &Vector<Complex>) -> Vector<Complex> {
+		let n = x.dim();
+		Vector::from_fn(n, |k| x.iter().enumerate().fold(Complex::ZERO, |sum, (j, e)| {
+			let theta = -TAU * (j * k) as f64 / n as f64;
+			sum + *e * Complex::new(theta.cos(), theta.sin())
+		}))
+	}
+
+	#[test]
+	fn matches_naive_dft_and_inverts() {
+		let x = Vector::from_fn(16, |n| Complex::new((n as f64 * 0.7).sin(), (n * n % 5) as f64));
+		let transformed = fft(&x);
+		assert!(close(&transformed, &naive_dft(&x)));
+		assert!(close(&ifft(&transformed), &x))
+	}
+
+	#[test]
+	fn correlation_matches_direct_sum() {
+		let a = Vector::new(vec![1.0, -2.0, 3.0, 0.5, 4.0]);
+		let b = Vector::new(vec![2.0, 1.0, -1.0]);
+		let correlation = xcorr(&a, &b);
+		for lag in correlation.min_lag()..=correlation.max_lag() {
+			let direct: f64 = (0..b.dim() as isize)
+				.filter(|n| (0..a.dim() as isize).contains(&(n + lag)))
+				.map(|n| a[(n + lag) as usize] * b[n as usize])
+				.sum();
+			assert!((correlation.at(lag).unwrap() - direct).abs() < 1e-12);
+		}
+		assert_eq!(correlation.at(correlation.max_lag() + 1), None)
+	}
+
+	#[test]
+	fn negative_lag_when_first_signal_leads() {
+		let a = Vector::new(vec![0.0, 5.0, 1.0, 0.0, 0.0, 0.0]);
+		let b = Vector::new(vec![0.0, 0.0, 0.0, 5.0, 1.0, 0.0]);
+		assert_eq!(xcorr(&a, &b).peak_lag(), -2)
+	}
+
+	#[test]
+	fn arbitrary_lengths_match_naive_dft() {
+		for n in [1, 3, 5, 12, 100, 441] {
+			let x = Vector::from_fn(n, |j| Complex::new((j as f64 * 1.3).cos(), (j % 7) as f64 - 3.0));
+			let transformed = fft(&x);
+			assert!(transformed.iter().zip(naive_dft(&x).iter()).all(|(a, b)| (*a - *b).modulus() < 1e-9));
+			assert!(ifft(&transformed).iter().zip(x.iter()).all(|(a, b)| (*a - *b).modulus() < 1e-9));
+		}
+	}
+
+	#[test]
+	fn goertzel_matches_dft_bins() {
+		let x = Vector::from_fn(10, |n| (n as f64 * 0.9).sin() + (n % 3) as f64);
+		let complex = Vector::from_fn(10, |n| Complex::new(x[n], 0.0));
+		let dft = naive_dft(&complex);
+		for k in 0..10 {
+			assert!((goertzel(&x, k as f64) - dft[k]).modulus() < 1e-12);
+		}
+		let tone = Vector::from_fn(64, |n| (TAU * 5.5 * n as f64 / 64.0).cos());
+		assert!(goertzel(&tone, 5.5).modulus() > 31.0)
+	}
+
+}
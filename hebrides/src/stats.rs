@@ -0,0 +1,705 @@
+//! Statistical utilities.
+//!
+//! `stats` builds on the linear algebra types in [`linal`](crate::linal) to
+//! turn raw data into the design matrices used by regression and other
+//! modelling workflows, and to evaluate the models fit to them.
+
+use crate::linal::{Vector, Matrix};
+use crate::random::Rng;
+
+/// Encodes each of `labels` as a row of a [`Matrix`] with a one in the column
+/// of its category and zeros elsewhere.
+///
+/// ```
+/// # use hebrides::{stats, linal::Matrix};
+/// let encoded = stats::one_hot(&[0, 2, 1], 3);
+/// assert_eq!(encoded, Matrix::new(vec![
+///     vec![1.0, 0.0, 0.0],
+///     vec![0.0, 0.0, 1.0],
+///     vec![0.0, 1.0, 0.0]
+/// ]));
+/// ```
+///
+/// # Panics
+/// Panics if `labels` is empty or if any label is not less than `n_categories`.
+pub fn one_hot(labels: &[usize], n_categories: usize) -> Matrix<f64> {
+	if labels.iter().any(|label| *label >= n_categories) {
+		panic!("Labels must be less than the number of categories to be one-hot encoded")
+	}
+	let rows = labels.iter()
+					 .map(|label| (0..n_categories).map(|j| if j == *label { 1.0 } else { 0.0 }).collect())
+					 .collect();
+	Matrix::new(rows)
+}
+
+/// Encodes each of `labels` as a row of dummy variables, dropping the column
+/// of the first category.
+///
+/// Dropping the reference category keeps the columns linearly independent of
+/// an intercept column in a design matrix.
+///
+/// # Panics
+/// Panics if `labels` is empty, if `n_categories` is less than two, or if any
+/// label is not less than `n_categories`.
+pub fn dummy(labels: &[usize], n_categories: usize) -> Matrix<f64> {
+	if n_categories < 2 {
+		panic!("Dummy encoding requires at least two categories")
+	}
+	if labels.iter().any(|label| *label >= n_categories) {
+		panic!("Labels must be less than the number of categories to be dummy encoded")
+	}
+	let rows = labels.iter()
+					 .map(|label| (1..n_categories).map(|j| if j == *label { 1.0 } else { 0.0 }).collect())
+					 .collect();
+	Matrix::new(rows)
+}
+
+/// Builds a design [`Matrix`] from several categorical features.
+///
+/// Each entry of `features` pairs the labels of one feature with its number of
+/// categories; every feature is dummy encoded and the encodings are placed
+/// side by side. If `intercept` is set, a leading column of ones is added.
+///
+/// ```
+/// # use hebrides::{stats, linal::Matrix};
+/// let colour = [0, 1, 2];
+/// let size = [1, 0, 1];
+/// let design = stats::design_matrix(&[(&colour, 3), (&size, 2)], true);
+/// assert_eq!(design, Matrix::new(vec![
+///     vec![1.0, 0.0, 0.0, 1.0],
+///     vec![1.0, 1.0, 0.0, 0.0],
+///     vec![1.0, 0.0, 1.0, 1.0]
+/// ]));
+/// ```
+///
+/// # Panics
+/// Panics if the features do not all have the same number of observations, or
+/// under any of the conditions that cause [`dummy`] to panic.
+pub fn design_matrix(features: &[(&[usize], usize)], intercept: bool) -> Matrix<f64> {
+	if features.is_empty() {
+		panic!("Must provide at least one feature to build a design matrix")
+	}
+	let n = features[0].0.len();
+	if features.iter().any(|feature| feature.0.len() != n) {
+		panic!("All features must have the same number of observations")
+	}
+	let mut rows: Vec<Vec<f64>> = vec![Vec::new(); n];
+	if intercept {
+		for row in rows.iter_mut() {
+			row.push(1.0);
+		}
+	}
+	for (labels, n_categories) in features {
+		let encoded = dummy(labels, *n_categories);
+		for (row, encoded_row) in rows.iter_mut().zip(encoded.rows) {
+			row.extend(encoded_row);
+		}
+	}
+	Matrix::new(rows)
+}
+
+/// Bijection between the distinct values of a categorical feature and the
+/// labels `0..n_classes`.
+#[derive(Debug, Clone)]
+pub struct LabelEncoder<T> {
+	classes: Vec<T>
+}
+
+impl<T> LabelEncoder<T> where T: Ord + Clone {
+
+	/// Constructs a [`LabelEncoder`] from the distinct values in `values`.
+	///
+	/// Classes are assigned labels in ascending order.
+	pub fn fit(values: &[T]) -> LabelEncoder<T> {
+		let mut classes = values.to_vec();
+		classes.sort();
+		classes.dedup();
+		Self { classes }
+	}
+
+	/// Returns the classes known to `self`, ordered by label.
+	pub fn classes(&self) -> &[T] {
+		&self.classes
+	}
+
+	/// Returns the number of classes known to `self`.
+	pub fn n_classes(&self) -> usize {
+		self.classes.len()
+	}
+
+	/// Returns the label of `value`, or `None` if `value` was not seen by `fit`.
+	pub fn label(&self, value: &T) -> Option<usize> {
+		self.classes.binary_search(value).ok()
+	}
+
+	/// Returns the labels of `values`, or `None` if any of `values` was not
+	/// seen by `fit`.
+	///
+	/// ```
+	/// # use hebrides::stats::LabelEncoder;
+	/// let encoder = LabelEncoder::fit(&["red", "green", "blue", "green"]);
+	/// assert_eq!(encoder.encode(&["green", "red"]), Some(vec![1, 2]));
+	/// assert_eq!(encoder.encode(&["purple"]), None);
+	/// ```
+	pub fn encode(&self, values: &[T]) -> Option<Vec<usize>> {
+		values.iter().map(|value| self.label(value)).collect()
+	}
+
+	/// Returns the classes corresponding to `labels`.
+	///
+	/// # Panics
+	/// Panics if any label is not less than `n_classes`.
+	pub fn decode(&self, labels: &[usize]) -> Vec<T> {
+		labels.iter().map(|label| self.classes[*label].clone()).collect()
+	}
+
+	/// One-hot encodes `values` using the classes known to `self`, or returns
+	/// `None` if any of `values` was not seen by `fit`.
+	pub fn one_hot(&self, values: &[T]) -> Option<Matrix<f64>> {
+		self.encode(values).map(|labels| one_hot(&labels, self.n_classes()))
+	}
+
+}
+
+/// Encodes `values` as labels, returning the labels along with the classes
+/// they index into.
+///
+/// ```
+/// # use hebrides::stats;
+/// let (labels, classes) = stats::label_encode(&['b', 'a', 'b']);
+/// assert_eq!(labels, vec![1, 0, 1]);
+/// assert_eq!(classes, vec!['a', 'b']);
+/// ```
+pub fn label_encode<T>(values: &[T]) -> (Vec<usize>, Vec<T>) where T: Ord + Clone {
+	let encoder = LabelEncoder::fit(values);
+	let labels = encoder.encode(values).unwrap();
+	(labels, encoder.classes)
+}
+
+/// Splits the indices `0..n` into `k` shuffled folds for cross-validation.
+///
+/// Returns `k` pairs of training and testing indices; every index appears in
+/// exactly one testing set, and fold sizes differ by at most one.
+///
+/// ```
+/// # use hebrides::{stats, random::Rng};
+/// let folds = stats::kfold(10, 5, &mut Rng::new(0));
+/// assert_eq!(folds.len(), 5);
+/// assert!(folds.iter().all(|(train, test)| train.len() == 8 && test.len() == 2));
+/// ```
+///
+/// # Panics
+/// Panics if `k` is less than two or greater than `n`.
+pub fn kfold(n: usize, k: usize, rng: &mut Rng) -> Vec<(Vec<usize>, Vec<usize>)> {
+	if k < 2 || k > n {
+		panic!("Number of folds must be between two and the number of samples")
+	}
+	let mut indices: Vec<usize> = (0..n).collect();
+	rng.shuffle(&mut indices);
+	let mut folds = Vec::with_capacity(k);
+	let mut start = 0;
+	for fold in 0..k {
+		let size = n / k + if fold < n % k { 1 } else { 0 };
+		let test = indices[start..start + size].to_vec();
+		let train = indices[..start].iter().chain(indices[start + size..].iter()).copied().collect();
+		folds.push((train, test));
+		start += size;
+	}
+	folds
+}
+
+/// Panics if `predictions` and `targets` cannot be compared componentwise.
+fn check_dims(predictions: &Vector<f64>, targets: &Vector<f64>) {
+	if !Vector::same_dim(predictions, targets) {
+		panic!("Predictions and targets must be of the same dimension")
+	}
+}
+
+/// Iterates over corresponding pairs of `predictions` and `targets`.
+fn pairs<'a>(predictions: &'a Vector<f64>, targets: &'a Vector<f64>) -> impl Iterator<Item=(f64, f64)> + 'a {
+	check_dims(predictions, targets);
+	(0..predictions.dim()).map(move |i| (predictions[i], targets[i]))
+}
+
+/// Mean squared error between `predictions` and `targets`.
+///
+/// ```
+/// # use hebrides::{stats, linal::Vector};
+/// let predictions = Vector::new(vec![1.0, 2.0, 3.0]);
+/// let targets = Vector::new(vec![1.0, 2.0, 5.0]);
+/// assert_eq!(stats::mse(&predictions, &targets), 4.0 / 3.0);
+/// ```
+pub fn mse(predictions: &Vector<f64>, targets: &Vector<f64>) -> f64 {
+	let total: f64 = pairs(predictions, targets).map(|(p, t)| (p - t) * (p - t)).sum();
+	total / predictions.dim() as f64
+}
+
+/// Mean absolute error between `predictions` and `targets`.
+pub fn mae(predictions: &Vector<f64>, targets: &Vector<f64>) -> f64 {
+	let total: f64 = pairs(predictions, targets).map(|(p, t)| (p - t).abs()).sum();
+	total / predictions.dim() as f64
+}
+
+/// Coefficient of determination (R²) of `predictions` against `targets`.
+///
+/// A perfect fit scores one, and always predicting the mean of `targets`
+/// scores zero. Returns NaN if `targets` has no variance.
+pub fn r_squared(predictions: &Vector<f64>, targets: &Vector<f64>) -> f64 {
+	check_dims(predictions, targets);
+	let mean = (0..targets.dim()).map(|i| targets[i]).sum::<f64>() / targets.dim() as f64;
+	let residual: f64 = pairs(predictions, targets).map(|(p, t)| (t - p) * (t - p)).sum();
+	let total: f64 = (0..targets.dim()).map(|i| (targets[i] - mean) * (targets[i] - mean)).sum();
+	1.0 - residual / total
+}
+
+/// Fraction of `predictions` that exactly match their `targets`.
+pub fn accuracy(predictions: &Vector<f64>, targets: &Vector<f64>) -> f64 {
+	let correct = pairs(predictions, targets).filter(|(p, t)| p == t).count();
+	correct as f64 / predictions.dim() as f64
+}
+
+/// Counts of (true positives, false positives, false negatives) for binary
+/// labels in which `1.0` is the positive class.
+fn confusion(predictions: &Vector<f64>, targets: &Vector<f64>) -> (usize, usize, usize) {
+	pairs(predictions, targets).fold((0, 0, 0), |(tp, fp, fn_), (p, t)| {
+		match (p == 1.0, t == 1.0) {
+			(true, true) => (tp + 1, fp, fn_),
+			(true, false) => (tp, fp + 1, fn_),
+			(false, true) => (tp, fp, fn_ + 1),
+			(false, false) => (tp, fp, fn_)
+		}
+	})
+}
+
+/// Precision of binary `predictions`, treating `1.0` as the positive class.
+///
+/// Returns NaN if nothing is predicted positive.
+pub fn precision(predictions: &Vector<f64>, targets: &Vector<f64>) -> f64 {
+	let (tp, fp, _) = confusion(predictions, targets);
+	tp as f64 / (tp + fp) as f64
+}
+
+/// Recall of binary `predictions`, treating `1.0` as the positive class.
+///
+/// Returns NaN if no target is positive.
+pub fn recall(predictions: &Vector<f64>, targets: &Vector<f64>) -> f64 {
+	let (tp, _, fn_) = confusion(predictions, targets);
+	tp as f64 / (tp + fn_) as f64
+}
+
+/// Area under the ROC curve of `scores` against binary `targets`, treating
+/// `1.0` as the positive class.
+///
+/// Computed as the probability that a randomly chosen positive is scored
+/// above a randomly chosen negative, with ties counting for half. Returns NaN
+/// if `targets` contains only one class.
+///
+/// ```
+/// # use hebrides::{stats, linal::Vector};
+/// let scores = Vector::new(vec![0.1, 0.4, 0.35, 0.8]);
+/// let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+/// assert_eq!(stats::roc_auc(&scores, &targets), 0.75);
+/// ```
+pub fn roc_auc(scores: &Vector<f64>, targets: &Vector<f64>) -> f64 {
+	let mut ranked: Vec<(f64, f64)> = pairs(scores, targets).collect();
+	ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+	let mut positive_rank_sum = 0.0;
+	let mut i = 0;
+	while i < ranked.len() {
+		let mut j = i;
+		while j + 1 < ranked.len() && ranked[j + 1].0 == ranked[i].0 {
+			j += 1;
+		}
+		let average_rank = (i + j) as f64 / 2.0 + 1.0;
+		positive_rank_sum += average_rank * ranked[i..=j].iter().filter(|pair| pair.1 == 1.0).count() as f64;
+		i = j + 1;
+	}
+	let positives = ranked.iter().filter(|pair| pair.1 == 1.0).count() as f64;
+	let negatives = ranked.len() as f64 - positives;
+	(positive_rank_sum - positives * (positives + 1.0) / 2.0) / (positives * negatives)
+}
+
+/// Principal component analysis fit incrementally over batches of rows.
+///
+/// Each call to [`partial_fit`](IncrementalPCA::partial_fit) folds a batch
+/// into the running mean and the top singular directions, so the model can be
+/// refreshed without revisiting earlier data. The result matches a batch fit
+/// exactly when the centred data has rank at most `n_components`, and
+/// approximates it otherwise.
+#[derive(Debug, Clone)]
+pub struct IncrementalPCA {
+	n_components: usize,
+	n_samples: usize,
+	mean: Vec<f64>,
+	components: Vec<Vector<f64>>,
+	singular_values: Vec<f64>
+}
+
+impl IncrementalPCA {
+
+	/// Constructs an unfit [`IncrementalPCA`] keeping `n_components`
+	/// principal components.
+	///
+	/// # Panics
+	/// Panics if `n_components` is zero.
+	pub fn new(n_components: usize) -> IncrementalPCA {
+		if n_components == 0 {
+			panic!("IncrementalPCA must keep at least one component")
+		}
+		Self { n_components, n_samples: 0, mean: Vec::new(), components: Vec::new(), singular_values: Vec::new() }
+	}
+
+	/// Returns the number of samples seen so far.
+	pub fn n_samples(&self) -> usize {
+		self.n_samples
+	}
+
+	/// Returns the mean of the samples seen so far.
+	pub fn mean(&self) -> Vector<f64> {
+		Vector::new(self.mean.clone())
+	}
+
+	/// Returns the principal components found so far, in decreasing order of
+	/// explained variance.
+	pub fn components(&self) -> &[Vector<f64>] {
+		&self.components
+	}
+
+	/// Returns the variance of the samples seen so far along each principal
+	/// component.
+	pub fn explained_variance(&self) -> Vec<f64> {
+		let dof = self.n_samples.saturating_sub(1).max(1) as f64;
+		self.singular_values.iter().map(|s| s * s / dof).collect()
+	}
+
+	/// Folds the rows of `batch` into the model.
+	///
+	/// # Panics
+	/// Panics if `batch` has a different number of columns than earlier
+	/// batches.
+	pub fn partial_fit(&mut self, batch: &Matrix<f64>) {
+		let (n_batch, dim) = (batch.dims.num_rows, batch.dims.num_cols);
+		if self.n_samples > 0 && dim != self.mean.len() {
+			panic!("Batches must have the same number of columns")
+		}
+		if n_batch == 0 {
+			return;
+		}
+		let batch_mean: Vec<f64> = (0..dim).map(|j| batch.cols[j].iter().sum::<f64>() / n_batch as f64).collect();
+		let mut rows: Vec<Vec<f64>> = self.components.iter().zip(self.singular_values.iter()).map(|(v, s)| {
+			(0..dim).map(|j| s * v[j]).collect()
+		}).collect();
+		rows.extend(batch.rows.iter().map(|row| row.iter().zip(batch_mean.iter()).map(|(x, m)| x - m).collect()));
+		let total = self.n_samples + n_batch;
+		if self.n_samples > 0 {
+			let weight = (self.n_samples as f64 * n_batch as f64 / total as f64).sqrt();
+			rows.push(self.mean.iter().zip(batch_mean.iter()).map(|(old, new)| weight * (old - new)).collect());
+			self.mean = self.mean.iter().zip(batch_mean.iter())
+				.map(|(old, new)| (old * self.n_samples as f64 + new * n_batch as f64) / total as f64)
+				.collect();
+		} else {
+			self.mean = batch_mean;
+		}
+		self.n_samples = total;
+		let svd = Matrix::new(rows).svd();
+		let keep = self.n_components.min(svd.singular_values().len());
+		self.singular_values = svd.singular_values()[..keep].to_vec();
+		self.components = (0..keep).map(|k| Vector::new(svd.v().cols[k].clone())).collect();
+	}
+
+	/// Projects the rows of `data` onto the principal components.
+	///
+	/// # Panics
+	/// Panics if `self` has not been fit or if `data` has a different number
+	/// of columns than the data `self` was fit on.
+	pub fn transform(&self, data: &Matrix<f64>) -> Matrix<f64> {
+		if self.n_samples == 0 {
+			panic!("IncrementalPCA must be fit before transforming data")
+		}
+		if data.dims.num_cols != self.mean.len() {
+			panic!("Data must have as many columns as the data the model was fit on")
+		}
+		Matrix::new(data.rows.iter().map(|row| self.components.iter().map(|v| {
+			row.iter().zip(self.mean.iter()).enumerate().map(|(j, (x, m))| (x - m) * v[j]).sum()
+		}).collect()).collect())
+	}
+
+}
+
+/// Partitions `points` into `k` clusters by k-means, returning each point's
+/// cluster label and the cluster centroids.
+///
+/// Centroids are seeded with k-means++ and refined with Lloyd's algorithm
+/// until the labels stop changing. A cluster that loses all of its points
+/// keeps its previous centroid.
+///
+/// ```
+/// # use hebrides::{stats, linal::Vector, random::Rng};
+/// let points: Vec<Vector<f64>> = [0.0, 0.1, 0.2, 5.0, 5.1].iter().map(|x| Vector::new(vec![*x])).collect();
+/// let (labels, _) = stats::kmeans(&points, 2, &mut Rng::new(0));
+/// assert!(labels[0] == labels[2] && labels[3] == labels[4] && labels[0] != labels[3]);
+/// ```
+///
+/// # Panics
+/// Panics if `k` is zero or greater than the number of points, or if the
+/// points are not all of the same dimension.
+pub fn kmeans(points: &[Vector<f64>], k: usize, rng: &mut Rng) -> (Vec<usize>, Vec<Vector<f64>>) {
+	if k == 0 || k > points.len() {
+		panic!("Number of clusters must be between one and the number of points")
+	}
+	if points.iter().any(|p| !Vector::same_dim(p, &points[0])) {
+		panic!("Points must be of the same dimension to be clustered")
+	}
+	let distance = |a: &Vector<f64>, b: &Vector<f64>| (a.clone() - b.clone()).square_norm();
+	let mut centroids = vec![points[rng.below(points.len())].clone()];
+	while centroids.len() < k {
+		let weights: Vec<f64> = points.iter().map(|p| {
+			centroids.iter().map(|c| distance(p, c)).fold(f64::INFINITY, f64::min)
+		}).collect();
+		let total: f64 = weights.iter().sum();
+		if total == 0.0 {
+			centroids.push(points[rng.below(points.len())].clone());
+			continue;
+		}
+		let mut target = rng.next_f64() * total;
+		let chosen = weights.iter().position(|w| {
+			target -= w;
+			target < 0.0
+		}).unwrap_or(points.len() - 1);
+		centroids.push(points[chosen].clone());
+	}
+	let mut labels = vec![usize::MAX; points.len()];
+	for _ in 0..300 {
+		let assigned: Vec<usize> = points.iter().map(|p| {
+			(0..k).min_by(|a, b| distance(p, &centroids[*a]).total_cmp(&distance(p, &centroids[*b]))).unwrap()
+		}).collect();
+		if assigned == labels {
+			break;
+		}
+		labels = assigned;
+		for (c, centroid) in centroids.iter_mut().enumerate() {
+			let members: Vec<&Vector<f64>> = points.iter().zip(labels.iter()).filter(|(_, l)| **l == c).map(|(p, _)| p).collect();
+			if members.is_empty() {
+				continue;
+			}
+			let sum = members.iter().skip(1).fold((*members[0]).clone(), |acc, p| acc + (*p).clone());
+			*centroid = sum / members.len() as f64;
+		}
+	}
+	(labels, centroids)
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	mod encoding {
+
+		use super::*;
+
+		#[test]
+		fn one_hot_rows_sum_to_one() {
+			let encoded = one_hot(&[3, 0, 1, 1], 4);
+			assert!(encoded.rows.iter().all(|row| row.iter().sum::<f64>() == 1.0));
+			assert_eq!(encoded.dims.num_cols, 4)
+		}
+
+		#[test]
+		#[should_panic]
+		fn one_hot_label_out_of_range() {
+			let _ = one_hot(&[0, 3], 3);
+		}
+
+		#[test]
+		fn dummy_drops_first_category() {
+			let encoded = dummy(&[0, 1, 2], 3);
+			assert_eq!(encoded, Matrix::new(vec![
+				vec![0.0, 0.0],
+				vec![1.0, 0.0],
+				vec![0.0, 1.0]
+			]))
+		}
+
+		#[test]
+		fn design_matrix_without_intercept() {
+			let design = design_matrix(&[(&[1, 0], 2)], false);
+			assert_eq!(design, Matrix::new(vec![vec![1.0], vec![0.0]]))
+		}
+
+		#[test]
+		#[should_panic]
+		fn design_matrix_mismatched_features() {
+			let _ = design_matrix(&[(&[1, 0], 2), (&[1], 2)], false);
+		}
+
+	}
+
+	mod cross_validation {
+
+		use super::*;
+
+		#[test]
+		fn folds_partition_indices() {
+			let folds = kfold(11, 3, &mut Rng::new(9));
+			let mut tested: Vec<usize> = folds.iter().flat_map(|fold| fold.1.clone()).collect();
+			tested.sort();
+			assert_eq!(tested, (0..11).collect::<Vec<usize>>());
+			for (train, test) in folds.iter() {
+				assert_eq!(train.len() + test.len(), 11);
+				assert!(test.iter().all(|i| !train.contains(i)));
+			}
+		}
+
+		#[test]
+		#[should_panic]
+		fn too_many_folds() {
+			let _ = kfold(3, 4, &mut Rng::new(0));
+		}
+
+	}
+
+	mod metrics {
+
+		use super::*;
+
+		#[test]
+		fn regression_metrics() {
+			let predictions = Vector::new(vec![2.0, 4.0, 6.0]);
+			let targets = Vector::new(vec![1.0, 4.0, 7.0]);
+			assert_eq!(mse(&predictions, &targets), 2.0 / 3.0);
+			assert_eq!(mae(&predictions, &targets), 2.0 / 3.0);
+			assert_eq!(r_squared(&predictions, &targets), 1.0 - 2.0 / 18.0);
+			assert_eq!(r_squared(&targets, &targets), 1.0);
+		}
+
+		#[test]
+		fn classification_metrics() {
+			let predictions = Vector::new(vec![1.0, 1.0, 0.0, 0.0, 1.0]);
+			let targets = Vector::new(vec![1.0, 0.0, 0.0, 1.0, 1.0]);
+			assert_eq!(accuracy(&predictions, &targets), 0.6);
+			assert_eq!(precision(&predictions, &targets), 2.0 / 3.0);
+			assert_eq!(recall(&predictions, &targets), 2.0 / 3.0);
+		}
+
+		#[test]
+		fn roc_auc_extremes() {
+			let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+			assert_eq!(roc_auc(&Vector::new(vec![0.1, 0.2, 0.8, 0.9]), &targets), 1.0);
+			assert_eq!(roc_auc(&Vector::new(vec![0.9, 0.8, 0.2, 0.1]), &targets), 0.0);
+			assert_eq!(roc_auc(&Vector::new(vec![0.5; 4]), &targets), 0.5);
+		}
+
+		#[test]
+		#[should_panic]
+		fn mismatched_dimensions() {
+			let _ = mse(&Vector::new(vec![1.0]), &Vector::new(vec![1.0, 2.0]));
+		}
+
+	}
+
+	mod label_encoder {
+
+		use super::*;
+
+		#[test]
+		fn round_trip() {
+			let values = ["dog", "cat", "fish", "cat"];
+			let encoder = LabelEncoder::fit(&values);
+			assert_eq!(encoder.n_classes(), 3);
+			let labels = encoder.encode(&values).unwrap();
+			assert_eq!(encoder.decode(&labels), values.to_vec())
+		}
+
+		#[test]
+		fn one_hot() {
+			let encoder = LabelEncoder::fit(&[10, 20]);
+			assert_eq!(encoder.one_hot(&[20, 10]).unwrap(), Matrix::new(vec![
+				vec![0.0, 1.0],
+				vec![1.0, 0.0]
+			]))
+		}
+
+	}
+
+	mod pca {
+
+		use super::*;
+
+		fn data() -> Vec<Vec<f64>> {
+			(0..40).map(|i| {
+				let (t, s) = (i as f64 / 4.0, (i as f64).sin());
+				vec![t + s, 2.0 * t, s - 3.0, 1.0 - 0.5 * t + 2.0 * s]
+			}).collect()
+		}
+
+		#[test]
+		fn batches_match_a_single_fit() {
+			let mut whole = IncrementalPCA::new(2);
+			whole.partial_fit(&Matrix::new(data()));
+			let mut streamed = IncrementalPCA::new(2);
+			for chunk in data().chunks(7) {
+				streamed.partial_fit(&Matrix::new(chunk.to_vec()));
+			}
+			assert_eq!(streamed.n_samples(), 40);
+			assert!((0..4).all(|j| (whole.mean()[j] - streamed.mean()[j]).abs() < 1e-12));
+			for (a, b) in whole.explained_variance().iter().zip(streamed.explained_variance().iter()) {
+				assert!((a - b).abs() < 1e-9 * a.max(1.0));
+			}
+			for (u, v) in whole.components().iter().zip(streamed.components().iter()) {
+				assert!((u.dot(v).abs() - 1.0).abs() < 1e-9);
+			}
+		}
+
+		#[test]
+		fn transform_centres_and_projects() {
+			let mut pca = IncrementalPCA::new(1);
+			pca.partial_fit(&Matrix::new(vec![vec![1.0, 1.0], vec![3.0, 3.0], vec![5.0, 5.0]]));
+			let projected = pca.transform(&Matrix::new(vec![vec![3.0, 3.0], vec![4.0, 4.0]]));
+			assert!(projected.rows[0][0].abs() < 1e-12);
+			assert!((projected.rows[1][0].abs() - 2.0_f64.sqrt()).abs() < 1e-12)
+		}
+
+		#[test]
+		#[should_panic]
+		fn mismatched_batches() {
+			let mut pca = IncrementalPCA::new(1);
+			pca.partial_fit(&Matrix::new(vec![vec![1.0, 2.0]]));
+			pca.partial_fit(&Matrix::new(vec![vec![1.0, 2.0, 3.0]]));
+		}
+
+	}
+
+	mod kmeans {
+
+		use super::*;
+
+		#[test]
+		fn separates_well_spaced_blobs() {
+			let mut rng = Rng::new(21);
+			let centres = [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)];
+			let points: Vec<Vector<f64>> = (0..90).map(|i| {
+				let (x, y) = centres[i % 3];
+				Vector::new(vec![x + rng.next_f64(), y + rng.next_f64()])
+			}).collect();
+			let (labels, centroids) = kmeans(&points, 3, &mut Rng::new(2));
+			assert!((0..90).all(|i| labels[i] == labels[i % 3]));
+			assert!(centroids.iter().all(|c| centres.iter().any(|(x, y)| (c[0] - x - 0.5).abs() < 0.5 && (c[1] - y - 0.5).abs() < 0.5)))
+		}
+
+		#[test]
+		fn duplicate_points() {
+			let points = vec![Vector::new(vec![1.0]); 4];
+			let (labels, _) = kmeans(&points, 2, &mut Rng::new(0));
+			assert_eq!(labels.len(), 4)
+		}
+
+		#[test]
+		#[should_panic]
+		fn too_many_clusters() {
+			let _ = kmeans(&[Vector::new(vec![1.0])], 2, &mut Rng::new(0));
+		}
+
+	}
+
+}
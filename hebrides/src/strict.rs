@@ -0,0 +1,661 @@
+//! Bit-reproducible floating point math.
+//!
+//! The transcendental functions on [`f64`] defer to the platform's math
+//! library, whose results may differ in the last bit between operating
+//! systems, compilers, and CPUs. The functions here are implemented in
+//! software from IEEE 754 addition, multiplication, division, and square root
+//! alone (following the fdlibm algorithms), so they produce identical bits
+//! wherever they run. Reductions are likewise evaluated in a fixed order.
+//!
+//! This module is only compiled with the `strict` feature, which also routes
+//! the methods of [`Real`](crate::Real) (and so of [`Complex`](crate::Complex)
+//! and [`Uncertain`](crate::Uncertain)) through it, sums and dot products of
+//! `f64` and [`Real`](crate::Real) vectors and matrices with [`sum`], and pins
+//! the [`kernels`](crate::linal::kernels) to their scalar implementations.
+
+// Coefficients are quoted digit-for-digit from fdlibm.
+#![allow(clippy::excessive_precision)]
+
+const LN2_HI: f64 = 6.93147180369123816490e-01;
+const LN2_LO: f64 = 1.90821492927058770002e-10;
+const INV_LN2: f64 = std::f64::consts::LOG2_E;
+const PI_LO: f64 = 1.2246467991473531772e-16;
+const PI: f64 = std::f64::consts::PI;
+const FRAC_PI_2: f64 = std::f64::consts::FRAC_PI_2;
+
+/// Returns the upper 32 bits of `x` with the sign bit cleared.
+fn high_word(x: f64) -> u32 {
+	((x.to_bits() >> 32) as u32) & 0x7fffffff
+}
+
+/// Returns `x` times two to the power of `n`.
+fn scalbn(mut x: f64, mut n: i32) -> f64 {
+	let two_1023 = f64::from_bits(0x7fe0000000000000);
+	let two_m969 = f64::from_bits(0x0360000000000000);
+	if n > 1023 {
+		x *= two_1023;
+		n -= 1023;
+		if n > 1023 {
+			x *= two_1023;
+			n = (n - 1023).min(1023);
+		}
+	} else if n < -1022 {
+		// Scale by 2^-969 rather than 2^-1022 so no rounding happens in the subnormal range twice.
+		x *= two_m969;
+		n += 969;
+		if n < -1022 {
+			x *= two_m969;
+			n = (n + 969).max(-1022);
+		}
+	}
+	x * f64::from_bits(((0x3ff + n) as u64) << 52)
+}
+
+/// e to the power of `x`.
+///
+/// ```
+/// # use hebrides::strict;
+/// assert!((strict::exp(1.0) - std::f64::consts::E).abs() < 1e-15);
+/// ```
+pub fn exp(x: f64) -> f64 {
+	const P: [f64; 5] = [
+		1.66666666666666019037e-01, -2.77777777770155933842e-03, 6.61375632143793436117e-05,
+		-1.65339022054652515390e-06, 4.13813679705723846039e-08
+	];
+	if x.is_nan() {
+		return x;
+	}
+	if x > 709.782712893383973096 {
+		return f64::INFINITY;
+	}
+	if x < -745.13321910194110842 {
+		return 0.0;
+	}
+	if x.abs() < f64::from_bits(0x3e30000000000000) {
+		return 1.0 + x;
+	}
+	let k = (INV_LN2 * x + if x < 0.0 { -0.5 } else { 0.5 }).trunc();
+	let hi = x - k * LN2_HI;
+	let lo = k * LN2_LO;
+	let r = hi - lo;
+	let rr = r * r;
+	let c = r - rr * (P[0] + rr * (P[1] + rr * (P[2] + rr * (P[3] + rr * P[4]))));
+	let y = 1.0 - ((lo - (r * c) / (2.0 - c)) - hi);
+	scalbn(y, k as i32)
+}
+
+/// e to the power of `x`, minus one, accurate for small `x`.
+pub fn exp_m1(x: f64) -> f64 {
+	let u = exp(x);
+	if u == 1.0 {
+		return x;
+	}
+	let um1 = u - 1.0;
+	if um1 == -1.0 {
+		return -1.0;
+	}
+	if u.is_infinite() {
+		return u;
+	}
+	um1 * x / ln(u)
+}
+
+/// Natural logarithm of `x`.
+///
+/// Returns NaN for negative `x` and negative infinity for zero.
+pub fn ln(x: f64) -> f64 {
+	const LG: [f64; 7] = [
+		6.666666666666735130e-01, 3.999999999940941908e-01, 2.857142874366239149e-01,
+		2.222219843214978396e-01, 1.818357216161805012e-01, 1.531383769920937332e-01,
+		1.479819860511658591e-01
+	];
+	let mut bits = x.to_bits();
+	let mut hx = (bits >> 32) as u32;
+	let mut k: i32 = 0;
+	if hx < 0x00100000 || hx >> 31 != 0 {
+		if x == 0.0 {
+			return f64::NEG_INFINITY;
+		}
+		if hx >> 31 != 0 {
+			return f64::NAN;
+		}
+		// Subnormal: scale into the normal range.
+		k -= 54;
+		bits = (x * f64::from_bits(0x4350000000000000)).to_bits();
+		hx = (bits >> 32) as u32;
+	} else if hx >= 0x7ff00000 {
+		return x;
+	} else if hx == 0x3ff00000 && bits << 32 == 0 {
+		return 0.0;
+	}
+	// Reduce x into [sqrt(2)/2, sqrt(2)).
+	hx += 0x3ff00000 - 0x3fe6a09e;
+	k += (hx >> 20) as i32 - 0x3ff;
+	hx = (hx & 0x000fffff) + 0x3fe6a09e;
+	let m = f64::from_bits(((hx as u64) << 32) | (bits & 0xffffffff));
+	let f = m - 1.0;
+	let hfsq = 0.5 * f * f;
+	let s = f / (2.0 + f);
+	let z = s * s;
+	let w = z * z;
+	let t1 = w * (LG[1] + w * (LG[3] + w * LG[5]));
+	let t2 = z * (LG[0] + w * (LG[2] + w * (LG[4] + w * LG[6])));
+	let dk = k as f64;
+	s * (hfsq + t1 + t2) + dk * LN2_LO - hfsq + f + dk * LN2_HI
+}
+
+/// Natural logarithm of one plus `x`, accurate for small `x`.
+pub fn ln_1p(x: f64) -> f64 {
+	let w = 1.0 + x;
+	if w == 1.0 {
+		return x;
+	}
+	if w.is_infinite() {
+		return w;
+	}
+	ln(w) * x / (w - 1.0)
+}
+
+/// Logarithm of `x` with base `base`.
+pub fn log(x: f64, base: f64) -> f64 {
+	ln(x) / ln(base)
+}
+
+/// Reduces `x` modulo pi/2, returning the quadrant and the remainder as an
+/// unevaluated sum of two floats.
+///
+/// Reduction is accurate for |`x`| up to 2^20·pi/2; beyond that it remains
+/// deterministic but loses precision.
+fn rem_pio2(x: f64) -> (i32, f64, f64) {
+	const INV_PIO2: f64 = std::f64::consts::FRAC_2_PI;
+	const PIO2: [(f64, f64); 3] = [
+		(1.57079632673412561417e+00, 6.07710050650619224932e-11),
+		(6.07710050630396597660e-11, 2.02226624879595063154e-21),
+		(2.02226624871116645580e-21, 8.47842766036889956997e-32)
+	];
+	if high_word(x) > 0x413921fb {
+		let reduced = x % (2.0 * PI);
+		let (n, y0, y1) = rem_pio2(reduced);
+		return (n, y0, y1);
+	}
+	let n = (x * INV_PIO2).round();
+	let exponent = |v: f64| ((v.to_bits() >> 52) & 0x7ff) as i32;
+	let mut r = x - n * PIO2[0].0;
+	let mut w = n * PIO2[0].1;
+	let mut y0 = r - w;
+	for (part, tail) in &PIO2[1..] {
+		if exponent(x) - exponent(y0) <= 16 {
+			break;
+		}
+		let t = r;
+		w = n * part;
+		r = t - w;
+		w = n * tail - ((t - r) - w);
+		y0 = r - w;
+	}
+	(n as i32, y0, (r - y0) - w)
+}
+
+/// Sine on [-pi/4, pi/4] of `x + y`.
+fn kernel_sin(x: f64, y: f64) -> f64 {
+	const S: [f64; 6] = [
+		-1.66666666666666324348e-01, 8.33333333332248946124e-03, -1.98412698298579493134e-04,
+		2.75573137070700676789e-06, -2.50507602534068634195e-08, 1.58969099521155010221e-10
+	];
+	let z = x * x;
+	let w = z * z;
+	let r = S[1] + z * (S[2] + z * S[3]) + z * w * (S[4] + z * S[5]);
+	let v = z * x;
+	x - ((z * (0.5 * y - v * r) - y) - v * S[0])
+}
+
+/// Cosine on [-pi/4, pi/4] of `x + y`.
+fn kernel_cos(x: f64, y: f64) -> f64 {
+	const C: [f64; 6] = [
+		4.16666666666666019037e-02, -1.38888888888741095749e-03, 2.48015872894767294178e-05,
+		-2.75573143513906633035e-07, 2.08757232129817482790e-09, -1.13596475577881948265e-11
+	];
+	let z = x * x;
+	let w = z * z;
+	let r = z * (C[0] + z * (C[1] + z * C[2])) + w * w * (C[3] + z * (C[4] + z * C[5]));
+	let hz = 0.5 * z;
+	let w = 1.0 - hz;
+	w + (((1.0 - w) - hz) + (z * r - x * y))
+}
+
+/// Sine of `x` (in radians).
+///
+/// ```
+/// # use hebrides::strict;
+/// assert!((strict::sin(std::f64::consts::FRAC_PI_6) - 0.5).abs() < 1e-15);
+/// ```
+pub fn sin(x: f64) -> f64 {
+	if !x.is_finite() {
+		return f64::NAN;
+	}
+	if high_word(x) < 0x3e500000 {
+		return x;
+	}
+	let (n, y0, y1) = rem_pio2(x);
+	match n & 3 {
+		0 => kernel_sin(y0, y1),
+		1 => kernel_cos(y0, y1),
+		2 => -kernel_sin(y0, y1),
+		_ => -kernel_cos(y0, y1)
+	}
+}
+
+/// Cosine of `x` (in radians).
+pub fn cos(x: f64) -> f64 {
+	if !x.is_finite() {
+		return f64::NAN;
+	}
+	let (n, y0, y1) = rem_pio2(x);
+	match n & 3 {
+		0 => kernel_cos(y0, y1),
+		1 => -kernel_sin(y0, y1),
+		2 => -kernel_cos(y0, y1),
+		_ => kernel_sin(y0, y1)
+	}
+}
+
+/// Tangent of `x` (in radians).
+pub fn tan(x: f64) -> f64 {
+	if !x.is_finite() {
+		return f64::NAN;
+	}
+	if high_word(x) < 0x3e400000 {
+		return x;
+	}
+	let (n, y0, y1) = rem_pio2(x);
+	let (s, c) = (kernel_sin(y0, y1), kernel_cos(y0, y1));
+	if n & 1 == 0 { s / c } else { -c / s }
+}
+
+/// Arctangent of `x`, in [-pi/2, pi/2].
+pub fn atan(x: f64) -> f64 {
+	const ATAN_HI: [f64; 4] = [4.63647609000806093515e-01, std::f64::consts::FRAC_PI_4, 9.82793723247329054082e-01, FRAC_PI_2];
+	const ATAN_LO: [f64; 4] = [2.26987774529616870924e-17, 3.06161699786838301793e-17, 1.39033110312309984516e-17, 6.12323399573676603587e-17];
+	const AT: [f64; 11] = [
+		3.33333333333329318027e-01, -1.99999999998764832476e-01, 1.42857142725034663711e-01,
+		-1.11111104054623557880e-01, 9.09088713343650656196e-02, -7.69187620504482999495e-02,
+		6.66107313738753120669e-02, -5.83357013379057348645e-02, 4.97687799461593236017e-02,
+		-3.65315727442169155270e-02, 1.62858201153657823623e-02
+	];
+	let ix = high_word(x);
+	let negative = x.is_sign_negative();
+	if ix >= 0x44100000 {
+		if x.is_nan() {
+			return x;
+		}
+		let z = ATAN_HI[3] + ATAN_LO[3];
+		return if negative { -z } else { z };
+	}
+	let (id, x) = if ix < 0x3fdc0000 {
+		if ix < 0x3e400000 {
+			return x;
+		}
+		(None, x)
+	} else {
+		let x = x.abs();
+		if ix < 0x3ff30000 {
+			if ix < 0x3fe60000 {
+				(Some(0), (2.0 * x - 1.0) / (2.0 + x))
+			} else {
+				(Some(1), (x - 1.0) / (x + 1.0))
+			}
+		} else if ix < 0x40038000 {
+			(Some(2), (x - 1.5) / (1.0 + 1.5 * x))
+		} else {
+			(Some(3), -1.0 / x)
+		}
+	};
+	let z = x * x;
+	let w = z * z;
+	let s1 = z * (AT[0] + w * (AT[2] + w * (AT[4] + w * (AT[6] + w * (AT[8] + w * AT[10])))));
+	let s2 = w * (AT[1] + w * (AT[3] + w * (AT[5] + w * (AT[7] + w * AT[9]))));
+	match id {
+		None => x - x * (s1 + s2),
+		Some(id) => {
+			let z = ATAN_HI[id] - ((x * (s1 + s2) - ATAN_LO[id]) - x);
+			if negative { -z } else { z }
+		}
+	}
+}
+
+/// Four-quadrant arctangent of `y / x`, in [-pi, pi].
+pub fn atan2(y: f64, x: f64) -> f64 {
+	if x.is_nan() || y.is_nan() {
+		return f64::NAN;
+	}
+	let sign = |v: f64| if y.is_sign_negative() { -v } else { v };
+	if y == 0.0 {
+		return if x.is_sign_negative() { sign(PI) } else { y };
+	}
+	if x == 0.0 {
+		return sign(FRAC_PI_2);
+	}
+	if x.is_infinite() {
+		return match (y.is_infinite(), x > 0.0) {
+			(true, true) => sign(FRAC_PI_2 / 2.0),
+			(true, false) => sign(3.0 * FRAC_PI_2 / 2.0),
+			(false, true) => sign(0.0),
+			(false, false) => sign(PI)
+		};
+	}
+	if y.is_infinite() {
+		return sign(FRAC_PI_2);
+	}
+	let z = atan((y / x).abs());
+	if x > 0.0 { sign(z) } else { sign(PI - (z - PI_LO)) }
+}
+
+/// Euclidean length √(x² + y²) of (`x`, `y`), without overflow or underflow
+/// in the intermediate squares.
+pub fn hypot(x: f64, y: f64) -> f64 {
+	let (x, y) = (x.abs(), y.abs());
+	if x.is_infinite() || y.is_infinite() {
+		return f64::INFINITY;
+	}
+	if x.is_nan() || y.is_nan() {
+		return f64::NAN;
+	}
+	let (large, small) = if x >= y { (x, y) } else { (y, x) };
+	if large == 0.0 {
+		return 0.0;
+	}
+	let ratio = small / large;
+	large * (1.0 + ratio * ratio).sqrt()
+}
+
+/// Arcsine of `x`, in [-pi/2, pi/2]; NaN outside [-1, 1].
+pub fn asin(x: f64) -> f64 {
+	atan2(x, ((1.0 - x) * (1.0 + x)).sqrt())
+}
+
+/// Arccosine of `x`, in [0, pi]; NaN outside [-1, 1].
+pub fn acos(x: f64) -> f64 {
+	atan2(((1.0 - x) * (1.0 + x)).sqrt(), x)
+}
+
+/// Hyperbolic sine of `x`.
+pub fn sinh(x: f64) -> f64 {
+	let a = x.abs();
+	let h = if x.is_sign_negative() { -0.5 } else { 0.5 };
+	if a < 22.0 {
+		let t = exp_m1(a);
+		return h * (t + t / (t + 1.0));
+	}
+	let e = exp(0.5 * a);
+	h * e * e
+}
+
+/// Hyperbolic cosine of `x`.
+pub fn cosh(x: f64) -> f64 {
+	let a = x.abs();
+	if a < 22.0 {
+		let e = exp(a);
+		return 0.5 * e + 0.5 / e;
+	}
+	let e = exp(0.5 * a);
+	0.5 * e * e
+}
+
+/// Hyperbolic tangent of `x`.
+pub fn tanh(x: f64) -> f64 {
+	let a = x.abs();
+	let t = if a >= 22.0 {
+		1.0
+	} else if a >= 1.0 {
+		1.0 - 2.0 / (exp_m1(2.0 * a) + 2.0)
+	} else {
+		let e = exp_m1(-2.0 * a);
+		-e / (e + 2.0)
+	};
+	if x.is_sign_negative() { -t } else { t }
+}
+
+/// Inverse hyperbolic sine of `x`.
+pub fn asinh(x: f64) -> f64 {
+	let a = x.abs();
+	let t = if a > 2.0_f64.powi(28) {
+		ln(a) + LN2_HI + LN2_LO
+	} else {
+		ln_1p(a + a * a / (1.0 + (1.0 + a * a).sqrt()))
+	};
+	if x.is_sign_negative() { -t } else { t }
+}
+
+/// Inverse hyperbolic cosine of `x`; NaN below one.
+pub fn acosh(x: f64) -> f64 {
+	if x < 1.0 {
+		return f64::NAN;
+	}
+	if x > 2.0_f64.powi(28) {
+		return ln(x) + LN2_HI + LN2_LO;
+	}
+	ln_1p((x - 1.0) + ((x - 1.0) * (x + 1.0)).sqrt())
+}
+
+/// Inverse hyperbolic tangent of `x`; NaN outside [-1, 1].
+pub fn atanh(x: f64) -> f64 {
+	let a = x.abs();
+	let t = 0.5 * ln_1p(2.0 * a / (1.0 - a));
+	if x.is_sign_negative() { -t } else { t }
+}
+
+/// `x` to the power of `y`.
+///
+/// Integral powers of magnitude at most 64 are computed by repeated squaring;
+/// negative `x` with a non-integral `y` gives NaN.
+pub fn powf(x: f64, y: f64) -> f64 {
+	if y == 0.0 || x == 1.0 {
+		return 1.0;
+	}
+	if x.is_nan() || y.is_nan() {
+		return f64::NAN;
+	}
+	if y == 0.5 && x >= 0.0 {
+		return x.sqrt();
+	}
+	let integral = y.trunc() == y;
+	if integral && y.abs() <= 64.0 {
+		let mut result = 1.0;
+		let mut base = x;
+		let mut n = y.abs() as u32;
+		while n > 0 {
+			if n & 1 == 1 {
+				result *= base;
+			}
+			base *= base;
+			n >>= 1;
+		}
+		return if y < 0.0 { 1.0 / result } else { result };
+	}
+	if x < 0.0 {
+		if !integral {
+			return f64::NAN;
+		}
+		let magnitude = powf(-x, y);
+		return if y % 2.0 == 0.0 { magnitude } else { -magnitude };
+	}
+	if x == 0.0 {
+		return if y > 0.0 { 0.0 } else { f64::INFINITY };
+	}
+	exp(y * ln(x))
+}
+
+/// Cube root of `x`, defined for every real `x`.
+///
+/// ```
+/// # use hebrides::strict;
+/// assert_eq!(strict::cbrt(-27.0), -3.0);
+/// ```
+pub fn cbrt(x: f64) -> f64 {
+	const B1: u32 = 715094163;
+	const B2: u32 = 696219795;
+	const P: [f64; 5] = [
+		1.87595182427177009643, -1.88497979543377169875, 1.621429720105354466140,
+		-0.758397934778766047437, 0.145996192886612446982
+	];
+	let mut hx = high_word(x);
+	if hx >= 0x7ff00000 {
+		return x + x;
+	}
+	let sign = x.to_bits() & (1 << 63);
+	if hx < 0x00100000 {
+		// Zero or subnormal: scale into the normal range before estimating.
+		hx = high_word(x * f64::from_bits(0x4350000000000000));
+		if hx == 0 {
+			return x;
+		}
+		hx = hx / 3 + B2;
+	} else {
+		hx = hx / 3 + B1;
+	}
+	let mut t = f64::from_bits(sign | (hx as u64) << 32);
+	let r = (t * t) * (t / x);
+	t *= (P[0] + r * (P[1] + r * P[2])) + ((r * r) * r) * (P[3] + r * P[4]);
+	t = f64::from_bits((t.to_bits() + 0x80000000) & 0xffffffffc0000000);
+	let s = t * t;
+	let r = x / s;
+	let w = t + t;
+	let r = (r - t) / (w + r);
+	t + t * r
+}
+
+/// Sum of `values`, evaluated left to right with Neumaier compensation.
+pub fn sum(values: &[f64]) -> f64 {
+	let mut total = 0.0;
+	let mut compensation = 0.0;
+	for v in values {
+		let t = total + v;
+		if total.abs() >= v.abs() {
+			compensation += (total - t) + v;
+		} else {
+			compensation += (v - t) + total;
+		}
+		total = t;
+	}
+	total + compensation
+}
+
+/// Dot product of `a` and `b`, evaluated left to right with Neumaier
+/// compensation.
+///
+/// # Panics
+/// Panics if `a` and `b` have different lengths.
+pub fn dot(a: &[f64], b: &[f64]) -> f64 {
+	if a.len() != b.len() {
+		panic!("Slices must be of the same length to take their dot product")
+	}
+	let products: Vec<f64> = a.iter().zip(b.iter()).map(|(x, y)| x * y).collect();
+	sum(&products)
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	/// Asserts that `f` agrees with `reference` to within `ulps` units in the
+	/// last place over `inputs`.
+	fn assert_close(f: fn(f64) -> f64, reference: fn(f64) -> f64, inputs: impl Iterator<Item=f64>, ulps: f64) {
+		for x in inputs {
+			let (found, expected) = (f(x), reference(x));
+			let tolerance = ulps * f64::EPSILON * expected.abs().max(f64::MIN_POSITIVE);
+			assert!((found - expected).abs() <= tolerance, "{x}: {found} != {expected}");
+		}
+	}
+
+	fn grid(from: f64, to: f64, n: usize) -> impl Iterator<Item=f64> {
+		(0..=n).map(move |i| from + (to - from) * i as f64 / n as f64)
+	}
+
+	#[test]
+	fn exponentials_and_logarithms() {
+		assert_close(exp, f64::exp, grid(-700.0, 700.0, 10007), 1.0);
+		assert_close(exp_m1, f64::exp_m1, grid(-1.0, 1.0, 1001), 2.0);
+		assert_close(ln, f64::ln, grid(1e-300, 1e300, 10007).chain(grid(0.5, 2.0, 1001)), 1.0);
+		assert_close(ln_1p, f64::ln_1p, grid(-0.5, 0.5, 1001), 2.0);
+		assert_eq!(exp(800.0), f64::INFINITY);
+		assert_eq!(exp(-800.0), 0.0);
+		assert_eq!(ln(0.0), f64::NEG_INFINITY);
+		assert!(ln(-1.0).is_nan());
+		assert_close(ln, f64::ln, [5e-324, 1e-310].into_iter(), 1.0)
+	}
+
+	#[test]
+	fn trigonometry() {
+		assert_close(sin, f64::sin, grid(-1e5, 1e5, 100003), 1.0);
+		assert_close(cos, f64::cos, grid(-1e5, 1e5, 100003), 1.0);
+		assert_close(tan, f64::tan, grid(-1.5, 1.5, 1001), 4.0);
+		assert_close(atan, f64::atan, grid(-20.0, 20.0, 10007), 1.0);
+		assert_close(asin, f64::asin, grid(-0.99, 0.99, 1001), 4.0);
+		assert_close(acos, f64::acos, grid(-0.99, 0.99, 1001), 4.0);
+		assert_eq!(atan2(1.0, -1.0), 0.75 * PI);
+		assert_eq!(atan2(-0.0, -1.0), -PI);
+		assert!(asin(1.5).is_nan())
+	}
+
+	#[test]
+	fn hyperbolics() {
+		assert_close(sinh, f64::sinh, grid(-30.0, 30.0, 1001), 4.0);
+		assert_close(cosh, f64::cosh, grid(-30.0, 30.0, 1001), 4.0);
+		assert_close(tanh, f64::tanh, grid(-30.0, 30.0, 1001), 4.0);
+		assert_close(asinh, f64::asinh, grid(-30.0, 30.0, 1001), 4.0);
+		assert_close(acosh, f64::acosh, grid(1.5, 30.0, 1001), 4.0);
+		assert_close(atanh, f64::atanh, grid(-0.9, 0.9, 1001), 4.0)
+	}
+
+	#[test]
+	fn powers() {
+		assert_eq!(powf(2.0, 10.0), 1024.0);
+		assert_eq!(powf(-2.0, 3.0), -8.0);
+		assert_eq!(powf(9.0, 0.5), 3.0);
+		assert!(powf(-2.0, 0.5).is_nan());
+		assert!((powf(2.0, 0.3) - 2.0_f64.powf(0.3)).abs() < 1e-15);
+		assert_close(cbrt, f64::cbrt, grid(-1e3, 1e3, 1001), 1.0);
+		assert_eq!(cbrt(f64::MIN_POSITIVE / 8.0), f64::cbrt(f64::MIN_POSITIVE / 8.0))
+	}
+
+	#[test]
+	fn lengths() {
+		assert_close(|y| hypot(3.7, y), |y| 3.7_f64.hypot(y), grid(-1e3, 1e3, 1001), 2.0);
+		assert_eq!(hypot(3.0, -4.0), 5.0);
+		assert_eq!(hypot(1e300, 1e300), 1e300_f64.hypot(1e300));
+		assert_eq!(hypot(f64::NAN, f64::INFINITY), f64::INFINITY)
+	}
+
+	#[test]
+	fn real_complex_trigonometry_matches_real() {
+		use crate::{Real, Complex};
+		for x in grid(-30.0, 30.0, 10007) {
+			let (real, complex) = (Real::new(x), Complex::new(x, 0.0));
+			assert_eq!(complex.sin().re().to_bits(), real.sin().value().to_bits());
+			assert_eq!(complex.cos().re().to_bits(), real.cos().value().to_bits());
+			assert_eq!(complex.tan().re().to_bits(), real.tan().value().to_bits());
+			assert_eq!(complex.sinh().re().to_bits(), real.sinh().value().to_bits());
+			assert_eq!(complex.cosh().re().to_bits(), real.cosh().value().to_bits());
+		}
+	}
+
+	#[test]
+	fn real_complex_powers_match_real() {
+		use crate::{Real, Complex};
+		for x in grid(1e-3, 1e3, 10007) {
+			let (real, complex) = (Real::new(x), Complex::new(x, 0.0));
+			assert_eq!(complex.ln().re().to_bits(), real.ln().unwrap().value().to_bits());
+			assert_eq!(complex.powf(0.3).re().to_bits(), real.powf(0.3).unwrap().value().to_bits());
+			assert_eq!(complex.exp().re().to_bits(), real.exp().value().to_bits());
+		}
+	}
+
+	#[test]
+	fn compensated_sum() {
+		let values = [1.0, 1e100, 1.0, -1e100];
+		assert_eq!(sum(&values), 2.0);
+		assert_eq!(dot(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]), 32.0)
+	}
+
+}
@@ -0,0 +1,620 @@
+//! Exact symbolic expressions.
+//!
+//! [`Expr`] represents rational functions of named variables with exact
+//! rational coefficients, kept in a simplified canonical form. Small
+//! parametric matrices can then be analyzed symbolically through
+//! `Matrix<Expr>`, which supports determinants, reduced row echelon forms,
+//! and characteristic polynomials.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+use crate::Rational;
+use crate::linal::Matrix;
+
+/// A product of variables raised to positive powers, sorted by name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Monomial(Vec<(String, u32)>);
+
+impl Monomial {
+
+	fn variable(name: &str) -> Self {
+		Monomial(vec![(name.to_string(), 1)])
+	}
+
+	fn degree(&self) -> u32 {
+		self.0.iter().map(|(_, e)| e).sum()
+	}
+
+	fn exponent(&self, name: &str) -> u32 {
+		self.0.iter().find(|(v, _)| v == name).map_or(0, |(_, e)| *e)
+	}
+
+	/// Combines the exponents of `self` and `other` variable by variable,
+	/// dropping variables whose combined exponent is zero.
+	fn merge(&self, other: &Self, op: impl Fn(u32, u32) -> u32) -> Self {
+		let mut names: Vec<&String> = self.0.iter().chain(other.0.iter()).map(|(v, _)| v).collect();
+		names.sort();
+		names.dedup();
+		Monomial(names.into_iter()
+					  .map(|v| (v.clone(), op(self.exponent(v), other.exponent(v))))
+					  .filter(|(_, e)| *e > 0)
+					  .collect())
+	}
+
+	fn mul(&self, other: &Self) -> Self {
+		self.merge(other, |a, b| a + b)
+	}
+
+	fn divides(&self, other: &Self) -> bool {
+		self.0.iter().all(|(v, e)| other.exponent(v) >= *e)
+	}
+
+	fn div(&self, other: &Self) -> Self {
+		self.merge(other, |a, b| a - b)
+	}
+
+	fn gcd(&self, other: &Self) -> Self {
+		self.merge(other, u32::min)
+	}
+
+}
+
+/// Graded lexicographic order, with variables earlier in the alphabet
+/// ranking higher.
+impl Ord for Monomial {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.degree().cmp(&other.degree()).then_with(|| {
+			let mut names: Vec<&String> = self.0.iter().chain(other.0.iter()).map(|(v, _)| v).collect();
+			names.sort();
+			names.dedup();
+			names.into_iter()
+				 .map(|v| self.exponent(v).cmp(&other.exponent(v)))
+				 .find(|o| o.is_ne())
+				 .unwrap_or(Ordering::Equal)
+		})
+	}
+}
+
+impl PartialOrd for Monomial {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl std::fmt::Display for Monomial {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let factors: Vec<String> = self.0.iter().map(|(v, e)| {
+			if *e == 1 { v.clone() } else { format!("{}^{}", v, e) }
+		}).collect();
+		write!(f, "{}", factors.join("*"))
+	}
+}
+
+/// A polynomial with exact rational coefficients, storing only nonzero
+/// terms.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Polynomial(BTreeMap<Monomial, Rational>);
+
+impl Polynomial {
+
+	fn constant(c: Rational) -> Self {
+		Polynomial::term(Monomial::default(), c)
+	}
+
+	fn term(monomial: Monomial, c: Rational) -> Self {
+		let mut terms = BTreeMap::new();
+		if c != Rational::ZERO {
+			terms.insert(monomial, c);
+		}
+		Polynomial(terms)
+	}
+
+	fn is_zero(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	fn is_one(&self) -> bool {
+		*self == Polynomial::constant(Rational::ONE)
+	}
+
+	fn leading(&self) -> Option<(&Monomial, &Rational)> {
+		self.0.last_key_value()
+	}
+
+	fn add_term(&mut self, monomial: Monomial, c: Rational) {
+		let sum = self.0.get(&monomial).map_or(c, |d| *d + c);
+		if sum == Rational::ZERO {
+			self.0.remove(&monomial);
+		} else {
+			self.0.insert(monomial, sum);
+		}
+	}
+
+	fn add(&self, other: &Self) -> Self {
+		let mut sum = self.clone();
+		for (m, c) in &other.0 {
+			sum.add_term(m.clone(), *c);
+		}
+		sum
+	}
+
+	fn neg(&self) -> Self {
+		Polynomial(self.0.iter().map(|(m, c)| (m.clone(), -*c)).collect())
+	}
+
+	fn mul_term(&self, monomial: &Monomial, c: Rational) -> Self {
+		let mut product = Polynomial::default();
+		for (m, d) in &self.0 {
+			product.add_term(m.mul(monomial), *d * c);
+		}
+		product
+	}
+
+	fn mul(&self, other: &Self) -> Self {
+		other.0.iter().fold(Polynomial::default(), |sum, (m, c)| sum.add(&self.mul_term(m, *c)))
+	}
+
+	/// Returns `self` / `divisor` if the division is exact.
+	fn div_exact(&self, divisor: &Self) -> Option<Self> {
+		let (lead, lead_c) = divisor.leading()?;
+		let mut remaining = self.clone();
+		let mut quotient = Polynomial::default();
+		while let Some((m, c)) = remaining.leading() {
+			if !lead.divides(m) {
+				return None;
+			}
+			let (m, c) = (m.div(lead), *c / *lead_c);
+			remaining = remaining.add(&divisor.mul_term(&m, -c));
+			quotient.add_term(m, c);
+		}
+		Some(quotient)
+	}
+
+	/// Returns the greatest monomial dividing every term of `self`.
+	fn monomial_content(&self) -> Monomial {
+		let mut terms = self.0.keys();
+		let first = terms.next().cloned().unwrap_or_default();
+		terms.fold(first, |g, m| g.gcd(m))
+	}
+
+	fn div_monomial(&self, monomial: &Monomial) -> Self {
+		Polynomial(self.0.iter().map(|(m, c)| (m.div(monomial), *c)).collect())
+	}
+
+	fn variables(&self) -> impl Iterator<Item=&String> {
+		self.0.keys().flat_map(|m| m.0.iter().map(|(v, _)| v))
+	}
+
+}
+
+impl std::fmt::Display for Polynomial {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.is_zero() {
+			return write!(f, "0");
+		}
+		for (i, (m, c)) in self.0.iter().rev().enumerate() {
+			let size = c.abs();
+			match (i, c.numer() < 0) {
+				(0, true) => write!(f, "-")?,
+				(0, false) => {},
+				(_, true) => write!(f, " - ")?,
+				(_, false) => write!(f, " + ")?
+			}
+			if m.0.is_empty() {
+				write!(f, "{}", size)?;
+			} else if size == Rational::ONE {
+				write!(f, "{}", m)?;
+			} else {
+				write!(f, "{}*{}", size, m)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// An exact symbolic expression: a quotient of polynomials in named
+/// variables with rational coefficients.
+///
+/// Expressions are simplified as they are built. Denominators are made monic,
+/// and common factors between numerator and denominator are cancelled when
+/// one divides the other or when they are monomials; general multivariate
+/// common factors are not sought. Equality is mathematical, so `x/x == 1`
+/// even before simplification.
+///
+/// Coefficients are [`Rational`]s, and arithmetic panics if one overflows.
+///
+/// ```
+/// # use hebrides::symbolic::Expr;
+/// let (x, y) = (Expr::var("x"), Expr::var("y"));
+/// let e = (x.clone() + y.clone()) * (x.clone() - y.clone());
+/// assert_eq!(e.to_string(), "x^2 - y^2");
+/// assert_eq!((e / (x + y)).to_string(), "x - y");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Expr {
+	numerator: Polynomial,
+	denominator: Polynomial
+}
+
+impl Expr {
+
+	fn from_parts(numerator: Polynomial, denominator: Polynomial) -> Self {
+		if denominator.is_zero() {
+			panic!("Cannot divide an Expr by zero")
+		}
+		if numerator.is_zero() {
+			return Expr::default();
+		}
+		let (mut numerator, mut denominator) = match numerator.div_exact(&denominator) {
+			Some(quotient) => (quotient, Polynomial::constant(Rational::ONE)),
+			None => match denominator.div_exact(&numerator) {
+				Some(quotient) => (Polynomial::constant(Rational::ONE), quotient),
+				None => (numerator, denominator)
+			}
+		};
+		let common = numerator.monomial_content().gcd(&denominator.monomial_content());
+		numerator = numerator.div_monomial(&common);
+		denominator = denominator.div_monomial(&common);
+		let lead = denominator.leading().unwrap().1.recip();
+		Expr {
+			numerator: numerator.mul_term(&Monomial::default(), lead),
+			denominator: denominator.mul_term(&Monomial::default(), lead)
+		}
+	}
+
+	fn polynomial(numerator: Polynomial) -> Self {
+		Expr { numerator, denominator: Polynomial::constant(Rational::ONE) }
+	}
+
+	/// Returns the variable named `name`.
+	pub fn var(name: &str) -> Self {
+		Expr::polynomial(Polynomial::term(Monomial::variable(name), Rational::ONE))
+	}
+
+	/// Returns the rational constant `num`/`den`.
+	///
+	/// # Panics
+	/// Panics if `den` is zero.
+	pub fn rational(num: i64, den: i64) -> Self {
+		Expr::polynomial(Polynomial::constant(Rational::new(num.into(), den.into())))
+	}
+
+	/// Returns whether `self` is identically zero.
+	pub fn is_zero(&self) -> bool {
+		self.numerator.is_zero()
+	}
+
+	/// Returns whether `self` is a polynomial, with no variables in its
+	/// denominator.
+	pub fn is_polynomial(&self) -> bool {
+		self.denominator.leading().is_some_and(|(m, _)| m.0.is_empty())
+	}
+
+	/// Returns `self` raised to the `n`th power.
+	pub fn pow(&self, n: u32) -> Self {
+		(0..n).fold(Expr::from(1), |product, _| product * self.clone())
+	}
+
+	/// Returns the names of the variables appearing in `self`, sorted.
+	pub fn variables(&self) -> Vec<String> {
+		let mut names: Vec<String> = self.numerator.variables().chain(self.denominator.variables()).cloned().collect();
+		names.sort();
+		names.dedup();
+		names
+	}
+
+	/// Returns `self` with every occurrence of the variable `name` replaced
+	/// by `value`.
+	///
+	/// ```
+	/// # use hebrides::symbolic::Expr;
+	/// let x = Expr::var("x");
+	/// let e = x.pow(2) - Expr::from(1);
+	/// assert_eq!(e.substitute("x", &(Expr::var("t") + Expr::from(1))).to_string(), "t^2 + 2*t");
+	/// ```
+	pub fn substitute(&self, name: &str, value: &Expr) -> Self {
+		let substitute = |p: &Polynomial| -> Expr {
+			p.0.iter().fold(Expr::default(), |sum, (m, c)| {
+				let term = m.0.iter().fold(Expr::polynomial(Polynomial::constant(*c)), |product, (v, e)| {
+					let factor = if v == name { value.clone() } else { Expr::var(v) };
+					product * factor.pow(*e)
+				});
+				sum + term
+			})
+		};
+		substitute(&self.numerator) / substitute(&self.denominator)
+	}
+
+	/// Evaluates `self` with each variable bound to a value in `bindings`.
+	///
+	/// # Panics
+	/// Panics if a variable of `self` is unbound.
+	pub fn evaluate(&self, bindings: &[(&str, f64)]) -> f64 {
+		let evaluate = |p: &Polynomial| -> f64 {
+			p.0.iter().map(|(m, c)| {
+				c.to_f64() * m.0.iter().map(|(v, e)| {
+					let value = bindings.iter().find(|(name, _)| name == v).map(|(_, x)| *x);
+					value.unwrap_or_else(|| panic!("Variable {} must be bound to evaluate an Expr", v)).powi(*e as i32)
+				}).product::<f64>()
+			}).sum()
+		};
+		evaluate(&self.numerator) / evaluate(&self.denominator)
+	}
+
+}
+
+impl Default for Expr {
+	fn default() -> Self {
+		Expr::polynomial(Polynomial::default())
+	}
+}
+
+impl From<i64> for Expr {
+	fn from(n: i64) -> Self {
+		Expr::rational(n, 1)
+	}
+}
+
+impl PartialEq for Expr {
+	fn eq(&self, other: &Self) -> bool {
+		self.numerator.mul(&other.denominator) == other.numerator.mul(&self.denominator)
+	}
+}
+
+impl std::fmt::Display for Expr {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.denominator.is_one() {
+			return write!(f, "{}", self.numerator);
+		}
+		let group = |p: &Polynomial| -> String {
+			let shown = p.to_string();
+			if p.0.len() > 1 || shown.contains('*') || shown.contains('/') { format!("({})", shown) } else { shown }
+		};
+		let numerator = if self.numerator.0.len() > 1 { format!("({})", self.numerator) } else { self.numerator.to_string() };
+		write!(f, "{}/{}", numerator, group(&self.denominator))
+	}
+}
+
+impl Add<Self> for Expr {
+	type Output = Self;
+	fn add(self, other: Self) -> Self {
+		if self.denominator == other.denominator {
+			return Expr::from_parts(self.numerator.add(&other.numerator), self.denominator);
+		}
+		Expr::from_parts(
+			self.numerator.mul(&other.denominator).add(&other.numerator.mul(&self.denominator)),
+			self.denominator.mul(&other.denominator)
+		)
+	}
+}
+
+impl Sub<Self> for Expr {
+	type Output = Self;
+	fn sub(self, other: Self) -> Self {
+		self + -other
+	}
+}
+
+impl Mul<Self> for Expr {
+	type Output = Self;
+	fn mul(self, other: Self) -> Self {
+		Expr::from_parts(self.numerator.mul(&other.numerator), self.denominator.mul(&other.denominator))
+	}
+}
+
+impl Div<Self> for Expr {
+	type Output = Self;
+	fn div(self, other: Self) -> Self {
+		Expr::from_parts(self.numerator.mul(&other.denominator), self.denominator.mul(&other.numerator))
+	}
+}
+
+impl Neg for Expr {
+	type Output = Self;
+	fn neg(self) -> Self {
+		Expr { numerator: self.numerator.neg(), denominator: self.denominator }
+	}
+}
+
+/// Symbolic linear algebra. Pivots are taken to be nonzero whenever they are
+/// not identically zero as expressions, so results hold for generic values
+/// of the variables and may fail where some pivot vanishes.
+impl Matrix<Expr> {
+
+	/// Returns the determinant of `self`, computed by fraction-free
+	/// (Bareiss) elimination.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// # use hebrides::symbolic::Expr;
+	/// let m = Matrix::new(vec![
+	///     vec![Expr::var("a"), Expr::var("b")],
+	///     vec![Expr::var("c"), Expr::var("d")]
+	/// ]);
+	/// assert_eq!(m.determinant().to_string(), "a*d - b*c");
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square.
+	pub fn determinant(&self) -> Expr {
+		if !self.is_square() {
+			panic!("Matrix must be square to have a determinant")
+		}
+		let n = self.dims.num_rows;
+		let mut rows = self.rows.clone();
+		let mut sign = Expr::from(1);
+		let mut previous = Expr::from(1);
+		for k in 0..n - 1 {
+			match (k..n).find(|i| !rows[*i][k].is_zero()) {
+				None => return Expr::default(),
+				Some(pivot) if pivot != k => {
+					rows.swap(k, pivot);
+					sign = -sign;
+				},
+				Some(_) => {}
+			}
+			for i in k + 1..n {
+				for j in k + 1..n {
+					let cross = rows[i][j].clone() * rows[k][k].clone() - rows[i][k].clone() * rows[k][j].clone();
+					rows[i][j] = cross / previous.clone();
+				}
+			}
+			previous = rows[k][k].clone();
+		}
+		sign * rows[n - 1][n - 1].clone()
+	}
+
+	/// Returns the reduced row echelon form of `self` along with the indices
+	/// of its pivot columns.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// # use hebrides::symbolic::Expr;
+	/// let (a, b) = (Expr::var("a"), Expr::var("b"));
+	/// let m = Matrix::new(vec![vec![a.clone(), b.clone()], vec![a * Expr::from(2), b * Expr::from(2)]]);
+	/// let (r, pivots) = m.rref();
+	/// assert_eq!(r.rows[0][1].to_string(), "b/a");
+	/// assert_eq!(pivots, vec![0]);
+	/// ```
+	pub fn rref(&self) -> (Matrix<Expr>, Vec<usize>) {
+		let (m, n) = (self.dims.num_rows, self.dims.num_cols);
+		let mut rows = self.rows.clone();
+		let mut pivots = Vec::new();
+		for col in 0..n {
+			let row = pivots.len();
+			if row == m {
+				break;
+			}
+			let Some(pivot) = (row..m).find(|i| !rows[*i][col].is_zero()) else {
+				continue;
+			};
+			rows.swap(row, pivot);
+			let lead = rows[row][col].clone();
+			for e in rows[row].iter_mut() {
+				*e = e.clone() / lead.clone();
+			}
+			let pivot_row = rows[row].clone();
+			for (i, r) in rows.iter_mut().enumerate() {
+				if i == row || r[col].is_zero() {
+					continue;
+				}
+				let factor = r[col].clone();
+				for (e, p) in r.iter_mut().zip(pivot_row.iter()) {
+					*e = e.clone() - factor.clone() * p.clone();
+				}
+			}
+			pivots.push(col);
+		}
+		(Matrix::new(rows), pivots)
+	}
+
+	/// Returns the characteristic polynomial det(λI - `self`) of `self` in
+	/// the variable named `variable`.
+	///
+	/// ```
+	/// # use hebrides::linal::Matrix;
+	/// # use hebrides::symbolic::Expr;
+	/// let m = Matrix::new(vec![vec![Expr::from(0), Expr::from(1)], vec![Expr::var("k"), Expr::from(0)]]);
+	/// assert_eq!(m.characteristic_polynomial("s").to_string(), "s^2 - k");
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self` is not square or if `variable` already appears in
+	/// its entries.
+	pub fn characteristic_polynomial(&self, variable: &str) -> Expr {
+		if self.rows.iter().flatten().any(|e| e.variables().iter().any(|v| v == variable)) {
+			panic!("Variable of the characteristic polynomial must not appear in the Matrix")
+		}
+		let shifted = self.rows.iter().enumerate().map(|(i, row)| {
+			row.iter().enumerate().map(|(j, e)| {
+				if i == j { Expr::var(variable) - e.clone() } else { -e.clone() }
+			}).collect()
+		}).collect();
+		Matrix::new(shifted).determinant()
+	}
+
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn x() -> Expr {
+		Expr::var("x")
+	}
+
+	fn y() -> Expr {
+		Expr::var("y")
+	}
+
+	#[test]
+	fn arithmetic_simplifies() {
+		assert_eq!((x() - x()).to_string(), "0");
+		assert_eq!((x() / x()).to_string(), "1");
+		assert_eq!((x() * y() / (x() * Expr::from(2))).to_string(), "1/2*y");
+		assert_eq!((Expr::from(1) / x() + Expr::from(1) / y()).to_string(), "(x + y)/(x*y)");
+		assert_eq!((x().pow(2) - Expr::from(1)) / (x() - Expr::from(1)), x() + Expr::from(1));
+		assert_eq!(((x() - Expr::from(1)) / (x().pow(2) - Expr::from(1))).to_string(), "1/(x + 1)")
+	}
+
+	#[test]
+	fn equality_is_mathematical() {
+		let unsimplified = Expr { numerator: (x() * (x() + y())).numerator, denominator: x().numerator };
+		assert_eq!(unsimplified, x() + y());
+		assert_ne!(unsimplified, x() - y())
+	}
+
+	#[test]
+	fn evaluates_and_substitutes() {
+		let e = (x().pow(2) + y()) / (x() - y());
+		assert_eq!(e.evaluate(&[("x", 3.0), ("y", 1.0)]), 5.0);
+		assert_eq!(e.substitute("y", &Expr::from(0)), x());
+		assert_eq!(e.variables(), vec!["x".to_string(), "y".to_string()])
+	}
+
+	#[test]
+	fn determinant_matches_cofactor_expansion() {
+		let (a, b, c) = (Expr::var("a"), Expr::var("b"), Expr::var("c"));
+		let m = Matrix::new(vec![
+			vec![a.clone(), Expr::from(1), Expr::from(0)],
+			vec![Expr::from(1), b.clone(), Expr::from(1)],
+			vec![Expr::from(0), Expr::from(1), c.clone()]
+		]);
+		let expected = a.clone() * (b.clone() * c.clone() - Expr::from(1)) - c;
+		let det = m.determinant();
+		assert_eq!(det, expected);
+		assert!(det.is_polynomial())
+	}
+
+	#[test]
+	fn determinant_with_zero_leading_entry() {
+		let m = Matrix::new(vec![vec![Expr::from(0), x()], vec![y(), Expr::from(1)]]);
+		assert_eq!(m.determinant(), -(x() * y()))
+	}
+
+	#[test]
+	fn generic_rref_is_identity() {
+		let m = Matrix::new(vec![vec![x(), y()], vec![y(), x()]]);
+		let (r, pivots) = m.rref();
+		assert_eq!(pivots, vec![0, 1]);
+		assert!(r.rows.iter().enumerate().all(|(i, row)| row.iter().enumerate().all(|(j, e)| *e == Expr::from((i == j) as i64))))
+	}
+
+	#[test]
+	fn characteristic_polynomial_has_trace_and_determinant() {
+		let m = Matrix::new(vec![vec![x(), Expr::from(2)], vec![Expr::from(3), y()]]);
+		let p = m.characteristic_polynomial("t");
+		let t = Expr::var("t");
+		assert_eq!(p, t.pow(2) - (x() + y()) * t + m.determinant())
+	}
+
+	#[test]
+	#[should_panic]
+	fn division_by_zero() {
+		let _ = x() / (y() - y());
+	}
+
+}
@@ -0,0 +1,212 @@
+//! Host benchmarking and auto-tuning.
+//!
+//! Enabled by the `tune` feature. [`tune`] times the kernel variants and
+//! multiplication crossovers available on the running machine and reports the
+//! fastest as [`TuningParameters`]. [`parameters`] caches the result for the
+//! rest of the process, so nodes of a heterogeneous cluster each settle on
+//! their own parameters from the same binary. Once cached, the parameters
+//! replace the built-in defaults: [`MulContext::new`] starts from the tuned
+//! crossover, and [`Dispatch::kernels`] returns the tuned kernel variant.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::linal::kernels::{Dispatch, Kernels, SimdLevel};
+use crate::linal::{MulAlgorithm, MulContext};
+
+/// Matrix dimension benchmarked by [`parameters`] when it first tunes.
+pub const DEFAULT_MATRIX_SIZE: usize = 256;
+
+/// Length of the vectors used to benchmark kernel variants.
+const KERNEL_LENGTH: usize = 4096;
+
+/// Number of timed runs per candidate; the fastest run is kept.
+const REPETITIONS: usize = 5;
+
+/// The cached parameters, packed into a single word by
+/// [`TuningParameters::pack`] so that the multiplication hot path reads them
+/// without locking. Zero while nothing is cached.
+static CACHE: AtomicU64 = AtomicU64::new(0);
+
+/// Parameters chosen by benchmarking the running machine.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TuningParameters {
+	/// Dimension at or below which fast multiplication falls back to the
+	/// standard algorithm.
+	pub crossover: usize,
+	/// Fastest supported kernel variant.
+	pub simd_level: SimdLevel
+}
+
+impl TuningParameters {
+
+	/// Constructs a [`MulContext`] using `algorithm` with the tuned crossover.
+	pub fn mul_context<T>(&self, algorithm: MulAlgorithm) -> MulContext<T> {
+		MulContext::new(algorithm).with_crossover(self.crossover)
+	}
+
+	/// Returns the kernels for the tuned variant, falling back to the detected
+	/// variant if the tuned one is unsupported (for instance, when the
+	/// parameters were tuned on another machine).
+	///
+	/// Under the `strict` feature these are always the scalar kernels, even
+	/// for parameters installed with another variant.
+	pub fn kernels<T>(&self) -> Kernels<T> where T: Dispatch {
+		if cfg!(feature = "strict") {
+			return T::kernels_for(SimdLevel::Scalar).unwrap();
+		}
+		T::kernels_for(self.simd_level).unwrap_or_else(|| T::kernels_for(SimdLevel::detect()).unwrap())
+	}
+
+	/// Packs `self` into a nonzero word: the crossover above an eight-bit tag
+	/// for the kernel variant. Crossovers too large to fit saturate, which
+	/// makes no difference to any matrix that fits in memory.
+	fn pack(&self) -> u64 {
+		let tag = match self.simd_level {
+			SimdLevel::Scalar => 1,
+			SimdLevel::Avx2 => 2,
+			SimdLevel::Avx512 => 3,
+			SimdLevel::Neon => 4
+		};
+		(self.crossover as u64).min(u64::MAX >> 8) << 8 | tag
+	}
+
+	/// Reverses [`Self::pack`], returning `None` for the empty word.
+	fn unpack(word: u64) -> Option<TuningParameters> {
+		let simd_level = match word & 0xff {
+			1 => SimdLevel::Scalar,
+			2 => SimdLevel::Avx2,
+			3 => SimdLevel::Avx512,
+			4 => SimdLevel::Neon,
+			_ => return None
+		};
+		let crossover = usize::try_from(word >> 8).unwrap_or(usize::MAX);
+		Some(TuningParameters { crossover, simd_level })
+	}
+
+}
+
+/// Returns the fastest of `repetitions` timed runs of `f`.
+pub fn time(repetitions: usize, mut f: impl FnMut()) -> Duration {
+	(0..repetitions.max(1)).map(|_| {
+		let start = Instant::now();
+		f();
+		start.elapsed()
+	}).min().unwrap()
+}
+
+/// Returns the fastest kernel variant supported by the running machine.
+///
+/// Only the scalar variant is a candidate under the `strict` feature, for the
+/// same reason [`SimdLevel::detect`] never reports another there.
+fn tune_kernels() -> SimdLevel {
+	let a: Vec<f64> = (0..KERNEL_LENGTH).map(|i| i as f64).collect();
+	let mut y = a.clone();
+	[SimdLevel::Scalar, SimdLevel::Avx2, SimdLevel::Avx512, SimdLevel::Neon].into_iter()
+		.filter(|level| !cfg!(feature = "strict") || *level == SimdLevel::Scalar)
+		.filter_map(f64::kernels_for)
+		.min_by_key(|kernels| time(REPETITIONS, || {
+			std::hint::black_box(kernels.dot(&a, &a));
+			kernels.axpy(1e-9, &a, &mut y);
+		}))
+		.unwrap()
+		.level()
+}
+
+/// Benchmarks the running machine on `matrix_size`x`matrix_size` products,
+/// returning the fastest parameters without caching them.
+///
+/// # Panics
+/// Panics if `matrix_size` is zero.
+pub fn tune(matrix_size: usize) -> TuningParameters {
+	if matrix_size == 0 {
+		panic!("Matrix size must be positive to tune")
+	}
+	// The crossover is tuned from the built-in default rather than from any
+	// cached one, so that retuning does not depend on earlier results.
+	let crossover = MulContext::<f64>::new(MulAlgorithm::Strassen)
+		.with_crossover(MulContext::<f64>::DEFAULT_CROSSOVER)
+		.tune(matrix_size);
+	TuningParameters { crossover, simd_level: tune_kernels() }
+}
+
+/// Returns the cached [`TuningParameters`], tuning with
+/// [`DEFAULT_MATRIX_SIZE`] on first use.
+pub fn parameters() -> TuningParameters {
+	if let Some(cached) = cached() {
+		return cached;
+	}
+	// Concurrent first calls may each tune, but only the first result is
+	// kept, so every caller sees the same parameters.
+	let tuned = tune(DEFAULT_MATRIX_SIZE);
+	match CACHE.compare_exchange(0, tuned.pack(), Ordering::Relaxed, Ordering::Relaxed) {
+		Ok(_) => tuned,
+		Err(word) => TuningParameters::unpack(word).unwrap()
+	}
+}
+
+/// Returns the cached [`TuningParameters`], if any, without tuning.
+///
+/// This is a single atomic load, cheap enough for every kernel lookup and
+/// [`MulContext`] to make.
+pub fn cached() -> Option<TuningParameters> {
+	TuningParameters::unpack(CACHE.load(Ordering::Relaxed))
+}
+
+/// Re-runs [`tune`] and caches the result.
+pub fn retune(matrix_size: usize) -> TuningParameters {
+	let tuned = tune(matrix_size);
+	install(tuned);
+	tuned
+}
+
+/// Caches `parameters`, for instance ones tuned in an earlier run.
+pub fn install(parameters: TuningParameters) {
+	CACHE.store(parameters.pack(), Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn tuned_parameters_are_usable() {
+		let tuned = tune(32);
+		assert!(tuned.simd_level.is_supported());
+		assert_eq!(tuned.mul_context::<f64>(MulAlgorithm::Winograd).crossover(), tuned.crossover);
+		assert_eq!(tuned.kernels::<f32>().level(), tuned.simd_level)
+	}
+
+	#[test]
+	fn installed_parameters_are_cached() {
+		let installed = TuningParameters { crossover: 48, simd_level: SimdLevel::Scalar };
+		install(installed);
+		assert_eq!(parameters(), installed);
+		assert_eq!(MulContext::<f64>::new(MulAlgorithm::Strassen).crossover(), 48);
+		assert_eq!(f64::kernels().level(), SimdLevel::Scalar);
+		// Installed in the same test, as the cache is shared by every test.
+		if cfg!(feature = "strict") {
+			install(TuningParameters { simd_level: SimdLevel::Avx2, ..installed });
+			assert_eq!(f64::kernels().level(), SimdLevel::Scalar);
+			assert_eq!(f32::kernels().level(), SimdLevel::Scalar);
+			install(installed);
+		}
+	}
+
+	#[test]
+	fn packing_round_trips() {
+		for simd_level in [SimdLevel::Scalar, SimdLevel::Avx2, SimdLevel::Avx512, SimdLevel::Neon] {
+			let parameters = TuningParameters { crossover: 96, simd_level };
+			assert_eq!(TuningParameters::unpack(parameters.pack()), Some(parameters));
+		}
+		assert_eq!(TuningParameters::unpack(0), None)
+	}
+
+	#[test]
+	fn foreign_parameters_fall_back() {
+		let foreign = TuningParameters { crossover: 64, simd_level: SimdLevel::Neon };
+		assert!(foreign.kernels::<f64>().level().is_supported())
+	}
+
+}